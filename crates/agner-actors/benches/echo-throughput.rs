@@ -0,0 +1,82 @@
+//! Message throughput of a tight echo actor.
+//!
+//! The driver keeps `WINDOW` numbers in flight towards the echo actor, which sends each of them
+//! back to its [sender](agner_actors::Context::sender); the round is over once `MESSAGES` replies
+//! have arrived. Every message thus passes through two actor inboxes.
+//!
+//! Run with `cargo bench -p agner-actors --bench echo-throughput`.
+
+use std::time::{Duration, Instant};
+
+use agner_actors::{ActorID, Context, System};
+use tokio::sync::oneshot;
+
+const MESSAGES: usize = 1_000_000;
+const WINDOW: usize = 256;
+const ROUNDS: usize = 5;
+
+type ReportTo = std::sync::Arc<std::sync::Mutex<Option<oneshot::Sender<Duration>>>>;
+
+async fn echo(context: &mut Context<usize>, _arg: ()) {
+    loop {
+        let n = context.next_message().await;
+        let reply_to = context.sender().expect("Anonymous request");
+        context.send(reply_to, n).await.expect("Failed to reply");
+    }
+}
+
+async fn driver(context: &mut Context<usize>, (echo, report_to): (ActorID, ReportTo)) {
+    let t0 = Instant::now();
+    let mut sent = 0;
+    while sent < WINDOW {
+        context.send(echo, sent).await.expect("Failed to send");
+        sent += 1;
+    }
+    for _ in 0..MESSAGES {
+        context.next_message().await;
+        if sent < MESSAGES {
+            context.send(echo, sent).await.expect("Failed to send");
+            sent += 1;
+        }
+    }
+    if let Some(report_to) = report_to.lock().unwrap().take() {
+        let _ = report_to.send(t0.elapsed());
+    }
+}
+
+async fn round(system: &System) -> Duration {
+    let echo = system.spawn(echo, (), Default::default()).await.expect("Failed to start echo");
+    let (tx, rx) = oneshot::channel();
+    let driver = system
+        .spawn(
+            driver,
+            (echo, std::sync::Arc::new(std::sync::Mutex::new(Some(tx)))),
+            Default::default(),
+        )
+        .await
+        .expect("Failed to start the driver");
+
+    let elapsed = rx.await.expect("The driver is gone");
+
+    system.wait(driver).await;
+    system.exit(echo, Default::default()).await;
+    system.wait(echo).await;
+
+    elapsed
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_time()
+        .build()
+        .expect("Failed to create tokio-runtime");
+
+    runtime.block_on(async {
+        let system = System::new(Default::default());
+        for idx in 1..=ROUNDS {
+            let elapsed = round(&system).await;
+            let rate = MESSAGES as f64 / elapsed.as_secs_f64();
+            println!("round {}/{}: {:?} ({:.0} msg/s)", idx, ROUNDS, elapsed, rate);
+        }
+    })
+}
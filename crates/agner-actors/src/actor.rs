@@ -2,22 +2,27 @@ use std::future::Future;
 
 use crate::context::Context;
 use crate::exit::Exit;
+use crate::imports::Never;
 
 /// A marker trait for actor behaviour function.
 ///
 /// It is recommended to rely on the existing implementation of this trait for certain
 /// async-functions, rather than implementing this trait manually.
-pub trait Actor<'a, A, M>: Send + 'static {
+///
+/// The type of the control messages `C` (see
+/// [`Context::next_control`](crate::context::Context::next_control)) is fixed by the behaviour.
+pub trait Actor<'a, A, M, C = Never>: Send + 'static {
     type Out: Into<Exit>;
     type Fut: Future<Output = Self::Out> + Send + 'a;
 
-    fn run(self, context: &'a mut Context<M>, args: A) -> Self::Fut;
+    fn run(self, context: &'a mut Context<M, C>, args: A) -> Self::Fut;
 }
 
-impl<'a, A, M, F, Fut, Out> Actor<'a, A, M> for F
+impl<'a, A, M, C, F, Fut, Out> Actor<'a, A, M, C> for F
 where
     M: 'a,
-    F: FnOnce(&'a mut Context<M>, A) -> Fut,
+    C: 'a,
+    F: FnOnce(&'a mut Context<M, C>, A) -> Fut,
     Fut: Future<Output = Out> + 'a,
     Fut: Send,
     Out: Into<Exit>,
@@ -26,7 +31,7 @@ where
     type Out = Out;
     type Fut = Fut;
 
-    fn run(self, context: &'a mut Context<M>, args: A) -> Self::Fut {
+    fn run(self, context: &'a mut Context<M, C>, args: A) -> Self::Fut {
         self(context, args)
     }
 }
@@ -7,9 +7,33 @@ mod serde;
 mod tests;
 
 /// Identifier of an actor.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// Displayed as `<system_id>.<slot>.<seq>`, so that the actors of different
+/// [systems](crate::system::System) are distinguishable in the logs.
+///
+/// This textual form is also the stable wire format of an [`ActorID`]: it is parsed back via
+/// [`FromStr`](std::str::FromStr) (`"3.14.15".parse::<ActorID>()`), and, with the `serde` feature
+/// enabled, an [`ActorID`] is serialized as that very string. Hence an id printed into the logs,
+/// or reported by an external tool, can be fed back into the
+/// [`System`](crate::system::System).
+///
+/// An [`ActorID`] is merely a handle: a parsed (or deserialized) id does not prove that such actor
+/// has ever existed, or that it is still alive — sending to it may well fail with
+/// [`SysChannelError::NoActor`](crate::system_error::SysChannelError::NoActor).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ActorID(Inner);
 
+impl ActorID {
+    /// The id of the [`System`](crate::system::System) this actor belongs to
+    pub fn system_id(&self) -> usize {
+        self.system()
+    }
+    /// The slot occupied by this actor within its [`System`](crate::system::System)
+    pub fn slot(&self) -> usize {
+        self.actor()
+    }
+}
+
 impl ActorID {
     /// Create a new [`ActorID`] from the provided components
     pub(crate) fn new(system: usize, actor: usize, seq: usize) -> Self {
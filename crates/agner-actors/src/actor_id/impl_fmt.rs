@@ -11,6 +11,12 @@ impl fmt::Display for ActorID {
     }
 }
 
+impl fmt::Debug for ActorID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ActorID({})", self)
+    }
+}
+
 impl FromStr for ActorID {
     type Err = &'static str;
 
@@ -57,6 +63,15 @@ fn actor_id_to_and_from_str() {
     }
 }
 
+#[test]
+fn actor_id_debug_and_accessors() {
+    let actor_id = ActorID::new(3, 14, 15);
+
+    assert_eq!(format!("{:?}", actor_id), "ActorID(3.14.15)");
+    assert_eq!(actor_id.system_id(), 3);
+    assert_eq!(actor_id.slot(), 14);
+}
+
 #[test]
 fn actor_id_extra_part() {
     assert!("0.0.0.0".parse::<ActorID>().is_err());
@@ -66,3 +81,14 @@ fn actor_id_extra_part() {
 fn actor_id_missing_part() {
     assert!("0.0".parse::<ActorID>().is_err());
 }
+
+#[test]
+fn actor_id_from_str_of_to_string() {
+    let actor_id = ActorID::new(1, 2, 3);
+    assert_eq!(actor_id.to_string(), "1.2.3");
+    assert_eq!(ActorID::from_str(&actor_id.to_string()), Ok(actor_id));
+
+    for malformed in ["", "1.2.", ".2.3", "1..3", "1.2.x", "-1.2.3", " 1.2.3"] {
+        assert!(ActorID::from_str(malformed).is_err(), "{:?}", malformed);
+    }
+}
@@ -20,3 +20,15 @@ impl<'de> serde::Deserialize<'de> for ActorID {
         Ok(actor_id)
     }
 }
+
+#[test]
+fn actor_id_serde_is_a_string() {
+    let actor_id = ActorID::new(1, 2, 3);
+
+    let json = serde_json::to_string(&actor_id).unwrap();
+    assert_eq!(json, r#""1.2.3""#);
+    assert_eq!(serde_json::from_str::<ActorID>(&json).unwrap(), actor_id);
+
+    assert!(serde_json::from_str::<ActorID>(r#""1.2""#).is_err());
+    assert!(serde_json::from_str::<ActorID>("[1, 2, 3]").is_err());
+}
@@ -1,109 +1,222 @@
-use std::future::Future;
-use std::pin::Pin;
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
 
+use agner_utils::async_yield::async_yield;
 use agner_utils::std_error_pp::StdErrorPP;
-use futures::stream::FuturesUnordered;
-use futures::StreamExt;
+use futures::{FutureExt, StreamExt};
 use tokio::sync::{mpsc, oneshot};
-use tracing::Instrument;
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, Level};
 
 use crate::actor::Actor;
 use crate::actor_id::ActorID;
 use crate::context::{Context, Signal};
 use crate::exit::{BackendFailure, Exit};
 use crate::exit_handler::ExitHandler;
-use crate::spawn_opts::SpawnOpts;
-use crate::system::SystemWeakRef;
+use crate::spawn_opts::{PanicPolicy, Priority, SpawnOpts, TaskPriority};
+use crate::system::{Envelope, SystemWeakRef};
 
+mod backend_view;
 pub(crate) mod call_msg;
+mod handler_timer;
 mod impl_debug;
 pub(crate) mod pipe;
 pub(crate) mod sys_msg;
+mod tasks;
 mod watches;
 
-use call_msg::CallMsg;
+use call_msg::{CallMsg, Deferred, Job};
+pub(crate) use handler_timer::HandlerTimer;
 use sys_msg::SysMsg;
+use tasks::Tasks;
 use watches::Watches;
+pub(crate) use watches::{MonitorGuard, Monitors};
 
+pub use self::backend_view::BackendView;
 use self::pipe::{PipeRx, PipeTx};
-pub use self::sys_msg::ActorInfo;
+pub use self::sys_msg::{ActorInfo, TraceFlags};
 
-pub(crate) struct ActorRunner<Message> {
+pub(crate) struct ActorRunner<Message, Control> {
     pub actor_id: ActorID,
     pub system_opt: SystemWeakRef,
-    pub messages_rx: mpsc::UnboundedReceiver<Message>,
+    pub messages_rx: mpsc::UnboundedReceiver<Envelope<Message>>,
+    pub control_rx: mpsc::UnboundedReceiver<Envelope<Control>>,
     pub sys_msg_rx: mpsc::UnboundedReceiver<SysMsg>,
-    pub sys_msg_tx: mpsc::UnboundedSender<SysMsg>,
     pub exit_handler: Arc<dyn ExitHandler>,
     pub spawn_opts: SpawnOpts,
+    pub running: Arc<()>,
 }
 
-impl<Message> ActorRunner<Message>
+impl<Message, Control> ActorRunner<Message, Control>
 where
     Message: Unpin,
+    Control: Unpin,
 {
     #[tracing::instrument(skip_all, fields(
         actor_id = display(self.actor_id),
         behaviour = std::any::type_name::<Behaviour>(),
         msg_type = std::any::type_name::<Message>(),
     ))]
-    pub async fn run<Behaviour, Args>(self, behaviour: Behaviour, args: Args)
+    pub async fn run<Behaviour, Args>(self, mut behaviour: BehaviourSource<Behaviour>, args: Args)
     where
-        for<'a> Behaviour: Actor<'a, Args, Message>,
+        for<'a> Behaviour: Actor<'a, Args, Message, Control>,
+        Args: 'static,
     {
         let Self {
             actor_id,
             system_opt,
             messages_rx,
+            control_rx,
             sys_msg_rx,
-            sys_msg_tx,
             exit_handler,
             mut spawn_opts,
+            running,
         } = self;
 
-        tracing::trace!(
-            "init [m-inbox: {:?}, s-inbox: {:?}, msg-type: {}]",
-            spawn_opts.msg_inbox_size(),
-            spawn_opts.sig_inbox_size(),
-            std::any::type_name::<Message>()
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %actor_id,
+            behaviour = std::any::type_name::<Behaviour>(),
+            event = "init",
+            m_inbox = ?spawn_opts.msg_inbox_size(),
+            s_inbox = ?spawn_opts.sig_inbox_size(),
+            msg_type = std::any::type_name::<Message>(),
         );
 
-        let (inbox_w, inbox_r) = pipe::new::<Message>(spawn_opts.msg_inbox_size());
+        let (inbox_w, inbox_r) = pipe::new::<Envelope<Message>>(spawn_opts.msg_inbox_size());
         let (signals_w, signals_r) = pipe::new::<Signal>(spawn_opts.sig_inbox_size());
+        let (controls_w, controls_r) = pipe::new::<Envelope<Control>>(spawn_opts.msg_inbox_size());
         let (calls_w, calls_r) = pipe::new::<CallMsg<Message>>(1);
-        let mut context =
-            Context::new(actor_id, system_opt.to_owned(), inbox_r, signals_r, calls_w)
-                .with_data(spawn_opts.take_data());
-
+        let monitors = Monitors::default();
+        let handler_timer = spawn_opts.slow_handler_threshold().map(HandlerTimer::new);
+        let cancellation_token = CancellationToken::new();
+        let (reinit_tx, mut reinit_rx) = mpsc::unbounded_channel::<Box<dyn Any + Send>>();
+        let mut context = Context::new(
+            actor_id,
+            system_opt.to_owned(),
+            inbox_r,
+            signals_r,
+            controls_r,
+            calls_w,
+            reinit_tx,
+        )
+        .with_monitors(monitors.to_owned())
+        .with_handler_timer(handler_timer.to_owned())
+        .with_parent(spawn_opts.parent())
+        .with_behaviour(std::any::type_name::<Behaviour>())
+        .with_cancellation_token(cancellation_token.to_owned())
+        .with_data(spawn_opts.take_data());
+
+        let unexpected_return = spawn_opts.unexpected_return().cloned();
         let behaviour_running = async move {
-            let exit_reason = behaviour
-                .run(&mut context, args)
-                .instrument(tracing::span!(tracing::Level::TRACE, "<behaviour as Actor>::run"))
-                .await
-                .into();
+            let mut args = args;
+            let exit_reason = loop {
+                let Some(behaviour) = behaviour.next() else {
+                    break Exit::from_message(
+                        "reinit: the actor has not been spawned as re-runnable \
+                         (see System::spawn_rerunnable)",
+                    )
+                };
+                let behaviour_run = behaviour
+                    .run(&mut context, args)
+                    .instrument(tracing::span!(tracing::Level::TRACE, "<behaviour as Actor>::run"));
+                tokio::select! {
+                    biased;
+
+                    new_args = reinit_rx.recv() => {
+                        let new_args = new_args.expect("The sender is held by the context");
+                        match new_args.downcast::<Args>() {
+                            Ok(new_args) => {
+                                tracing::event!(
+                                    Level::TRACE,
+                                    actor_id = %actor_id,
+                                    behaviour = std::any::type_name::<Behaviour>(),
+                                    event = "reinit",
+                                );
+                                args = *new_args;
+                            },
+                            Err(_) =>
+                                break Exit::from_message(format!(
+                                    "reinit: args type mismatch (expected {})",
+                                    std::any::type_name::<Args>()
+                                )),
+                        }
+                    },
+                    exit_reason = behaviour_run => {
+                        let exit_reason: Exit = exit_reason.into();
+                        match unexpected_return {
+                            Some(substitute) if exit_reason.is_normal() => {
+                                tracing::event!(
+                                    Level::TRACE,
+                                    actor_id = %actor_id,
+                                    behaviour = std::any::type_name::<Behaviour>(),
+                                    event = "unexpected-return",
+                                );
+                                break substitute
+                            },
+                            _ => break exit_reason,
+                        }
+                    },
+                }
+            };
             context
                 .exit(exit_reason.clone())
                 .instrument(tracing::span!(tracing::Level::TRACE, "Context::exit"))
                 .await;
             unreachable!()
         };
+        let (panicked_tx, panicked_rx) = oneshot::channel();
+        let panic_policy = spawn_opts.panic_policy();
+        let behaviour_running = async move {
+            let panic = AssertUnwindSafe(behaviour_running)
+                .catch_unwind()
+                .await
+                .expect_err("Future<Output = Infallible> has returned");
+            let exit_reason = Exit::from_panic(panic_message(panic.as_ref()));
+            let abort = panic_policy == PanicPolicy::Abort;
+            tracing::event!(
+                Level::ERROR,
+                actor_id = %actor_id,
+                behaviour = std::any::type_name::<Behaviour>(),
+                event = "panicked",
+                abort,
+                exit_reason = %exit_reason.pp(),
+            );
+            if abort {
+                std::process::abort()
+            }
+            let _ = panicked_tx.send(exit_reason);
+            std::future::pending::<()>().await
+        };
 
         let mut actor_backend = Backend {
             actor_id,
+            parent: spawn_opts.parent(),
+            label: spawn_opts.label().map(ToOwned::to_owned),
             system_opt: system_opt.to_owned(),
             sys_msg_rx,
-            sys_msg_tx,
             messages_rx,
             inbox_w,
+            control_rx,
+            controls_w,
             signals_w,
             calls_r,
-            watches: Default::default(),
-            tasks: FuturesUnordered::<
-                Pin<Box<dyn Future<Output = Option<Message>> + Send + Sync + 'static>>,
-            >::new(),
+            behaviour_panicked: panicked_rx,
+            inbox_fmt: None,
+            drain_on_exit: spawn_opts.drain_on_exit(),
+            drain_handler: None,
+            deferred: Default::default(),
+            watches: Watches { monitors, ..Default::default() },
+            handler_timer,
+            reductions: (0, spawn_opts.reductions()),
+            task_priority: spawn_opts.task_priority(),
+            tasks_turn: false,
+            cancellation_token,
+            tasks: Default::default(),
 
             exit_handler,
+            priority: spawn_opts.priority(),
 
             actor_type_info: (
                 std::any::type_name::<Behaviour>(),
@@ -118,78 +231,247 @@ where
 
         let actor_backend_running = actor_backend.run_actor_backend();
 
-        tracing::trace!("running...");
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %actor_id,
+            behaviour = std::any::type_name::<Behaviour>(),
+            event = "running",
+        );
         let exit_reason = tokio::select! {
             biased;
 
             exit_reason = actor_backend_running => exit_reason,
             _ = behaviour_running => unreachable!("Future<Output = Infallible> has returned"),
         };
-        tracing::trace!("exited: {}", exit_reason.pp());
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %actor_id,
+            behaviour = std::any::type_name::<Behaviour>(),
+            event = "exited",
+            exit_reason = %exit_reason.pp(),
+        );
 
         if let Some(system) = system_opt.rc_upgrade() {
-            tracing::trace!("cleaning up actor-entry...");
+            tracing::event!(
+                Level::TRACE,
+                actor_id = %actor_id,
+                behaviour = std::any::type_name::<Behaviour>(),
+                event = "cleanup",
+            );
             system.actor_entry_terminate(actor_id, exit_reason).await;
         }
+
+        // the actor-entry is cleaned up: it should not be considered stale.
+        drop(running);
+    }
+}
+
+/// The behaviour of an actor: either run once, or cloned for each run (see
+/// [`Context::reinit`](crate::context::Context::reinit)).
+pub(crate) struct BehaviourSource<Behaviour> {
+    behaviour: Option<Behaviour>,
+    rerun: Option<fn(&Behaviour) -> Behaviour>,
+}
+
+impl<Behaviour> BehaviourSource<Behaviour> {
+    pub fn once(behaviour: Behaviour) -> Self {
+        Self { behaviour: Some(behaviour), rerun: None }
+    }
+
+    pub fn rerunnable(behaviour: Behaviour) -> Self
+    where
+        Behaviour: Clone,
+    {
+        Self { behaviour: Some(behaviour), rerun: Some(Behaviour::clone) }
+    }
+
+    fn next(&mut self) -> Option<Behaviour> {
+        match self.rerun {
+            Some(rerun) => self.behaviour.as_ref().map(rerun),
+            None => self.behaviour.take(),
+        }
     }
 }
 
-struct Backend<Message> {
+/// Either a message sent to the actor, or an outcome of one of its tasks.
+enum Inbound<Message> {
+    Message(Option<Envelope<Message>>),
+    Task(Option<Result<Option<Message>, Exit>>),
+}
+
+/// Receive the next message or task outcome (see [`TaskPriority`]).
+///
+/// The task outcomes are only taken when there are no messages pending, unless `tasks_first`.
+async fn next_inbound<Message>(
+    messages_rx: &mut mpsc::UnboundedReceiver<Envelope<Message>>,
+    tasks: &mut Tasks<Message>,
+    tasks_first: bool,
+) -> Inbound<Message> {
+    let tasks_allowed = tasks_first || messages_rx.is_empty();
+    let task_next = async {
+        if tasks.is_empty() {
+            std::future::pending().await
+        } else {
+            tasks.next().await
+        }
+    };
+
+    if tasks_first {
+        tokio::select! {
+            biased;
+
+            task_ready = task_next => Inbound::Task(task_ready),
+            message_recv = messages_rx.recv() => Inbound::Message(message_recv),
+        }
+    } else {
+        tokio::select! {
+            biased;
+
+            message_recv = messages_rx.recv() => Inbound::Message(message_recv),
+            task_ready = task_next, if tasks_allowed => Inbound::Task(task_ready),
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.to_owned()
+    } else {
+        "Box<dyn Any>".to_owned()
+    }
+}
+
+struct Backend<Message, Control> {
     actor_id: ActorID,
+    parent: Option<ActorID>,
+    label: Option<String>,
     system_opt: SystemWeakRef,
     sys_msg_rx: mpsc::UnboundedReceiver<SysMsg>,
-    sys_msg_tx: mpsc::UnboundedSender<SysMsg>,
-    messages_rx: mpsc::UnboundedReceiver<Message>,
-    inbox_w: PipeTx<Message>,
+    messages_rx: mpsc::UnboundedReceiver<Envelope<Message>>,
+    inbox_w: PipeTx<Envelope<Message>>,
+    control_rx: mpsc::UnboundedReceiver<Envelope<Control>>,
+    controls_w: PipeTx<Envelope<Control>>,
     signals_w: PipeTx<Signal>,
     calls_r: PipeRx<CallMsg<Message>>,
+    behaviour_panicked: oneshot::Receiver<Exit>,
+    inbox_fmt: Option<fn(&Message) -> String>,
+    drain_on_exit: bool,
+    drain_handler: Option<Box<dyn FnMut(Message) + Send + Sync + 'static>>,
+    deferred: Vec<Deferred>,
     watches: Watches,
-    tasks: FuturesUnordered<Pin<Box<dyn Future<Output = Option<Message>> + Send + Sync + 'static>>>,
+    handler_timer: Option<HandlerTimer>,
+    /// The number of messages delivered in the current scheduling quantum, and its limit (see
+    /// [`SpawnOpts::with_reductions`]).
+    reductions: (usize, Option<usize>),
+    task_priority: TaskPriority,
+    /// Whether a task outcome is to be taken ahead of the pending messages (see
+    /// [`TaskPriority::Interleaved`]).
+    tasks_turn: bool,
+    cancellation_token: CancellationToken,
+    tasks: Tasks<Message>,
     exit_handler: Arc<dyn ExitHandler>,
+    priority: Priority,
 
     actor_type_info: (&'static str, &'static str, &'static str),
 }
 
-impl<Message> Backend<Message>
+impl<Message, Control> Backend<Message, Control>
 where
     Message: Unpin,
+    Control: Unpin,
 {
     #[tracing::instrument(skip_all)]
     async fn run_actor_backend(mut self) -> Exit {
-        tracing::trace!("running actor-backend");
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %self.actor_id,
+            behaviour = self.actor_type_info.0,
+            event = "backend-running",
+        );
 
         let exit_reason = loop {
-            let task_next = async {
-                if self.tasks.is_empty() {
+            let tasks_first = self.task_priority == TaskPriority::Interleaved && self.tasks_turn;
+            let inbound = next_inbound(&mut self.messages_rx, &mut self.tasks, tasks_first);
+            let link_failures = &self.watches.link_failures;
+            let link_failures_due = async move {
+                if link_failures.is_empty() {
                     std::future::pending().await
                 } else {
-                    self.tasks.next().await
+                    // let the behaviour issue its next calls first: it may unlink right away.
+                    async_yield().await
                 }
             };
 
             if let Err(exit_reason) = tokio::select! {
                 biased;
 
+                Ok(exit_reason) = &mut self.behaviour_panicked => Err(exit_reason),
                 sys_msg_recv = self.sys_msg_rx.recv() =>
                     self.handle_sys_msg(sys_msg_recv).await,
                 call_msg = self.calls_r.recv() =>
                     self.handle_call_msg(call_msg).await,
-                message_recv = self.messages_rx.recv() =>
-                    self.handle_message_recv(message_recv).await,
-                task_ready = task_next =>
-                    if let Some(message) = task_ready.flatten() {
-                        self.handle_message_recv(Some(message)).await
-                    } else {
-                        Ok(())
+                control_recv = self.control_rx.recv() =>
+                    self.handle_control_recv(control_recv).await,
+                inbound = inbound => match inbound {
+                    Inbound::Message(message_recv) => {
+                        self.tasks_turn = true;
+                        let result = self.handle_message_recv(message_recv).await;
+                        self.spend_reduction().await;
+                        result
                     },
+                    Inbound::Task(task_ready) => {
+                        self.tasks_turn = false;
+                        match task_ready {
+                            Some(Ok(Some(message))) => {
+                                let result = self
+                                    .handle_message_recv(Some(Envelope::anonymous(message)))
+                                    .await;
+                                self.spend_reduction().await;
+                                result
+                            },
+                            Some(Err(exit_reason)) => Err(exit_reason),
+                            Some(Ok(None)) | None => Ok(()),
+                        }
+                    },
+                },
+                () = link_failures_due => self.handle_link_failures().await,
             } {
                 break exit_reason
             }
         };
-        tracing::trace!("exiting: {}", exit_reason.pp());
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %self.actor_id,
+            behaviour = self.actor_type_info.0,
+            event = "backend-exiting",
+            exit_reason = %exit_reason.pp(),
+        );
+
+        self.cancellation_token.cancel();
 
         self.sys_msg_rx.close();
         self.messages_rx.close();
+        self.control_rx.close();
+
+        // the behaviour may have registered a deferred function the backend has not handled yet.
+        let (pending_calls, _) = self.calls_r.len();
+        for call_msg in self.calls_r.drain(pending_calls).await {
+            if let CallMsg::Defer(deferred) = call_msg {
+                self.deferred.push(deferred);
+            }
+        }
+
+        // the queued messages are drained first: the deferred functions may well release the
+        // resources the drain handler relies upon.
+        if self.drain_on_exit && !exit_reason.is_kill() {
+            self.drain_inbox().await;
+        }
+
+        while let Some(deferred) = self.deferred.pop() {
+            deferred(&exit_reason);
+        }
 
         self.exit_handler.on_actor_exit(self.actor_id, exit_reason.to_owned());
 
@@ -199,28 +481,80 @@ where
             self.handle_sys_msg_on_shutdown(sys_msg, exit_reason.to_owned()).await
         }
 
-        tracing::trace!("exited");
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %self.actor_id,
+            behaviour = self.actor_type_info.0,
+            event = "backend-exited",
+        );
 
         exit_reason
     }
 
+    async fn drain_inbox(&mut self) {
+        let Some(mut drain_handler) = self.drain_handler.take() else { return };
+
+        let mut drained = 0;
+        for Envelope { message, .. } in self.inbox_w.take_all().await {
+            drain_handler(message);
+            drained += 1;
+        }
+        while let Ok(Envelope { message, .. }) = self.messages_rx.try_recv() {
+            drain_handler(message);
+            drained += 1;
+        }
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %self.actor_id,
+            behaviour = self.actor_type_info.0,
+            event = "inbox-drained",
+            drained,
+        );
+    }
+
     #[tracing::instrument(skip_all)]
     async fn handle_sys_msg(&mut self, sys_msg_recv: Option<SysMsg>) -> Result<(), Exit> {
-        let sys_msg = sys_msg_recv.ok_or(BackendFailure::RxClosed("sys-msg"))?;
-        tracing::trace!("[received sys-msg: {:?}", sys_msg);
-
-        match sys_msg {
-            SysMsg::SigExit(terminated, exit_reason) =>
-                self.handle_sys_msg_sig_exit(terminated, exit_reason).await,
-            SysMsg::Link(link_to) => self.handle_sys_msg_link(link_to).await,
-            SysMsg::Unlink(unlink_from) => self.handle_sys_msg_unlink(unlink_from).await,
-            SysMsg::GetInfo(report_to) => self.handle_sys_msg_get_info(report_to).await,
+        let mut sys_msg = sys_msg_recv.ok_or(BackendFailure::RxClosed("sys-msg"))?;
+        loop {
+            tracing::event!(
+                Level::TRACE,
+                actor_id = %self.actor_id,
+                behaviour = self.actor_type_info.0,
+                event = "sys-msg-received",
+                sys_msg = ?sys_msg,
+            );
+
+            break match sys_msg {
+                SysMsg::SigExit(terminated, exit_reason) =>
+                    self.handle_sys_msg_sig_exit(terminated, exit_reason).await,
+                SysMsg::Link(link_to) => self.handle_sys_msg_link(link_to).await,
+                SysMsg::Unlink(unlink_from) => self.handle_sys_msg_unlink(unlink_from).await,
+                SysMsg::LinkFailed(link_to) => self.handle_sys_msg_link_failed(link_to),
+                SysMsg::GetInfo(report_to) =>
+                    if let Some(next) = self.handle_sys_msg_get_info(report_to).await {
+                        sys_msg = next;
+                        continue
+                    } else {
+                        Ok(())
+                    },
+                SysMsg::PeekInbox(max, report_to) =>
+                    self.handle_sys_msg_peek_inbox(max, report_to).await,
+                SysMsg::SetTrace(trace_flags) => self.handle_sys_msg_set_trace(trace_flags),
+                SysMsg::Sync(ack) => self.handle_sys_msg_sync(ack).await,
+                SysMsg::SystemDropped => Err(Exit::shutdown()),
+            }
         }
     }
 
     #[tracing::instrument(skip_all)]
     async fn handle_sys_msg_on_shutdown(&mut self, sys_msg: SysMsg, exit_reason: Exit) {
-        tracing::trace!("received sys-msg when shutting down: {:?}", sys_msg);
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %self.actor_id,
+            behaviour = self.actor_type_info.0,
+            event = "sys-msg-received-on-shutdown",
+            sys_msg = ?sys_msg,
+        );
         match sys_msg {
             SysMsg::Link(linked) =>
                 if exit_reason.is_normal() {
@@ -230,10 +564,15 @@ where
                 },
 
             SysMsg::GetInfo(report_to) => {
-                let _ = self.handle_sys_msg_get_info(report_to).await;
+                let _ = report_to.send(self.actor_info().await);
             },
+            SysMsg::PeekInbox { .. } => (),
+            SysMsg::SetTrace { .. } => (),
+            SysMsg::Sync { .. } => (),
             SysMsg::Unlink { .. } => (),
             SysMsg::SigExit { .. } => (),
+            SysMsg::LinkFailed { .. } => (),
+            SysMsg::SystemDropped => (),
         }
     }
 
@@ -243,49 +582,185 @@ where
             CallMsg::Exit(exit_reason) => Err(exit_reason),
             CallMsg::Link(link_to) => self.handle_call_link(link_to).await,
             CallMsg::Unlink(unlink_from) => self.handle_call_unlink(unlink_from).await,
+            CallMsg::LinkAll(link_to) => self.handle_call_link_all(link_to).await,
+            CallMsg::UnlinkAll(unlink_from) => self.handle_call_unlink_all(unlink_from).await,
             CallMsg::TrapExit(trap_exit) => self.handle_set_trap_exit(trap_exit),
+            CallMsg::GetLinks(reply_to) => self.handle_call_get_links(reply_to),
+            CallMsg::Yield(reply_to) => {
+                let _ = reply_to.send(());
+                Ok(())
+            },
             CallMsg::SpawnJob(fut) => self.handle_spawn_job(fut),
+            CallMsg::SpawnOrderedJob(fut) => self.handle_spawn_ordered_job(fut),
+            CallMsg::EnableInboxPeek(inbox_fmt) => self.handle_enable_inbox_peek(inbox_fmt),
+            CallMsg::SetDrainHandler(drain_handler) => {
+                self.drain_handler = Some(drain_handler);
+                Ok(())
+            },
+            CallMsg::Defer(deferred) => {
+                self.deferred.push(deferred);
+                Ok(())
+            },
+            CallMsg::Custom(custom) => self.handle_call_custom(custom).await,
         }
     }
 
-    fn handle_spawn_job(
-        &mut self,
-        fut: Pin<Box<dyn Future<Output = Option<Message>> + Send + Sync + 'static>>,
-    ) -> Result<(), Exit> {
+    fn handle_enable_inbox_peek(&mut self, inbox_fmt: fn(&Message) -> String) -> Result<(), Exit> {
+        self.inbox_fmt = Some(inbox_fmt);
+        Ok(())
+    }
+
+    fn handle_spawn_job(&mut self, fut: Job<Message>) -> Result<(), Exit> {
         self.tasks.push(fut);
         Ok(())
     }
 
+    fn handle_spawn_ordered_job(&mut self, fut: Job<Message>) -> Result<(), Exit> {
+        self.tasks.push_ordered(fut);
+        Ok(())
+    }
+
+    /// Account for a delivered message: once the reductions are spent, yield to the runtime.
+    ///
+    /// The count restarts whenever the inbox drains: only the messages delivered in a row are
+    /// charged to the same quantum.
+    async fn spend_reduction(&mut self) {
+        let (spent, Some(reductions)) = &mut self.reductions else { return };
+        if self.messages_rx.is_empty() {
+            *spent = 0;
+            return
+        }
+        *spent += 1;
+        if *spent >= *reductions {
+            *spent = 0;
+            async_yield().await;
+        }
+    }
+
     #[tracing::instrument(skip_all)]
-    async fn handle_message_recv(&mut self, message_recv: Option<Message>) -> Result<(), Exit> {
-        let message = message_recv.ok_or(BackendFailure::RxClosed("messages"))?;
+    async fn handle_message_recv(
+        &mut self,
+        message_recv: Option<Envelope<Message>>,
+    ) -> Result<(), Exit> {
+        let envelope = message_recv.ok_or(BackendFailure::RxClosed("messages"))?;
+        if self.watches.trace.messages {
+            let rendered = self
+                .inbox_fmt
+                .map(|inbox_fmt| inbox_fmt(&envelope.message))
+                .unwrap_or_else(|| std::any::type_name::<Message>().to_owned());
+            tracing::event!(
+                Level::INFO,
+                actor_id = %self.actor_id,
+                behaviour = self.actor_type_info.0,
+                event = "message-delivered",
+                sender = ?envelope.sender,
+                at = ?std::time::SystemTime::now(),
+                "[trace] message delivered: {}",
+                rendered
+            );
+        }
         self.inbox_w
-            .send(message)
+            .send(envelope)
             .await
             .map_err(|_rejected| BackendFailure::InboxFull("messages"))?;
         Ok(())
     }
 
+    #[tracing::instrument(skip_all)]
+    async fn handle_control_recv(
+        &mut self,
+        control_recv: Option<Envelope<Control>>,
+    ) -> Result<(), Exit> {
+        let envelope = control_recv.ok_or(BackendFailure::RxClosed("controls"))?;
+        self.controls_w
+            .send(envelope)
+            .await
+            .map_err(|_rejected| BackendFailure::InboxFull("controls"))?;
+        Ok(())
+    }
+
+    /// Answer the `GetInfo`-request, along with all the `GetInfo`-requests queued right after it,
+    /// using a single snapshot.
+    ///
+    /// Returns the first queued sys-msg that is not a `GetInfo`-request (if any).
     #[tracing::instrument(skip_all)]
     async fn handle_sys_msg_get_info(
-        &self,
+        &mut self,
         report_to: oneshot::Sender<ActorInfo>,
-    ) -> Result<(), Exit> {
-        let info = ActorInfo {
+    ) -> Option<SysMsg> {
+        let mut reports_to = vec![report_to];
+        let mut next = None;
+        while let Ok(sys_msg) = self.sys_msg_rx.try_recv() {
+            match sys_msg {
+                SysMsg::GetInfo(report_to) => reports_to.push(report_to),
+                sys_msg => {
+                    next = Some(sys_msg);
+                    break
+                },
+            }
+        }
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %self.actor_id,
+            behaviour = self.actor_type_info.0,
+            event = "get-info-answered",
+            requests = reports_to.len(),
+        );
+
+        let info = self.actor_info().await;
+        for report_to in reports_to {
+            let _ = report_to.send(info.to_owned());
+        }
+        next
+    }
+
+    async fn actor_info(&self) -> ActorInfo {
+        ActorInfo {
             actor_id: self.actor_id,
+            parent: self.parent,
+            name: self.registered_name(),
+            label: self.label.to_owned(),
 
             behaviour: self.actor_type_info.0,
             args_type: self.actor_type_info.1,
             message_type: self.actor_type_info.2,
 
-            m_queue_len: self.inbox_w.len().await,
-            s_queue_len: self.signals_w.len().await,
-            c_queue_len: self.calls_r.len().await,
+            m_queue_len: self.inbox_w.len(),
+            s_queue_len: self.signals_w.len(),
+            c_queue_len: self.calls_r.len(),
             tasks_count: self.tasks.len(),
+            priority: self.priority,
             trap_exit: self.watches.trap_exit,
             links: self.watches.links.iter().copied().collect(),
+            monitors: self.watches.monitors.list().into(),
+            monitored_by: self.monitored_by().await.into(),
+            max_handler_time: self.handler_timer.as_ref().map(HandlerTimer::max_handler_time),
+        }
+    }
+
+    /// Move the messages sent before the `Sync`-request into the inbox, and acknowledge.
+    #[tracing::instrument(skip_all)]
+    async fn handle_sys_msg_sync(&mut self, ack: oneshot::Sender<()>) -> Result<(), Exit> {
+        for _ in 0..self.messages_rx.len() {
+            let Ok(envelope) = self.messages_rx.try_recv() else { break };
+            self.handle_message_recv(Some(envelope)).await?;
+        }
+        let _ = ack.send(());
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn handle_sys_msg_peek_inbox(
+        &self,
+        max: usize,
+        report_to: oneshot::Sender<Vec<String>>,
+    ) -> Result<(), Exit> {
+        let previews = if let Some(inbox_fmt) = self.inbox_fmt {
+            self.inbox_w.peek(max, |envelope| inbox_fmt(&envelope.message)).await
+        } else {
+            Default::default()
         };
-        let _ = report_to.send(info);
+        let _ = report_to.send(previews);
         Ok(())
     }
 }
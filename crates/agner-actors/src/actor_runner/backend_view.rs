@@ -0,0 +1,130 @@
+use std::collections::BTreeSet;
+use std::panic::AssertUnwindSafe;
+
+use crate::actor_id::ActorID;
+use crate::context::MonitorRef;
+
+use super::*;
+
+/// The operations on the actor's backend, available to the closure passed to
+/// [`Context::with_backend`].
+///
+/// The closure runs within the backend, between the processing of the system messages and the
+/// calls: no exit-signal, link or unlink is handled while it runs, hence what it reads and what it
+/// changes is consistent.
+///
+/// The links, the unlinks and the monitors requested by the closure are performed right after it
+/// returns, in the order they were requested, before the backend handles anything else. Should
+/// the closure panic, none of them is performed, and the actor exits with
+/// [`Exit::from_panic`].
+pub struct BackendView<'a> {
+    actor_id: ActorID,
+    parent: Option<ActorID>,
+    watches: &'a mut Watches,
+    links: BTreeSet<ActorID>,
+    ops: Vec<Op>,
+}
+
+pub(super) type CustomCall = Box<dyn FnOnce(&mut BackendView<'_>) + Send + 'static>;
+
+#[derive(Debug)]
+enum Op {
+    Link(ActorID),
+    Unlink(ActorID),
+    Monitor(ActorID, oneshot::Sender<Exit>),
+}
+
+impl<'a> BackendView<'a> {
+    pub fn actor_id(&self) -> ActorID {
+        self.actor_id
+    }
+
+    pub fn parent(&self) -> Option<ActorID> {
+        self.parent
+    }
+
+    /// The actors this actor is linked to, including the links requested by the closure.
+    pub fn links(&self) -> impl Iterator<Item = ActorID> + '_ {
+        self.links.iter().copied()
+    }
+
+    pub fn is_linked(&self, actor_id: ActorID) -> bool {
+        self.links.contains(&actor_id)
+    }
+
+    /// Request a link to `actor_id` (see [`Context::link`]).
+    pub fn link(&mut self, actor_id: ActorID) {
+        if self.links.insert(actor_id) {
+            self.ops.push(Op::Link(actor_id));
+        }
+    }
+
+    /// Request an unlink from `actor_id` (see [`Context::unlink`]).
+    pub fn unlink(&mut self, actor_id: ActorID) {
+        if self.links.remove(&actor_id) {
+            self.ops.push(Op::Unlink(actor_id));
+        }
+    }
+
+    pub fn trap_exit(&self) -> bool {
+        self.watches.trap_exit
+    }
+
+    /// Set the trap-exit flag (see [`Context::trap_exit`]).
+    pub fn set_trap_exit(&mut self, trap_exit: bool) {
+        self.watches.trap_exit = trap_exit;
+    }
+
+    /// The actors this actor [monitors](Context::monitor_fut).
+    pub fn monitors(&self) -> Vec<ActorID> {
+        self.watches.monitors.list()
+    }
+
+    /// Request a monitor of `actor_id` (see [`Context::monitor_fut`]).
+    ///
+    /// The target is listed in [`Self::monitors`] right away. The returned [`MonitorRef`]
+    /// resolves into [`Exit::no_actor()`] if there is no such actor.
+    pub fn monitor(&mut self, actor_id: ActorID) -> MonitorRef {
+        let (tx, rx) = oneshot::channel();
+        let monitor_guard = self.watches.monitors.track(actor_id);
+        self.ops.push(Op::Monitor(actor_id, tx));
+        MonitorRef::new(actor_id, monitor_guard, rx)
+    }
+}
+
+impl<M, C> Backend<M, C> {
+    #[tracing::instrument(skip_all, fields(actor_id = display(self.actor_id)))]
+    pub(super) async fn handle_call_custom(&mut self, custom: CustomCall) -> Result<(), Exit> {
+        let ops = {
+            let links = self.watches.links.iter().copied().collect();
+            let mut view = BackendView {
+                actor_id: self.actor_id,
+                parent: self.parent,
+                watches: &mut self.watches,
+                links,
+                ops: vec![],
+            };
+            std::panic::catch_unwind(AssertUnwindSafe(|| custom(&mut view)))
+                .map_err(|panic| Exit::from_panic(panic_message(panic.as_ref())))?;
+            view.ops
+        };
+        for op in ops {
+            match op {
+                Op::Link(link_to) => self.do_link(link_to).await,
+                Op::Unlink(unlink_from) => self.do_unlink(unlink_from).await,
+                Op::Monitor(target, tx) => self.do_monitor(target, tx).await,
+            }
+        }
+        Ok(())
+    }
+
+    async fn do_monitor(&self, target: ActorID, tx: oneshot::Sender<Exit>) {
+        let Some(system) = self.system_opt.rc_upgrade() else { return };
+        match system.actor_entry_write(target).await {
+            Some(mut entry) => entry.add_watch(Some(self.actor_id), tx),
+            None => {
+                let _ = tx.send(Exit::no_actor());
+            },
+        };
+    }
+}
@@ -2,15 +2,33 @@ use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 
+use tokio::sync::oneshot;
+
 use crate::actor_id::ActorID;
 use crate::exit::Exit;
 
+use super::backend_view::CustomCall;
+
+pub type Deferred = Box<dyn FnOnce(&Exit) + Send + Sync + 'static>;
+
+/// A job run by the actor-backend: it may yield a message into the inbox, or terminate the actor.
+pub type Job<M> = Pin<Box<dyn Future<Output = Result<Option<M>, Exit>> + Send + Sync + 'static>>;
+
 pub enum CallMsg<M> {
     Exit(Exit),
     Link(ActorID),
     Unlink(ActorID),
+    LinkAll(Vec<ActorID>),
+    UnlinkAll(Vec<ActorID>),
     TrapExit(bool),
-    SpawnJob(Pin<Box<dyn Future<Output = Option<M>> + Send + Sync + 'static>>),
+    GetLinks(oneshot::Sender<Vec<ActorID>>),
+    Yield(oneshot::Sender<()>),
+    SpawnJob(Job<M>),
+    SpawnOrderedJob(Job<M>),
+    EnableInboxPeek(fn(&M) -> String),
+    SetDrainHandler(Box<dyn FnMut(M) + Send + Sync + 'static>),
+    Defer(Deferred),
+    Custom(CustomCall),
 }
 
 impl<M> fmt::Debug for CallMsg<M> {
@@ -19,8 +37,17 @@ impl<M> fmt::Debug for CallMsg<M> {
             Self::Exit(reason) => f.debug_tuple("Exit").field(reason).finish(),
             Self::Link(actor_id) => f.debug_tuple("Link").field(actor_id).finish(),
             Self::Unlink(actor_id) => f.debug_tuple("Unlink").field(actor_id).finish(),
+            Self::LinkAll(actor_ids) => f.debug_tuple("LinkAll").field(actor_ids).finish(),
+            Self::UnlinkAll(actor_ids) => f.debug_tuple("UnlinkAll").field(actor_ids).finish(),
             Self::TrapExit(trap_exit) => f.debug_tuple("TrapExit").field(trap_exit).finish(),
+            Self::GetLinks { .. } => f.debug_tuple("GetLinks").finish(),
+            Self::Yield { .. } => f.debug_tuple("Yield").finish(),
             Self::SpawnJob { .. } => f.debug_tuple("SpawnJob").finish(),
+            Self::SpawnOrderedJob { .. } => f.debug_tuple("SpawnOrderedJob").finish(),
+            Self::EnableInboxPeek { .. } => f.debug_tuple("EnableInboxPeek").finish(),
+            Self::SetDrainHandler { .. } => f.debug_tuple("SetDrainHandler").finish(),
+            Self::Defer { .. } => f.debug_tuple("Defer").finish(),
+            Self::Custom { .. } => f.debug_tuple("Custom").finish(),
         }
     }
 }
@@ -0,0 +1,49 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tracing::Level;
+
+use crate::actor_id::ActorID;
+
+/// The time the behaviour spends handling an event, i.e. between the consecutive receives (see
+/// [`SpawnOpts::with_slow_handler_threshold`](crate::SpawnOpts::with_slow_handler_threshold)).
+///
+/// Shared between the context (which measures the time) and the backend (which reports the
+/// maximum).
+#[derive(Debug, Clone)]
+pub(crate) struct HandlerTimer(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    threshold: Duration,
+    max_nanos: AtomicU64,
+}
+
+impl HandlerTimer {
+    pub fn new(threshold: Duration) -> Self {
+        Self(Arc::new(Inner { threshold, max_nanos: AtomicU64::new(0) }))
+    }
+
+    /// The handling of an event that has started at `since` is over.
+    pub fn handled(&self, actor_id: ActorID, since: Instant) {
+        let elapsed = since.elapsed();
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.0.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+
+        if elapsed > self.0.threshold {
+            tracing::event!(
+                Level::WARN,
+                actor_id = %actor_id,
+                event = "slow-handler",
+                elapsed = ?elapsed,
+                threshold = ?self.0.threshold,
+            );
+        }
+    }
+
+    /// The longest time an event has been handled for.
+    pub fn max_handler_time(&self) -> Duration {
+        Duration::from_nanos(self.0.max_nanos.load(Ordering::Relaxed))
+    }
+}
@@ -2,7 +2,7 @@ use std::fmt;
 
 use crate::actor_runner::ActorRunner;
 
-impl<Message> fmt::Debug for ActorRunner<Message> {
+impl<Message, Control> fmt::Debug for ActorRunner<Message, Control> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ActorRunner")
             .field("message_type", &std::any::type_name::<Message>())
@@ -28,11 +28,19 @@ where
         self.0.send(message, self.1).await
     }
 
-    pub async fn len(&self) -> (usize, usize)
+    pub fn len(&self) -> (usize, usize)
     where
         T: Unpin,
     {
-        self.0.len().await
+        self.0.len()
+    }
+
+    pub async fn peek<R>(&self, max: usize, f: impl FnMut(&T) -> R) -> Vec<R> {
+        self.0.peek(max, f).await
+    }
+
+    pub async fn take_all(&mut self) -> Vec<T> {
+        self.0.take_all().await
     }
 }
 
@@ -44,11 +52,15 @@ where
         self.0.recv(true).await.unwrap()
     }
 
-    pub async fn len(&self) -> (usize, usize)
+    pub async fn drain(&mut self, max: usize) -> Vec<T> {
+        self.0.drain(max).await
+    }
+
+    pub fn len(&self) -> (usize, usize)
     where
         T: Unpin,
     {
-        self.0.len().await
+        self.0.len()
     }
 }
 
@@ -1,7 +1,10 @@
+use std::time::Duration;
+
 use tokio::sync::oneshot;
 
 use crate::actor_id::ActorID;
 use crate::exit::Exit;
+use crate::spawn_opts::Priority;
 
 use super::Backend;
 
@@ -10,7 +13,24 @@ pub enum SysMsg {
     Link(ActorID),
     Unlink(ActorID),
     SigExit(ActorID, Exit),
+    /// The system is gone: exit regardless of trap-exit.
+    SystemDropped,
+    /// The link to the actor could not be established, as it is not running.
+    LinkFailed(ActorID),
     GetInfo(oneshot::Sender<ActorInfo>),
+    PeekInbox(usize, oneshot::Sender<Vec<String>>),
+    SetTrace(TraceFlags),
+    Sync(oneshot::Sender<()>),
+}
+
+/// Which events delivered to an actor should be traced.
+///
+/// See [`System::set_trace(&self, ActorID, TraceFlags)`](crate::system::System::set_trace).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceFlags {
+    pub messages: bool,
+    pub signals: bool,
 }
 
 /// Information about a running actor.
@@ -21,6 +41,15 @@ pub enum SysMsg {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ActorInfo {
     pub actor_id: ActorID,
+    /// See [`Context::parent`](crate::Context::parent).
+    pub parent: Option<ActorID>,
+    /// The name the actor is [registered](crate::System::register) under (the first one in the
+    /// lexicographic order, should there be several).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
+    /// See [`SpawnOpts::with_label`](crate::SpawnOpts::with_label).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label: Option<String>,
     pub behaviour: &'static str,
     pub args_type: &'static str,
     pub message_type: &'static str,
@@ -28,11 +57,19 @@ pub struct ActorInfo {
     pub s_queue_len: (usize, usize),
     pub c_queue_len: (usize, usize),
     pub tasks_count: usize,
+    pub priority: Priority,
     pub trap_exit: bool,
     pub links: Box<[ActorID]>,
+    /// The actors this actor monitors (see [`Context::monitor_fut`](crate::Context::monitor_fut)).
+    pub monitors: Box<[ActorID]>,
+    /// The actors monitoring this actor.
+    pub monitored_by: Box<[ActorID]>,
+    /// The longest time the behaviour has spent handling a single event, if measured (see
+    /// [`SpawnOpts::with_slow_handler_threshold`](crate::SpawnOpts::with_slow_handler_threshold)).
+    pub max_handler_time: Option<Duration>,
 }
 
-impl<M> Backend<M> {
+impl<M, C> Backend<M, C> {
     pub(super) async fn send_sys_msg(&self, to: ActorID, sys_msg: SysMsg) -> bool {
         if let Some(system) = self.system_opt.rc_upgrade() {
             system.send_sys_msg(to, sys_msg).await
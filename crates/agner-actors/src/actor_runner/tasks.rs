@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+
+use futures::stream::FuturesUnordered;
+use futures::{Stream, StreamExt};
+
+use crate::exit::Exit;
+
+use super::call_msg::Job;
+
+type Outcome<M> = Result<Option<M>, Exit>;
+
+/// The jobs run by the actor-backend.
+///
+/// The outcomes of the unordered jobs are yielded in the order the jobs complete. The outcomes of
+/// the ordered jobs are yielded in the order the jobs were submitted: each ordered job is tagged
+/// with its index, and an outcome that is ready ahead of its turn is buffered until the outcomes
+/// of all the ordered jobs submitted before it are yielded.
+pub(super) struct Tasks<M> {
+    unordered: FuturesUnordered<Job<M>>,
+    ordered: FuturesUnordered<TaggedJob<M>>,
+    /// The outcomes of the ordered jobs, that are ready ahead of their turn. Only ever accessed
+    /// via `get_mut`: the mutex is there to keep the backend `Sync` for the messages that are not.
+    ordered_ready: Mutex<BTreeMap<u64, Outcome<M>>>,
+    ordered_submitted: u64,
+    ordered_yielded: u64,
+}
+
+impl<M> Tasks<M> {
+    pub fn push(&mut self, job: Job<M>) {
+        self.unordered.push(job);
+    }
+
+    pub fn push_ordered(&mut self, job: Job<M>) {
+        let idx = self.ordered_submitted;
+        self.ordered_submitted += 1;
+        self.ordered.push(TaggedJob(idx, job));
+    }
+}
+
+impl<M> Tasks<M> {
+    pub fn len(&self) -> usize {
+        self.unordered.len() + (self.ordered_submitted - self.ordered_yielded) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<M> Default for Tasks<M> {
+    fn default() -> Self {
+        Self {
+            unordered: Default::default(),
+            ordered: Default::default(),
+            ordered_ready: Default::default(),
+            ordered_submitted: 0,
+            ordered_yielded: 0,
+        }
+    }
+}
+
+impl<M> Stream for Tasks<M> {
+    type Item = Outcome<M>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        if this.is_empty() {
+            return Poll::Ready(None)
+        }
+
+        let ordered_ready = this.ordered_ready.get_mut().expect("poisoned");
+        if let Some(outcome) = ordered_ready.remove(&this.ordered_yielded) {
+            this.ordered_yielded += 1;
+            return Poll::Ready(Some(outcome))
+        }
+
+        if let Poll::Ready(Some(outcome)) = this.unordered.poll_next_unpin(cx) {
+            return Poll::Ready(Some(outcome))
+        }
+
+        while let Poll::Ready(Some((idx, outcome))) = this.ordered.poll_next_unpin(cx) {
+            if idx == this.ordered_yielded {
+                this.ordered_yielded += 1;
+                return Poll::Ready(Some(outcome))
+            }
+            ordered_ready.insert(idx, outcome);
+        }
+
+        Poll::Pending
+    }
+}
+
+/// An ordered job, tagged with its index.
+struct TaggedJob<M>(u64, Job<M>);
+
+impl<M> Future for TaggedJob<M> {
+    type Output = (u64, Outcome<M>);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let idx = self.0;
+        self.1.as_mut().poll(cx).map(|outcome| (idx, outcome))
+    }
+}
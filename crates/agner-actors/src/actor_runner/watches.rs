@@ -1,4 +1,5 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
 
 use crate::actor_id::ActorID;
 
@@ -8,9 +9,51 @@ use super::*;
 pub(crate) struct Watches {
     pub trap_exit: bool,
     pub links: HashSet<ActorID>,
+    /// The linked actors found not running: the exit-signals from them are yet to be delivered.
+    ///
+    /// The delivery is deferred until the backend is done with the queued calls, so that the
+    /// behaviour unlinking right after linking does not receive the signal.
+    pub link_failures: HashSet<ActorID>,
+    pub monitors: Monitors,
+    pub trace: TraceFlags,
 }
 
-impl<M> Backend<M> {
+/// The actors monitored by this actor via [`Context::monitor_fut`].
+///
+/// Shared between the context (which installs the monitors) and the backend (which reports them).
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Monitors(Arc<Mutex<HashMap<ActorID, usize>>>);
+
+/// Keeps the target listed in [`Monitors`] while the monitor is alive.
+#[derive(Debug)]
+pub(crate) struct MonitorGuard(Monitors, ActorID);
+
+impl Monitors {
+    pub fn track(&self, target: ActorID) -> MonitorGuard {
+        *self.0.lock().expect("poisoned").entry(target).or_default() += 1;
+        MonitorGuard(self.to_owned(), target)
+    }
+
+    pub fn list(&self) -> Vec<ActorID> {
+        let mut monitors = self.0.lock().expect("poisoned").keys().copied().collect::<Vec<_>>();
+        monitors.sort();
+        monitors
+    }
+}
+
+impl Drop for MonitorGuard {
+    fn drop(&mut self) {
+        let mut monitors = (self.0).0.lock().expect("poisoned");
+        if let Some(count) = monitors.get_mut(&self.1) {
+            *count -= 1;
+            if *count == 0 {
+                monitors.remove(&self.1);
+            }
+        }
+    }
+}
+
+impl<M, C> Backend<M, C> {
     #[tracing::instrument(skip_all, fields(
         actor_id = display(self.actor_id),
         exit_reason = display(exit_reason.pp())
@@ -36,7 +79,7 @@ impl<M> Backend<M> {
             tracing::trace!("linking to {}", link_to);
 
             if !self.send_sys_msg(link_to, SysMsg::Link(self.actor_id)).await {
-                let _ = self.sys_msg_tx.send(SysMsg::SigExit(link_to, Exit::no_actor()));
+                self.watches.link_failures.insert(link_to);
             }
         }
     }
@@ -46,6 +89,7 @@ impl<M> Backend<M> {
         unlink_from = display(unlink_from)
     ))]
     pub(super) async fn do_unlink(&mut self, unlink_from: ActorID) {
+        self.watches.link_failures.remove(&unlink_from);
         if self.watches.links.remove(&unlink_from) {
             tracing::trace!("[{}] unlinking from {}", self.actor_id, unlink_from);
 
@@ -53,6 +97,15 @@ impl<M> Backend<M> {
         }
     }
 
+    #[tracing::instrument(skip(self), fields(
+        actor_id = display(self.actor_id)
+    ))]
+    pub(super) fn handle_sys_msg_set_trace(&mut self, trace: TraceFlags) -> Result<(), Exit> {
+        tracing::trace!("trace = {:?}", trace);
+        self.watches.trace = trace;
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self), fields(
         actor_id = display(self.actor_id)
     ))]
@@ -82,6 +135,53 @@ impl<M> Backend<M> {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(
+        actor_id = display(self.actor_id),
+        count = link_to.len()
+    ))]
+    pub(super) async fn handle_call_link_all(&mut self, link_to: Vec<ActorID>) -> Result<(), Exit> {
+        for link_to in link_to {
+            self.do_link(link_to).await;
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(
+        actor_id = display(self.actor_id),
+        count = unlink_from.len()
+    ))]
+    pub(super) async fn handle_call_unlink_all(
+        &mut self,
+        unlink_from: Vec<ActorID>,
+    ) -> Result<(), Exit> {
+        for unlink_from in unlink_from {
+            self.do_unlink(unlink_from).await;
+        }
+        Ok(())
+    }
+
+    /// The actors waiting for this one to terminate via [`Context::monitor_fut`].
+    pub(super) async fn monitored_by(&self) -> Vec<ActorID> {
+        let Some(system) = self.system_opt.rc_upgrade() else { return vec![] };
+        let Some(entry) = system.actor_entry_read(self.actor_id).await else { return vec![] };
+        entry.watchers()
+    }
+
+    pub(super) fn registered_name(&self) -> Option<String> {
+        self.system_opt.rc_upgrade()?.registered_name(self.actor_id)
+    }
+
+    #[tracing::instrument(skip_all, fields(
+        actor_id = display(self.actor_id)
+    ))]
+    pub(super) fn handle_call_get_links(
+        &mut self,
+        reply_to: oneshot::Sender<Vec<ActorID>>,
+    ) -> Result<(), Exit> {
+        let _ = reply_to.send(self.watches.links.iter().copied().collect());
+        Ok(())
+    }
+
     #[tracing::instrument(skip_all, fields(
         actor_id = display(self.actor_id),
         receiver_id = display(receiver_id),
@@ -92,6 +192,18 @@ impl<M> Backend<M> {
         receiver_id: ActorID,
         exit_reason: Exit,
     ) -> Result<(), Exit> {
+        if self.watches.trace.signals {
+            tracing::event!(
+                Level::INFO,
+                actor = %self.actor_id,
+                behaviour = self.actor_type_info.0,
+                event = "exit-signal-delivered",
+                from = %receiver_id,
+                reason = %exit_reason.pp(),
+                at = ?std::time::SystemTime::now(),
+                "[trace] exit-signal delivered"
+            );
+        }
         if receiver_id == self.actor_id || self.watches.links.remove(&receiver_id) {
             tracing::trace!(
                 "[{}] Received SigExit({}, ..) [trap-exit: {}]",
@@ -134,7 +246,26 @@ impl<M> Backend<M> {
         unlink_from = display(unlink_from)
     ))]
     pub(super) async fn handle_sys_msg_unlink(&mut self, unlink_from: ActorID) -> Result<(), Exit> {
+        self.watches.link_failures.remove(&unlink_from);
         self.watches.links.remove(&unlink_from);
         Ok(())
     }
+
+    #[tracing::instrument(skip_all, fields(
+        actor_id = display(self.actor_id),
+        link_to = display(link_to)
+    ))]
+    pub(super) fn handle_sys_msg_link_failed(&mut self, link_to: ActorID) -> Result<(), Exit> {
+        self.watches.link_failures.insert(link_to);
+        Ok(())
+    }
+
+    /// Deliver the exit-signals from the actors the links to which have failed (unless unlinked
+    /// since).
+    pub(super) async fn handle_link_failures(&mut self) -> Result<(), Exit> {
+        for link_to in std::mem::take(&mut self.watches.link_failures) {
+            self.handle_sys_msg_sig_exit(link_to, Exit::no_actor()).await?;
+        }
+        Ok(())
+    }
 }
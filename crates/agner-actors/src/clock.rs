@@ -0,0 +1,87 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use tokio::sync::watch;
+
+/// `Clock` is the source of the current time for the actors.
+///
+/// It is possible to specify the clock for a [`System`](crate::system::System) via
+/// [`SystemConfig::clock`](crate::system_config::SystemConfig::clock). The actors read it via
+/// [`Context::now_instant`](crate::context::Context::now_instant) and
+/// [`Context::now_system`](crate::context::Context::now_system), and the timers of the actors (e.g.
+/// [`Context::deadline`](crate::context::Context::deadline)) fire by it.
+pub trait Clock: fmt::Debug + Send + Sync + 'static {
+    fn now_instant(&self) -> Instant;
+    fn now_system(&self) -> SystemTime;
+
+    /// Wait until the clock reaches the instant `at`.
+    ///
+    /// By default, waits for the time to pass as measured by the tokio timer.
+    fn sleep_until(&self, at: Instant) -> Pin<Box<dyn Future<Output = ()> + Send + Sync>> {
+        let timeout = at.saturating_duration_since(self.now_instant());
+        Box::pin(tokio::time::sleep(timeout))
+    }
+}
+
+/// A [`Clock`](crate::clock::Clock) reading the actual time.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_instant(&self) -> Instant {
+        Instant::now()
+    }
+    fn now_system(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A [`Clock`](crate::clock::Clock) that stands still unless [advanced](MockClock::advance).
+///
+/// The clones share the same time. The [sleeps](Clock::sleep_until) complete as the clock is
+/// advanced past their instants.
+#[derive(Debug, Clone)]
+pub struct MockClock(Arc<watch::Sender<(Instant, SystemTime)>>);
+
+impl MockClock {
+    /// Create a clock stopped at the current time.
+    pub fn new() -> Self {
+        let (now, _) = watch::channel((Instant::now(), SystemTime::now()));
+        Self(Arc::new(now))
+    }
+
+    /// Move the clock forward.
+    pub fn advance(&self, by: Duration) {
+        self.0.send_modify(|now| {
+            now.0 += by;
+            now.1 += by;
+        });
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now_instant(&self) -> Instant {
+        self.0.borrow().0
+    }
+    fn now_system(&self) -> SystemTime {
+        self.0.borrow().1
+    }
+    fn sleep_until(&self, at: Instant) -> Pin<Box<dyn Future<Output = ()> + Send + Sync>> {
+        let mut now = self.0.subscribe();
+        Box::pin(async move {
+            // the clock is gone: it is never going to reach the instant
+            if now.wait_for(|(now, _)| *now >= at).await.is_err() {
+                std::future::pending().await
+            }
+        })
+    }
+}
@@ -1,23 +1,54 @@
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant, SystemTime};
 
-use futures::Future;
+use futures::{future, stream, Future, Stream};
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
 
+use crate::actor::Actor;
 use crate::actor_id::ActorID;
 use crate::actor_runner::call_msg::CallMsg;
 use crate::actor_runner::pipe::{PipeRx, PipeTx};
+use crate::actor_runner::{BackendView, HandlerTimer, Monitors};
 use crate::exit::Exit;
 use crate::imports::Never;
-use crate::system::{System, SystemWeakRef};
+use crate::spawn_opts::SpawnOpts;
+use crate::system::{
+    Envelope, SysCallError, SysChannelError, SysSpawnError, System, SystemWeakRef,
+};
+
+mod log;
+pub use log::ActorLog;
+
+mod monitor_ref;
+pub use monitor_ref::MonitorRef;
+
+mod select;
+pub use select::{Select, Selected};
 
 /// Actor's API to itself
+///
+/// `C` is the type of the control messages, received ahead of the inbox (see
+/// [`Context::next_control`]).
 #[derive(Debug)]
-pub struct Context<M> {
+pub struct Context<M, C = Never> {
     actor_id: ActorID,
+    parent: Option<ActorID>,
+    behaviour: &'static str,
     system: SystemWeakRef,
-    messages: PipeRx<M>,
+    messages: PipeRx<Envelope<M>>,
+    sender: Option<ActorID>,
+    inbox_len: (usize, usize),
     signals: PipeRx<Signal>,
     calls: PipeTx<CallMsg<M>>,
+    reinit: mpsc::UnboundedSender<Box<dyn Any + Send>>,
+    monitors: Monitors,
+    handler_timer: Option<HandlerTimer>,
+    handling_since: Option<Instant>,
+    cancellation_token: CancellationToken,
+    controls: PipeRx<Envelope<C>>,
     data: HashMap<TypeId, Box<dyn Any + Send + Sync + 'static>>,
 }
 
@@ -30,6 +61,14 @@ pub enum Event<M> {
     Signal(Signal),
 }
 
+/// Either a control message or an [`Event`] received by an actor (see
+/// [`Context::next_prioritized`](crate::context::Context::next_prioritized)).
+#[derive(Debug)]
+pub enum Prioritized<C, M> {
+    Control(C),
+    Event(Event<M>),
+}
+
 /// A signal received by an actor.
 ///
 /// Note: only actors that ["trap exits"](crate::context::Context::trap_exit) can handle signals.
@@ -38,30 +77,116 @@ pub enum Signal {
     Exit(ActorID, Exit),
 }
 
-impl<M> Context<M> {
+impl<M, C> Context<M, C> {
     /// Get current actor's [`ActorID`]
     pub fn actor_id(&self) -> ActorID {
         self.actor_id
     }
 
+    /// The actor that has spawned this one, if specified upon spawn (see
+    /// [`SpawnOpts::with_parent`](crate::spawn_opts::SpawnOpts::with_parent)).
+    ///
+    /// The supervisors specify themselves as the parent of their children.
+    pub fn parent(&self) -> Option<ActorID> {
+        self.parent
+    }
+
+    /// A logger tagged with this actor's id and behaviour.
+    ///
+    /// ```
+    /// use agner_actors::{actor_info, Context};
+    ///
+    /// async fn connection(context: &mut Context<()>, peer: String) {
+    ///     actor_info!(context.log(), "connected to {}", peer);
+    /// }
+    /// ```
+    pub fn log(&self) -> ActorLog {
+        ActorLog::new(self.actor_id, self.behaviour)
+    }
+
+    /// A token that is cancelled once this actor begins its termination — for whatever reason,
+    /// including a kill or a panic.
+    ///
+    /// Meant for the tasks this actor spawns on its own (i.e. not via
+    /// [`Context::future_to_inbox`] or [`Context::spawn_job`]): those can observe the token and
+    /// stop promptly. The returned token is a [child](CancellationToken::child_token) of the
+    /// actor's one: cancelling it does not affect the actor, nor the other tokens.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.child_token()
+    }
+
     /// Get the [`System`] this actor is running in.
     pub fn system(&self) -> System {
         self.system.rc_upgrade().expect("System gone")
     }
 
+    /// The current [`Instant`], according to the [clock](crate::clock::Clock) of the system.
+    pub fn now_instant(&self) -> Instant {
+        self.system().config().clock.now_instant()
+    }
+
+    /// The current [`SystemTime`], according to the [clock](crate::clock::Clock) of the system.
+    pub fn now_system(&self) -> SystemTime {
+        self.system().config().clock.now_system()
+    }
+
+    /// A future resolving upon termination of the `target` actor into its [`Exit`].
+    ///
+    /// Unlike [`Context::link`] it does not cause any exit-signals to be delivered. The future
+    /// does not borrow the context, hence it can be polled alongside [`Context::next_event`] (e.g.
+    /// in a `select!`), or routed back to the inbox via [`Context::future_to_inbox`].
+    pub fn monitor_fut(
+        &self,
+        target: ActorID,
+    ) -> impl Future<Output = Exit> + Send + Sync + 'static {
+        let monitor_guard = self.monitors.track(target);
+        let wait = self.system().wait_from(Some(self.actor_id), target);
+        async move {
+            let exit = wait.await;
+            drop(monitor_guard);
+            exit
+        }
+    }
+
+    /// Spawn an actor, and monitor it (see [`Context::monitor_fut`]).
+    ///
+    /// The monitor is installed before the actor starts, so that its exit is not missed even if
+    /// it terminates right away.
+    pub async fn spawn_monitor<Behaviour, Args, Message, Control>(
+        &mut self,
+        behaviour: Behaviour,
+        args: Args,
+        spawn_opts: SpawnOpts,
+    ) -> Result<(ActorID, MonitorRef), SysSpawnError>
+    where
+        Args: Send + 'static,
+        Message: Unpin + Send + 'static,
+        Control: Unpin + Send + 'static,
+        for<'a> Behaviour: Actor<'a, Args, Message, Control>,
+    {
+        let (actor_id, exit_rx) =
+            self.system().spawn_watched(self.actor_id, behaviour, args, spawn_opts).await?;
+        let monitor_guard = self.monitors.track(actor_id);
+        Ok((actor_id, MonitorRef::new(actor_id, monitor_guard, exit_rx)))
+    }
+
     /// Receive next event (message or signal)
     pub async fn next_event(&mut self) -> Event<M>
     where
         M: Unpin,
     {
-        tokio::select! {
+        self.handler_done();
+        let event = tokio::select! {
             biased;
 
             signal = self.signals.recv() =>
                 Event::Signal(signal),
-            message = self.messages.recv() =>
-                Event::Message(message),
-        }
+            envelope = self.messages.recv() =>
+                Event::Message(self.take_envelope(envelope)),
+        };
+        self.refresh_inbox_len();
+        self.handler_started();
+        event
     }
 
     /// Receive next message.
@@ -69,20 +194,184 @@ impl<M> Context<M> {
     where
         M: Unpin,
     {
-        self.messages.recv().await
+        self.handler_done();
+        let envelope = self.messages.recv().await;
+        let message = self.take_envelope(envelope);
+        self.refresh_inbox_len();
+        self.handler_started();
+        message
+    }
+
+    /// Receive next control message (see [`System::send_control`]).
+    ///
+    /// The control messages are delivered into a lane of their own: they are not queued behind
+    /// the messages in the inbox.
+    pub async fn next_control(&mut self) -> C
+    where
+        C: Unpin,
+    {
+        self.handler_done();
+        let envelope = self.controls.recv().await;
+        let control = self.take_envelope(envelope);
+        self.handler_started();
+        control
+    }
+
+    /// Receive next event (signal, control message, or message) — in that order of priority:
+    /// while there are signals or control messages pending, the inbox is not read.
+    pub async fn next_prioritized(&mut self) -> Prioritized<C, M>
+    where
+        M: Unpin,
+        C: Unpin,
+    {
+        self.handler_done();
+        let prioritized = tokio::select! {
+            biased;
+
+            signal = self.signals.recv() =>
+                Prioritized::Event(Event::Signal(signal)),
+            envelope = self.controls.recv() =>
+                Prioritized::Control(self.take_envelope(envelope)),
+            envelope = self.messages.recv() =>
+                Prioritized::Event(Event::Message(self.take_envelope(envelope))),
+        };
+        self.refresh_inbox_len();
+        self.handler_started();
+        prioritized
+    }
+
+    /// The sender of the most recently received message (in case of
+    /// [`Context::recv_batch`] — of the last message in the batch).
+    ///
+    /// The sender is known for the messages sent via [`Context::send`] (and alike), or via
+    /// [`System::send_from`]; it is `None` for the messages sent via [`System::send`], or routed
+    /// via [`Context::future_to_inbox`].
+    pub fn sender(&self) -> Option<ActorID> {
+        self.sender
+    }
+
+    /// The number of messages in the inbox of this actor.
+    ///
+    /// The value is refreshed upon each [`Context::next_event`], [`Context::next_message`], and
+    /// [`Context::recv_batch`], i.e. it does not account for the messages that have arrived since.
+    pub fn inbox_len(&self) -> usize {
+        self.inbox_len.0
+    }
+
+    /// The maximum number of messages the inbox of this actor can hold
+    /// (see [`SpawnOpts::with_msg_inbox_size`](crate::SpawnOpts::with_msg_inbox_size)).
+    pub fn inbox_capacity(&self) -> usize {
+        self.inbox_len.1
+    }
+
+    /// Receive up to `max` messages at once.
+    ///
+    /// Waits for the first message, and then takes the messages that are immediately available,
+    /// without waiting for more. The signals are not consumed by this call, and remain available
+    /// via [`Context::next_signal`] or [`Context::next_event`].
+    pub async fn recv_batch(&mut self, max: usize) -> Vec<M>
+    where
+        M: Unpin,
+    {
+        if max == 0 {
+            return vec![]
+        }
+        self.handler_done();
+        let first = self.messages.recv().await;
+        let mut batch = Vec::with_capacity(max);
+        batch.push(self.take_envelope(first));
+        for envelope in self.messages.drain(max - 1).await {
+            batch.push(self.take_envelope(envelope));
+        }
+        self.refresh_inbox_len();
+        self.handler_started();
+        batch
+    }
+
+    /// The messages received by this actor as a [`Stream`].
+    ///
+    /// The stream yields messages only: the signals are not consumed by it, and remain available
+    /// via [`Context::next_signal`] or [`Context::next_event`] once the stream is dropped.
+    /// Note that only the actors that ["trap exits"](crate::context::Context::trap_exit) receive
+    /// signals at all — the others are terminated upon an exit-signal regardless of whether they
+    /// are polling the stream.
+    pub fn message_stream(&mut self) -> impl Stream<Item = M> + '_
+    where
+        M: Unpin,
+    {
+        stream::unfold(self, |context| async move {
+            let message = context.next_message().await;
+            Some((message, context))
+        })
+    }
+
+    /// Await the next event alongside some other futures (e.g. a read from a socket).
+    ///
+    /// Unlike a hand-written `select!` over [`Context::next_event`] and those futures, this keeps
+    /// the signals first in line: a [trapped](Context::trap_exit) exit-signal is returned as soon
+    /// as it arrives, even if the registered futures are ready too. (The actors that do not trap
+    /// exits need no help here: an exit-signal terminates such actor, dropping whatever its
+    /// behaviour has been awaiting.)
+    ///
+    /// Example:
+    /// ```
+    /// use agner_actors::{Context, Event, Selected};
+    /// use tokio::sync::mpsc;
+    ///
+    /// async fn relay(context: &mut Context<String>, mut external: mpsc::Receiver<String>) {
+    ///     context.trap_exit(true).await;
+    ///     loop {
+    ///         match context.select().with(external.recv()).await {
+    ///             Selected::Ready(Some(line)) => eprintln!("external: {}", line),
+    ///             Selected::Ready(None) => break,
+    ///             Selected::Event(Event::Message(message)) => eprintln!("received: {}", message),
+    ///             Selected::Event(Event::Signal(signal)) => {
+    ///                 eprintln!("signal: {:?}", signal);
+    ///                 break
+    ///             },
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn select<T>(&mut self) -> Select<'_, M, T, C> {
+        Select::new(self)
     }
 
     /// Receive next signal.
     pub async fn next_signal(&mut self) -> Signal {
-        self.signals.recv().await
+        self.handler_done();
+        let signal = self.signals.recv().await;
+        self.handler_started();
+        signal
     }
 
-    /// Exit with the provided reason
+    /// Exit with the provided reason.
+    ///
+    /// Never returns: the behaviour is dropped once the actor's backend has completed the cleanup
+    /// (see [`System::wait`] for the guarantees regarding its sequencing).
     pub async fn exit(&mut self, exit_reason: Exit) -> Never {
         self.backend_call(CallMsg::Exit(exit_reason)).await;
         std::future::pending().await
     }
 
+    /// Re-run the behaviour function of this actor with the new `args`, discarding the local
+    /// state of the current invocation.
+    ///
+    /// The [`ActorID`], the links, the registered names, the trap-exit flag, the context data,
+    /// and the background jobs (see [`Context::future_to_inbox`]) are kept. The queued messages
+    /// and signals are preserved too: the new invocation receives them in their original order.
+    ///
+    /// The actor should have been spawned via
+    /// [`System::spawn_rerunnable`](crate::system::System::spawn_rerunnable), and `A` should be
+    /// the type of the arguments the actor has been spawned with; otherwise the actor exits.
+    pub async fn reinit<A>(&mut self, args: A) -> Never
+    where
+        A: Send + 'static,
+    {
+        let _ = self.reinit.send(Box::new(args));
+        std::future::pending().await
+    }
+
     /// Link this actor to another actor.
     pub async fn link(&mut self, to: ActorID) {
         self.backend_call(CallMsg::Link(to)).await;
@@ -93,25 +382,270 @@ impl<M> Context<M> {
         self.backend_call(CallMsg::Unlink(from)).await;
     }
 
+    /// Link this actor to each of the `others` at once.
+    ///
+    /// As with [`Context::link`], linking to an actor that does not exist results in an
+    /// exit-signal with [`Exit::no_actor()`].
+    pub async fn link_all(&mut self, others: impl IntoIterator<Item = ActorID>) {
+        self.backend_call(CallMsg::LinkAll(others.into_iter().collect())).await;
+    }
+
+    /// Unlink this actor from each of the `others` at once.
+    pub async fn unlink_all(&mut self, others: impl IntoIterator<Item = ActorID>) {
+        self.backend_call(CallMsg::UnlinkAll(others.into_iter().collect())).await;
+    }
+
+    /// The actors this actor is currently linked to.
+    pub async fn links(&mut self) -> Vec<ActorID> {
+        let (tx, rx) = oneshot::channel();
+        self.backend_call(CallMsg::GetLinks(tx)).await;
+        rx.await.unwrap_or_default()
+    }
+
+    /// Run `f` within the actor's backend, and return its result.
+    ///
+    /// The [`BackendView`] lets the closure inspect and modify the links, the monitors and the
+    /// trap-exit flag atomically: e.g. to unlink from every linked actor but one, with no
+    /// exit-signal handled in between. Should `f` panic, the actor exits with
+    /// [`Exit::from_panic`].
+    pub async fn with_backend<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut BackendView<'_>) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        self.backend_call(CallMsg::Custom(Box::new(move |view| {
+            let _ = tx.send(f(view));
+        })))
+        .await;
+        match rx.await {
+            Ok(output) => output,
+            // the backend is gone: the behaviour is about to be dropped
+            Err(_) => future::pending().await,
+        }
+    }
+
+    /// Send a clone of `message` to each actor this actor is linked to (see
+    /// [`send_many`](Self::send_many)).
+    ///
+    /// The linked actors that do not accept messages of type `T` are skipped.
+    /// Returns the number of actors the message has been delivered to.
+    pub async fn broadcast_to_links<T>(&mut self, message: T) -> usize
+    where
+        T: Clone + Send + 'static,
+    {
+        let links = self.links().await;
+        self.send_many(&links, message).await
+    }
+
+    /// Send a clone of `message` to each of the `targets`.
+    ///
+    /// Unlike a series of [`System::send`] calls, this does not yield between the sends (unless
+    /// the system is spawning or terminating one of the `targets` at the very moment).
+    /// Returns the number of actors the message has been delivered to.
+    pub async fn send_many<T>(&self, targets: &[ActorID], message: T) -> usize
+    where
+        T: Clone + Send + 'static,
+    {
+        self.system().send_many_from(Some(self.actor_id), targets, message).await
+    }
+
+    /// Send `message` to the actor `to` on behalf of this actor.
+    ///
+    /// Unlike [`System::send`], the message is attributed to this actor, hence is subject to the
+    /// [sender quota](crate::spawn_opts::SpawnOpts::with_sender_quota) of the receiver.
+    pub async fn send<T>(&self, to: ActorID, message: T) -> Result<(), SysChannelError>
+    where
+        T: Send + 'static,
+    {
+        self.system().try_send_from(Some(self.actor_id), to, message).await
+    }
+
+    /// Send a control message to the actor `to` on behalf of this actor (see
+    /// [`System::send_control`]).
+    pub async fn send_control<T>(&self, to: ActorID, control: T) -> Result<(), SysChannelError>
+    where
+        T: Send + 'static,
+    {
+        self.system().send_control_from(Some(self.actor_id), to, control).await
+    }
+
+    /// Send `message` to the actor bound to the `name` (see [`System::register`]).
+    ///
+    /// Returns [`SysChannelError::NoActor`] if the name is not bound to any actor.
+    pub async fn send_named<T>(&self, name: &str, message: T) -> Result<(), SysChannelError>
+    where
+        T: Send + 'static,
+    {
+        let system = self.system();
+        let to = system.whereis(name).await.ok_or(SysChannelError::NoActor)?;
+        system.try_send_from(Some(self.actor_id), to, message).await
+    }
+
+    /// Send a request produced by `make_req` to the actor `to`, and wait for the reply sent via
+    /// the provided [`oneshot::Sender`]. The `timeout` is measured by the
+    /// [clock](crate::clock::Clock) of the system.
+    ///
+    /// The callee is monitored for the duration of the call: should it terminate before replying,
+    /// the call fails with [`SysCallError::CalleeDown`] right away, rather than upon the `timeout`.
+    /// A callee that drops the reply-sender without replying is treated as one that never replies.
+    ///
+    /// The actor remains responsive while waiting: the exit-signals are processed by the actor's
+    /// backend, hence an actor that does not [trap exits](Context::trap_exit) terminates mid-call
+    /// as usual; for an actor that does, the signals (as well as the messages) received meanwhile
+    /// stay queued and are available via [`Context::next_event`] after the call.
+    pub async fn call<Req, Rep, F>(
+        &mut self,
+        to: ActorID,
+        make_req: F,
+        timeout: Duration,
+    ) -> Result<Rep, SysCallError>
+    where
+        F: FnOnce(oneshot::Sender<Rep>) -> Req,
+        Req: Send + 'static,
+    {
+        let deadline = self.now_instant() + timeout;
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let callee_down = self.monitor_fut(to);
+        self.send(to, make_req(reply_tx)).await.map_err(SysCallError::Send)?;
+
+        let reply = async move {
+            match reply_rx.await {
+                Ok(reply) => reply,
+                Err(_) => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            biased;
+
+            reply = reply => Ok(reply),
+            exit = callee_down => Err(SysCallError::CalleeDown(exit)),
+            () = self.system().config().clock.sleep_until(deadline) => Err(SysCallError::Timeout),
+        }
+    }
+
+    /// Yield control to the executor and let the actor's backend catch up.
+    ///
+    /// The backend processes the pending system messages (e.g. exit-signals) before resuming the
+    /// behaviour, so a long computation interspersed with `yield_now` remains responsive to
+    /// shutdown.
+    pub async fn yield_now(&mut self) {
+        let (tx, rx) = oneshot::channel();
+        self.backend_call(CallMsg::Yield(tx)).await;
+        if rx.await.is_err() {
+            // the backend is gone: the behaviour is about to be dropped
+            future::pending().await
+        }
+    }
+
     /// Set whether this actor upon receiving a [`Signal`](crate::context::Signal) will be able to
     /// handle it (`trap_exit = true`) or crash (`trap_exit = false`).
     pub async fn trap_exit(&mut self, trap_exit: bool) {
         self.backend_call(CallMsg::TrapExit(trap_exit)).await;
     }
 
+    /// Allow the messages queued in this actor's inbox to be previewed via
+    /// [`System::peek_messages`](crate::system::System::peek_messages).
+    ///
+    /// Only the actors whose message type implements [`Debug`](std::fmt::Debug) can opt into
+    /// this; for the others `peek_messages` returns an empty list.
+    pub async fn enable_inbox_peek(&mut self)
+    where
+        M: fmt::Debug,
+    {
+        self.backend_call(CallMsg::EnableInboxPeek(|message| format!("{:?}", message)))
+            .await;
+    }
+
+    /// Install a handler to which, upon this actor's exit, the messages remaining in the inbox will
+    /// be passed (in the order of their arrival).
+    ///
+    /// The handler is only invoked if the actor was spawned with
+    /// [`SpawnOpts::with_drain_on_exit(true)`](crate::spawn_opts::SpawnOpts::with_drain_on_exit),
+    /// and the exit reason is not [`Exit::kill()`].
+    pub async fn on_drain<F>(&mut self, drain_handler: F)
+    where
+        F: FnMut(M) + Send + Sync + 'static,
+    {
+        self.backend_call(CallMsg::SetDrainHandler(Box::new(drain_handler))).await;
+    }
+
+    /// Register a function to be invoked with the exit reason upon this actor's termination.
+    ///
+    /// The deferred functions are invoked in the reverse order of their registration, regardless
+    /// of the exit reason (including [`Exit::kill()`]).
+    /// They are invoked after the inbox has been passed to the
+    /// [drain-handler](Context::on_drain) (if any).
+    pub async fn defer<F>(&mut self, deferred: F)
+    where
+        F: FnOnce(&Exit) + Send + Sync + 'static,
+    {
+        self.backend_call(CallMsg::Defer(Box::new(deferred))).await;
+    }
+
+    /// Terminate this actor once the `parent` terminates, with the exit reason produced by
+    /// `on_parent_down` from the parent's one.
+    ///
+    /// Unlike [`Context::link`], this works regardless of [`Context::trap_exit`], and allows to
+    /// choose the exit reason. As with any other exit, the [deferred](Context::defer) functions are
+    /// invoked.
+    pub async fn die_with_parent<F>(&mut self, parent: ActorID, on_parent_down: F)
+    where
+        F: FnOnce(&Exit) -> Exit + Send + Sync + 'static,
+    {
+        let parent_down = self.monitor_fut(parent);
+        self.backend_call(CallMsg::SpawnJob(Box::pin(async move {
+            Err(on_parent_down(&parent_down.await))
+        })))
+        .await
+    }
+
+    /// Shut this actor down once the sender of `tie` is either used or dropped.
+    ///
+    /// This allows to scope the lifetime of an actor to that of the value holding the sender (e.g.
+    /// a handle owned by the code outside of the actor system). As with
+    /// [`Context::die_with_parent`], this works regardless of [`Context::trap_exit`].
+    pub async fn tie_lifetime_to(&mut self, tie: oneshot::Receiver<()>) {
+        self.backend_call(CallMsg::SpawnJob(Box::pin(async move {
+            let _ = tie.await;
+            Err(Exit::shutdown())
+        })))
+        .await
+    }
+
+    /// Arm a timer: should the actor still run at the instant `at`, it exits with
+    /// [`Exit::timeout()`].
+    ///
+    /// The timer fires by the [clock](crate::clock::Clock) of the system (see
+    /// [`Clock::sleep_until`](crate::clock::Clock::sleep_until)).
+    pub async fn deadline(&mut self, at: Instant) {
+        let reached = self.system().config().clock.sleep_until(at);
+        self.backend_call(CallMsg::SpawnJob(Box::pin(async move {
+            reached.await;
+            Err(Exit::timeout())
+        })))
+        .await
+    }
+
     pub async fn spawn_job<F>(&mut self, fut: F)
     where
         F: Future + Send + Sync + 'static,
     {
         self.backend_call(CallMsg::SpawnJob(Box::pin(async move {
             let _ = fut.await;
-            None
+            Ok(None)
         })))
         .await
     }
 
     /// Process the provided future "in background" and upon its completion send the output to the
     /// message-inbox.
+    ///
+    /// How the output is ordered relative to the messages sent to the actor, is specified by
+    /// [`SpawnOpts::with_task_priority`](crate::spawn_opts::SpawnOpts::with_task_priority). The
+    /// outputs of several futures are delivered in the order the futures complete (see
+    /// [`Context::future_to_inbox_ordered`]).
     pub async fn future_to_inbox<F>(&mut self, fut: F)
     where
         F: Future + Send + Sync + 'static,
@@ -119,14 +653,35 @@ impl<M> Context<M> {
     {
         self.backend_call(CallMsg::SpawnJob(Box::pin(async move {
             let message = fut.await.into();
-            Some(message)
+            Ok(Some(message))
+        })))
+        .await;
+    }
+
+    /// Same as [`Context::future_to_inbox`], but the outputs of the futures submitted via this
+    /// method are delivered in the order the futures were submitted, rather than in the order
+    /// they complete (e.g. the responses to the pipelined requests, that are to be applied in
+    /// order).
+    ///
+    /// This comes at the cost of head-of-line blocking: a future that has completed is held
+    /// until all the futures submitted before it complete, so a slow future delays the delivery
+    /// of the outputs of those following it. The futures submitted via
+    /// [`Context::future_to_inbox`] are not affected.
+    pub async fn future_to_inbox_ordered<F>(&mut self, fut: F)
+    where
+        F: Future + Send + Sync + 'static,
+        F::Output: Into<M>,
+    {
+        self.backend_call(CallMsg::SpawnOrderedJob(Box::pin(async move {
+            let message = fut.await.into();
+            Ok(Some(message))
         })))
         .await;
     }
 }
 
 /// "data-bag" related methods
-impl<M> Context<M> {
+impl<M, C> Context<M, C> {
     pub fn put<D>(&mut self, data: D) -> Option<D>
     where
         D: Any + Send + Sync + 'static,
@@ -177,21 +732,93 @@ impl<M> Context<M> {
     }
 }
 
-impl<M> Context<M> {
+impl<M, C> Context<M, C> {
     /// Create a new instance of [`Context`]
     pub(crate) fn new(
         actor_id: ActorID,
         system: SystemWeakRef,
-        inbox: PipeRx<M>,
+        inbox: PipeRx<Envelope<M>>,
         signals: PipeRx<Signal>,
+        controls: PipeRx<Envelope<C>>,
         calls: PipeTx<CallMsg<M>>,
-    ) -> Self {
+        reinit: mpsc::UnboundedSender<Box<dyn Any + Send>>,
+    ) -> Self
+    where
+        M: Unpin,
+    {
         let calls = calls.blocking();
-        Self { actor_id, system, messages: inbox, signals, calls, data: Default::default() }
+        let inbox_len = inbox.len();
+        Self {
+            actor_id,
+            parent: None,
+            behaviour: "",
+            system,
+            messages: inbox,
+            sender: None,
+            inbox_len,
+            signals,
+            calls,
+            reinit,
+            monitors: Default::default(),
+            handler_timer: None,
+            handling_since: None,
+            cancellation_token: Default::default(),
+            controls,
+            data: Default::default(),
+        }
+    }
+
+    pub(crate) fn with_monitors(self, monitors: Monitors) -> Self {
+        Self { monitors, ..self }
+    }
+
+    pub(crate) fn with_handler_timer(self, handler_timer: Option<HandlerTimer>) -> Self {
+        Self { handler_timer, ..self }
+    }
+
+    pub(crate) fn with_parent(self, parent: Option<ActorID>) -> Self {
+        Self { parent, ..self }
+    }
+
+    pub(crate) fn with_behaviour(self, behaviour: &'static str) -> Self {
+        Self { behaviour, ..self }
+    }
+
+    pub(crate) fn with_cancellation_token(self, cancellation_token: CancellationToken) -> Self {
+        Self { cancellation_token, ..self }
     }
 }
 
-impl<M> Context<M> {
+impl<M, C> Context<M, C> {
+    /// The behaviour is about to receive the next event: the handling of the previous one is over.
+    fn handler_done(&mut self) {
+        if let Some((timer, since)) = self.handler_timer.as_ref().zip(self.handling_since.take()) {
+            timer.handled(self.actor_id, since);
+        }
+    }
+
+    /// The behaviour has received an event, and is handling it.
+    fn handler_started(&mut self) {
+        if self.handler_timer.is_some() {
+            self.handling_since = Some(Instant::now());
+        }
+    }
+
+    fn refresh_inbox_len(&mut self)
+    where
+        M: Unpin,
+    {
+        self.inbox_len = self.messages.len();
+    }
+
+    /// Remember the sender of the received message; the quota ticket is released here.
+    fn take_envelope<T>(&mut self, envelope: Envelope<T>) -> T {
+        let Envelope { sender, ticket, message } = envelope;
+        drop(ticket);
+        self.sender = sender;
+        message
+    }
+
     async fn backend_call(&mut self, call: CallMsg<M>) {
         self.calls.send(call).await.expect("It's a blocking Tx. Should not reject.")
     }
@@ -0,0 +1,111 @@
+use std::fmt;
+
+use crate::actor_id::ActorID;
+
+/// Emit a [`tracing`] event at the `level`, tagged with the actor's id and behaviour (see
+/// [`ActorLog`](crate::ActorLog)).
+///
+/// Being a macro, the event's callsite (hence its target, for filtering) is the caller's.
+///
+/// ```
+/// use agner_actors::{actor_log, Context};
+/// use tracing::Level;
+///
+/// async fn connection(context: &mut Context<()>, peer: String) {
+///     actor_log!(context.log(), Level::INFO, "connected to {}", peer);
+/// }
+/// ```
+#[macro_export]
+macro_rules! actor_log {
+    ($log:expr, $level:expr, $($arg:tt)+) => {{
+        let log: $crate::ActorLog = $log;
+        $crate::__tracing::event!(
+            $level,
+            actor_id = %log.actor_id(),
+            behaviour = log.behaviour(),
+            $($arg)+
+        )
+    }};
+}
+
+/// [`actor_log!`] at the `TRACE` level.
+#[macro_export]
+macro_rules! actor_trace {
+    ($log:expr, $($arg:tt)+) => {
+        $crate::actor_log!($log, $crate::__tracing::Level::TRACE, $($arg)+)
+    };
+}
+
+/// [`actor_log!`] at the `DEBUG` level.
+#[macro_export]
+macro_rules! actor_debug {
+    ($log:expr, $($arg:tt)+) => {
+        $crate::actor_log!($log, $crate::__tracing::Level::DEBUG, $($arg)+)
+    };
+}
+
+/// [`actor_log!`] at the `INFO` level.
+#[macro_export]
+macro_rules! actor_info {
+    ($log:expr, $($arg:tt)+) => {
+        $crate::actor_log!($log, $crate::__tracing::Level::INFO, $($arg)+)
+    };
+}
+
+/// [`actor_log!`] at the `WARN` level.
+#[macro_export]
+macro_rules! actor_warn {
+    ($log:expr, $($arg:tt)+) => {
+        $crate::actor_log!($log, $crate::__tracing::Level::WARN, $($arg)+)
+    };
+}
+
+/// [`actor_log!`] at the `ERROR` level.
+#[macro_export]
+macro_rules! actor_error {
+    ($log:expr, $($arg:tt)+) => {
+        $crate::actor_log!($log, $crate::__tracing::Level::ERROR, $($arg)+)
+    };
+}
+
+/// A logger tagged with the actor's id and behaviour (see [`Context::log`](super::Context::log)).
+///
+/// The records are emitted via [`actor_log!`](crate::actor_log) (or its per-level shorthands,
+/// e.g. [`actor_info!`](crate::actor_info)) as [`tracing`] events with the `actor_id` and
+/// `behaviour` fields — the same ones the actor's backend uses for its own events.
+///
+/// Its [`Display`](fmt::Display) renders the `[<actor-id>/<label>]` prefix, for the plain-text
+/// output.
+#[derive(Debug, Clone, Copy)]
+pub struct ActorLog {
+    actor_id: ActorID,
+    behaviour: &'static str,
+}
+
+impl ActorLog {
+    pub(crate) fn new(actor_id: ActorID, behaviour: &'static str) -> Self {
+        Self { actor_id, behaviour }
+    }
+
+    /// The id of the actor the records are tagged with.
+    pub fn actor_id(&self) -> ActorID {
+        self.actor_id
+    }
+
+    /// The type name of the actor's behaviour.
+    pub fn behaviour(&self) -> &'static str {
+        self.behaviour
+    }
+
+    /// The short name of the actor's behaviour (the last segment of its type name).
+    pub fn label(&self) -> &'static str {
+        let name = self.behaviour.split('<').next().unwrap_or(self.behaviour);
+        name.rsplit("::").find(|segment| !segment.starts_with('{')).unwrap_or(name)
+    }
+}
+
+impl fmt::Display for ActorLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}/{}]", self.actor_id, self.label())
+    }
+}
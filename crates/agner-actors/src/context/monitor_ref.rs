@@ -0,0 +1,51 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::sync::oneshot;
+
+use crate::actor_id::ActorID;
+use crate::actor_runner::MonitorGuard;
+use crate::exit::Exit;
+
+/// A monitor installed by [`Context::spawn_monitor`](crate::context::Context::spawn_monitor): a
+/// future resolving upon termination of the monitored actor into its [`Exit`].
+///
+/// As the future returned by [`Context::monitor_fut`](crate::context::Context::monitor_fut), it
+/// does not borrow the context, and can be routed back to the inbox via
+/// [`Context::future_to_inbox`](crate::context::Context::future_to_inbox). Dropping it removes
+/// the monitor.
+#[derive(Debug)]
+#[must_use = "the monitor is removed once dropped"]
+pub struct MonitorRef {
+    target: ActorID,
+    monitor_guard: Option<MonitorGuard>,
+    exit_rx: oneshot::Receiver<Exit>,
+}
+
+impl MonitorRef {
+    pub(crate) fn new(
+        target: ActorID,
+        monitor_guard: MonitorGuard,
+        exit_rx: oneshot::Receiver<Exit>,
+    ) -> Self {
+        Self { target, monitor_guard: Some(monitor_guard), exit_rx }
+    }
+
+    /// The monitored actor.
+    pub fn target(&self) -> ActorID {
+        self.target
+    }
+}
+
+impl Future for MonitorRef {
+    type Output = Exit;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let exit = futures::ready!(Pin::new(&mut this.exit_rx).poll(cx))
+            .unwrap_or_else(|_| Exit::no_actor());
+        this.monitor_guard.take();
+        Poll::Ready(exit)
+    }
+}
@@ -0,0 +1,90 @@
+use std::future::{Future, IntoFuture};
+use std::task::Poll;
+
+use futures::future::BoxFuture;
+
+use crate::context::{Context, Event};
+use crate::imports::Never;
+
+/// A [`Context::select`] builder: awaits the first of the actor's events and the registered
+/// futures.
+///
+/// The signals are polled first, then the registered futures (in the order of registration),
+/// then the inbox.
+#[must_use = "the select does nothing until awaited"]
+pub struct Select<'a, M, T, C = Never> {
+    context: &'a mut Context<M, C>,
+    futures: Vec<BoxFuture<'a, T>>,
+    messages: bool,
+}
+
+/// The outcome of a [`Select`].
+#[derive(Debug)]
+pub enum Selected<M, T> {
+    /// An event has been received by the actor.
+    Event(Event<M>),
+    /// One of the registered futures has completed.
+    Ready(T),
+}
+
+impl<'a, M, T, C> Select<'a, M, T, C> {
+    pub(super) fn new(context: &'a mut Context<M, C>) -> Self {
+        Self { context, futures: Default::default(), messages: true }
+    }
+
+    /// Register a future to await alongside the actor's events.
+    ///
+    /// The futures not completed by the time the select resolves are dropped.
+    pub fn with<F>(mut self, fut: F) -> Self
+    where
+        F: Future<Output = T> + Send + 'a,
+    {
+        self.futures.push(Box::pin(fut));
+        self
+    }
+
+    /// Leave the messages in the inbox: only the signals and the registered futures are awaited.
+    pub fn without_messages(mut self) -> Self {
+        self.messages = false;
+        self
+    }
+}
+
+impl<'a, M, T, C> IntoFuture for Select<'a, M, T, C>
+where
+    M: Unpin + Send,
+    C: Send,
+    T: Send + 'a,
+{
+    type Output = Selected<M, T>;
+    type IntoFuture = BoxFuture<'a, Selected<M, T>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let Self { context, mut futures, messages } = self;
+        Box::pin(async move {
+            context.handler_done();
+            let registered = futures::future::poll_fn(|cx| {
+                futures
+                    .iter_mut()
+                    .find_map(|fut| match fut.as_mut().poll(cx) {
+                        Poll::Ready(output) => Some(output),
+                        Poll::Pending => None,
+                    })
+                    .map_or(Poll::Pending, Poll::Ready)
+            });
+            let selected = tokio::select! {
+                biased;
+
+                signal = context.signals.recv() =>
+                    Selected::Event(Event::Signal(signal)),
+                output = registered =>
+                    Selected::Ready(output),
+                envelope = context.messages.recv(), if messages =>
+                    Selected::Event(Event::Message(context.take_envelope(envelope))),
+            };
+            context.refresh_inbox_len();
+            context.handler_started();
+            selected
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::sync::Arc;
 
 use crate::actor_id::ActorID;
@@ -5,20 +6,31 @@ use crate::imports::ArcError;
 
 mod into_exit;
 
+#[cfg(feature = "backtrace")]
+mod backtrace;
+#[cfg(feature = "backtrace")]
+pub use self::backtrace::WithBacktrace;
+
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "serde")]
+pub use self::serde::SerializableReason;
+
+#[cfg(test)]
+mod tests;
+
 /// An reason an actor exited.
 ///
 /// Exit reasons are supposed to be cheaply cloneable, as when an actor fails each linked actor
 /// receives a signal containing a clone of that reason.
-#[derive(Debug, Clone, thiserror::Error)]
+///
+/// The [source](std::error::Error::source) of a custom reason is the error it has been created
+/// with (even if wrapped into [`WithBacktrace`](crate::exit::WithBacktrace)).
+#[derive(Debug, Clone)]
 pub enum Exit {
-    #[error("Well known")]
-    Standard(#[source] WellKnown),
-
-    #[error("Actor backend failure")]
-    Backend(#[source] BackendFailure),
-
-    #[error("Custom")]
-    Custom(#[source] ArcError),
+    Standard(WellKnown),
+    Backend(BackendFailure),
+    Custom(ArcError),
 }
 
 /// Standard exit reasons.
@@ -38,13 +50,32 @@ pub enum WellKnown {
 
     #[error("Shutdown")]
     Shutdown(#[source] Shutdown),
+
+    #[error("Panic: {}", _0)]
+    Panic(String),
 }
 
+/// The custom reason of an actor that has not completed by its deadline (see [`Exit::timeout`]).
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("Timeout")]
+pub struct Timeout;
+
 #[derive(Debug, Clone, Default, thiserror::Error)]
 #[error("Shutdown")]
 pub struct Shutdown(#[source] pub Option<ArcError>);
 
+/// A note attached to an exit reason (see [`Exit::with_note`]), followed in the source chain by
+/// the reason's original source.
 #[derive(Debug, Clone, thiserror::Error)]
+#[error("{}", note)]
+pub struct ExitNote {
+    note: String,
+
+    #[source]
+    source: Option<ArcError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 pub enum BackendFailure {
     #[error("Inbox Full: {}", _0)]
     InboxFull(&'static str),
@@ -53,6 +84,32 @@ pub enum BackendFailure {
     RxClosed(&'static str),
 }
 
+impl fmt::Display for Exit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Standard(_) => write!(f, "Well known"),
+            Self::Backend(_) => write!(f, "Actor backend failure"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+impl std::error::Error for Exit {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Standard(e) => Some(e),
+            Self::Backend(e) => Some(e),
+            Self::Custom(e) => {
+                #[cfg(feature = "backtrace")]
+                if let Some(with_backtrace) = e.downcast_ref::<WithBacktrace>() {
+                    return Some(with_backtrace.inner())
+                }
+                Some(e.as_ref())
+            },
+        }
+    }
+}
+
 impl Default for Exit {
     fn default() -> Self {
         Self::Standard(WellKnown::Normal)
@@ -90,10 +147,33 @@ impl Exit {
     pub fn is_shutdown(&self) -> bool {
         matches!(self, Self::Standard(WellKnown::Shutdown(_)))
     }
+    pub fn is_panic(&self) -> bool {
+        matches!(self, Self::Standard(WellKnown::Panic(_)))
+    }
+    pub fn is_timeout(&self) -> bool {
+        self.custom_downcast_ref::<Timeout>().is_some()
+    }
     pub fn is_custom(&self) -> bool {
         matches!(self, Self::Custom(_))
     }
 
+    /// Compare the structural kind of two exit reasons.
+    ///
+    /// The sources of [`Shutdown`] and the contents of [`Exit::Custom`] are not compared, as
+    /// the errors inside are not required to be comparable.
+    pub fn eq_kind(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Standard(left), Self::Standard(right)) => match (left, right) {
+                (WellKnown::Linked(left_id, left), WellKnown::Linked(right_id, right)) =>
+                    left_id == right_id && left.eq_kind(right),
+                (left, right) => std::mem::discriminant(left) == std::mem::discriminant(right),
+            },
+            (Self::Backend(left), Self::Backend(right)) => left == right,
+            (Self::Custom(_), Self::Custom(_)) => true,
+            (_, _) => false,
+        }
+    }
+
     pub fn normal() -> Self {
         WellKnown::Normal.into()
     }
@@ -115,8 +195,130 @@ impl Exit {
         WellKnown::Shutdown(shutdown).into()
     }
 
+    /// The exit reason of an actor that has not completed by its deadline (see
+    /// [`Context::deadline`](crate::context::Context::deadline)).
+    ///
+    /// This is a [custom](Exit::Custom) reason: its error is [`Timeout`].
+    pub fn timeout() -> Self {
+        Self::custom(Timeout)
+    }
+
+    /// Attach a note to this exit reason (e.g. why an operator has shut the actor down).
+    ///
+    /// The note becomes a part of the reason's source chain, hence it is shown by
+    /// [`pp()`](agner_utils::std_error_pp::StdErrorPP::pp), and it is retained as the reason
+    /// travels wrapped into the [linked](Exit::linked) exits.
+    ///
+    /// Only the shutdown and the custom reasons (and the linked ones — via the reason they wrap)
+    /// have room for notes: the other reasons are returned as they are.
+    ///
+    /// ```
+    /// use agner_actors::{ActorID, Exit};
+    ///
+    /// let exit = Exit::shutdown().with_note("operator-initiated").with_note("maintenance");
+    /// assert_eq!(exit.notes(), ["maintenance", "operator-initiated"]);
+    ///
+    /// let linked = Exit::linked("1.2.3".parse::<ActorID>().unwrap(), exit);
+    /// assert_eq!(linked.notes(), ["maintenance", "operator-initiated"]);
+    /// ```
+    pub fn with_note(self, note: impl Into<String>) -> Self {
+        let note = note.into();
+        match self {
+            Self::Standard(WellKnown::Shutdown(Shutdown(source))) =>
+                Self::shutdown_with_source(Arc::new(ExitNote { note, source })),
+            Self::Standard(WellKnown::Linked(who, reason)) =>
+                Self::linked(who, (*reason).with_note(note)),
+            Self::Custom(e) => Self::Custom(Arc::new(ExitNote { note, source: Some(e) })),
+            as_is => as_is,
+        }
+    }
+
+    /// Specify the error that has caused the shutdown, keeping the notes attached to the reason.
+    ///
+    /// The reasons other than shutdown are returned as they are.
+    pub fn with_source<E>(self, source: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        fn attach(existing: Option<&ArcError>, source: ArcError) -> ArcError {
+            match existing.and_then(|e| e.downcast_ref::<ExitNote>()) {
+                Some(note) => Arc::new(ExitNote {
+                    note: note.note.to_owned(),
+                    source: Some(attach(note.source.as_ref(), source)),
+                }),
+                None => source,
+            }
+        }
+
+        match self {
+            Self::Standard(WellKnown::Shutdown(Shutdown(existing))) =>
+                Self::shutdown_with_source(attach(existing.as_ref(), Arc::new(source))),
+            as_is => as_is,
+        }
+    }
+
+    /// The notes attached to this reason (see [`Exit::with_note`]), the most recent first.
+    pub fn notes(&self) -> Vec<&str> {
+        self.source_chain()
+            .filter_map(|e| e.downcast_ref::<ExitNote>())
+            .map(|note| note.note.as_str())
+            .collect()
+    }
+
+    /// Find the first error of the type `E` in the source chain of this reason.
+    pub fn source_downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: std::error::Error + 'static,
+    {
+        self.source_chain().find_map(|e| e.downcast_ref::<E>())
+    }
+
+    fn source_chain(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+        // an `ArcError` in the chain is the `Arc` itself: look into it
+        std::iter::successors(std::error::Error::source(self), |e| e.source()).map(|e| {
+            let e = match e.downcast_ref::<ArcError>() {
+                Some(arc) => arc.as_ref() as &(dyn std::error::Error + 'static),
+                None => e,
+            };
+            #[cfg(feature = "backtrace")]
+            if let Some(with_backtrace) = e.downcast_ref::<WithBacktrace>() {
+                return with_backtrace.inner() as &(dyn std::error::Error + 'static)
+            }
+            e
+        })
+    }
+
+    /// The exit reason of an actor whose behaviour has panicked.
+    pub fn from_panic(message: impl Into<String>) -> Self {
+        WellKnown::Panic(message.into()).into()
+    }
+
+    /// Create a custom exit reason.
+    ///
+    /// With the `backtrace` feature enabled, the error is wrapped into [`WithBacktrace`],
+    /// capturing the backtrace at the point of this call. The wrapper is transparent to
+    /// [`source`](std::error::Error::source) and [`Exit::source_downcast_ref`]: those yield `e`.
     pub fn custom<E: std::error::Error + Send + Sync + 'static>(e: E) -> Exit {
-        Self::Custom(Arc::new(e))
+        Self::custom_arc(Arc::new(e))
+    }
+
+    /// Create a custom exit reason that retains its value upon serialization.
+    ///
+    /// The value is kept as JSON, tagged with the name of its type (see [`SerializableReason`]).
+    /// Fails if the value cannot be represented as JSON.
+    #[cfg(feature = "serde")]
+    pub fn custom_serializable<T>(value: T) -> Result<Exit, serde_json::Error>
+    where
+        T: ::serde::Serialize,
+    {
+        SerializableReason::new(&value).map(Self::custom)
+    }
+
+    /// The [`SerializableReason`] of this exit reason, if it has been created by
+    /// [`Exit::custom_serializable`].
+    #[cfg(feature = "serde")]
+    pub fn serializable_reason(&self) -> Option<&SerializableReason> {
+        self.custom_downcast_ref()
     }
 
     pub fn from_message<S>(message: S) -> Exit
@@ -125,7 +327,50 @@ impl Exit {
     {
         let s = message.into();
         let b: Box<dyn std::error::Error + Send + Sync + 'static> = s.into();
-        Self::Custom(b.into())
+        Self::custom_arc(b.into())
+    }
+
+    #[cfg(not(feature = "backtrace"))]
+    fn custom_arc(e: ArcError) -> Exit {
+        Self::Custom(e)
+    }
+
+    #[cfg(feature = "backtrace")]
+    fn custom_arc(e: ArcError) -> Exit {
+        Self::Custom(Arc::new(WithBacktrace::capture(e)))
+    }
+
+    /// The backtrace captured when this custom exit reason was created.
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        self.custom_error()?
+            .downcast_ref::<WithBacktrace>()
+            .map(WithBacktrace::backtrace)
+    }
+
+    /// The error of a custom reason of the type `E`, unwrapped of the notes and the backtrace.
+    fn custom_downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: std::error::Error + 'static,
+    {
+        let e = self.custom_error()?;
+
+        #[cfg(feature = "backtrace")]
+        if let Some(with_backtrace) = e.downcast_ref::<WithBacktrace>() {
+            return with_backtrace.inner().downcast_ref()
+        }
+
+        e.downcast_ref()
+    }
+
+    /// The error of a custom reason, without the notes attached to it.
+    fn custom_error(&self) -> Option<&ArcError> {
+        let Self::Custom(e) = self else { return None };
+        let mut e = e;
+        while let Some(ExitNote { source: Some(source), .. }) = e.downcast_ref::<ExitNote>() {
+            e = source;
+        }
+        Some(e)
     }
 }
 
@@ -0,0 +1,44 @@
+use std::backtrace::Backtrace;
+use std::error::Error as StdError;
+use std::fmt;
+
+use crate::imports::ArcError;
+
+/// A custom exit reason along with the backtrace captured upon its creation.
+///
+/// The backtrace is captured using [`Backtrace::capture`], i.e. it is only collected if enabled
+/// via the `RUST_BACKTRACE`/`RUST_LIB_BACKTRACE` environment variables. It is not a part of the
+/// [`Display`](fmt::Display): see [`Exit::backtrace`](crate::exit::Exit::backtrace).
+#[derive(Debug)]
+pub struct WithBacktrace {
+    source: ArcError,
+    backtrace: Backtrace,
+}
+
+impl WithBacktrace {
+    pub(crate) fn capture(source: ArcError) -> Self {
+        Self { source, backtrace: Backtrace::capture() }
+    }
+
+    /// The wrapped error
+    pub fn inner(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        self.source.as_ref()
+    }
+
+    /// The captured backtrace
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+}
+
+impl fmt::Display for WithBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl StdError for WithBacktrace {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source.source()
+    }
+}
@@ -0,0 +1,139 @@
+use std::error::Error as StdError;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+
+use crate::actor_id::ActorID;
+use crate::exit::{Exit, Shutdown, WellKnown};
+use crate::imports::ArcError;
+
+/// A custom exit reason that survives serialization: the name of the original type along with the
+/// JSON-representation of the value.
+///
+/// Created by [`Exit::custom_serializable`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error, serde::Serialize, serde::Deserialize)]
+#[error("{}: {}", type_name, payload)]
+pub struct SerializableReason {
+    pub type_name: String,
+    pub payload: serde_json::Value,
+}
+
+impl SerializableReason {
+    pub(crate) fn new<T: serde::Serialize>(value: &T) -> Result<Self, serde_json::Error> {
+        let payload = serde_json::to_value(value)?;
+        Ok(Self { type_name: std::any::type_name::<T>().to_owned(), payload })
+    }
+
+    /// Restore the original value, provided it was of the type `T`.
+    pub fn restore<T: DeserializeOwned>(&self) -> Option<T> {
+        if self.type_name != std::any::type_name::<T>() {
+            return None
+        }
+        serde_json::from_value(self.payload.to_owned()).ok()
+    }
+}
+
+/// The serialized form of [`Exit`].
+///
+/// The custom reasons other than [`SerializableReason`] (as well as the backend failures) are
+/// kept as the chain of their messages, and are deserialized as [`Exit::Custom`].
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ExitRepr {
+    Normal,
+    Kill,
+    Linked(ActorID, Box<ExitRepr>),
+    NoActor,
+    Shutdown(Option<ErrorRepr>),
+    Panic(String),
+    Timeout,
+    Backend(ErrorRepr),
+    Custom(CustomRepr),
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CustomRepr {
+    Serializable(SerializableReason),
+    Error(ErrorRepr),
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, thiserror::Error)]
+#[error("{}", message)]
+struct ErrorRepr {
+    message: String,
+
+    #[source]
+    source: Option<Box<Self>>,
+}
+
+impl ErrorRepr {
+    fn from_std_error(error: &(dyn StdError + 'static)) -> Self {
+        Self {
+            message: error.to_string(),
+            source: error.source().map(Self::from_std_error).map(Box::new),
+        }
+    }
+}
+
+impl From<&Exit> for ExitRepr {
+    fn from(exit: &Exit) -> Self {
+        match exit {
+            Exit::Standard(WellKnown::Normal) => Self::Normal,
+            Exit::Standard(WellKnown::Kill) => Self::Kill,
+            Exit::Standard(WellKnown::Linked(actor_id, reason)) =>
+                Self::Linked(*actor_id, Box::new(reason.as_ref().into())),
+            Exit::Standard(WellKnown::NoActor) => Self::NoActor,
+            Exit::Standard(WellKnown::Shutdown(Shutdown(source))) =>
+                Self::Shutdown(source.as_deref().map(|e| ErrorRepr::from_std_error(e))),
+            Exit::Standard(WellKnown::Panic(message)) => Self::Panic(message.to_owned()),
+            Exit::Backend(failure) => Self::Backend(ErrorRepr::from_std_error(failure)),
+            Exit::Custom(_) if exit.is_timeout() => Self::Timeout,
+            Exit::Custom(_) => Self::Custom(match exit.serializable_reason() {
+                Some(reason) => CustomRepr::Serializable(reason.to_owned()),
+                // the source of a custom reason is its error, unwrapped of the backtrace
+                None => CustomRepr::Error(ErrorRepr::from_std_error(
+                    StdError::source(exit).expect("a custom reason has a source"),
+                )),
+            }),
+        }
+    }
+}
+
+impl From<ExitRepr> for Exit {
+    fn from(repr: ExitRepr) -> Self {
+        fn arc_error(e: impl StdError + Send + Sync + 'static) -> ArcError {
+            Arc::new(e)
+        }
+        match repr {
+            ExitRepr::Normal => Self::normal(),
+            ExitRepr::Kill => Self::kill(),
+            ExitRepr::Linked(actor_id, reason) => Self::linked(actor_id, Self::from(*reason)),
+            ExitRepr::NoActor => Self::no_actor(),
+            ExitRepr::Shutdown(source) => Shutdown(source.map(arc_error)).into(),
+            ExitRepr::Panic(message) => Self::from_panic(message),
+            ExitRepr::Timeout => Self::timeout(),
+            ExitRepr::Backend(error) => Self::Custom(arc_error(error)),
+            ExitRepr::Custom(CustomRepr::Serializable(reason)) => Self::Custom(arc_error(reason)),
+            ExitRepr::Custom(CustomRepr::Error(error)) => Self::Custom(arc_error(error)),
+        }
+    }
+}
+
+impl serde::Serialize for Exit {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        ExitRepr::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Exit {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        ExitRepr::deserialize(deserializer).map(Into::into)
+    }
+}
@@ -0,0 +1,131 @@
+use agner_utils::std_error_pp::StdErrorPP;
+
+use crate::actor_id::ActorID;
+use crate::exit::{BackendFailure, Exit};
+
+#[derive(Debug, thiserror::Error)]
+#[error("a failure")]
+struct Failure;
+
+#[test]
+fn eq_kind_standard() {
+    assert!(Exit::normal().eq_kind(&Exit::normal()));
+    assert!(Exit::kill().eq_kind(&Exit::kill()));
+    assert!(Exit::no_actor().eq_kind(&Exit::no_actor()));
+    assert!(Exit::shutdown().eq_kind(&Exit::shutdown_with_source(std::sync::Arc::new(Failure))));
+
+    assert!(!Exit::normal().eq_kind(&Exit::kill()));
+    assert!(!Exit::shutdown().eq_kind(&Exit::normal()));
+    assert!(!Exit::kill().eq_kind(&Exit::custom(Failure)));
+}
+
+#[test]
+fn eq_kind_linked() {
+    let one = ActorID::new(1, 0, 0);
+    let two = ActorID::new(1, 1, 1);
+
+    assert!(Exit::linked(one, Exit::kill()).eq_kind(&Exit::linked(one, Exit::kill())));
+    assert!(!Exit::linked(one, Exit::kill()).eq_kind(&Exit::linked(two, Exit::kill())));
+    assert!(!Exit::linked(one, Exit::kill()).eq_kind(&Exit::linked(one, Exit::normal())));
+}
+
+#[test]
+fn eq_kind_backend_and_custom() {
+    let inbox_full: Exit = BackendFailure::InboxFull("messages").into();
+    let rx_closed: Exit = BackendFailure::RxClosed("messages").into();
+
+    assert!(inbox_full.eq_kind(&BackendFailure::InboxFull("messages").into()));
+    assert!(!inbox_full.eq_kind(&rx_closed));
+    assert!(Exit::custom(Failure).eq_kind(&Exit::from_message("something else")));
+}
+
+#[cfg(feature = "backtrace")]
+#[test]
+fn custom_exit_carries_a_backtrace() {
+    use agner_utils::std_error_pp::StdErrorPP;
+
+    let exit = Exit::custom(Failure);
+    assert!(exit.is_custom());
+    assert!(exit.backtrace().is_some());
+    assert!(exit.pp().to_string().contains("a failure"));
+
+    // the wrapper is transparent
+    let source = std::error::Error::source(&exit).expect("no source");
+    assert!(source.downcast_ref::<Failure>().is_some());
+    assert!(exit.source_downcast_ref::<Failure>().is_some());
+    let Exit::Custom(custom) = &exit else { unreachable!() };
+    assert_eq!(custom.to_string(), "a failure");
+
+    assert!(Exit::shutdown().backtrace().is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn custom_serializable_round_trip() {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Disconnected {
+        peer: String,
+        idle_secs: u64,
+    }
+
+    let reason = Disconnected { peer: "alice".into(), idle_secs: 30 };
+    let exit = Exit::linked(
+        ActorID::new(1, 2, 3),
+        Exit::custom_serializable(Disconnected { peer: "alice".into(), idle_secs: 30 }).unwrap(),
+    );
+
+    let json = serde_json::to_string(&exit).unwrap();
+    let restored: Exit = serde_json::from_str(&json).unwrap();
+    assert!(restored.eq_kind(&exit));
+
+    let Exit::Standard(crate::exit::WellKnown::Linked(_, inner)) = restored else {
+        panic!("unexpected: {:?}", restored)
+    };
+    let serializable = inner.serializable_reason().expect("not serializable");
+    assert_eq!(serializable.restore::<Disconnected>(), Some(reason));
+    assert_eq!(serializable.restore::<String>(), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn unserializable_custom_reasons_are_rejected() {
+    // the keys of a JSON-object are strings
+    let value = std::collections::HashMap::from([((1, 2), "three")]);
+    assert!(Exit::custom_serializable(value).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn custom_errors_are_serialized_as_messages() {
+    let json = serde_json::to_string(&Exit::custom(Failure)).unwrap();
+    let restored: Exit = serde_json::from_str(&json).unwrap();
+    assert!(restored.is_custom());
+    assert!(restored.serializable_reason().is_none());
+    let source = std::error::Error::source(&restored).expect("no source");
+    assert_eq!(source.to_string(), "a failure");
+
+    let shutdown: Exit =
+        serde_json::from_str(&serde_json::to_string(&Exit::shutdown()).unwrap()).unwrap();
+    assert!(shutdown.is_shutdown());
+
+    let timeout: Exit =
+        serde_json::from_str(&serde_json::to_string(&Exit::timeout()).unwrap()).unwrap();
+    assert!(timeout.is_timeout());
+}
+
+#[test]
+fn notes_and_source() {
+    let exit = Exit::shutdown().with_note("operator-initiated").with_source(Failure);
+    assert!(exit.is_shutdown());
+    assert_eq!(exit.notes(), ["operator-initiated"]);
+    assert!(exit.source_downcast_ref::<Failure>().is_some());
+    assert!(exit.pp().to_string().contains("operator-initiated"));
+
+    let custom = Exit::custom(Failure).with_note("while connecting");
+    assert!(custom.is_custom());
+    assert_eq!(custom.notes(), ["while connecting"]);
+    assert!(custom.source_downcast_ref::<Failure>().is_some());
+
+    assert!(Exit::kill().with_note("ignored").notes().is_empty());
+    assert!(Exit::shutdown().notes().is_empty());
+}
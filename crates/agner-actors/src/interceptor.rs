@@ -0,0 +1,19 @@
+use std::fmt;
+
+use crate::actor_id::ActorID;
+
+/// `Interceptor` is an entity that observes the messages sent within a
+/// [`System`](crate::system::System), and the system's usage of its capacity.
+///
+/// The interceptors are specified via
+/// [`SystemConfig::with_interceptor`](crate::system_config::SystemConfig::with_interceptor), and
+/// are invoked in the order they were added.
+pub trait Interceptor: fmt::Debug + Send + Sync + 'static {
+    fn on_send(&self, from: Option<ActorID>, to: ActorID, message_type: &'static str);
+
+    /// Invoked once the number of actors in the system reaches the
+    /// [high watermark](crate::system_config::SystemConfig::high_watermark).
+    fn on_near_capacity(&self, used: usize, total: usize) {
+        let _ = (used, total);
+    }
+}
@@ -4,37 +4,83 @@
 mod actor;
 mod actor_id;
 mod actor_runner;
+mod clock;
 mod context;
 mod exit;
 mod exit_handler;
+mod interceptor;
+mod name_store;
 mod spawn_opts;
+mod spawner;
 mod system;
 mod system_config;
 
+#[cfg(feature = "unstable-sys-ops")]
+mod sys_ops;
+
 mod exports {
     pub use crate::actor::Actor;
     pub use crate::actor_id::ActorID;
-    pub use crate::context::{Context, Event, Signal};
+    pub use crate::clock::Clock;
+    pub use crate::context::{
+        ActorLog, Context, Event, MonitorRef, Prioritized, Select, Selected, Signal,
+    };
     pub use crate::exit::{Exit, Shutdown};
     pub use crate::exit_handler::ExitHandler;
-    pub use crate::spawn_opts::SpawnOpts;
-    pub use crate::system::{ActorChannel, System, SystemWeakRef};
+    pub use crate::interceptor::Interceptor;
+    pub use crate::name_store::{NameStore, NameStoreError};
+    pub use crate::spawn_opts::{PanicPolicy, Priority, SpawnOpts, TaskPriority};
+    pub use crate::spawner::Spawner;
+    pub use crate::system::{
+        ActorChannel, SendOutcome, System, SystemWeakRef, TimerHandle, WeakActorChannel,
+    };
     pub use crate::system_config::SystemConfig;
 
-    pub use crate::actor_runner::ActorInfo;
+    pub use crate::actor_runner::{ActorInfo, BackendView, TraceFlags};
 
     pub mod system_error {
-        pub use crate::system::{SysChannelError, SysSpawnError};
+        pub use crate::system::{
+            SysCallError, SysChannelError, SysRebindError, SysRegisterError, SysSpawnError,
+            SysWaitError,
+        };
     }
 
     pub mod exit_reason {
-        pub use crate::exit::{BackendFailure, WellKnown};
+        pub use crate::exit::{BackendFailure, ExitNote, Timeout, WellKnown};
+
+        #[cfg(feature = "backtrace")]
+        pub use crate::exit::WithBacktrace;
+
+        #[cfg(feature = "serde")]
+        pub use crate::exit::SerializableReason;
+    }
+
+    /// Low-level operations for the custom supervision (see
+    /// [`SystemExt`](crate::system_ext::SystemExt))
+    #[cfg(feature = "unstable-sys-ops")]
+    pub mod system_ext {
+        pub use crate::sys_ops::{SysOp, SystemExt};
     }
 
     /// Standard [exit-handlers](crate::exit_handler::ExitHandler)
     pub mod exit_handlers {
         pub use crate::exit_handler::{LogExitHandler, NoopExitHandler};
     }
+
+    /// Standard [clocks](crate::clock::Clock)
+    pub mod clocks {
+        pub use crate::clock::{MockClock, SystemClock};
+    }
+
+    /// Standard [name-stores](crate::name_store::NameStore)
+    pub mod name_stores {
+        pub use crate::name_store::InMemoryNameStore;
+    }
+
+    /// Standard [spawners](crate::spawner::Spawner)
+    pub mod spawners {
+        pub use crate::spawner::TokioSpawner;
+    }
 }
 mod imports {
     use std::sync::Arc;
@@ -51,3 +97,7 @@ mod imports {
 
 pub use exports::*;
 pub use imports::*;
+
+// used by the `actor_log!` macros
+#[doc(hidden)]
+pub use tracing as __tracing;
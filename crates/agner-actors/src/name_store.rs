@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use arc_swap::ArcSwap;
+use futures::future::{self, BoxFuture};
+
+use crate::actor_id::ActorID;
+
+/// `NameStore` keeps the bindings of the names to the actors.
+///
+/// It is possible to specify the store for a [`System`](crate::system::System) via
+/// [`SystemConfig::name_store`](crate::system_config::SystemConfig::name_store). The names are
+/// bound and looked up via [`System::register`](crate::system::System::register),
+/// [`System::whereis`](crate::system::System::whereis) and the like.
+///
+/// The system serializes the modifications of the bindings, checks that the actors being bound
+/// are running, and keeps track of the names bound to each of its actors: the store only has to
+/// keep the bindings. A store backed by an external service reports the failures of that service
+/// as [`NameStoreError`].
+pub trait NameStore: fmt::Debug + Send + Sync + 'static {
+    /// Bind the `name` to the `actor_id`, unless it is already bound: in that case, return the
+    /// actor it is bound to.
+    fn register<'a>(
+        &'a self,
+        name: &'a str,
+        actor_id: ActorID,
+    ) -> BoxFuture<'a, Result<Result<(), ActorID>, NameStoreError>>;
+
+    /// Bind the `name` to the `actor_id`, returning the actor it was bound to (if any).
+    fn rebind<'a>(
+        &'a self,
+        name: &'a str,
+        actor_id: ActorID,
+    ) -> BoxFuture<'a, Result<Option<ActorID>, NameStoreError>>;
+
+    /// Release the `name`, returning the actor it was bound to (if any).
+    fn unregister<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> BoxFuture<'a, Result<Option<ActorID>, NameStoreError>>;
+
+    /// Look up the actor bound to the `name`.
+    fn whereis<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> BoxFuture<'a, Result<Option<ActorID>, NameStoreError>>;
+
+    /// All of the bindings.
+    fn list(&self) -> BoxFuture<'_, Result<Vec<(String, ActorID)>, NameStoreError>>;
+
+    /// Release those of the `names` that are still bound to the terminated `actor_id` (i.e. have
+    /// not been [rebound](NameStore::rebind) to another actor since).
+    fn release<'a>(
+        &'a self,
+        actor_id: ActorID,
+        names: &'a [String],
+    ) -> BoxFuture<'a, Result<(), NameStoreError>>;
+}
+
+/// A failure of a [`NameStore`](crate::name_store::NameStore): e.g. the service backing it is
+/// unavailable.
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct NameStoreError(Box<dyn StdError + Send + Sync + 'static>);
+
+impl NameStoreError {
+    pub fn new(reason: impl Into<Box<dyn StdError + Send + Sync + 'static>>) -> Self {
+        Self(reason.into())
+    }
+}
+
+/// A [`NameStore`](crate::name_store::NameStore) keeping the bindings in memory.
+///
+/// The lookups do not take any locks: they read the current snapshot of the bindings, which is
+/// replaced upon each modification.
+#[derive(Debug, Default)]
+pub struct InMemoryNameStore {
+    names: ArcSwap<HashMap<String, ActorID>>,
+    modifying: Mutex<()>,
+}
+
+impl InMemoryNameStore {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Replace the snapshot with its modified copy. Returns the result of `modify`.
+    fn modify<T>(&self, modify: impl FnOnce(&mut HashMap<String, ActorID>) -> T) -> T {
+        let _modifying = self.modifying.lock().expect("poisoned");
+        let mut names = HashMap::clone(&self.names.load());
+        let out = modify(&mut names);
+        self.names.store(Arc::new(names));
+        out
+    }
+}
+
+impl NameStore for InMemoryNameStore {
+    fn register<'a>(
+        &'a self,
+        name: &'a str,
+        actor_id: ActorID,
+    ) -> BoxFuture<'a, Result<Result<(), ActorID>, NameStoreError>> {
+        let result = if let Some(bound) = self.names.load().get(name) {
+            Err(*bound)
+        } else {
+            self.modify(|names| match names.get(name) {
+                Some(bound) => Err(*bound),
+                None => {
+                    names.insert(name.to_owned(), actor_id);
+                    Ok(())
+                },
+            })
+        };
+        Box::pin(future::ok(result))
+    }
+
+    fn rebind<'a>(
+        &'a self,
+        name: &'a str,
+        actor_id: ActorID,
+    ) -> BoxFuture<'a, Result<Option<ActorID>, NameStoreError>> {
+        let old = self.modify(|names| names.insert(name.to_owned(), actor_id));
+        Box::pin(future::ok(old))
+    }
+
+    fn unregister<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> BoxFuture<'a, Result<Option<ActorID>, NameStoreError>> {
+        let unbound = if self.names.load().contains_key(name) {
+            self.modify(|names| names.remove(name))
+        } else {
+            None
+        };
+        Box::pin(future::ok(unbound))
+    }
+
+    fn whereis<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> BoxFuture<'a, Result<Option<ActorID>, NameStoreError>> {
+        Box::pin(future::ok(self.names.load().get(name).copied()))
+    }
+
+    fn list(&self) -> BoxFuture<'_, Result<Vec<(String, ActorID)>, NameStoreError>> {
+        let names = self.names.load();
+        let list = names.iter().map(|(name, actor_id)| (name.to_owned(), *actor_id)).collect();
+        Box::pin(future::ok(list))
+    }
+
+    fn release<'a>(
+        &'a self,
+        actor_id: ActorID,
+        names: &'a [String],
+    ) -> BoxFuture<'a, Result<(), NameStoreError>> {
+        let is_bound =
+            |bound: &HashMap<String, ActorID>, name: &String| bound.get(name) == Some(&actor_id);
+        if names.iter().any(|name| is_bound(&self.names.load(), name)) {
+            self.modify(|bound| {
+                for name in names {
+                    if is_bound(bound, name) {
+                        bound.remove(name);
+                    }
+                }
+            });
+        }
+        Box::pin(future::ok(()))
+    }
+}
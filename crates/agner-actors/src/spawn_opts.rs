@@ -1,8 +1,10 @@
 use std::any::{Any, TypeId};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::actor_id::ActorID;
+use crate::exit::Exit;
 use crate::exit_handler::ExitHandler;
 
 const DEFAULT_MSG_INBOX_SIZE: usize = 1024;
@@ -13,25 +15,97 @@ const DEFAULT_SIG_INBOX_SIZE: usize = 16;
 /// It is possible to specify:
 /// - the set of [actor-ids](crate::actor_id::ActorID) the newly spawned actor will be immediately
 ///   linked to;
+/// - the [parent](crate::context::Context::parent) of the actor;
+/// - the [label](crate::spawn_opts::SpawnOpts::with_label) of the actor;
 /// - the sizes for msg-inbox and signal-inbox;
 /// - [exit-handler](crate::exit_handler::ExitHandler);
+/// - [priority](crate::spawn_opts::Priority);
+/// - whether the queued messages should be [drained on exit](crate::context::Context::on_drain);
+/// - what to do should the behaviour [panic](crate::spawn_opts::PanicPolicy);
+/// - the exit reason should the behaviour [return
+///   normally](crate::spawn_opts::SpawnOpts::with_unexpected_return);
+/// - the [per-sender quota](crate::spawn_opts::SpawnOpts::with_sender_quota) on the msg-inbox;
+/// - the [threshold](crate::spawn_opts::SpawnOpts::with_slow_handler_threshold) for the time spent
+///   handling a single event;
+/// - the number of [reductions](crate::spawn_opts::SpawnOpts::with_reductions) per scheduling
+///   quantum;
+/// - the ordering of the [task outcomes](crate::spawn_opts::TaskPriority) relative to the messages;
 /// - a "bag" of arbitrary properties (identified by their types).
 #[derive(Debug)]
 pub struct SpawnOpts {
     links: HashSet<ActorID>,
+    parent: Option<ActorID>,
+    label: Option<String>,
     msg_inbox_size: usize,
     sig_inbox_size: usize,
     exit_handler: Option<Arc<dyn ExitHandler>>,
+    priority: Priority,
+    drain_on_exit: bool,
+    panic_policy: PanicPolicy,
+    unexpected_return: Option<Exit>,
+    sender_quota: Option<usize>,
+    slow_handler_threshold: Option<Duration>,
+    reductions: Option<usize>,
+    task_priority: TaskPriority,
     data: HashMap<TypeId, Box<dyn Any + Send + Sync + 'static>>,
 }
 
+/// A hint for the [spawner](crate::spawner::Spawner) on how to schedule an actor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// What happens if the actor's behaviour panics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PanicPolicy {
+    /// The actor exits with [`Exit::from_panic`](crate::exit::Exit::from_panic).
+    #[default]
+    Exit,
+    /// The whole process is aborted.
+    Abort,
+}
+
+/// How the outcomes of the actor's tasks (see
+/// [`Context::future_to_inbox`](crate::context::Context::future_to_inbox)) are ordered relative to
+/// the messages sent to the actor.
+///
+/// Either way, the messages are delivered in the order they have been sent, and the task outcomes
+/// — in the order the tasks complete.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TaskPriority {
+    /// A task outcome is delivered only when no sent messages are pending: the messages sent
+    /// before the task's completion is noticed by the actor are delivered ahead of its outcome.
+    #[default]
+    AfterMessages,
+    /// With both the messages and the task outcomes pending, they are delivered alternately: one
+    /// message, one task outcome, and so forth.
+    Interleaved,
+}
+
 impl Default for SpawnOpts {
     fn default() -> Self {
         Self {
             links: Default::default(),
+            parent: None,
+            label: None,
             msg_inbox_size: DEFAULT_MSG_INBOX_SIZE,
             sig_inbox_size: DEFAULT_SIG_INBOX_SIZE,
             exit_handler: None,
+            priority: Default::default(),
+            drain_on_exit: false,
+            panic_policy: Default::default(),
+            unexpected_return: None,
+            sender_quota: None,
+            slow_handler_threshold: None,
+            reductions: None,
+            task_priority: Default::default(),
             data: Default::default(),
         }
     }
@@ -56,6 +130,31 @@ impl SpawnOpts {
     }
 }
 
+impl SpawnOpts {
+    /// specify the actor that has spawned this one (e.g. its supervisor)
+    pub fn with_parent(mut self, parent: ActorID) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+    /// the actor that has spawned this one
+    pub fn parent(&self) -> Option<ActorID> {
+        self.parent
+    }
+}
+
+impl SpawnOpts {
+    /// specify a human-readable label for the actor (reported via
+    /// [`ActorInfo::label`](crate::ActorInfo::label))
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+    /// the label of the actor
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+}
+
 impl SpawnOpts {
     /// specify the capacity limit for msg-inbox
     pub fn with_msg_inbox_size(mut self, sz: usize) -> Self {
@@ -82,6 +181,130 @@ impl SpawnOpts {
     }
 }
 
+impl SpawnOpts {
+    /// specify the [priority](crate::spawn_opts::Priority) of the actor
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// the [priority](crate::spawn_opts::Priority) of the actor
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+}
+
+impl SpawnOpts {
+    /// specify whether the messages remaining in the inbox should be passed to the
+    /// [drain-handler](crate::context::Context::on_drain) upon the actor's exit
+    pub fn with_drain_on_exit(mut self, drain_on_exit: bool) -> Self {
+        self.drain_on_exit = drain_on_exit;
+        self
+    }
+
+    /// whether the messages remaining in the inbox are drained upon the actor's exit
+    pub fn drain_on_exit(&self) -> bool {
+        self.drain_on_exit
+    }
+}
+
+impl SpawnOpts {
+    /// specify what happens if the actor's behaviour panics
+    pub fn with_panic_policy(mut self, panic_policy: PanicPolicy) -> Self {
+        self.panic_policy = panic_policy;
+        self
+    }
+
+    /// what happens if the actor's behaviour panics
+    pub fn panic_policy(&self) -> PanicPolicy {
+        self.panic_policy
+    }
+
+    /// specify the exit reason to substitute for a normal one, should the behaviour return
+    ///
+    /// Meant for the actors that are supposed to run forever: their supervisors then see an
+    /// unexpected return as a failure. An explicit [`Context::exit`](crate::context::Context::exit)
+    /// is not affected.
+    pub fn with_unexpected_return(mut self, exit_reason: Exit) -> Self {
+        self.unexpected_return = Some(exit_reason);
+        self
+    }
+
+    /// the exit reason substituted for a normal one, should the behaviour return
+    pub fn unexpected_return(&self) -> Option<&Exit> {
+        self.unexpected_return.as_ref()
+    }
+}
+
+impl SpawnOpts {
+    /// limit the number of messages from a single sender that the msg-inbox may hold at once
+    ///
+    /// Only the messages sent on behalf of an actor (e.g. via
+    /// [`Context::send`](crate::context::Context::send)) are accounted for. The messages over the
+    /// quota are dropped, and the sender gets
+    /// [`SysChannelError::QuotaExceeded`](crate::system::SysChannelError::QuotaExceeded).
+    pub fn with_sender_quota(mut self, max_inflight_per_sender: usize) -> Self {
+        self.sender_quota = Some(max_inflight_per_sender);
+        self
+    }
+
+    /// the limit on the number of messages from a single sender held by the msg-inbox
+    pub fn sender_quota(&self) -> Option<usize> {
+        self.sender_quota
+    }
+}
+
+impl SpawnOpts {
+    /// Measure the time the behaviour spends handling each event — from the moment it is
+    /// received, till the behaviour is ready to receive the next one — and emit a warning should
+    /// it exceed the `threshold`.
+    ///
+    /// The longest handling time is reported as
+    /// [`ActorInfo::max_handler_time`](crate::ActorInfo::max_handler_time).
+    pub fn with_slow_handler_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_handler_threshold = Some(threshold);
+        self
+    }
+
+    /// the threshold for the time spent handling a single event
+    pub fn slow_handler_threshold(&self) -> Option<Duration> {
+        self.slow_handler_threshold
+    }
+}
+
+impl SpawnOpts {
+    /// Yield to the runtime after delivering `reductions` messages in a row, even if more messages
+    /// are ready (loosely mirroring the reduction budget of the Erlang processes). The count
+    /// restarts once the inbox drains.
+    ///
+    /// Without it, an actor flooded with messages is only preempted by the runtime's own
+    /// cooperative budget, possibly starving its siblings on the same worker.
+    pub fn with_reductions(mut self, reductions: usize) -> Self {
+        self.reductions = Some(reductions.max(1));
+        self
+    }
+
+    /// the number of messages delivered to the actor per scheduling quantum
+    pub fn reductions(&self) -> Option<usize> {
+        self.reductions
+    }
+}
+
+impl SpawnOpts {
+    /// specify how the [task outcomes](crate::spawn_opts::TaskPriority) are ordered relative to
+    /// the messages
+    pub fn with_task_priority(mut self, task_priority: TaskPriority) -> Self {
+        self.task_priority = task_priority;
+        self
+    }
+
+    /// the ordering of the [task outcomes](crate::spawn_opts::TaskPriority) relative to the
+    /// messages
+    pub fn task_priority(&self) -> TaskPriority {
+        self.task_priority
+    }
+}
+
 impl SpawnOpts {
     /// add arbitrary data into the [`Context`](crate::context::Context)
     pub fn with_data<D>(mut self, data: D) -> Self
@@ -0,0 +1,29 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::spawn_opts::Priority;
+
+/// `Spawner` is an entity that runs the actors' futures.
+///
+/// It is possible to specify the spawner for a [`System`](crate::system::System) via
+/// [`SystemConfig::spawner`](crate::system_config::SystemConfig::spawner).
+pub trait Spawner: fmt::Debug + Send + Sync + 'static {
+    fn spawn(&self, priority: Priority, actor: Pin<Box<dyn Future<Output = ()> + Send + 'static>>);
+}
+
+/// A [`Spawner`](crate::spawner::Spawner) that runs the actors using [`tokio::spawn`].
+///
+/// The [priority](crate::spawn_opts::Priority) is ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct TokioSpawner;
+
+impl Spawner for TokioSpawner {
+    fn spawn(
+        &self,
+        _priority: Priority,
+        actor: Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
+    ) {
+        tokio::spawn(actor);
+    }
+}
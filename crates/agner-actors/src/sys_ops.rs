@@ -0,0 +1,62 @@
+use std::future::Future;
+
+use crate::actor_runner::sys_msg::SysMsg;
+use crate::{ActorID, Exit, System};
+
+/// A system operation that can be sent to an actor via [`SystemExt::send_sys_op`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SysOp {
+    /// The target considers itself linked to the specified peer: the exit-signals from the peer
+    /// are no longer ignored.
+    Link(ActorID),
+
+    /// The target no longer considers itself linked to the specified peer.
+    Unlink(ActorID),
+
+    /// Deliver an exit-signal on behalf of the specified actor.
+    ///
+    /// The signal is ignored unless the target is linked to that actor, or that actor is the
+    /// target itself.
+    SigExit(ActorID, Exit),
+}
+
+/// The low-level operations on the actors, for the components implementing their own supervision
+/// (enabled by the `unstable-sys-ops` feature).
+///
+/// Unlike [`System::link`], [`Context::link`](crate::Context::link) and the like, these operations
+/// affect a single actor only: e.g. [`SysOp::Link`] makes the target consider itself linked to the
+/// peer, but does not tell the peer anything. It is up to the caller to keep both sides of a link
+/// consistent.
+///
+/// The rest of what a supervisor needs is already public: the actor's information
+/// ([`System::actor_info`]), and waiting for the termination of an actor ([`System::wait`],
+/// [`Context::monitor_fut`](crate::Context::monitor_fut)).
+///
+/// **Stability.** This API exposes the internals of the actor runtime: it is exempt from the
+/// semver guarantees of the crate, and may change in any release.
+///
+/// This trait is sealed: it is not to be implemented outside of this crate.
+pub trait SystemExt: sealed::Sealed {
+    /// Send a [`SysOp`] to the specified actor.
+    ///
+    /// Returns `true` if the actor was running by the time the operation has been sent.
+    fn send_sys_op(&self, to: ActorID, sys_op: SysOp) -> impl Future<Output = bool> + Send + '_;
+}
+
+impl SystemExt for System {
+    fn send_sys_op(&self, to: ActorID, sys_op: SysOp) -> impl Future<Output = bool> + Send + '_ {
+        let sys_msg = match sys_op {
+            SysOp::Link(peer) => SysMsg::Link(peer),
+            SysOp::Unlink(peer) => SysMsg::Unlink(peer),
+            SysOp::SigExit(from, exit) => SysMsg::SigExit(from, exit),
+        };
+        self.send_sys_msg(to, sys_msg)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for crate::System {}
+}
@@ -1,20 +1,24 @@
 use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
 use std::future::Future;
+use std::pin::Pin;
 use std::sync::atomic::AtomicUsize;
 use std::sync::{Arc, Weak};
 
 use agner_utils::std_error_pp::StdErrorPP;
 use futures::{stream, Stream, StreamExt};
-use tokio::sync::{mpsc, oneshot, RwLock};
-use tracing::Instrument;
+use tokio::sync::{mpsc, oneshot, watch, RwLock};
+use tracing::{Instrument, Level};
 
 use crate::actor::Actor;
 use crate::actor_id::ActorID;
-use crate::actor_runner::sys_msg::{ActorInfo, SysMsg};
-use crate::actor_runner::ActorRunner;
+use crate::actor_runner::sys_msg::{ActorInfo, SysMsg, TraceFlags};
+use crate::actor_runner::{ActorRunner, BehaviourSource};
 use crate::exit::Exit;
 use crate::exit_handler::ExitHandler;
-use crate::spawn_opts::SpawnOpts;
+use crate::interceptor::Interceptor;
+use crate::spawn_opts::{Priority, SpawnOpts};
 use crate::system_config::SystemConfig;
 
 mod actor_entry;
@@ -22,14 +26,66 @@ mod sys_actor_entry;
 use actor_entry::ActorEntry;
 
 mod actor_id_pool;
-use actor_id_pool::ActorIDPool;
+use actor_id_pool::{ActorIDLease, ActorIDPool};
+
+mod names;
+
+mod timers;
+pub use timers::TimerHandle;
 
 mod errors;
-pub use errors::{SysChannelError, SysSpawnError};
+pub use errors::{
+    SysCallError, SysChannelError, SysRebindError, SysRegisterError, SysSpawnError, SysWaitError,
+};
+
+mod sender_quota;
+pub(crate) use sender_quota::Envelope;
+use sender_quota::SenderQuota;
+
+/// An actor's runner, yet to be spawned.
+type Runner = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// A channel to an actor (see [`System::channel`]).
+///
+/// The messages sent via the channel are not attributed to any sender, hence are not subject to
+/// the [sender quota](crate::spawn_opts::SpawnOpts::with_sender_quota).
+///
+/// Mirrors the API of [`mpsc::UnboundedSender`]. The sends are seen by the system's
+/// [interceptors](crate::interceptor::Interceptor).
+pub struct ActorChannel<M> {
+    to: ActorID,
+    tx: mpsc::UnboundedSender<Envelope<M>>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+}
+
+/// A channel to an actor, that does not keep the actor's inbox open (see
+/// [`ActorChannel::downgrade`]).
+pub struct WeakActorChannel<M> {
+    to: ActorID,
+    tx: mpsc::WeakUnboundedSender<Envelope<M>>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+}
 
-pub type ActorChannel<M> = mpsc::UnboundedSender<M>;
+/// The outcome of [`System::try_send_sync`].
+#[derive(Debug)]
+pub enum SendOutcome<M> {
+    /// The message has been put into the actor's inbox.
+    Sent,
+    /// The actor's entry is being modified at the moment (e.g. the actor is being spawned, or is
+    /// terminating): the message is handed back, so that it can be sent again.
+    Busy(M),
+    /// The message could not be delivered.
+    Failed(SysChannelError),
+}
 
 /// A [`System`](crate::system::System) is a scope within which the actors run.
+///
+/// The actors hold the [`System`] by a [weak reference](SystemWeakRef). Once the last [`System`]
+/// is dropped, each of the actors still running is told to exit with
+/// [`Exit::shutdown()`](crate::exit::Exit::shutdown), and is left to terminate on its own: the
+/// drop does not wait for the actors to terminate. This cannot be trapped (see
+/// [`Context::trap_exit`](crate::context::Context::trap_exit)): with the system gone, an actor
+/// would not be able to act upon the signal anyway.
 #[derive(Debug, Clone)]
 pub struct System(Arc<Inner>);
 
@@ -48,6 +104,10 @@ impl SystemWeakRef {
 }
 
 impl System {
+    /// The maximum number of concurrent requests made by
+    /// [`actor_info_batch`](Self::actor_info_batch).
+    pub const ACTOR_INFO_BATCH_CONCURRENCY: usize = 32;
+
     /// Create a new [`System`] using the provided config.
     pub fn new(config: SystemConfig) -> Self {
         static NEXT_SYSTEM_ID: AtomicUsize = AtomicUsize::new(1);
@@ -60,7 +120,16 @@ impl System {
 
         let exit_handler = config.exit_handler.to_owned();
 
-        let inner = Inner { config, system_id, actor_id_pool, actor_entries, exit_handler };
+        let inner = Inner {
+            config,
+            system_id,
+            actor_id_pool,
+            actor_entries,
+            names: Default::default(),
+            name_watches: Default::default(),
+            name_index: Default::default(),
+            exit_handler,
+        };
         Self(Arc::new(inner))
     }
 
@@ -68,6 +137,21 @@ impl System {
     pub fn config(&self) -> &SystemConfig {
         &self.0.config
     }
+
+    /// The number of the actor-ids in use, and the total number of them (i.e.
+    /// [`max_actors`](crate::system_config::SystemConfig::max_actors)).
+    pub fn capacity(&self) -> (usize, usize) {
+        (self.0.actor_id_pool.in_use(), self.0.actor_id_pool.capacity())
+    }
+
+    /// The number of actors, upon reaching which the system is considered near capacity.
+    fn high_watermark(&self) -> Option<usize> {
+        let total = self.0.actor_id_pool.capacity();
+        self.0
+            .config
+            .high_watermark
+            .map(|fraction| ((total as f64 * fraction).ceil() as usize).max(1))
+    }
 }
 
 impl System {
@@ -95,46 +179,263 @@ impl System {
         sys_id = self.0.system_id,
         behaviour = std::any::type_name::<Behaviour>(),
     ))]
-    pub async fn spawn<Behaviour, Args, Message>(
+    pub async fn spawn<Behaviour, Args, Message, Control>(
         &self,
         behaviour: Behaviour,
         args: Args,
-        mut spawn_opts: SpawnOpts,
+        spawn_opts: SpawnOpts,
+    ) -> Result<ActorID, SysSpawnError>
+    where
+        Args: Send + 'static,
+        Message: Unpin + Send + 'static,
+        Control: Unpin + Send + 'static,
+        for<'a> Behaviour: Actor<'a, Args, Message, Control>,
+    {
+        self.do_spawn(BehaviourSource::once(behaviour), args, spawn_opts, None).await
+    }
+
+    /// Same as [`System::spawn`], but the actor can re-run its behaviour via
+    /// [`Context::reinit`](crate::Context::reinit): the behaviour is cloned for each run.
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        behaviour = std::any::type_name::<Behaviour>(),
+    ))]
+    pub async fn spawn_rerunnable<Behaviour, Args, Message, Control>(
+        &self,
+        behaviour: Behaviour,
+        args: Args,
+        spawn_opts: SpawnOpts,
     ) -> Result<ActorID, SysSpawnError>
     where
         Args: Send + 'static,
         Message: Unpin + Send + 'static,
-        for<'a> Behaviour: Actor<'a, Args, Message>,
+        Control: Unpin + Send + 'static,
+        for<'a> Behaviour: Actor<'a, Args, Message, Control> + Clone,
+    {
+        self.do_spawn(BehaviourSource::rerunnable(behaviour), args, spawn_opts, None)
+            .await
+    }
+
+    /// Spawn an actor, and return along with its [`ActorID`] a future resolving into its [`Exit`].
+    ///
+    /// Unlike a [`System::wait`] invoked after [`System::spawn`], the future is wired up before
+    /// the actor starts, so it cannot miss the exit of an actor that terminates right away.
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        behaviour = std::any::type_name::<Behaviour>(),
+    ))]
+    pub async fn spawn_with_join<Behaviour, Args, Message, Control>(
+        &self,
+        behaviour: Behaviour,
+        args: Args,
+        spawn_opts: SpawnOpts,
+    ) -> Result<(ActorID, impl Future<Output = Exit>), SysSpawnError>
+    where
+        Args: Send + 'static,
+        Message: Unpin + Send + 'static,
+        Control: Unpin + Send + 'static,
+        for<'a> Behaviour: Actor<'a, Args, Message, Control>,
+    {
+        let (tx, rx) = oneshot::channel();
+        let actor_id = self
+            .do_spawn(BehaviourSource::once(behaviour), args, spawn_opts, Some((None, tx)))
+            .await?;
+        Ok((actor_id, async move { rx.await.unwrap_or_else(|_| Exit::no_actor()) }))
+    }
+
+    /// Spawn an actor, watched by the `observer` since before the actor starts.
+    pub(crate) async fn spawn_watched<Behaviour, Args, Message, Control>(
+        &self,
+        observer: ActorID,
+        behaviour: Behaviour,
+        args: Args,
+        spawn_opts: SpawnOpts,
+    ) -> Result<(ActorID, oneshot::Receiver<Exit>), SysSpawnError>
+    where
+        Args: Send + 'static,
+        Message: Unpin + Send + 'static,
+        Control: Unpin + Send + 'static,
+        for<'a> Behaviour: Actor<'a, Args, Message, Control>,
+    {
+        let (tx, rx) = oneshot::channel();
+        let actor_id = self
+            .do_spawn(
+                BehaviourSource::once(behaviour),
+                args,
+                spawn_opts,
+                Some((Some(observer), tx)),
+            )
+            .await?;
+        Ok((actor_id, rx))
+    }
+
+    /// Spawn an actor per each of the `args`, and return their [`ActorID`]s in the same order.
+    ///
+    /// The ids for all the actors are acquired before any of them is started: should the
+    /// [`max_actors`](crate::system_config::SystemConfig::max_actors) limit be hit partway, the
+    /// acquired ids are released, and none of the actors is spawned.
+    ///
+    /// As [`SpawnOpts`] are not [`Clone`], the options for each of the actors are produced by
+    /// `spawn_opts`; the `behaviour` is cloned for each of the actors.
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        behaviour = std::any::type_name::<Behaviour>(),
+        count = args.len(),
+    ))]
+    pub async fn spawn_many<Behaviour, Args, Message, Control>(
+        &self,
+        behaviour: Behaviour,
+        args: Vec<Args>,
+        mut spawn_opts: impl FnMut() -> SpawnOpts,
+    ) -> Result<Vec<ActorID>, SysSpawnError>
+    where
+        Args: Send + 'static,
+        Message: Unpin + Send + 'static,
+        Control: Unpin + Send + 'static,
+        for<'a> Behaviour: Actor<'a, Args, Message, Control> + Clone,
+    {
+        let leases = args.iter().map(|_| self.acquire_actor_id()).collect::<Result<Vec<_>, _>>()?;
+
+        let mut actor_ids = Vec::with_capacity(leases.len());
+        let mut entries = Vec::with_capacity(leases.len());
+        let mut actors = Vec::with_capacity(leases.len());
+        for (actor_id_lease, args) in leases.into_iter().zip(args) {
+            actor_ids.push(*actor_id_lease);
+            let (entry, actor) = self.prepare_leased(
+                actor_id_lease,
+                BehaviourSource::once(behaviour.to_owned()),
+                args,
+                spawn_opts(),
+                None,
+            );
+            entries.push(entry);
+            actors.push(actor);
+        }
+
+        // all the entries go first: the actors may well address each other right away.
+        self.actor_entries_put(entries).await;
+        for (priority, actor) in actors {
+            self.0.config.spawner.spawn(priority, actor);
+        }
+        Ok(actor_ids)
+    }
+
+    async fn do_spawn<Behaviour, Args, Message, Control>(
+        &self,
+        behaviour: BehaviourSource<Behaviour>,
+        args: Args,
+        spawn_opts: SpawnOpts,
+        watch: Option<(Option<ActorID>, oneshot::Sender<Exit>)>,
+    ) -> Result<ActorID, SysSpawnError>
+    where
+        Args: Send + 'static,
+        Message: Unpin + Send + 'static,
+        Control: Unpin + Send + 'static,
+        for<'a> Behaviour: Actor<'a, Args, Message, Control>,
+    {
+        let actor_id_lease = self.acquire_actor_id()?;
+        Ok(self.spawn_leased(actor_id_lease, behaviour, args, spawn_opts, watch).await)
+    }
+
+    fn acquire_actor_id(&self) -> Result<ActorIDLease, SysSpawnError> {
+        let (actor_id_lease, in_use) =
+            self.0.actor_id_pool.acquire_id_counted().ok_or(SysSpawnError::MaxActorsLimit)?;
+        if Some(in_use) == self.high_watermark() {
+            let total = self.0.actor_id_pool.capacity();
+            tracing::event!(
+                Level::WARN,
+                actor_id = %*actor_id_lease,
+                event = "near-capacity",
+                used = in_use,
+                total,
+            );
+            for interceptor in self.0.config.interceptors.iter() {
+                interceptor.on_near_capacity(in_use, total);
+            }
+        }
+        Ok(actor_id_lease)
+    }
+
+    async fn spawn_leased<Behaviour, Args, Message, Control>(
+        &self,
+        actor_id_lease: ActorIDLease,
+        behaviour: BehaviourSource<Behaviour>,
+        args: Args,
+        spawn_opts: SpawnOpts,
+        watch: Option<(Option<ActorID>, oneshot::Sender<Exit>)>,
+    ) -> ActorID
+    where
+        Args: Send + 'static,
+        Message: Unpin + Send + 'static,
+        Control: Unpin + Send + 'static,
+        for<'a> Behaviour: Actor<'a, Args, Message, Control>,
+    {
+        let actor_id = *actor_id_lease;
+        let (entry, (priority, actor)) =
+            self.prepare_leased(actor_id_lease, behaviour, args, spawn_opts, watch);
+
+        // the entry goes first: the actor may well terminate before this function returns.
+        self.actor_entry_put(entry).await;
+        self.0.config.spawner.spawn(priority, actor);
+
+        actor_id
+    }
+
+    /// Make the entry for the actor, and the future running it (to be spawned with the returned
+    /// priority once the entry is put).
+    fn prepare_leased<Behaviour, Args, Message, Control>(
+        &self,
+        actor_id_lease: ActorIDLease,
+        behaviour: BehaviourSource<Behaviour>,
+        args: Args,
+        mut spawn_opts: SpawnOpts,
+        watch: Option<(Option<ActorID>, oneshot::Sender<Exit>)>,
+    ) -> (ActorEntry, (Priority, Runner))
+    where
+        Args: Send + 'static,
+        Message: Unpin + Send + 'static,
+        Control: Unpin + Send + 'static,
+        for<'a> Behaviour: Actor<'a, Args, Message, Control>,
     {
         let exit_handler =
             spawn_opts.take_exit_handler().unwrap_or_else(|| self.0.exit_handler.to_owned());
 
         let system = self.to_owned();
-        let actor_id_lease =
-            system.0.actor_id_pool.acquire_id().ok_or(SysSpawnError::MaxActorsLimit)?;
         let actor_id = *actor_id_lease;
 
-        let (messages_tx, messages_rx) = mpsc::unbounded_channel::<Message>();
+        let priority = spawn_opts.priority();
+
+        let (messages_tx, messages_rx) = mpsc::unbounded_channel::<Envelope<Message>>();
+        let sender_quota = spawn_opts.sender_quota().map(SenderQuota::new);
+        let (control_tx, control_rx) = mpsc::unbounded_channel::<Envelope<Control>>();
         let (sys_msg_tx, sys_msg_rx) = mpsc::unbounded_channel();
+        let running = Arc::new(());
 
         let actor = ActorRunner {
             actor_id,
             system_opt: system.rc_downgrade(),
             messages_rx,
+            control_rx,
             sys_msg_rx,
-            sys_msg_tx: sys_msg_tx.to_owned(),
             exit_handler,
             spawn_opts,
+            running: running.to_owned(),
         };
-        tokio::spawn(actor.run(behaviour, args));
-
-        let entry = ActorEntry::new(actor_id_lease, messages_tx, sys_msg_tx);
+        let mut entry = ActorEntry::new(
+            actor_id_lease,
+            messages_tx,
+            control_tx,
+            sys_msg_tx,
+            Arc::downgrade(&running),
+        )
+        .with_sender_quota(sender_quota);
+        if let Some((observer, watch)) = watch {
+            entry.add_watch(observer, watch);
+        }
         // let entry = ActorEntryOld { actor_id_lease, messages_tx: Box::new(messages_tx),
         // sys_msg_tx };
 
-        self.actor_entry_put(entry).await;
-
-        Ok(actor_id)
+        (entry, (priority, Box::pin(actor.run(behaviour, args))))
     }
 
     /// Send SigExit to the specified actor.
@@ -150,25 +451,66 @@ impl System {
     /// Wait for the specified actor to terminate, and return upon its termination the
     /// [`Exit`](crate::exit::Exit). In case the actor with the specified `actor_id` does not exist
     /// — return [`Exit::no_actor()`](`crate::exit::Exit::no_actor`) right away.
+    ///
+    /// By the time the wait resolves, the actor's termination is complete: the
+    /// [deferred functions](crate::context::Context::defer) and the
+    /// [exit handler](crate::exit_handler::ExitHandler) have been invoked, the linked actors have
+    /// been notified, the behaviour (along with its state) has been dropped, and the names bound to
+    /// the actor have been released.
     pub fn wait(&self, actor_id: ActorID) -> impl Future<Output = Exit> {
+        self.wait_from(None, actor_id)
+    }
+
+    /// Same as [`System::wait`], but tells the actor that could not be found (i.e. has never
+    /// existed, or is long gone) — [`SysWaitError::NotFound`] — from the actor that has exited
+    /// with [`Exit::no_actor()`](crate::exit::Exit::no_actor).
+    pub fn wait_checked(
+        &self,
+        actor_id: ActorID,
+    ) -> impl Future<Output = Result<Exit, SysWaitError>> {
+        self.wait_checked_from(None, actor_id)
+    }
+
+    pub(crate) fn wait_from(
+        &self,
+        observer: Option<ActorID>,
+        actor_id: ActorID,
+    ) -> impl Future<Output = Exit> {
+        let waiting = self.wait_checked_from(observer, actor_id);
+        async move {
+            waiting.await.unwrap_or_else(|SysWaitError::NotFound| {
+                tracing::event!(
+                    Level::WARN,
+                    actor_id = %actor_id,
+                    event = "watch-on-uninitialized-entry",
+                    "attempt to install a watch before the ActorEntry is initialized"
+                );
+                Exit::no_actor()
+            })
+        }
+    }
+
+    fn wait_checked_from(
+        &self,
+        observer: Option<ActorID>,
+        actor_id: ActorID,
+    ) -> impl Future<Output = Result<Exit, SysWaitError>> {
         let sys = self.clone();
         async move {
             let (tx, rx) = oneshot::channel();
 
-            if let Some(mut entry) = sys.actor_entry_write(actor_id).await {
-                entry.add_watch(tx);
-            } else {
-                tracing::warn!("attempt to install a watch before the ActorEntry is initialized [actor_id: {}]", actor_id);
-            }
-            rx.await.unwrap_or_else(|_| Exit::no_actor())
-        }.instrument(
-            tracing::span!(
-                tracing::Level::TRACE,
-                "System::wait",
-                sys_id = self.0.system_id,
-                actor_id = display(actor_id)
-            )
-        )
+            sys.actor_entry_write(actor_id)
+                .await
+                .ok_or(SysWaitError::NotFound)?
+                .add_watch(observer, tx);
+            Ok(rx.await.unwrap_or_else(|_| Exit::no_actor()))
+        }
+        .instrument(tracing::span!(
+            tracing::Level::TRACE,
+            "System::wait",
+            sys_id = self.0.system_id,
+            actor_id = display(actor_id)
+        ))
     }
 
     /// Send a [`SysMsg`] to the specified process.
@@ -181,11 +523,11 @@ impl System {
         to = display(to)
     ))]
     pub(crate) async fn send_sys_msg(&self, to: ActorID, sys_msg: SysMsg) -> bool {
-        tracing::trace!(
-            "[sys:{}] trying to send sys-msg [to: {}, sys-msg: {:?}]",
-            self.0.system_id,
-            to,
-            sys_msg
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %to,
+            event = "sys-msg-sending",
+            sys_msg = ?sys_msg,
         );
 
         if let Some(entry) = self.actor_entry_read(to).await {
@@ -193,10 +535,20 @@ impl System {
                 if let Some(tx) = entry.sys_msg_tx() {
                     return tx.send(sys_msg).is_ok()
                 } else {
-                    tracing::warn!("actor_entry is not occupied")
+                    tracing::event!(
+                        Level::WARN,
+                        actor_id = %to,
+                        event = "sys-msg-not-sent",
+                        "actor_entry is not occupied"
+                    )
                 }
             } else {
-                tracing::warn!("actor_id mismatch")
+                tracing::event!(
+                    Level::WARN,
+                    actor_id = %to,
+                    event = "sys-msg-not-sent",
+                    "actor_id mismatch"
+                )
             }
         }
         false
@@ -212,19 +564,210 @@ impl System {
     where
         M: Send + 'static,
     {
-        tracing::trace!("trying to send message",);
-        if let Some(entry) = self.actor_entry_read(to).await {
-            if entry.running_actor_id() == Some(to) {
-                if let Some(tx) = entry.messages_tx::<M>() {
-                    let _ = tx.send(message);
-                } else {
-                    tracing::warn!("message-type mismatch or actor_entry is not occupied");
-                }
-            } else {
-                tracing::warn!("actor_id mismatch")
+        self.do_send(None, to, message).await
+    }
+
+    /// Send a single message to the specified actor on behalf of the actor `from`.
+    ///
+    /// The recipient learns the sender via [`Context::sender`](crate::context::Context::sender).
+    /// The message is only attributed to `from`: it is not charged to the [sender
+    /// quota](crate::spawn_opts::SpawnOpts::with_sender_quota) of `from` on the recipient's
+    /// inbox (only the messages an actor sends itself, via its
+    /// [`Context`](crate::context::Context), are).
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        from = display(from),
+        to = display(to),
+        msg_type = std::any::type_name::<M>()
+    ))]
+    pub async fn send_from<M>(&self, from: ActorID, to: ActorID, message: M)
+    where
+        M: Send + 'static,
+    {
+        let entry = self.actor_entry_read(to).await;
+        let outcome = self.send_via_entry(entry.as_deref(), Some(from), false, to, message);
+        Self::report_not_sent(to, outcome)
+    }
+
+    /// Send a single message to the specified actor, reporting the reason if it could not be
+    /// delivered.
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        to = display(to),
+        msg_type = std::any::type_name::<M>()
+    ))]
+    pub async fn try_send<M>(&self, to: ActorID, message: M) -> Result<(), SysChannelError>
+    where
+        M: Send + 'static,
+    {
+        self.try_send_from(None, to, message).await
+    }
+
+    /// Send a single message to the specified actor without awaiting: e.g. from a synchronous
+    /// context outside of the runtime.
+    ///
+    /// Should the actor's entry be locked at the moment, the message is not sent, but returned as
+    /// [`SendOutcome::Busy`]. Once opened (via [`System::channel`]), an [`ActorChannel`] sends
+    /// without awaiting too.
+    pub fn try_send_sync<M>(&self, to: ActorID, message: M) -> SendOutcome<M>
+    where
+        M: Send + 'static,
+    {
+        let Ok(entry) = self.try_actor_entry_read(to) else { return SendOutcome::Busy(message) };
+        match self.send_via_entry(entry.as_deref(), None, false, to, message) {
+            Ok(()) => SendOutcome::Sent,
+            Err(reason) => SendOutcome::Failed(reason),
+        }
+    }
+
+    /// Send a control message to the specified actor: it is received via
+    /// [`Context::next_control`](crate::context::Context::next_control), ahead of the messages
+    /// queued in the inbox.
+    ///
+    /// Fails with [`SysChannelError::InvalidMessageType`] unless the behaviour of the actor
+    /// accepts the control messages of type `C`.
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        to = display(to),
+        control_type = std::any::type_name::<C>()
+    ))]
+    pub async fn send_control<C>(&self, to: ActorID, control: C) -> Result<(), SysChannelError>
+    where
+        C: Send + 'static,
+    {
+        self.send_control_from(None, to, control).await
+    }
+
+    pub(crate) async fn send_control_from<C>(
+        &self,
+        from: Option<ActorID>,
+        to: ActorID,
+        control: C,
+    ) -> Result<(), SysChannelError>
+    where
+        C: Send + 'static,
+    {
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %to,
+            event = "control-sending",
+            control_type = std::any::type_name::<C>(),
+        );
+        for interceptor in self.0.config.interceptors.iter() {
+            interceptor.on_send(from, to, std::any::type_name::<C>());
+        }
+        let entry = self.actor_entry_read(to).await.ok_or(SysChannelError::NoActor)?;
+        entry.send_control(to, from, control)
+    }
+
+    /// Send a clone of `message` to each of the `targets`.
+    ///
+    /// Returns the number of actors the message has been delivered to: the actors that do not
+    /// exist, or do not accept messages of type `M`, are skipped.
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        targets = targets.len(),
+        msg_type = std::any::type_name::<M>()
+    ))]
+    pub async fn send_many<M>(&self, targets: &[ActorID], message: M) -> usize
+    where
+        M: Clone + Send + 'static,
+    {
+        self.send_many_from(None, targets, message).await
+    }
+
+    pub(crate) async fn send_many_from<M>(
+        &self,
+        from: Option<ActorID>,
+        targets: &[ActorID],
+        message: M,
+    ) -> usize
+    where
+        M: Clone + Send + 'static,
+    {
+        let mut delivered = 0;
+        for &to in targets {
+            for interceptor in self.0.config.interceptors.iter() {
+                interceptor.on_send(from, to, std::any::type_name::<M>());
             }
-        } else {
-            tracing::trace!("no actor_entry")
+
+            // only wait for the slot if it is being modified at the moment.
+            let Some(slot) = self.actor_entry_slot(to) else { continue };
+            let entry = match slot.try_read() {
+                Ok(entry) => entry,
+                Err(_) => slot.read().await,
+            };
+            if entry.send_message(to, from, message.to_owned()).is_ok() {
+                delivered += 1;
+            }
+        }
+        delivered
+    }
+
+    async fn do_send<M>(&self, from: Option<ActorID>, to: ActorID, message: M)
+    where
+        M: Send + 'static,
+    {
+        Self::report_not_sent(to, self.try_send_from(from, to, message).await)
+    }
+
+    fn report_not_sent(to: ActorID, outcome: Result<(), SysChannelError>) {
+        match outcome {
+            Ok(()) => (),
+            Err(reason @ SysChannelError::InvalidMessageType) => tracing::event!(
+                Level::WARN,
+                actor_id = %to,
+                event = "message-not-sent",
+                reason = %reason,
+            ),
+            Err(reason) => tracing::event!(
+                Level::TRACE,
+                actor_id = %to,
+                event = "message-not-sent",
+                reason = %reason,
+            ),
+        }
+    }
+
+    pub(crate) async fn try_send_from<M>(
+        &self,
+        from: Option<ActorID>,
+        to: ActorID,
+        message: M,
+    ) -> Result<(), SysChannelError>
+    where
+        M: Send + 'static,
+    {
+        let entry = self.actor_entry_read(to).await;
+        self.send_via_entry(entry.as_deref(), from, true, to, message)
+    }
+
+    /// Notify the interceptors, and send the message via the recipient's `entry` (unless there is
+    /// no such actor), charging it to the quota of the sender if `charged`.
+    fn send_via_entry<M>(
+        &self,
+        entry: Option<&ActorEntry>,
+        from: Option<ActorID>,
+        charged: bool,
+        to: ActorID,
+        message: M,
+    ) -> Result<(), SysChannelError>
+    where
+        M: Send + 'static,
+    {
+        tracing::event!(
+            Level::TRACE,
+            actor_id = %to,
+            event = "message-sending",
+            msg_type = std::any::type_name::<M>(),
+        );
+        for interceptor in self.0.config.interceptors.iter() {
+            interceptor.on_send(from, to, std::any::type_name::<M>());
+        }
+        let entry = entry.ok_or(SysChannelError::NoActor)?;
+        match from {
+            Some(from) if !charged => entry.send_message_on_behalf_of(to, from, message),
+            _ => entry.send_message(to, from, message),
         }
     }
 
@@ -241,12 +784,36 @@ impl System {
     where
         M: Send + 'static,
     {
-        self.actor_entry_read(to)
+        let entry = self.actor_entry_read(to).await.ok_or(SysChannelError::NoActor)?;
+        entry.resolve_messages_tx(to).map(|tx| ActorChannel {
+            to,
+            tx: tx.to_owned(),
+            interceptors: self.0.config.interceptors.to_owned(),
+        })
+    }
+
+    /// Check whether the specified actor accepts messages of type `M`, before sending any.
+    ///
+    /// Returns [`SysChannelError::InvalidMessageType`] if it does not (the accepted type is named
+    /// by [`System::message_type_name`]).
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        to = display(to),
+        msg_type = std::any::type_name::<M>()
+    ))]
+    pub async fn check_message_type<M>(&self, to: ActorID) -> Result<(), SysChannelError>
+    where
+        M: 'static,
+    {
+        let entry = self.actor_entry_read(to).await.ok_or(SysChannelError::NoActor)?;
+        entry.check_message_type::<M>(to)
+    }
+
+    /// The name of the type of the messages accepted by the specified actor (if it is running).
+    pub async fn message_type_name(&self, actor_id: ActorID) -> Option<&'static str> {
+        self.actor_entry_read(actor_id)
             .await
-            .ok_or(SysChannelError::NoActor)?
-            .messages_tx()
-            .cloned()
-            .ok_or(SysChannelError::InvalidMessageType)
+            .and_then(|entry| entry.message_type_name())
     }
 
     /// Link two actors
@@ -260,10 +827,10 @@ impl System {
         let right_accepted_sys_msg = self.send_sys_msg(right, SysMsg::Link(left)).await;
 
         if !right_accepted_sys_msg {
-            self.send_sys_msg(left, SysMsg::SigExit(right, Exit::no_actor())).await;
+            self.send_sys_msg(left, SysMsg::LinkFailed(right)).await;
         }
         if !left_accepted_sys_msg {
-            self.send_sys_msg(right, SysMsg::SigExit(left, Exit::no_actor())).await;
+            self.send_sys_msg(right, SysMsg::LinkFailed(left)).await;
         }
     }
 
@@ -303,6 +870,45 @@ impl System {
             .and_then(|mut actor_entry| actor_entry.take_data())
     }
 
+    /// Reclaim the entries of the actors whose runners are gone without cleaning up after
+    /// themselves (e.g. due to a panic in an [exit-handler](crate::exit_handler::ExitHandler)).
+    ///
+    /// The [`ActorID`]s of such actors are returned into the pool, their names are released, and
+    /// the pending [`wait`](Self::wait)ers receive [`Exit::no_actor()`].
+    ///
+    /// Returns the number of the reclaimed entries.
+    #[tracing::instrument(skip_all, fields(sys_id = self.0.system_id))]
+    pub async fn reap_dead(&self) -> usize {
+        let mut reaped = 0;
+        for slot in self.0.actor_entries.iter() {
+            if slot.read().await.stale_actor_id().is_none() {
+                continue
+            }
+            let mut entry = slot.write().await;
+            let Some(actor_id) = entry.stale_actor_id() else { continue };
+            let pending_waits = match entry.terminate(actor_id, Exit::no_actor()) {
+                Ok(pending_waits) => pending_waits,
+                Err(reason) => {
+                    tracing::event!(
+                        Level::ERROR,
+                        actor_id = %actor_id,
+                        event = "reap-failed",
+                        reason = %reason.as_ref().pp(),
+                    );
+                    continue
+                },
+            };
+            std::mem::drop(entry);
+            pending_waits.resolve();
+
+            tracing::event!(Level::WARN, actor_id = %actor_id, event = "reaped");
+            self.names_release(actor_id).await;
+            self.names_released(actor_id);
+            reaped += 1;
+        }
+        reaped
+    }
+
     pub fn all_actors(&self) -> impl Stream<Item = ActorID> + '_ {
         stream::iter(&self.0.actor_entries[..])
             .filter_map(|slot| async move { slot.read().await.running_actor_id() })
@@ -317,6 +923,71 @@ impl System {
         self.send_sys_msg(actor_id, SysMsg::GetInfo(tx)).await;
         rx.await.ok()
     }
+
+    /// Wait until the messages sent to the specified actor so far have been put into its inbox.
+    ///
+    /// [`System::send`] returns before the message has reached the recipient's inbox: this is a
+    /// barrier to be used in the tests (or for debugging), e.g. before inspecting
+    /// [`ActorInfo::m_queue_len`]. It does not wait for the actor to actually handle the
+    /// messages. The messages routed via
+    /// [`Context::future_to_inbox`](crate::context::Context::future_to_inbox) are not accounted
+    /// for.
+    ///
+    /// Returns `false` if the actor is not running (or terminates before having caught up).
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        actor_id = display(actor_id)
+    ))]
+    pub async fn sync(&self, actor_id: ActorID) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send_sys_msg(actor_id, SysMsg::Sync(tx)).await;
+        rx.await.is_ok()
+    }
+
+    /// Query the information about several actors, concurrently.
+    ///
+    /// No more than [`ACTOR_INFO_BATCH_CONCURRENCY`](Self::ACTOR_INFO_BATCH_CONCURRENCY) requests
+    /// are in flight at a time. The results are in the order of `actor_ids`.
+    #[tracing::instrument(skip_all, fields(sys_id = self.0.system_id, count = actor_ids.len()))]
+    pub async fn actor_info_batch(
+        &self,
+        actor_ids: &[ActorID],
+    ) -> Vec<(ActorID, Option<ActorInfo>)> {
+        stream::iter(actor_ids.iter().copied())
+            .map(|actor_id| async move { (actor_id, self.actor_info(actor_id).await) })
+            .buffered(Self::ACTOR_INFO_BATCH_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Enable (or disable) tracing of the events delivered to the specified actor.
+    ///
+    /// The traced events are logged at the `INFO` level along with the time of delivery. The
+    /// messages are rendered using their [`Debug`](std::fmt::Debug) implementation if the actor
+    /// [has enabled inbox peeking](crate::context::Context::enable_inbox_peek), or by their
+    /// type-name otherwise.
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        actor_id = display(actor_id)
+    ))]
+    pub async fn set_trace(&self, actor_id: ActorID, trace_flags: TraceFlags) {
+        self.send_sys_msg(actor_id, SysMsg::SetTrace(trace_flags)).await;
+    }
+
+    /// Preview up to `max` messages queued in the inbox of the specified actor, each rendered
+    /// using its [`Debug`](std::fmt::Debug) implementation.
+    ///
+    /// The list is empty if the actor does not exist, or if it has not opted into peeking via
+    /// [`Context::enable_inbox_peek`](crate::context::Context::enable_inbox_peek).
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        actor_id = display(actor_id)
+    ))]
+    pub async fn peek_messages(&self, actor_id: ActorID, max: usize) -> Vec<String> {
+        let (tx, rx) = oneshot::channel();
+        self.send_sys_msg(actor_id, SysMsg::PeekInbox(max, tx)).await;
+        rx.await.unwrap_or_default()
+    }
 }
 
 #[derive(Debug)]
@@ -325,5 +996,108 @@ struct Inner {
     system_id: usize,
     actor_id_pool: ActorIDPool,
     actor_entries: Box<[RwLock<ActorEntry>]>,
+    /// Serializes the modifications of the names (see [`NameStore`](crate::NameStore)).
+    names: tokio::sync::Mutex<()>,
+    name_watches: std::sync::Mutex<HashMap<String, watch::Sender<Option<ActorID>>>>,
+    name_index: std::sync::Mutex<names::NameIndex>,
     exit_handler: Arc<dyn ExitHandler>,
 }
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        for entry in self.actor_entries.iter_mut() {
+            let entry = entry.get_mut();
+            if let (Some(actor_id), Some(sys_msg_tx)) =
+                (entry.running_actor_id(), entry.sys_msg_tx())
+            {
+                tracing::trace!(
+                    sys_id = self.system_id,
+                    actor_id = display(actor_id),
+                    "system dropped: shutting the actor down"
+                );
+                let _ = sys_msg_tx.send(SysMsg::SystemDropped);
+            }
+        }
+    }
+}
+
+impl<M> ActorChannel<M> {
+    /// Send a message to the actor. Fails if the actor has terminated.
+    pub fn send(&self, message: M) -> Result<(), mpsc::error::SendError<M>> {
+        for interceptor in self.interceptors.iter() {
+            interceptor.on_send(None, self.to, std::any::type_name::<M>());
+        }
+        self.tx
+            .send(Envelope::anonymous(message))
+            .map_err(|mpsc::error::SendError(envelope)| mpsc::error::SendError(envelope.message))
+    }
+
+    /// Whether the actor has terminated.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Resolves once the actor has terminated.
+    pub async fn closed(&self) {
+        self.tx.closed().await
+    }
+
+    /// Whether both channels lead to the same actor's inbox.
+    pub fn same_channel(&self, other: &Self) -> bool {
+        self.tx.same_channel(&other.tx)
+    }
+
+    pub fn downgrade(&self) -> WeakActorChannel<M> {
+        WeakActorChannel {
+            to: self.to,
+            tx: self.tx.downgrade(),
+            interceptors: self.interceptors.to_owned(),
+        }
+    }
+
+    pub fn strong_count(&self) -> usize {
+        self.tx.strong_count()
+    }
+
+    pub fn weak_count(&self) -> usize {
+        self.tx.weak_count()
+    }
+}
+
+impl<M> WeakActorChannel<M> {
+    /// The channel, unless the actor's inbox has been closed.
+    pub fn upgrade(&self) -> Option<ActorChannel<M>> {
+        let tx = self.tx.upgrade()?;
+        Some(ActorChannel { to: self.to, tx, interceptors: self.interceptors.to_owned() })
+    }
+}
+
+impl<M> SendOutcome<M> {
+    pub fn is_sent(&self) -> bool {
+        matches!(self, Self::Sent)
+    }
+}
+
+impl<M> Clone for ActorChannel<M> {
+    fn clone(&self) -> Self {
+        Self { to: self.to, tx: self.tx.to_owned(), interceptors: self.interceptors.to_owned() }
+    }
+}
+
+impl<M> fmt::Debug for ActorChannel<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ActorChannel").field(&std::any::type_name::<M>()).finish()
+    }
+}
+
+impl<M> Clone for WeakActorChannel<M> {
+    fn clone(&self) -> Self {
+        Self { to: self.to, tx: self.tx.to_owned(), interceptors: self.interceptors.to_owned() }
+    }
+}
+
+impl<M> fmt::Debug for WeakActorChannel<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WeakActorChannel").field(&std::any::type_name::<M>()).finish()
+    }
+}
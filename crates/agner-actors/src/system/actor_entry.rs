@@ -1,6 +1,7 @@
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::error::Error as StdError;
+use std::sync::{Arc, Weak};
 use std::time::Instant;
 
 use tokio::sync::{mpsc, oneshot};
@@ -10,6 +11,8 @@ use crate::actor_runner::sys_msg::SysMsg;
 use crate::exit::Exit;
 
 use super::actor_id_pool::ActorIDLease;
+use super::errors::SysChannelError;
+use super::sender_quota::{Envelope, SenderQuota};
 
 pub type Data = Box<dyn Any + Send + Sync + 'static>;
 
@@ -26,13 +29,41 @@ enum Entry {
 struct Occupied {
     actor_id_lease: ActorIDLease,
     messages_tx: Box<dyn Any + Send + Sync + 'static>,
+    message_type: (TypeId, &'static str),
     sys_msg_tx: mpsc::UnboundedSender<SysMsg>,
-    watches: Vec<oneshot::Sender<Exit>>,
+    running: Weak<()>,
+    sender_quota: Option<Arc<SenderQuota>>,
+    control_tx: Box<dyn Any + Send + Sync + 'static>,
+    watches: Vec<Watch>,
     data: HashMap<TypeId, Data>,
 }
 
 type Vacant = Option<Terminated>;
 
+/// A pending [`System::wait`](crate::System::wait), along with the actor that has installed it
+/// (if any).
+type Watch = (Option<ActorID>, oneshot::Sender<Exit>);
+
+/// The waits pending upon the termination of an actor (see [`ActorEntry::terminate`]).
+#[derive(Debug)]
+#[must_use = "the waits are resolved via `PendingWaits::resolve`"]
+pub(crate) struct PendingWaits {
+    actor_id: ActorID,
+    exit: Exit,
+    watches: Vec<Watch>,
+}
+
+impl PendingWaits {
+    /// Notify the waiters: should be invoked once the entry is unlocked, so that the waiters
+    /// find the entry terminated rather than locked.
+    pub fn resolve(self) {
+        self.watches.into_iter().enumerate().for_each(|(idx, (_, tx))| {
+            tracing::trace!("[{}] notifying waiting chan #{}", self.actor_id, idx);
+            let _ = tx.send(self.exit.to_owned());
+        });
+    }
+}
+
 #[derive(Debug)]
 struct Terminated {
     actor_id: ActorID,
@@ -60,30 +91,145 @@ impl ActorEntry {
         }
     }
 
-    pub fn messages_tx<M>(&self) -> Option<&mpsc::UnboundedSender<M>>
+    pub fn resolve_messages_tx<M>(
+        &self,
+        actor_id: ActorID,
+    ) -> Result<&mpsc::UnboundedSender<Envelope<M>>, SysChannelError>
+    where
+        M: Send + 'static,
+    {
+        match &self.0 {
+            Entry::Occupied(occupied) if *occupied.actor_id_lease == actor_id =>
+                occupied.messages_tx.downcast_ref().ok_or(SysChannelError::InvalidMessageType),
+            Entry::Vacant(Some(terminated)) if terminated.actor_id == actor_id =>
+                Err(SysChannelError::Terminated),
+            _ => Err(SysChannelError::NoActor),
+        }
+    }
+
+    /// Send the `message` to the actor on behalf of the sender (if any), charging it to the quota
+    /// of the sender.
+    pub fn send_message<M>(
+        &self,
+        actor_id: ActorID,
+        from: Option<ActorID>,
+        message: M,
+    ) -> Result<(), SysChannelError>
     where
         M: Send + 'static,
     {
-        self.occupied().and_then(|oe| oe.messages_tx.downcast_ref())
+        let tx = self.resolve_messages_tx::<M>(actor_id)?;
+        self.send_envelope(tx, from, message)
     }
+
+    /// Send the `message` to the actor attributed to `on_behalf_of`, without charging it to the
+    /// quota of that actor.
+    pub fn send_message_on_behalf_of<M>(
+        &self,
+        actor_id: ActorID,
+        on_behalf_of: ActorID,
+        message: M,
+    ) -> Result<(), SysChannelError>
+    where
+        M: Send + 'static,
+    {
+        let tx = self.resolve_messages_tx::<M>(actor_id)?;
+        tx.send(Envelope { sender: Some(on_behalf_of), ticket: None, message })
+            .map_err(|_| SysChannelError::Terminated)
+    }
+
+    /// Send the `control` message into the lane of the control messages of the actor (see
+    /// [`Context::next_control`](crate::context::Context::next_control)), charging it to the
+    /// quota of the sender (if any).
+    pub fn send_control<C>(
+        &self,
+        actor_id: ActorID,
+        from: Option<ActorID>,
+        control: C,
+    ) -> Result<(), SysChannelError>
+    where
+        C: Send + 'static,
+    {
+        let tx = match &self.0 {
+            Entry::Occupied(occupied) if *occupied.actor_id_lease == actor_id => occupied
+                .control_tx
+                .downcast_ref::<mpsc::UnboundedSender<Envelope<C>>>()
+                .ok_or(SysChannelError::InvalidMessageType)?,
+            Entry::Vacant(Some(terminated)) if terminated.actor_id == actor_id =>
+                return Err(SysChannelError::Terminated),
+            _ => return Err(SysChannelError::NoActor),
+        };
+        self.send_envelope(tx, from, control)
+    }
+
+    fn send_envelope<M>(
+        &self,
+        tx: &mpsc::UnboundedSender<Envelope<M>>,
+        from: Option<ActorID>,
+        message: M,
+    ) -> Result<(), SysChannelError> {
+        let sender_quota = self.occupied().and_then(|oe| oe.sender_quota.as_ref());
+        let ticket = match from.zip(sender_quota) {
+            Some((from, sender_quota)) =>
+                Some(sender_quota.acquire(from).ok_or(SysChannelError::QuotaExceeded)?),
+            None => None,
+        };
+        tx.send(Envelope { sender: from, ticket, message })
+            .map_err(|_| SysChannelError::Terminated)
+    }
+
+    /// Check whether the actor accepts messages of type `M` (without resolving the channel).
+    pub fn check_message_type<M: 'static>(&self, actor_id: ActorID) -> Result<(), SysChannelError> {
+        match &self.0 {
+            Entry::Occupied(occupied) if *occupied.actor_id_lease == actor_id =>
+                if occupied.message_type.0 == TypeId::of::<M>() {
+                    Ok(())
+                } else {
+                    Err(SysChannelError::InvalidMessageType)
+                },
+            Entry::Vacant(Some(terminated)) if terminated.actor_id == actor_id =>
+                Err(SysChannelError::Terminated),
+            _ => Err(SysChannelError::NoActor),
+        }
+    }
+
+    /// The name of the type of the messages accepted by the running actor.
+    pub fn message_type_name(&self) -> Option<&'static str> {
+        self.occupied().map(|oe| oe.message_type.1)
+    }
+
     pub fn sys_msg_tx(&self) -> Option<&mpsc::UnboundedSender<SysMsg>> {
         self.occupied().map(|oe| &oe.sys_msg_tx)
     }
+
+    /// The actor whose runner has gone without terminating this entry.
+    pub fn stale_actor_id(&self) -> Option<ActorID> {
+        self.occupied()
+            .filter(|oe| oe.running.strong_count() == 0)
+            .map(|oe| *oe.actor_id_lease)
+    }
 }
 
 impl ActorEntry {
-    pub fn new<Message>(
+    pub fn new<Message, Control>(
         actor_id_lease: ActorIDLease,
-        messages_tx: mpsc::UnboundedSender<Message>,
+        messages_tx: mpsc::UnboundedSender<Envelope<Message>>,
+        control_tx: mpsc::UnboundedSender<Envelope<Control>>,
         sys_msg_tx: mpsc::UnboundedSender<SysMsg>,
+        running: Weak<()>,
     ) -> Self
     where
         Message: Send + 'static,
+        Control: Send + 'static,
     {
         let occupied = Occupied {
             actor_id_lease,
             messages_tx: Box::new(messages_tx),
+            message_type: (TypeId::of::<Message>(), std::any::type_name::<Message>()),
             sys_msg_tx,
+            running,
+            sender_quota: None,
+            control_tx: Box::new(control_tx),
             watches: Default::default(),
             data: Default::default(),
         };
@@ -91,6 +237,13 @@ impl ActorEntry {
         Self(entry)
     }
 
+    pub fn with_sender_quota(mut self, sender_quota: Option<Arc<SenderQuota>>) -> Self {
+        if let Entry::Occupied(occupied) = &mut self.0 {
+            occupied.sender_quota = sender_quota;
+        }
+        self
+    }
+
     pub fn put_data<D: Any + Send + Sync + 'static>(&mut self, data: D) {
         if let Entry::Occupied(occupied) = &mut self.0 {
             let type_id = data.type_id();
@@ -119,13 +272,24 @@ impl ActorEntry {
         }
     }
 
-    pub fn add_watch(&mut self, watch: oneshot::Sender<Exit>) {
-        fn replace_or_append(
-            actor_id: ActorID,
-            watches: &mut Vec<oneshot::Sender<Exit>>,
-            watch: oneshot::Sender<Exit>,
-        ) {
-            let maybe_replace = watches.iter_mut().enumerate().find(|(_idx, tx)| tx.is_closed());
+    /// The actors waiting for this one to terminate (see [`Self::add_watch`]).
+    pub fn watchers(&self) -> Vec<ActorID> {
+        let Some(occupied) = self.occupied() else { return vec![] };
+        let mut watchers = occupied
+            .watches
+            .iter()
+            .filter(|(_, tx)| !tx.is_closed())
+            .filter_map(|(observer, _)| *observer)
+            .collect::<Vec<_>>();
+        watchers.sort();
+        watchers.dedup();
+        watchers
+    }
+
+    pub fn add_watch(&mut self, observer: Option<ActorID>, watch: oneshot::Sender<Exit>) {
+        fn replace_or_append(actor_id: ActorID, watches: &mut Vec<Watch>, watch: Watch) {
+            let maybe_replace =
+                watches.iter_mut().enumerate().find(|(_idx, (_, tx))| tx.is_closed());
             if let Some((idx, to_replace)) = maybe_replace {
                 tracing::trace!("[{}] adding 'wait' [replace #{}]", actor_id, idx);
                 *to_replace = watch;
@@ -147,16 +311,22 @@ impl ActorEntry {
                 let _ = watch.send(exit.to_owned());
             },
             Entry::Occupied(occupied) => {
-                replace_or_append(*occupied.actor_id_lease, &mut occupied.watches, watch);
+                replace_or_append(
+                    *occupied.actor_id_lease,
+                    &mut occupied.watches,
+                    (observer, watch),
+                );
             },
         }
     }
 
+    /// Mark the entry terminated, returning the waits to be [resolved](PendingWaits::resolve)
+    /// once the entry is unlocked.
     pub fn terminate(
         &mut self,
         actor_id: ActorID,
         exit_reason: Exit,
-    ) -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
+    ) -> Result<PendingWaits, Box<dyn StdError + Send + Sync + 'static>> {
         if self.running_actor_id() != Some(actor_id) {
             Err("this entry does not have a running entry with the specified actor_id")?
         }
@@ -170,13 +340,11 @@ impl ActorEntry {
             })),
         );
 
-        if let Entry::Occupied(Occupied { actor_id_lease, mut watches, .. }) = to_terminate {
-            watches.drain(..).enumerate().for_each(|(idx, tx)| {
-                tracing::trace!("[{}] notifying waiting chan #{}", *actor_id_lease, idx);
-                let _ = tx.send(exit_reason.to_owned());
-            });
-        }
-        Ok(())
+        let watches = match to_terminate {
+            Entry::Occupied(Occupied { watches, .. }) => watches,
+            Entry::Vacant(_) => Default::default(),
+        };
+        Ok(PendingWaits { actor_id, exit: exit_reason, watches })
     }
 }
 
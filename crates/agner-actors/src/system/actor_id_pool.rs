@@ -13,6 +13,7 @@ pub struct ActorIDPool(Arc<Inner>);
 struct Inner {
     system_id: usize,
     next_seq_id: AtomicUsize,
+    in_use: AtomicUsize,
     head: AtomicUsize,
     tail: AtomicUsize,
     slots: Box<[AtomicUsize]>,
@@ -45,6 +46,7 @@ impl ActorIDPool {
         let inner = Arc::new(Inner {
             system_id,
             next_seq_id: AtomicUsize::new(0),
+            in_use: AtomicUsize::new(0),
             head: AtomicUsize::new(0),
             tail: AtomicUsize::new(max_actors),
             slots: (0..max_actors)
@@ -57,7 +59,13 @@ impl ActorIDPool {
     }
 
     /// Acquire an unused [`ActorID`]
+    #[cfg(test)]
     pub fn acquire_id(&self) -> Option<ActorIDLease> {
+        self.acquire_id_counted().map(|(lease, _in_use)| lease)
+    }
+
+    /// Acquire an unused [`ActorID`], along with the number of ids in use (including this one).
+    pub fn acquire_id_counted(&self) -> Option<(ActorIDLease, usize)> {
         let slot_idx = self.0.take_nonempty_slot()?;
         let slot = &self.0.slots[slot_idx];
 
@@ -79,12 +87,23 @@ impl ActorIDPool {
 
         let seq_id = self.0.next_seq_id();
 
-        let in_use = ActorIDLease {
+        let lease = ActorIDLease {
             inner: Arc::clone(&self.0),
             actor_id: ActorID::new(self.0.system_id, actor_id, seq_id),
         };
+        let in_use = self.0.in_use.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+
+        Some((lease, in_use))
+    }
 
-        Some(in_use)
+    /// The number of ids currently in use.
+    pub fn in_use(&self) -> usize {
+        self.0.in_use.load(AtomicOrdering::Relaxed)
+    }
+
+    /// The total number of ids in the pool.
+    pub fn capacity(&self) -> usize {
+        self.0.slots.len() - 1
     }
 }
 
@@ -145,6 +164,8 @@ impl Inner {
     }
 
     fn release_id(&self, actor_id: usize) {
+        self.in_use.fetch_sub(1, AtomicOrdering::Relaxed);
+
         let slot_idx = self
             .take_empty_slot()
             .expect("An attempt to release an id into already full pool.");
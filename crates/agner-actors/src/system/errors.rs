@@ -1,3 +1,7 @@
+use crate::actor_id::ActorID;
+use crate::exit::Exit;
+use crate::name_store::NameStoreError;
+
 /// A failure to spawn an actor by [`System::spawn(&self, ...)`](crate::system::System::spawn).
 #[derive(Debug, thiserror::Error)]
 pub enum SysSpawnError {
@@ -12,6 +16,56 @@ pub enum SysChannelError {
     #[error("No such actor")]
     NoActor,
 
+    #[error("Actor has terminated")]
+    Terminated,
+
     #[error("Invalid message-type")]
     InvalidMessageType,
+
+    #[error("The sender has exceeded its quota on the actor's inbox")]
+    QuotaExceeded,
+}
+
+/// A failure to wait for an actor by [`System::wait_checked(&self,
+/// ActorID)`](crate::system::System::wait_checked).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum SysWaitError {
+    #[error("No such actor")]
+    NotFound,
+}
+
+/// A failure to get a reply by [`Context::call(&mut self, ...)`](crate::context::Context::call).
+#[derive(Debug, thiserror::Error)]
+pub enum SysCallError {
+    #[error("Failed to send the request")]
+    Send(#[source] SysChannelError),
+
+    #[error("The callee has terminated")]
+    CalleeDown(#[source] Exit),
+
+    #[error("Timeout")]
+    Timeout,
+}
+
+/// A failure to bind a name by [`System::register(&self, ...)`](crate::system::System::register).
+#[derive(Debug, thiserror::Error)]
+pub enum SysRegisterError {
+    #[error("No such actor")]
+    NoActor,
+
+    #[error("The name is already bound to {}", _0)]
+    NameTaken(ActorID),
+
+    #[error("Name-store failure")]
+    NameStore(#[source] NameStoreError),
+}
+
+/// A failure to rebind a name by [`System::rebind(&self, ...)`](crate::system::System::rebind).
+#[derive(Debug, thiserror::Error)]
+pub enum SysRebindError {
+    #[error("No such actor")]
+    NoActor,
+
+    #[error("Name-store failure")]
+    NameStore(#[source] NameStoreError),
 }
@@ -0,0 +1,235 @@
+use std::collections::{hash_map, BTreeSet, HashSet};
+
+use super::*;
+use crate::name_store::{NameStore, NameStoreError};
+
+/// The names bound to each of the actors of the system.
+///
+/// Only modified with the names locked; read without locking them.
+#[derive(Debug, Default)]
+pub(super) struct NameIndex {
+    by_actor: HashMap<ActorID, BTreeSet<String>>,
+    /// The terminating actors, whose names are being released: no name can be bound to them.
+    releasing: HashSet<ActorID>,
+}
+
+impl System {
+    /// Bind the `name` to the specified actor.
+    ///
+    /// The name is released automatically when the actor terminates.
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        actor_id = display(actor_id),
+        name = name,
+    ))]
+    pub async fn register(&self, name: &str, actor_id: ActorID) -> Result<(), SysRegisterError> {
+        let _names = self.0.names.lock().await;
+        if let Some(bound) =
+            self.name_store().whereis(name).await.map_err(SysRegisterError::NameStore)?
+        {
+            return Err(SysRegisterError::NameTaken(bound))
+        }
+        if !self.is_bindable(actor_id).await {
+            return Err(SysRegisterError::NoActor)
+        }
+        self.name_store()
+            .register(name, actor_id)
+            .await
+            .map_err(SysRegisterError::NameStore)?
+            .map_err(SysRegisterError::NameTaken)?;
+        self.name_index().bind(name, actor_id);
+        self.name_changed(name, Some(actor_id));
+        Ok(())
+    }
+
+    /// Release the `name`. Returns the actor the name was bound to (if any).
+    #[tracing::instrument(skip_all, fields(sys_id = self.0.system_id, name = name))]
+    pub async fn unregister(&self, name: &str) -> Result<Option<ActorID>, NameStoreError> {
+        let _names = self.0.names.lock().await;
+        let unbound = self.name_store().unregister(name).await?;
+        if let Some(actor_id) = unbound {
+            self.name_index().unbind(name, actor_id);
+            self.name_changed(name, None);
+        }
+        Ok(unbound)
+    }
+
+    /// Look up the actor bound to the `name`.
+    ///
+    /// Should the [name-store](crate::NameStore) fail, the failure is logged, and the name is
+    /// considered unbound (see [`System::try_whereis`]).
+    pub async fn whereis(&self, name: &str) -> Option<ActorID> {
+        self.try_whereis(name).await.unwrap_or_else(|reason| {
+            tracing::event!(
+                Level::WARN,
+                name,
+                event = "whereis-failed",
+                reason = %reason.pp(),
+            );
+            None
+        })
+    }
+
+    /// Same as [`System::whereis`], but the failure of the [name-store](crate::NameStore) is
+    /// returned to the caller.
+    pub async fn try_whereis(&self, name: &str) -> Result<Option<ActorID>, NameStoreError> {
+        self.name_store().whereis(name).await
+    }
+
+    /// All of the names bound to the actors.
+    pub async fn registered_names(&self) -> Result<Vec<(String, ActorID)>, NameStoreError> {
+        self.name_store().list().await
+    }
+
+    /// Atomically bind the `name` to the `new` actor, returning the previously bound one (if any).
+    ///
+    /// The binding is replaced in a single operation of the [name-store](crate::NameStore), so
+    /// that no concurrent [`whereis`](crate::system::System::whereis) can observe the name
+    /// unbound.
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        actor_id = display(new),
+        name = name,
+    ))]
+    pub async fn rebind(
+        &self,
+        name: &str,
+        new: ActorID,
+    ) -> Result<Option<ActorID>, SysRebindError> {
+        let _names = self.0.names.lock().await;
+        if !self.is_bindable(new).await {
+            return Err(SysRebindError::NoActor)
+        }
+        let old = self.name_store().rebind(name, new).await.map_err(SysRebindError::NameStore)?;
+        {
+            let mut name_index = self.name_index();
+            if let Some(old) = old {
+                name_index.unbind(name, old);
+            }
+            name_index.bind(name, new);
+        }
+        self.name_changed(name, Some(new));
+        Ok(old)
+    }
+
+    /// Watch the binding of the `name`: the stream yields the current binding, and then each
+    /// subsequent change of it (as the name gets bound, rebound, or released).
+    ///
+    /// Only the latest binding is guaranteed to be observed: the intermediate ones may be skipped
+    /// if the stream is not polled fast enough.
+    pub async fn watch_name(&self, name: &str) -> impl Stream<Item = Option<ActorID>> {
+        // holding the names locked, so that no change is missed between the lookup and the
+        // subscription.
+        let _names = self.0.names.lock().await;
+        let current = self.whereis(name).await;
+        let rx = self
+            .0
+            .name_watches
+            .lock()
+            .expect("poisoned")
+            .entry(name.to_owned())
+            .or_insert_with(|| watch::channel(current).0)
+            .subscribe();
+
+        stream::unfold((rx, true), |(mut rx, first)| async move {
+            if !first && rx.changed().await.is_err() {
+                return None
+            }
+            let bound = *rx.borrow_and_update();
+            Some((bound, (rx, false)))
+        })
+    }
+
+    /// The name the actor is registered under: the first one in the lexicographic order, should
+    /// there be several.
+    pub(crate) fn registered_name(&self, actor_id: ActorID) -> Option<String> {
+        self.name_index().by_actor.get(&actor_id)?.first().cloned()
+    }
+
+    /// Release the names bound to the terminating `actor_id`. No name can be bound to the actor
+    /// from then on, until [`System::names_released`] is invoked.
+    ///
+    /// The names are taken off the index with the names locked, while the
+    /// [name-store](crate::NameStore) is updated without holding the lock: a slow store does not
+    /// stall the modifications of the other names.
+    pub(crate) async fn names_release(&self, actor_id: ActorID) {
+        let names = {
+            let _names = self.0.names.lock().await;
+            let names = self.name_index().take(actor_id);
+            for name in names.iter() {
+                self.name_changed(name, None);
+            }
+            names
+        };
+        if names.is_empty() {
+            return
+        }
+        if let Err(reason) = self.name_store().release(actor_id, &names).await {
+            tracing::event!(
+                Level::WARN,
+                actor_id = %actor_id,
+                event = "names-release-failed",
+                names = ?names,
+                reason = %reason.pp(),
+            );
+        }
+    }
+
+    /// The entry of the actor, whose names have been released, is terminated (see
+    /// [`System::names_release`]).
+    pub(crate) fn names_released(&self, actor_id: ActorID) {
+        self.name_index().releasing.remove(&actor_id);
+    }
+
+    /// Notify the watchers of the `name` (if any). Invoked with the names locked.
+    fn name_changed(&self, name: &str, bound: Option<ActorID>) {
+        let mut name_watches = self.0.name_watches.lock().expect("poisoned");
+        if let Some(tx) = name_watches.get(name) {
+            if tx.receiver_count() == 0 {
+                name_watches.remove(name);
+            } else {
+                tx.send_replace(bound);
+            }
+        }
+    }
+
+    fn name_store(&self) -> &dyn NameStore {
+        self.0.config.name_store.as_ref()
+    }
+
+    fn name_index(&self) -> std::sync::MutexGuard<'_, NameIndex> {
+        self.0.name_index.lock().expect("poisoned")
+    }
+
+    /// Whether the actor is running, and is not releasing its names. Invoked with the names
+    /// locked.
+    async fn is_bindable(&self, actor_id: ActorID) -> bool {
+        let releasing = self.name_index().releasing.contains(&actor_id);
+        !releasing &&
+            self.actor_entry_read(actor_id)
+                .await
+                .and_then(|entry| entry.running_actor_id())
+                .is_some()
+    }
+}
+
+impl NameIndex {
+    fn bind(&mut self, name: &str, actor_id: ActorID) {
+        self.by_actor.entry(actor_id).or_default().insert(name.to_owned());
+    }
+
+    fn unbind(&mut self, name: &str, actor_id: ActorID) {
+        if let hash_map::Entry::Occupied(mut names) = self.by_actor.entry(actor_id) {
+            names.get_mut().remove(name);
+            if names.get().is_empty() {
+                names.remove();
+            }
+        }
+    }
+
+    /// Take the names bound to the `actor_id` off the index, marking the actor as releasing them.
+    fn take(&mut self, actor_id: ActorID) -> Vec<String> {
+        self.releasing.insert(actor_id);
+        self.by_actor.remove(&actor_id).map(Vec::from_iter).unwrap_or_default()
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::actor_id::ActorID;
+
+/// A message, along with its sender (if known), and the [`QuotaTicket`] of the sender (if the
+/// sender is subject to a quota).
+#[derive(Debug)]
+pub(crate) struct Envelope<M> {
+    pub sender: Option<ActorID>,
+    pub ticket: Option<QuotaTicket>,
+    pub message: M,
+}
+
+impl<M> Envelope<M> {
+    /// A message without a known sender.
+    pub fn anonymous(message: M) -> Self {
+        Self { sender: None, ticket: None, message }
+    }
+}
+
+/// The limit on the number of messages from each sender that an actor's inbox may hold at once
+/// (see [`SpawnOpts::with_sender_quota`](crate::spawn_opts::SpawnOpts::with_sender_quota)).
+#[derive(Debug)]
+pub(crate) struct SenderQuota {
+    max_inflight: usize,
+    inflight: Mutex<HashMap<ActorID, usize>>,
+}
+
+/// Accounts for a single message in flight: released upon drop, i.e. once the message is either
+/// received by the actor, or discarded.
+#[derive(Debug)]
+pub(crate) struct QuotaTicket(Arc<SenderQuota>, ActorID);
+
+impl SenderQuota {
+    pub fn new(max_inflight: usize) -> Arc<Self> {
+        Arc::new(Self { max_inflight, inflight: Default::default() })
+    }
+
+    pub fn acquire(self: &Arc<Self>, sender: ActorID) -> Option<QuotaTicket> {
+        let mut inflight = self.inflight.lock().expect("poisoned");
+        let count = inflight.entry(sender).or_default();
+        if *count >= self.max_inflight {
+            None
+        } else {
+            *count += 1;
+            Some(QuotaTicket(self.to_owned(), sender))
+        }
+    }
+}
+
+impl Drop for QuotaTicket {
+    fn drop(&mut self) {
+        let mut inflight = self.0.inflight.lock().expect("poisoned");
+        if let Some(count) = inflight.get_mut(&self.1) {
+            *count -= 1;
+            if *count == 0 {
+                inflight.remove(&self.1);
+            }
+        }
+    }
+}
@@ -2,53 +2,71 @@ use std::ops::{Deref, DerefMut};
 
 use agner_utils::std_error_pp::StdErrorPP;
 
+use super::actor_entry::PendingWaits;
 use super::*;
 
 impl System {
     pub(crate) async fn actor_entry_put(&self, entry: ActorEntry) {
-        let actor_id =
-            entry.running_actor_id().expect("Attempt to insert a non-running actor-entry");
-        assert_eq!(
-            actor_id.system(), self.0.system_id,
-            "attempt to insert an entry with a foreign actor-id [this-system-id: {}; entry-system-id: {}]",
-            self.0.system_id, actor_id.system());
+        self.actor_entries_put([entry]).await
+    }
+
+    pub(crate) async fn actor_entries_put(&self, entries: impl IntoIterator<Item = ActorEntry>) {
+        for entry in entries {
+            let actor_id =
+                entry.running_actor_id().expect("Attempt to insert a non-running actor-entry");
+            assert_eq!(
+                actor_id.system(), self.0.system_id,
+                "attempt to insert an entry with a foreign actor-id [this-system-id: {}; entry-system-id: {}]",
+                self.0.system_id, actor_id.system());
 
-        let slot_idx = actor_id.actor();
-        let mut slot = self.0.actor_entries[slot_idx].write().await;
-        let should_be_none = std::mem::replace(&mut *slot, entry);
-        assert!(should_be_none.running_actor_id().is_none());
+            let slot_idx = actor_id.actor();
+            let mut slot = self.0.actor_entries[slot_idx].write().await;
+            let should_be_none = std::mem::replace(&mut *slot, entry);
+            assert!(should_be_none.running_actor_id().is_none());
+        }
     }
 
-    pub(crate) fn actor_entry_slot(&self, actor_id: ActorID) -> &RwLock<ActorEntry> {
+    /// The slot the `actor_id` refers to.
+    ///
+    /// An [`ActorID`] may well be parsed (or deserialized), hence `None` if it belongs to another
+    /// system, or refers to a slot this system does not have.
+    pub(crate) fn actor_entry_slot(&self, actor_id: ActorID) -> Option<&RwLock<ActorEntry>> {
         if actor_id.system() != self.0.system_id {
-            panic!(
-                "attempt to terminate an entry with a foreign actor-id [this-system-id: {}; entry-system-id: {}]",
-                self.0.system_id,
-                actor_id.system()
-            )
+            return None
         }
-
-        let slot_idx = actor_id.actor();
-
-        &self.0.actor_entries[slot_idx]
+        self.0.actor_entries.get(actor_id.actor())
     }
 
     pub(crate) async fn actor_entry_read(
         &self,
         actor_id: ActorID,
     ) -> Option<impl Deref<Target = ActorEntry> + '_> {
-        let locked = self.actor_entry_slot(actor_id).read().await;
+        let locked = self.actor_entry_slot(actor_id)?.read().await;
         if locked.running_or_terminated_actor_id() == Some(actor_id) {
             Some(locked)
         } else {
             None
         }
     }
+    /// Same as [`actor_entry_read`](Self::actor_entry_read), but fails rather than waits if the
+    /// entry is locked for writing at the moment.
+    pub(crate) fn try_actor_entry_read(
+        &self,
+        actor_id: ActorID,
+    ) -> Result<Option<impl Deref<Target = ActorEntry> + '_>, tokio::sync::TryLockError> {
+        let Some(slot) = self.actor_entry_slot(actor_id) else { return Ok(None) };
+        let locked = slot.try_read()?;
+        if locked.running_or_terminated_actor_id() == Some(actor_id) {
+            Ok(Some(locked))
+        } else {
+            Ok(None)
+        }
+    }
     pub(crate) async fn actor_entry_write(
         &self,
         actor_id: ActorID,
     ) -> Option<impl DerefMut<Target = ActorEntry> + '_> {
-        let locked = self.actor_entry_slot(actor_id).write().await;
+        let locked = self.actor_entry_slot(actor_id)?.write().await;
         if locked.running_or_terminated_actor_id() == Some(actor_id) {
             Some(locked)
         } else {
@@ -56,14 +74,24 @@ impl System {
         }
     }
 
+    /// Release the names of the terminated actor, and mark its entry terminated (resolving the
+    /// pending waits).
+    ///
+    /// The names are released by the time the waits resolve, and no name can be bound to the
+    /// actor in between (see [`System::names_release`]).
     pub(crate) async fn actor_entry_terminate(&self, actor_id: ActorID, exit_reason: Exit) {
-        if let Err(reason) = self
+        self.names_release(actor_id).await;
+
+        match self
             .actor_entry_write(actor_id)
             .await
             .map(|mut ae| ae.terminate(actor_id, exit_reason))
             .transpose()
         {
-            tracing::error!("Failed to terminate ActorEntry: {}", reason.as_ref().pp());
+            Ok(pending_waits) => pending_waits.into_iter().for_each(PendingWaits::resolve),
+            Err(reason) =>
+                tracing::error!("Failed to terminate ActorEntry: {}", reason.as_ref().pp()),
         }
+        self.names_released(actor_id);
     }
 }
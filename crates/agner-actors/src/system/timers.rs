@@ -0,0 +1,89 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+use crate::spawn_opts::Priority;
+
+use super::*;
+
+const PENDING: u8 = 0;
+const DELIVERED: u8 = 1;
+const CANCELLED: u8 = 2;
+
+/// A handle to a message scheduled with [`System::send_after`].
+///
+/// Dropping the handle does not cancel the delivery.
+#[derive(Debug, Clone)]
+pub struct TimerHandle(Arc<TimerState>);
+
+#[derive(Debug, Default)]
+struct TimerState {
+    status: AtomicU8,
+    cancelled: Notify,
+}
+
+impl System {
+    /// Send the `message` to the specified actor after the `delay` (according to the
+    /// [clock](crate::clock::Clock) of the system).
+    ///
+    /// The timer does not keep the system alive: if the system is gone by the time the delay
+    /// elapses, the message is discarded.
+    #[tracing::instrument(skip_all, fields(
+        sys_id = self.0.system_id,
+        to = display(to),
+        msg_type = std::any::type_name::<M>()
+    ))]
+    pub fn send_after<M>(&self, to: ActorID, delay: Duration, message: M) -> TimerHandle
+    where
+        M: Send + 'static,
+    {
+        let state = Arc::new(TimerState::default());
+        let system = self.rc_downgrade();
+        let clock = &self.0.config.clock;
+        let elapsed = clock.sleep_until(clock.now_instant() + delay);
+        let timer = {
+            let state = Arc::clone(&state);
+            async move {
+                tokio::select! {
+                    () = elapsed => (),
+                    _ = state.cancelled.notified() => return,
+                }
+                if state
+                    .status
+                    .compare_exchange(PENDING, DELIVERED, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_err()
+                {
+                    return
+                }
+                if let Some(system) = system.rc_upgrade() {
+                    system.send(to, message).await;
+                }
+            }
+        };
+        self.0
+            .config
+            .spawner
+            .spawn(Priority::default(), Box::pin(timer.instrument(tracing::Span::current())));
+
+        TimerHandle(state)
+    }
+}
+
+impl TimerHandle {
+    /// Cancel the delivery.
+    ///
+    /// Returns `true` if the message has not been delivered (and now never will be), `false` if it
+    /// is too late to cancel.
+    pub fn cancel(&self) -> bool {
+        let cancelled = self
+            .0
+            .status
+            .compare_exchange(PENDING, CANCELLED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok();
+        if cancelled {
+            self.0.cancelled.notify_one();
+        }
+        cancelled
+    }
+}
@@ -1,7 +1,11 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::clock::{Clock, SystemClock};
 use crate::exit_handler::{ExitHandler, NoopExitHandler};
+use crate::interceptor::Interceptor;
+use crate::name_store::{InMemoryNameStore, NameStore};
+use crate::spawner::{Spawner, TokioSpawner};
 
 /// Configuration for [`System`](crate::system::System)
 #[derive(Debug, Clone)]
@@ -13,9 +17,31 @@ pub struct SystemConfig {
     /// max duration given for an actor to gracefully terminate
     pub actor_termination_timeout: Duration,
 
+    /// the fraction of `max_actors`, upon reaching which a warning is emitted, and the
+    /// [interceptors](crate::interceptor::Interceptor::on_near_capacity) are notified (none by
+    /// default)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub high_watermark: Option<f64>,
+
     /// exit handler
     #[cfg_attr(feature = "serde", serde(skip, default = "defaults::default_exit_handler"))]
     pub exit_handler: Arc<dyn ExitHandler>,
+
+    /// spawner running the actors
+    #[cfg_attr(feature = "serde", serde(skip, default = "defaults::default_spawner"))]
+    pub spawner: Arc<dyn Spawner>,
+
+    /// clock providing the current time to the actors
+    #[cfg_attr(feature = "serde", serde(skip, default = "defaults::default_clock"))]
+    pub clock: Arc<dyn Clock>,
+
+    /// store of the names bound to the actors
+    #[cfg_attr(feature = "serde", serde(skip, default = "defaults::default_name_store"))]
+    pub name_store: Arc<dyn NameStore>,
+
+    /// interceptors observing the messages sent within the system
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub interceptors: Vec<Arc<dyn Interceptor>>,
 }
 
 impl Default for SystemConfig {
@@ -23,11 +49,24 @@ impl Default for SystemConfig {
         Self {
             max_actors: defaults::DEFAULT_MAX_ACTORS,
             actor_termination_timeout: defaults::DEFAULT_ACTOR_TERMINATION_TIMEOUT,
+            high_watermark: None,
             exit_handler: defaults::default_exit_handler(),
+            spawner: defaults::default_spawner(),
+            clock: defaults::default_clock(),
+            name_store: defaults::default_name_store(),
+            interceptors: Default::default(),
         }
     }
 }
 
+impl SystemConfig {
+    /// Add an [interceptor](crate::interceptor::Interceptor)
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+}
+
 mod defaults {
     use super::*;
 
@@ -37,4 +76,16 @@ mod defaults {
     pub(super) fn default_exit_handler() -> Arc<dyn ExitHandler> {
         Arc::new(NoopExitHandler)
     }
+
+    pub(super) fn default_spawner() -> Arc<dyn Spawner> {
+        Arc::new(TokioSpawner)
+    }
+
+    pub(super) fn default_clock() -> Arc<dyn Clock> {
+        Arc::new(SystemClock)
+    }
+
+    pub(super) fn default_name_store() -> Arc<dyn NameStore> {
+        Arc::new(InMemoryNameStore::new())
+    }
 }
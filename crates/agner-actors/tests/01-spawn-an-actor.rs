@@ -1,6 +1,9 @@
 use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 
-use agner_actors::{Context, Exit, System};
+use agner_actors::{Context, Exit, Priority, SpawnOpts, Spawner, System, SystemConfig};
 use tokio::sync::oneshot;
 
 mod common;
@@ -60,3 +63,202 @@ fn actor_returning_never() {
         rx.await.expect("oneshot recv closed");
     });
 }
+
+#[test]
+fn unexpected_return_substitutes_the_normal_exit() {
+    async fn returns(_context: &mut Context<Infallible>, exit: Exit) -> Exit {
+        exit
+    }
+    async fn exits(context: &mut Context<Infallible>, exit: Exit) -> Infallible {
+        context.exit(exit).await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let spawn_opts = || SpawnOpts::new().with_unexpected_return(Exit::from_message("returned"));
+        assert!(spawn_opts().unexpected_return().is_some());
+
+        let (_, join) =
+            system.spawn_with_join(returns, Exit::normal(), spawn_opts()).await.unwrap();
+        assert!(join.await.eq_kind(&Exit::from_message("returned")));
+
+        // the abnormal exits are kept as is
+        let (_, join) =
+            system.spawn_with_join(returns, Exit::shutdown(), spawn_opts()).await.unwrap();
+        assert!(join.await.is_shutdown());
+
+        // as well as an explicit normal exit
+        let (_, join) = system.spawn_with_join(exits, Exit::normal(), spawn_opts()).await.unwrap();
+        assert!(join.await.is_normal());
+    });
+}
+
+#[test]
+fn busy_actor_yielding_observes_exit_signal() {
+    async fn actor_behaviour(context: &mut Context<Infallible>, arg: oneshot::Sender<()>) {
+        arg.send(()).expect("oneshot send error");
+        loop {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            context.yield_now().await;
+        }
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .expect("Failed to create tokio-runtime");
+    runtime.block_on(async {
+        let system = System::new(Default::default());
+        let (tx, rx) = oneshot::channel();
+        let actor = system
+            .spawn(actor_behaviour, tx, Default::default())
+            .await
+            .expect("Failed to start an actor");
+        rx.await.expect("oneshot recv closed");
+
+        system.exit(actor, Exit::shutdown()).await;
+        assert!(system.wait(actor).await.is_shutdown());
+    });
+}
+
+#[test]
+fn spawner_receives_the_priority() {
+    #[derive(Debug, Default)]
+    struct RecordingSpawner(Mutex<Vec<Priority>>);
+
+    impl Spawner for RecordingSpawner {
+        fn spawn(
+            &self,
+            priority: Priority,
+            actor: Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
+        ) {
+            self.0.lock().unwrap().push(priority);
+            tokio::spawn(actor);
+        }
+    }
+
+    async fn actor_behaviour(_context: &mut Context<Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let spawner = Arc::new(RecordingSpawner::default());
+        let system =
+            System::new(SystemConfig { spawner: spawner.to_owned(), ..Default::default() });
+
+        let normal = system.spawn(actor_behaviour, (), Default::default()).await.unwrap();
+        let high = system
+            .spawn(actor_behaviour, (), SpawnOpts::new().with_priority(Priority::High))
+            .await
+            .unwrap();
+
+        assert_eq!(*spawner.0.lock().unwrap(), [Priority::Normal, Priority::High]);
+        assert_eq!(system.actor_info(normal).await.unwrap().priority, Priority::Normal);
+        assert_eq!(system.actor_info(high).await.unwrap().priority, Priority::High);
+    });
+}
+
+#[test]
+fn actor_info_batch() {
+    async fn actor_behaviour(_context: &mut Context<Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let mut actors = vec![];
+        for _ in 0..3 {
+            actors.push(system.spawn(actor_behaviour, (), Default::default()).await.unwrap());
+        }
+        let gone = system.spawn(actor_behaviour, (), Default::default()).await.unwrap();
+        system.exit(gone, Exit::shutdown()).await;
+        system.wait(gone).await;
+        actors.insert(1, gone);
+        let alive = actors[0];
+
+        let infos = system.actor_info_batch(&actors).await;
+        assert_eq!(infos.len(), actors.len());
+        for ((actor_id, info), expected) in infos.into_iter().zip(actors) {
+            assert_eq!(actor_id, expected);
+            if actor_id == gone {
+                assert!(info.is_none());
+            } else {
+                assert_eq!(info.expect("no info").actor_id, actor_id);
+            }
+        }
+
+        let concurrent = futures::future::join_all((0..16).map(|_| system.actor_info(alive))).await;
+        assert!(concurrent.iter().all(|info| info.as_ref().map(|i| i.actor_id) == Some(alive)));
+    });
+}
+
+#[test]
+fn dropping_the_system_shuts_the_actors_down() {
+    async fn actor_behaviour(
+        context: &mut Context<Infallible>,
+        (trap_exit, ready, exited): (bool, oneshot::Sender<()>, oneshot::Sender<Exit>),
+    ) {
+        context.trap_exit(trap_exit).await;
+        context
+            .defer(move |exit| {
+                let _ = exited.send(exit.to_owned());
+            })
+            .await;
+        let _ = ready.send(());
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let mut exits = vec![];
+        // the actors trapping exits are shut down too
+        for trap_exit in [false, true, false, true] {
+            let (ready_tx, ready_rx) = oneshot::channel();
+            let (exited_tx, exited_rx) = oneshot::channel();
+            system
+                .spawn(actor_behaviour, (trap_exit, ready_tx, exited_tx), Default::default())
+                .await
+                .expect("Failed to start an actor");
+            ready_rx.await.expect("oneshot recv closed");
+            exits.push(exited_rx);
+        }
+        std::mem::drop(system);
+
+        for exited in exits {
+            let exit = tokio::time::timeout(std::time::Duration::from_secs(1), exited)
+                .await
+                .expect("the actor has not terminated")
+                .expect("oneshot recv closed");
+            assert!(exit.is_shutdown(), "{:?}", exit);
+        }
+    });
+}
+
+#[test]
+fn queued_info_requests_are_coalesced_in_order() {
+    async fn actor_behaviour(_context: &mut Context<Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    // on a single thread, the requests below are all queued before the actor gets to run
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    runtime.block_on(async {
+        let system = System::new(Default::default());
+        let actor = system.spawn(actor_behaviour, (), Default::default()).await.unwrap();
+        let peer = system.spawn(actor_behaviour, (), Default::default()).await.unwrap();
+
+        let (first, second, (), third) = futures::join!(
+            system.actor_info(actor),
+            system.actor_info(actor),
+            system.link(actor, peer),
+            system.actor_info(actor),
+        );
+
+        // the first two requests are answered with the same snapshot, taken before the link
+        assert!(first.unwrap().links.is_empty());
+        assert!(second.unwrap().links.is_empty());
+        // the request queued after the link is not answered ahead of it
+        assert_eq!(third.unwrap().links.as_ref(), [peer]);
+    });
+}
@@ -1,7 +1,8 @@
 use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use agner_actors::{Context, System, SystemConfig};
+use agner_actors::{ActorID, Context, Exit, Interceptor, System, SystemConfig};
 
 mod common;
 
@@ -34,3 +35,85 @@ async fn hit_system_limit(max_actors: usize) {
 
     tokio::time::sleep(Duration::from_secs(1)).await;
 }
+
+#[test]
+fn near_capacity_is_reported() {
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<(usize, usize)>>);
+    impl Interceptor for Recorder {
+        fn on_send(&self, _from: Option<ActorID>, _to: ActorID, _message_type: &'static str) {}
+        fn on_near_capacity(&self, used: usize, total: usize) {
+            self.0.lock().unwrap().push((used, total));
+        }
+    }
+
+    async fn idle(_context: &mut Context<Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let recorder = Arc::new(Recorder::default());
+        let system = System::new(
+            SystemConfig { max_actors: 4, high_watermark: Some(0.75), ..Default::default() }
+                .with_interceptor(recorder.to_owned()),
+        );
+        assert_eq!(system.capacity(), (0, 4));
+
+        let first = system.spawn(idle, (), Default::default()).await.unwrap();
+        let _second = system.spawn(idle, (), Default::default()).await.unwrap();
+        assert!(recorder.0.lock().unwrap().is_empty());
+
+        let _third = system.spawn(idle, (), Default::default()).await.unwrap();
+        assert_eq!(system.capacity(), (3, 4));
+        assert_eq!(*recorder.0.lock().unwrap(), [(3, 4)]);
+
+        let fourth = system.spawn(idle, (), Default::default()).await.unwrap();
+        assert_eq!(recorder.0.lock().unwrap().len(), 1);
+
+        system.exit(first, Exit::shutdown()).await;
+        system.wait(first).await;
+        while system.capacity().0 != 3 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        system.exit(fourth, Exit::shutdown()).await;
+        system.wait(fourth).await;
+        while system.capacity().0 != 2 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        // reaching the watermark again is reported again
+        let _ = system.spawn(idle, (), Default::default()).await.unwrap();
+        assert_eq!(*recorder.0.lock().unwrap(), [(3, 4), (3, 4)]);
+    })
+}
+
+#[test]
+fn spawn_many_is_all_or_nothing() {
+    type Started = Arc<Mutex<Vec<(usize, ActorID)>>>;
+
+    async fn report(context: &mut Context<Infallible>, (idx, started): (usize, Started)) {
+        started.lock().unwrap().push((idx, context.actor_id()));
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(SystemConfig { max_actors: 5, ..Default::default() });
+        let started: Started = Default::default();
+        let args = |n: usize| (0..n).map(|idx| (idx, started.to_owned())).collect::<Vec<_>>();
+
+        let actors = system.spawn_many(report, args(3), Default::default).await.unwrap();
+        assert_eq!(system.capacity(), (3, 5));
+        while started.lock().unwrap().len() < 3 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        for (idx, actor_id) in started.lock().unwrap().iter() {
+            assert_eq!(actors[*idx], *actor_id);
+        }
+
+        // the ids acquired before hitting the limit are released
+        assert!(system.spawn_many(report, args(3), Default::default).await.is_err());
+        assert_eq!(system.capacity(), (3, 5));
+        assert_eq!(system.spawn_many(report, args(2), Default::default).await.unwrap().len(), 2);
+        assert_eq!(system.capacity(), (5, 5));
+    })
+}
@@ -1,17 +1,21 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use agner_actors::{ActorID, Context, Event, System, SystemConfig};
-use tokio::sync::{oneshot, Mutex};
+use agner_actors::system_error::SysChannelError;
+use agner_actors::{ActorID, Context, Event, Exit, Interceptor, SendOutcome, System, SystemConfig};
+use futures::StreamExt;
+use tokio::sync::{mpsc, oneshot, Mutex};
 
 mod common;
 
+type Output = Arc<Mutex<Vec<(&'static str, &'static str)>>>;
+
 #[test]
 fn send_single_compatible_message() {
-    let output = Arc::new(Mutex::new(Vec::<(&'static str, &'static str)>::new()));
+    let output: Output = Default::default();
     async fn actor_behaviour(
         context: &mut Context<&'static str>,
-        (actor_name, output): (&'static str, Arc<Mutex<Vec<(&'static str, &'static str)>>>),
+        (actor_name, output): (&'static str, Output),
     ) {
         loop {
             if let Event::Message(message) = context.next_event().await {
@@ -104,6 +108,18 @@ fn echo_actor_via_chan() {
             echo_actor_tx.send((i, tx)).expect("mpsc tx failure");
             assert_eq!(i, rx.await.expect("oneshot rx error"));
         }
+
+        let other_tx = system
+            .channel::<(usize, oneshot::Sender<usize>)>(echo_actor)
+            .await
+            .expect("Failed to obtain actor's tx-chan");
+        assert!(echo_actor_tx.same_channel(&other_tx));
+        let weak_tx = echo_actor_tx.downgrade();
+        assert!(weak_tx.upgrade().expect("upgrade failure").same_channel(&echo_actor_tx));
+
+        system.exit(echo_actor, Exit::shutdown()).await;
+        echo_actor_tx.closed().await;
+        assert!(other_tx.is_closed());
     })
 }
 
@@ -158,3 +174,485 @@ async fn actor_ring(ring_size: usize) {
         }
     }
 }
+
+#[test]
+fn interceptors_observe_sends_in_order() {
+    type Observed = (&'static str, Option<ActorID>, ActorID, &'static str);
+
+    #[derive(Debug)]
+    struct Recorder(&'static str, Arc<std::sync::Mutex<Vec<Observed>>>);
+
+    impl Interceptor for Recorder {
+        fn on_send(&self, from: Option<ActorID>, to: ActorID, message_type: &'static str) {
+            self.1.lock().unwrap().push((self.0, from, to, message_type));
+        }
+    }
+
+    async fn actor_behaviour(context: &mut Context<usize>, _arg: ()) {
+        loop {
+            context.next_message().await;
+        }
+    }
+
+    async fn sender(context: &mut Context<usize>, (to, done): (ActorID, oneshot::Sender<()>)) {
+        context.send(to, 2usize).await.unwrap();
+        let _ = done.send(());
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let observed = Arc::new(std::sync::Mutex::new(vec![]));
+        let config = SystemConfig::default()
+            .with_interceptor(Arc::new(Recorder("first", observed.to_owned())))
+            .with_interceptor(Arc::new(Recorder("second", observed.to_owned())));
+        let system = System::new(config);
+
+        let actor = system.spawn(actor_behaviour, (), Default::default()).await.unwrap();
+        system.send(actor, 1usize).await;
+
+        let (done_tx, done_rx) = oneshot::channel();
+        let sender = system.spawn(sender, (actor, done_tx), Default::default()).await.unwrap();
+        done_rx.await.unwrap();
+
+        system.channel::<usize>(actor).await.unwrap().send(3).unwrap();
+
+        assert_eq!(
+            &observed.lock().unwrap()[..],
+            &[
+                ("first", None, actor, "usize"),
+                ("second", None, actor, "usize"),
+                ("first", Some(sender), actor, "usize"),
+                ("second", Some(sender), actor, "usize"),
+                ("first", None, actor, "usize"),
+                ("second", None, actor, "usize"),
+            ]
+        );
+    })
+}
+
+#[test]
+fn messages_as_a_stream() {
+    async fn actor_behaviour(context: &mut Context<usize>, report_to: oneshot::Sender<Vec<usize>>) {
+        let batch = context.message_stream().take(3).collect::<Vec<_>>().await;
+        let _ = report_to.send(batch);
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (tx, rx) = oneshot::channel();
+        let actor = system.spawn(actor_behaviour, tx, Default::default()).await.unwrap();
+
+        for i in 1..=3usize {
+            system.send(actor, i).await;
+        }
+
+        assert_eq!(rx.await.unwrap(), [1, 2, 3]);
+        assert!(system.wait(actor).await.is_normal());
+    })
+}
+
+#[test]
+fn messages_received_in_batches() {
+    async fn actor_behaviour(
+        context: &mut Context<usize>,
+        (go, report_to): (oneshot::Receiver<()>, mpsc::UnboundedSender<Vec<usize>>),
+    ) {
+        let _ = go.await;
+        loop {
+            let _ = report_to.send(context.recv_batch(3).await);
+        }
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (go_tx, go_rx) = oneshot::channel();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let actor = system.spawn(actor_behaviour, (go_rx, tx), Default::default()).await.unwrap();
+
+        for i in 1..=5usize {
+            system.send(actor, i).await;
+        }
+        assert!(system.sync(actor).await);
+        let _ = go_tx.send(());
+
+        assert_eq!(rx.recv().await.unwrap(), [1, 2, 3]);
+        assert_eq!(rx.recv().await.unwrap(), [4, 5]);
+
+        system.send(actor, 6usize).await;
+        assert_eq!(rx.recv().await.unwrap(), [6]);
+    })
+}
+
+#[test]
+fn try_send_reports_why_it_failed() {
+    async fn actor_behaviour(context: &mut Context<u32>, _: ()) {
+        context.next_message().await;
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let actor = system.spawn(actor_behaviour, (), Default::default()).await.unwrap();
+        let stale: ActorID =
+            format!("{}.{}.{}", actor.system_id(), actor.slot(), 1000).parse().unwrap();
+
+        assert!(matches!(system.try_send(stale, 1u32).await, Err(SysChannelError::NoActor)));
+        assert!(matches!(
+            system.try_send(actor, "wrong type").await,
+            Err(SysChannelError::InvalidMessageType)
+        ));
+        assert!(system.try_send(actor, 1u32).await.is_ok());
+        system.wait(actor).await;
+        assert!(matches!(system.try_send(actor, 2u32).await, Err(SysChannelError::Terminated)));
+    })
+}
+
+#[test]
+fn sending_to_an_unknown_actor_id() {
+    async fn idle(_context: &mut Context<u32>, _: ()) {
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let actor = system.spawn(idle, (), Default::default()).await.unwrap();
+
+        let foreign: ActorID = format!("{}.0.0", actor.system_id() + 42).parse().unwrap();
+        let out_of_range: ActorID =
+            format!("{}.{}.0", actor.system_id(), usize::MAX).parse().unwrap();
+
+        for unknown in [foreign, out_of_range] {
+            assert!(matches!(system.try_send(unknown, 1u32).await, Err(SysChannelError::NoActor)));
+            system.send(unknown, 1u32).await;
+            system.exit(unknown, Exit::shutdown()).await;
+            assert!(system.actor_info(unknown).await.is_none());
+            assert!(system.wait(unknown).await.is_no_actor());
+        }
+        assert!(system.actor_info(actor).await.is_some());
+    })
+}
+
+#[test]
+fn try_send_sync_from_outside_the_runtime() {
+    async fn actor_behaviour(context: &mut Context<u32>, report_to: oneshot::Sender<u32>) {
+        let _ = report_to.send(context.next_message().await);
+    }
+
+    // the test's thread sends, while the actor runs within a runtime of its own
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_time().build().unwrap();
+    let (system, actor, rx) = runtime.block_on(async {
+        let system = System::new(Default::default());
+        let (tx, rx) = oneshot::channel();
+        let actor = system.spawn(actor_behaviour, tx, Default::default()).await.unwrap();
+        assert!(system.sync(actor).await);
+        (system, actor, rx)
+    });
+
+    assert!(matches!(
+        system.try_send_sync(actor, "wrong type"),
+        SendOutcome::Failed(SysChannelError::InvalidMessageType)
+    ));
+    assert!(system.try_send_sync(actor, 42u32).is_sent());
+    assert_eq!(runtime.block_on(rx).unwrap(), 42);
+
+    runtime.block_on(system.wait(actor));
+    assert!(matches!(
+        system.try_send_sync(actor, 1u32),
+        SendOutcome::Failed(SysChannelError::Terminated)
+    ));
+}
+
+#[test]
+fn check_message_type_before_sending() {
+    async fn actor_behaviour(context: &mut Context<u32>, _: ()) {
+        context.next_message().await;
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let actor = system.spawn(actor_behaviour, (), Default::default()).await.unwrap();
+
+        assert_eq!(system.message_type_name(actor).await, Some("u32"));
+        assert!(system.check_message_type::<u32>(actor).await.is_ok());
+        assert!(matches!(
+            system.check_message_type::<&'static str>(actor).await,
+            Err(SysChannelError::InvalidMessageType)
+        ));
+
+        system.send(actor, 1u32).await;
+        system.wait(actor).await;
+        assert!(matches!(
+            system.check_message_type::<u32>(actor).await,
+            Err(SysChannelError::Terminated)
+        ));
+        assert_eq!(system.message_type_name(actor).await, None);
+    })
+}
+
+#[test]
+fn send_after_delivers_unless_cancelled() {
+    async fn actor_behaviour(
+        context: &mut Context<&'static str>,
+        report_to: tokio::sync::mpsc::UnboundedSender<&'static str>,
+    ) {
+        loop {
+            let _ = report_to.send(context.next_message().await);
+        }
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let actor = system.spawn(actor_behaviour, tx, Default::default()).await.unwrap();
+
+        let t0 = Instant::now();
+        let delivered = system.send_after(actor, Duration::from_millis(100), "delivered");
+        let cancelled = system.send_after(actor, Duration::from_millis(50), "cancelled");
+        assert!(cancelled.cancel());
+
+        assert_eq!(rx.recv().await, Some("delivered"));
+        assert!(t0.elapsed() >= Duration::from_millis(100));
+        assert!(!delivered.cancel());
+        assert!(!cancelled.cancel());
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(rx.try_recv().is_err());
+    })
+}
+
+#[test]
+fn context_reports_inbox_len_and_capacity() {
+    async fn actor_behaviour(
+        context: &mut Context<u32>,
+        (go, report_to): (oneshot::Receiver<()>, oneshot::Sender<(u32, usize, usize)>),
+    ) {
+        let _ = go.await;
+        let message = context.next_message().await;
+        let _ = report_to.send((message, context.inbox_len(), context.inbox_capacity()));
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (go_tx, go_rx) = oneshot::channel();
+        let (report_tx, report_rx) = oneshot::channel();
+        let actor = system
+            .spawn(
+                actor_behaviour,
+                (go_rx, report_tx),
+                agner_actors::SpawnOpts::new().with_msg_inbox_size(8),
+            )
+            .await
+            .unwrap();
+
+        for i in 0..5u32 {
+            system.send(actor, i).await;
+        }
+        while system.actor_info(actor).await.unwrap().m_queue_len.0 < 5 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        let _ = go_tx.send(());
+
+        assert_eq!(report_rx.await.unwrap(), (0, 4, 8));
+    })
+}
+
+#[test]
+fn context_send_many() {
+    async fn listener(
+        context: &mut Context<&'static str>,
+        report_to: oneshot::Sender<&'static str>,
+    ) {
+        let _ = report_to.send(context.next_message().await);
+        std::future::pending().await
+    }
+
+    async fn deaf(_context: &mut Context<std::convert::Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    async fn sender(
+        context: &mut Context<std::convert::Infallible>,
+        (targets, report_to): (Vec<ActorID>, oneshot::Sender<usize>),
+    ) {
+        let _ = report_to.send(context.send_many(&targets, "hello").await);
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let (one_tx, one_rx) = oneshot::channel();
+        let (two_tx, two_rx) = oneshot::channel();
+        let one = system.spawn(listener, one_tx, Default::default()).await.unwrap();
+        let two = system.spawn(listener, two_tx, Default::default()).await.unwrap();
+        let three = system.spawn(deaf, (), Default::default()).await.unwrap();
+        let gone = system.spawn(deaf, (), Default::default()).await.unwrap();
+        system.exit(gone, agner_actors::Exit::shutdown()).await;
+        system.wait(gone).await;
+
+        let (delivered_tx, delivered_rx) = oneshot::channel();
+        system
+            .spawn(sender, (vec![one, two, three, gone], delivered_tx), Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(delivered_rx.await.unwrap(), 2);
+        assert_eq!(one_rx.await.unwrap(), "hello");
+        assert_eq!(two_rx.await.unwrap(), "hello");
+    })
+}
+
+#[test]
+fn sender_quota_limits_inflight_messages_per_sender() {
+    type Outcomes = Vec<Result<(), SysChannelError>>;
+
+    async fn shared(
+        context: &mut Context<u32>,
+        (go, report_to): (oneshot::Receiver<()>, tokio::sync::mpsc::UnboundedSender<u32>),
+    ) {
+        let _ = go.await;
+        loop {
+            let _ = report_to.send(context.next_message().await);
+        }
+    }
+
+    async fn sender(
+        context: &mut Context<oneshot::Sender<Outcomes>>,
+        (target, count): (ActorID, u32),
+    ) {
+        loop {
+            let reply_to = context.next_message().await;
+            let mut outcomes = vec![];
+            for i in 0..count {
+                outcomes.push(context.send(target, i).await);
+            }
+            let _ = reply_to.send(outcomes);
+        }
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let (go_tx, go_rx) = oneshot::channel();
+        let (received_tx, mut received_rx) = tokio::sync::mpsc::unbounded_channel();
+        let target = system
+            .spawn(
+                shared,
+                (go_rx, received_tx),
+                agner_actors::SpawnOpts::new().with_sender_quota(2),
+            )
+            .await
+            .unwrap();
+        let flooder = system.spawn(sender, (target, 5), Default::default()).await.unwrap();
+        let polite = system.spawn(sender, (target, 1), Default::default()).await.unwrap();
+
+        let send_from = |actor: ActorID| {
+            let system = system.to_owned();
+            async move {
+                let (tx, rx) = oneshot::channel::<Outcomes>();
+                system.send(actor, tx).await;
+                rx.await.unwrap()
+            }
+        };
+
+        let outcomes = send_from(flooder).await;
+        assert_eq!(outcomes.iter().filter(|o| o.is_ok()).count(), 2);
+        assert!(outcomes[2..].iter().all(|o| matches!(o, Err(SysChannelError::QuotaExceeded))));
+        assert!(send_from(polite).await.iter().all(Result::is_ok));
+
+        // the messages sent via the system are not attributed to any sender
+        system.try_send(target, 42u32).await.unwrap();
+        // nor are those sent on behalf of an actor charged to its quota
+        system.send_from(flooder, target, 43u32).await;
+
+        let _ = go_tx.send(());
+        for _ in 0..5 {
+            received_rx.recv().await.unwrap();
+        }
+
+        // the quota is released as the messages are received
+        let outcomes = send_from(flooder).await;
+        assert!(outcomes[..2].iter().all(Result::is_ok));
+    })
+}
+
+#[test]
+fn recipient_learns_the_sender() {
+    #[derive(Debug)]
+    enum Request {
+        Ping,
+        Forward(ActorID),
+    }
+
+    async fn recipient(
+        context: &mut Context<&'static str>,
+        report_to: tokio::sync::mpsc::UnboundedSender<(&'static str, Option<ActorID>)>,
+    ) {
+        loop {
+            let message = context.next_message().await;
+            let _ = report_to.send((message, context.sender()));
+        }
+    }
+    async fn forwarder(context: &mut Context<Request>, to: ActorID) {
+        loop {
+            match context.next_message().await {
+                Request::Ping => context.send(to, "ping").await.unwrap(),
+                Request::Forward(on_behalf_of) =>
+                    context.system().send_from(on_behalf_of, to, "forwarded").await,
+            }
+        }
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let (report_tx, mut report_rx) = tokio::sync::mpsc::unbounded_channel();
+        let to = system.spawn(recipient, report_tx, Default::default()).await.unwrap();
+        let via = system.spawn(forwarder, to, Default::default()).await.unwrap();
+
+        system.send(to, "anonymous").await;
+        assert_eq!(report_rx.recv().await.unwrap(), ("anonymous", None));
+
+        system.send(via, Request::Ping).await;
+        assert_eq!(report_rx.recv().await.unwrap(), ("ping", Some(via)));
+
+        let origin: ActorID = format!("{}.{}.{}", to.system_id(), 0, 1000).parse().unwrap();
+        system.send(via, Request::Forward(origin)).await;
+        assert_eq!(report_rx.recv().await.unwrap(), ("forwarded", Some(origin)));
+    })
+}
+
+#[test]
+fn sender_via_next_event() {
+    async fn recipient(
+        context: &mut Context<&'static str>,
+        report_to: tokio::sync::mpsc::UnboundedSender<(&'static str, Option<ActorID>)>,
+    ) {
+        loop {
+            if let Event::Message(message) = context.next_event().await {
+                let _ = report_to.send((message, context.sender()));
+            }
+        }
+    }
+    async fn sender(context: &mut Context<ActorID>, _arg: ()) {
+        loop {
+            let to = context.next_message().await;
+            context.send(to, "tagged").await.unwrap();
+        }
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let (report_tx, mut report_rx) = tokio::sync::mpsc::unbounded_channel();
+        let to = system.spawn(recipient, report_tx, Default::default()).await.unwrap();
+        let from = system.spawn(sender, (), Default::default()).await.unwrap();
+
+        system.send(from, to).await;
+        assert_eq!(report_rx.recv().await.unwrap(), ("tagged", Some(from)));
+
+        // a system-originated message resets the sender
+        system.send(to, "anonymous").await;
+        assert_eq!(report_rx.recv().await.unwrap(), ("anonymous", None));
+    })
+}
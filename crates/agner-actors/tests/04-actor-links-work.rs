@@ -1,6 +1,9 @@
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use agner_actors::{ActorID, Context, Event, Exit, Signal, SpawnOpts, System};
+use agner_actors::{
+    ActorID, Context, Event, Exit, Interceptor, Signal, SpawnOpts, System, SystemConfig,
+};
 use futures::{stream, StreamExt, TryStreamExt};
 use tokio::sync::oneshot;
 
@@ -134,3 +137,323 @@ fn links_test() {
         tokio::time::sleep(Duration::from_millis(100)).await;
     })
 }
+
+#[test]
+fn context_links_test() {
+    async fn idle(_context: &mut Context<std::convert::Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    async fn actor_behaviour(
+        context: &mut Context<oneshot::Sender<Vec<ActorID>>>,
+        link_to: Vec<ActorID>,
+    ) {
+        for actor in link_to {
+            context.link(actor).await;
+        }
+        loop {
+            let reply_to = context.next_message().await;
+            let _ = reply_to.send(context.links().await);
+        }
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let one = system.spawn(idle, (), Default::default()).await.unwrap();
+        let two = system.spawn(idle, (), Default::default()).await.unwrap();
+        let linked_three = system.spawn(idle, (), Default::default()).await.unwrap();
+
+        let actor = system
+            .spawn(actor_behaviour, vec![one, two], SpawnOpts::new().with_link(linked_three))
+            .await
+            .unwrap();
+
+        let (tx, rx) = oneshot::channel::<Vec<ActorID>>();
+        system.send(actor, tx).await;
+        let mut links = rx.await.unwrap();
+        links.sort();
+
+        let mut expected = vec![one, two, linked_three];
+        expected.sort();
+
+        assert_eq!(links, expected);
+    })
+}
+
+#[test]
+fn broadcast_to_links_test() {
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<(Option<ActorID>, ActorID)>>);
+    impl Interceptor for Recorder {
+        fn on_send(&self, from: Option<ActorID>, to: ActorID, _message_type: &'static str) {
+            self.0.lock().unwrap().push((from, to));
+        }
+    }
+
+    async fn listener(
+        context: &mut Context<&'static str>,
+        report_to: oneshot::Sender<&'static str>,
+    ) {
+        let _ = report_to.send(context.next_message().await);
+        std::future::pending().await
+    }
+
+    async fn deaf(_context: &mut Context<std::convert::Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    async fn broadcaster(
+        context: &mut Context<std::convert::Infallible>,
+        (link_to, report_to): (Vec<ActorID>, oneshot::Sender<usize>),
+    ) {
+        for actor in link_to {
+            context.link(actor).await;
+        }
+        let _ = report_to.send(context.broadcast_to_links("hello").await);
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let recorder = Arc::new(Recorder::default());
+        let system = System::new(SystemConfig::default().with_interceptor(recorder.to_owned()));
+
+        let (one_tx, one_rx) = oneshot::channel();
+        let (two_tx, two_rx) = oneshot::channel();
+        let one = system.spawn(listener, one_tx, Default::default()).await.unwrap();
+        let two = system.spawn(listener, two_tx, Default::default()).await.unwrap();
+        let three = system.spawn(deaf, (), Default::default()).await.unwrap();
+
+        let (delivered_tx, delivered_rx) = oneshot::channel();
+        let broadcaster = system
+            .spawn(broadcaster, (vec![one, two, three], delivered_tx), Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(delivered_rx.await.unwrap(), 2);
+        assert_eq!(one_rx.await.unwrap(), "hello");
+        assert_eq!(two_rx.await.unwrap(), "hello");
+
+        // the sends are seen by the interceptors, on behalf of the broadcaster
+        let mut intercepted = recorder.0.lock().unwrap().to_owned();
+        intercepted.sort();
+        let mut expected =
+            vec![(Some(broadcaster), one), (Some(broadcaster), two), (Some(broadcaster), three)];
+        expected.sort();
+        assert_eq!(intercepted, expected);
+    })
+}
+
+#[test]
+fn link_all_and_unlink_all() {
+    type Report = (Vec<ActorID>, Signal, Vec<ActorID>);
+
+    async fn idle(_context: &mut Context<std::convert::Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    async fn actor_behaviour(
+        context: &mut Context<std::convert::Infallible>,
+        (link_to, report_to): (Vec<ActorID>, oneshot::Sender<Report>),
+    ) {
+        context.trap_exit(true).await;
+        context.link_all(link_to.to_owned()).await;
+        let signal = context.next_signal().await;
+        let mut linked = context.links().await;
+        linked.sort();
+
+        context.unlink_all(link_to).await;
+        let unlinked = context.links().await;
+
+        let _ = report_to.send((linked, signal, unlinked));
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let one = system.spawn(idle, (), Default::default()).await.unwrap();
+        let two = system.spawn(idle, (), Default::default()).await.unwrap();
+        let gone = system.spawn(idle, (), Default::default()).await.unwrap();
+        system.exit(gone, Exit::shutdown()).await;
+        system.wait(gone).await;
+
+        let (tx, rx) = oneshot::channel();
+        let actor = system
+            .spawn(actor_behaviour, (vec![one, gone, two], tx), Default::default())
+            .await
+            .unwrap();
+        let (linked, signal, unlinked) = rx.await.unwrap();
+
+        let mut expected = vec![one, two];
+        expected.sort();
+        assert_eq!(linked, expected);
+        assert!(matches!(signal, Signal::Exit(from, exit) if from == gone && exit.is_no_actor()));
+        assert!(unlinked.is_empty());
+
+        assert!(system.actor_info(one).await.unwrap().links.is_empty());
+        assert!(system.actor_info(two).await.unwrap().links.is_empty());
+        assert!(system.actor_info(actor).await.is_some());
+    })
+}
+
+#[test]
+fn unlink_suppresses_link_failure() {
+    async fn idle(_context: &mut Context<std::convert::Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    async fn actor_behaviour(
+        context: &mut Context<std::convert::Infallible>,
+        (link_to, unlink, report_to): (ActorID, bool, oneshot::Sender<Vec<ActorID>>),
+    ) {
+        context.link(link_to).await;
+        if unlink {
+            context.unlink(link_to).await;
+        }
+        let _ = report_to.send(context.links().await);
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let gone = system.spawn(idle, (), Default::default()).await.unwrap();
+        system.exit(gone, Exit::shutdown()).await;
+        system.wait(gone).await;
+
+        let (tx, rx) = oneshot::channel();
+        let unlinked = system
+            .spawn(actor_behaviour, (gone, true, tx), Default::default())
+            .await
+            .unwrap();
+        assert!(rx.await.unwrap().is_empty());
+
+        let (tx, _rx) = oneshot::channel();
+        let linked = system
+            .spawn(actor_behaviour, (gone, false, tx), Default::default())
+            .await
+            .unwrap();
+        let exit = system.wait(linked).await;
+        assert!(exit.eq_kind(&Exit::linked(gone, Exit::no_actor())), "{}", exit);
+
+        // the backend has gone through its loop since: the failure would have been delivered
+        assert!(system.sync(unlinked).await);
+        assert!(system.actor_info(unlinked).await.is_some());
+    })
+}
+
+#[test]
+fn with_backend_relinks_atomically() {
+    type Report = (Vec<ActorID>, bool, Vec<ActorID>);
+
+    async fn idle(_context: &mut Context<std::convert::Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    async fn actor_behaviour(
+        context: &mut Context<std::convert::Infallible>,
+        (link_to, keep, report_to): (Vec<ActorID>, ActorID, oneshot::Sender<Report>),
+    ) {
+        context.link_all(link_to).await;
+
+        // keep the single link, and start trapping exits: all in one step
+        let (before, trap_exit) = context
+            .with_backend(move |backend| {
+                let before = backend.links().collect::<Vec<_>>();
+                for linked in before.iter().copied().filter(|linked| *linked != keep) {
+                    backend.unlink(linked);
+                }
+                backend.set_trap_exit(true);
+                (before, backend.trap_exit())
+            })
+            .await;
+        let after = context.links().await;
+
+        let _ = report_to.send((before, trap_exit, after));
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let one = system.spawn(idle, (), Default::default()).await.unwrap();
+        let two = system.spawn(idle, (), Default::default()).await.unwrap();
+        let three = system.spawn(idle, (), Default::default()).await.unwrap();
+
+        let (tx, rx) = oneshot::channel();
+        let actor = system
+            .spawn(actor_behaviour, (vec![one, two, three], two, tx), Default::default())
+            .await
+            .unwrap();
+        let (before, trap_exit, after) = rx.await.unwrap();
+
+        let mut expected = vec![one, two, three];
+        expected.sort();
+        assert_eq!(before, expected);
+        assert!(trap_exit);
+        assert_eq!(after, [two]);
+
+        assert!(system.actor_info(one).await.unwrap().links.is_empty());
+        assert_eq!(system.actor_info(two).await.unwrap().links.as_ref(), [actor]);
+        assert!(system.actor_info(three).await.unwrap().links.is_empty());
+
+        // the actor traps exits now: the linked actor's failure does not bring it down
+        system.exit(two, Exit::from_message("oops")).await;
+        system.wait(two).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(system.actor_info(actor).await.is_some());
+    })
+}
+
+#[test]
+fn with_backend_monitors_and_panics() {
+    async fn idle(_context: &mut Context<std::convert::Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    async fn monitoring(
+        context: &mut Context<std::convert::Infallible>,
+        (target, report_to): (ActorID, oneshot::Sender<(Vec<ActorID>, Exit)>),
+    ) {
+        let (monitor, monitors) = context
+            .with_backend(move |backend| {
+                let monitor = backend.monitor(target);
+                (monitor, backend.monitors())
+            })
+            .await;
+        let exit = monitor.await;
+        let _ = report_to.send((monitors, exit));
+        std::future::pending().await
+    }
+
+    async fn panicking(context: &mut Context<std::convert::Infallible>, _arg: ()) {
+        context.with_backend(|_backend| panic!("oops")).await;
+        unreachable!()
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let target = system.spawn(idle, (), Default::default()).await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        let observer = system.spawn(monitoring, (target, tx), Default::default()).await.unwrap();
+        while system.actor_info(target).await.unwrap().monitored_by.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(system.actor_info(target).await.unwrap().monitored_by.as_ref(), [observer]);
+
+        system.exit(target, Exit::from_message("done")).await;
+        let (monitors, exit) = rx.await.unwrap();
+        assert_eq!(monitors, [target]);
+        assert!(exit.eq_kind(&Exit::from_message("done")), "{}", exit);
+        assert!(system.actor_info(observer).await.unwrap().monitors.is_empty());
+
+        let panicked = system.spawn(panicking, (), Default::default()).await.unwrap();
+        let exit = system.wait(panicked).await;
+        assert!(matches!(
+            &exit,
+            Exit::Standard(agner_actors::exit_reason::WellKnown::Panic(message)) if message == "oops"
+        ));
+    })
+}
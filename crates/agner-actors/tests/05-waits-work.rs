@@ -1,7 +1,8 @@
 use std::time::Duration;
 
-use agner_actors::{Context, System};
+use agner_actors::{ActorID, Context, Exit, System};
 use futures::future;
+use tokio::sync::oneshot;
 
 mod common;
 
@@ -42,3 +43,272 @@ fn waits_work() {
         assert!(future::join_all(waits).await.into_iter().all(|e| e.is_normal()));
     });
 }
+
+#[test]
+fn monitor_fut_can_be_selected_with_messages() {
+    async fn idle(_context: &mut Context<std::convert::Infallible>, _: ()) {
+        std::future::pending().await
+    }
+
+    async fn watcher(
+        context: &mut Context<&'static str>,
+        (target, acked, report_to): (ActorID, oneshot::Sender<()>, oneshot::Sender<Vec<String>>),
+    ) {
+        let mut down = Box::pin(context.monitor_fut(target));
+        let mut acked = Some(acked);
+        let mut log = vec![];
+        loop {
+            tokio::select! {
+                exit = down.as_mut() => {
+                    log.push(format!("down: {}", exit));
+                    break
+                },
+                message = context.next_message() => {
+                    log.push(message.to_owned());
+                    if let Some(acked) = acked.take() {
+                        let _ = acked.send(());
+                    }
+                },
+            }
+        }
+        let _ = report_to.send(log);
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let target = system.spawn(idle, (), Default::default()).await.unwrap();
+        let (acked_tx, acked_rx) = oneshot::channel();
+        let (tx, rx) = oneshot::channel();
+        let watcher =
+            system.spawn(watcher, (target, acked_tx, tx), Default::default()).await.unwrap();
+
+        system.send(watcher, "hello").await;
+        acked_rx.await.unwrap();
+        system.exit(target, Exit::shutdown()).await;
+
+        assert_eq!(rx.await.unwrap(), ["hello".to_owned(), format!("down: {}", Exit::shutdown())]);
+    })
+}
+
+#[test]
+fn die_with_parent() {
+    async fn idle(_context: &mut Context<std::convert::Infallible>, _: ()) {
+        std::future::pending().await
+    }
+
+    async fn child(
+        context: &mut Context<std::convert::Infallible>,
+        (parent, ready): (ActorID, oneshot::Sender<()>),
+    ) {
+        context.trap_exit(true).await;
+        context
+            .die_with_parent(parent, |exit| {
+                Exit::from_message(format!("parent is down: {}", exit.is_shutdown()))
+            })
+            .await;
+        let _ = ready.send(());
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let parent = system.spawn(idle, (), Default::default()).await.unwrap();
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let child = system.spawn(child, (parent, ready_tx), Default::default()).await.unwrap();
+        ready_rx.await.unwrap();
+
+        system.exit(parent, Exit::shutdown()).await;
+
+        let exit = system.wait(child).await;
+        assert!(exit.is_custom());
+        let source = std::error::Error::source(&exit).expect("no source");
+        assert_eq!(source.to_string(), "parent is down: true");
+    });
+}
+
+#[test]
+fn tie_lifetime_to() {
+    async fn service(
+        context: &mut Context<std::convert::Infallible>,
+        (tie, ready): (oneshot::Receiver<()>, oneshot::Sender<()>),
+    ) {
+        context.trap_exit(true).await;
+        context.tie_lifetime_to(tie).await;
+        let _ = ready.send(());
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (tie_tx, tie_rx) = oneshot::channel();
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let service = system.spawn(service, (tie_rx, ready_tx), Default::default()).await.unwrap();
+        ready_rx.await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(system.actor_info(service).await.is_some());
+
+        drop(tie_tx);
+        assert!(system.wait(service).await.is_shutdown());
+    });
+}
+
+#[test]
+fn deadline() {
+    async fn worker(context: &mut Context<std::convert::Infallible>, work_for: Duration) {
+        let at = context.now_instant() + Duration::from_millis(50);
+        context.deadline(at).await;
+        tokio::time::sleep(work_for).await;
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let fast = system.spawn(worker, Duration::ZERO, Default::default()).await.unwrap();
+        assert!(system.wait(fast).await.is_normal());
+
+        let slow = system.spawn(worker, Duration::from_secs(30), Default::default()).await.unwrap();
+        let exit = system.wait(slow).await;
+        assert!(exit.is_timeout());
+        assert!(exit.eq_kind(&Exit::timeout()));
+        assert!(!exit.eq_kind(&Exit::shutdown()));
+        assert!(!Exit::from_message("timeout").is_timeout());
+        assert!(exit.with_note("slow").is_timeout());
+    });
+}
+
+#[test]
+fn actor_info_reports_monitors() {
+    async fn idle(_context: &mut Context<std::convert::Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    async fn observer(context: &mut Context<oneshot::Sender<()>>, target: ActorID) {
+        let monitor = context.monitor_fut(target);
+        let reply_to = tokio::select! {
+            _ = monitor => unreachable!("the target never exits"),
+            reply_to = context.next_message() => reply_to,
+        };
+        let _ = reply_to.send(());
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let target = system.spawn(idle, (), Default::default()).await.unwrap();
+        let observer = system.spawn(observer, target, Default::default()).await.unwrap();
+        let _plain_wait = tokio::spawn(system.wait(target));
+
+        loop {
+            let info = system.actor_info(target).await.unwrap();
+            if !info.monitored_by.is_empty() {
+                assert_eq!(info.monitored_by.as_ref(), &[observer]);
+                break
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        let info = system.actor_info(observer).await.unwrap();
+        assert_eq!(info.monitors.as_ref(), &[target]);
+        assert!(info.links.is_empty());
+
+        let (tx, rx) = oneshot::channel::<()>();
+        system.send(observer, tx).await;
+        rx.await.unwrap();
+
+        assert!(system.actor_info(observer).await.unwrap().monitors.is_empty());
+        assert!(system.actor_info(target).await.unwrap().monitored_by.is_empty());
+    })
+}
+
+#[test]
+fn spawn_with_join_observes_an_immediate_exit() {
+    async fn quits_right_away(
+        _context: &mut Context<std::convert::Infallible>,
+        exit: Exit,
+    ) -> Exit {
+        exit
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        for _ in 0..100 {
+            let (actor, join) = system
+                .spawn_with_join(quits_right_away, Exit::shutdown(), Default::default())
+                .await
+                .unwrap();
+            let exit = tokio::time::timeout(Duration::from_secs(1), join).await.unwrap();
+            assert!(exit.is_shutdown(), "{}: {}", actor, exit);
+        }
+    })
+}
+
+#[test]
+fn spawn_monitor_observes_an_immediate_exit() {
+    async fn quits_right_away(
+        _context: &mut Context<std::convert::Infallible>,
+        exit: Exit,
+    ) -> Exit {
+        exit
+    }
+
+    async fn parent(context: &mut Context<(ActorID, Exit)>, report_to: oneshot::Sender<usize>) {
+        let mut children = vec![];
+        for _ in 0..100 {
+            let (child, monitor) = context
+                .spawn_monitor(quits_right_away, Exit::from_message("done"), Default::default())
+                .await
+                .unwrap();
+            assert_eq!(monitor.target(), child);
+            context.future_to_inbox(async move { (monitor.target(), monitor.await) }).await;
+            children.push(child);
+        }
+        while !children.is_empty() {
+            let (child, exit) = context.next_message().await;
+            assert!(exit.eq_kind(&Exit::from_message("done")), "{}: {}", child, exit);
+            children.retain(|c| *c != child);
+        }
+        let _ = report_to
+            .send(context.system().actor_info(context.actor_id()).await.unwrap().monitors.len());
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let (tx, rx) = oneshot::channel();
+        let parent = system.spawn(parent, tx, Default::default()).await.unwrap();
+        let monitors_left =
+            tokio::time::timeout(Duration::from_secs(5), rx).await.unwrap().unwrap();
+        assert_eq!(monitors_left, 0);
+
+        // the parent is not linked to the children, it survives their failures
+        assert!(system.actor_info(parent).await.is_some());
+    })
+}
+
+#[test]
+fn wait_checked_tells_missing_actors() {
+    use agner_actors::system_error::SysWaitError;
+
+    async fn actor_behaviour(context: &mut Context<Exit>, _: ()) {
+        let exit_reason = context.next_message().await;
+        context.exit(exit_reason).await;
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let actor = system.spawn(actor_behaviour, (), Default::default()).await.unwrap();
+        let waiting = system.wait_checked(actor);
+        system.send(actor, Exit::no_actor()).await;
+        assert!(waiting.await.unwrap().is_no_actor());
+        assert!(system.wait_checked(actor).await.unwrap().is_no_actor());
+
+        let missing: ActorID =
+            format!("{}.{}.{}", actor.system_id(), actor.slot(), 1000).parse().unwrap();
+        assert_eq!(system.wait_checked(missing).await.unwrap_err(), SysWaitError::NotFound);
+        assert!(system.wait(missing).await.is_no_actor());
+    })
+}
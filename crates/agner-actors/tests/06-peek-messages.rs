@@ -0,0 +1,71 @@
+use std::convert::Infallible;
+
+use agner_actors::{Context, System};
+use tokio::sync::oneshot;
+
+mod common;
+
+#[test]
+fn peek_messages_of_a_debug_actor() {
+    async fn actor_behaviour(context: &mut Context<usize>, ready: oneshot::Sender<()>) {
+        context.enable_inbox_peek().await;
+        let _ = ready.send(());
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (tx, rx) = oneshot::channel();
+        let actor = system.spawn(actor_behaviour, tx, Default::default()).await.unwrap();
+        rx.await.unwrap();
+
+        for i in 1..=3usize {
+            system.send(actor, i).await;
+        }
+        assert!(system.sync(actor).await);
+
+        assert_eq!(system.peek_messages(actor, 2).await, ["1", "2"]);
+        assert_eq!(system.peek_messages(actor, 10).await, ["1", "2", "3"]);
+    })
+}
+
+#[test]
+fn peek_messages_without_opt_in() {
+    async fn actor_behaviour(_context: &mut Context<Infallible>, _: ()) {
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let actor = system.spawn(actor_behaviour, (), Default::default()).await.unwrap();
+
+        assert!(system.peek_messages(actor, 10).await.is_empty());
+    })
+}
+
+#[test]
+fn sync_waits_for_the_messages_to_reach_the_inbox() {
+    async fn actor_behaviour(context: &mut Context<usize>, ready: oneshot::Sender<()>) {
+        context.enable_inbox_peek().await;
+        let _ = ready.send(());
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (tx, rx) = oneshot::channel();
+        let actor = system.spawn(actor_behaviour, tx, Default::default()).await.unwrap();
+        rx.await.unwrap();
+
+        for i in 1..=100usize {
+            system.send(actor, i).await;
+        }
+        assert!(system.sync(actor).await);
+        assert_eq!(system.actor_info(actor).await.unwrap().m_queue_len.0, 100);
+        assert_eq!(system.peek_messages(actor, 3).await, ["1", "2", "3"]);
+
+        system.exit(actor, Default::default()).await;
+        system.wait(actor).await;
+        assert!(!system.sync(actor).await);
+    })
+}
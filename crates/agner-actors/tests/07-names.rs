@@ -0,0 +1,285 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use agner_actors::name_stores::InMemoryNameStore;
+use agner_actors::system_error::{SysChannelError, SysRebindError, SysRegisterError};
+use agner_actors::{ActorID, Context, Exit, NameStore, NameStoreError, System, SystemConfig};
+use tokio::sync::oneshot;
+
+mod common;
+
+async fn idle(_context: &mut Context<Infallible>, _arg: ()) {
+    std::future::pending().await
+}
+
+#[test]
+fn register_and_whereis() {
+    common::run(async {
+        let system = System::new(Default::default());
+        let one = system.spawn(idle, (), Default::default()).await.unwrap();
+        let two = system.spawn(idle, (), Default::default()).await.unwrap();
+
+        assert_eq!(system.whereis("service").await, None);
+        system.register("service", one).await.unwrap();
+        assert_eq!(system.whereis("service").await, Some(one));
+
+        assert!(matches!(
+            system.register("service", two).await,
+            Err(SysRegisterError::NameTaken(bound)) if bound == one
+        ));
+
+        assert_eq!(system.unregister("service").await.unwrap(), Some(one));
+        assert_eq!(system.whereis("service").await, None);
+    })
+}
+
+#[test]
+fn rebind_moves_the_name() {
+    common::run(async {
+        let system = System::new(Default::default());
+        let old = system.spawn(idle, (), Default::default()).await.unwrap();
+        let new = system.spawn(idle, (), Default::default()).await.unwrap();
+
+        assert_eq!(system.rebind("service", old).await.unwrap(), None);
+        assert_eq!(system.rebind("service", new).await.unwrap(), Some(old));
+        assert_eq!(system.whereis("service").await, Some(new));
+
+        system.exit(new, Exit::shutdown()).await;
+        system.wait(new).await;
+        assert!(matches!(system.rebind("service", new).await, Err(SysRebindError::NoActor)));
+    })
+}
+
+#[test]
+fn name_released_on_exit() {
+    common::run(async {
+        let system = System::new(Default::default());
+        let actor = system.spawn(idle, (), Default::default()).await.unwrap();
+        system.register("service", actor).await.unwrap();
+
+        system.exit(actor, Exit::shutdown()).await;
+        // the names are released by the time the wait resolves
+        system.wait(actor).await;
+        assert_eq!(system.whereis("service").await, None);
+    })
+}
+
+#[test]
+fn context_send_named() {
+    type Outcome = (Result<(), SysChannelError>, Result<(), SysChannelError>);
+
+    async fn room(context: &mut Context<&'static str>, report_to: oneshot::Sender<&'static str>) {
+        let _ = report_to.send(context.next_message().await);
+        std::future::pending().await
+    }
+
+    async fn conn(context: &mut Context<Infallible>, report_to: oneshot::Sender<Outcome>) {
+        let sent = context.send_named("room", "hello").await;
+        let unbound = context.send_named("lobby", "hello").await;
+        let _ = report_to.send((sent, unbound));
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (room_tx, room_rx) = oneshot::channel();
+        let room = system.spawn(room, room_tx, Default::default()).await.unwrap();
+        system.register("room", room).await.unwrap();
+
+        let (conn_tx, conn_rx) = oneshot::channel();
+        system.spawn(conn, conn_tx, Default::default()).await.unwrap();
+
+        let (sent, unbound) = conn_rx.await.unwrap();
+        assert!(sent.is_ok());
+        assert!(matches!(unbound, Err(SysChannelError::NoActor)));
+        assert_eq!(room_rx.await.unwrap(), "hello");
+    })
+}
+
+#[test]
+fn watch_name_follows_the_binding() {
+    use futures::StreamExt;
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let first = system.spawn(idle, (), Default::default()).await.unwrap();
+        let second = system.spawn(idle, (), Default::default()).await.unwrap();
+
+        let updates = system.watch_name("room").await;
+        futures::pin_mut!(updates);
+        assert_eq!(updates.next().await, Some(None));
+
+        system.register("room", first).await.unwrap();
+        assert_eq!(updates.next().await, Some(Some(first)));
+
+        system.rebind("room", second).await.unwrap();
+        assert_eq!(updates.next().await, Some(Some(second)));
+
+        let late = system.watch_name("room").await;
+        futures::pin_mut!(late);
+        assert_eq!(late.next().await, Some(Some(second)));
+
+        system.exit(second, Exit::shutdown()).await;
+        system.wait(second).await;
+        assert_eq!(updates.next().await, Some(None));
+        assert_eq!(late.next().await, Some(None));
+    })
+}
+
+#[test]
+fn name_store_is_pluggable() {
+    common::run(async {
+        let name_store: Arc<dyn NameStore> = Arc::new(InMemoryNameStore::new());
+        let config = || SystemConfig { name_store: name_store.to_owned(), ..Default::default() };
+        let one = System::new(config());
+        let two = System::new(config());
+
+        let actor = one.spawn(idle, (), Default::default()).await.unwrap();
+        one.register("service", actor).await.unwrap();
+
+        // the names are resolved via the shared store
+        assert_eq!(two.whereis("service").await, Some(actor));
+        assert_eq!(two.registered_names().await.unwrap(), vec![("service".to_owned(), actor)]);
+        assert_eq!(name_store.whereis("service").await.unwrap(), Some(actor));
+
+        one.exit(actor, Exit::shutdown()).await;
+        one.wait(actor).await;
+        assert_eq!(two.whereis("service").await, None);
+        assert!(name_store.list().await.unwrap().is_empty());
+    })
+}
+
+#[test]
+fn name_store_is_not_awaited_with_the_names_locked() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use futures::future::{self, BoxFuture};
+    use futures::StreamExt;
+    use tokio::sync::Semaphore;
+
+    /// Releases the names once permitted to; fails the registrations once told to.
+    #[derive(Debug)]
+    struct GatedStore {
+        inner: InMemoryNameStore,
+        release_gate: Semaphore,
+        failing: AtomicBool,
+    }
+
+    impl NameStore for GatedStore {
+        fn register<'a>(
+            &'a self,
+            name: &'a str,
+            actor_id: ActorID,
+        ) -> BoxFuture<'a, Result<Result<(), ActorID>, NameStoreError>> {
+            if self.failing.load(Ordering::SeqCst) {
+                Box::pin(future::err(NameStoreError::new("the store is down")))
+            } else {
+                self.inner.register(name, actor_id)
+            }
+        }
+        fn rebind<'a>(
+            &'a self,
+            name: &'a str,
+            actor_id: ActorID,
+        ) -> BoxFuture<'a, Result<Option<ActorID>, NameStoreError>> {
+            self.inner.rebind(name, actor_id)
+        }
+        fn unregister<'a>(
+            &'a self,
+            name: &'a str,
+        ) -> BoxFuture<'a, Result<Option<ActorID>, NameStoreError>> {
+            self.inner.unregister(name)
+        }
+        fn whereis<'a>(
+            &'a self,
+            name: &'a str,
+        ) -> BoxFuture<'a, Result<Option<ActorID>, NameStoreError>> {
+            self.inner.whereis(name)
+        }
+        fn list(&self) -> BoxFuture<'_, Result<Vec<(String, ActorID)>, NameStoreError>> {
+            self.inner.list()
+        }
+        fn release<'a>(
+            &'a self,
+            actor_id: ActorID,
+            names: &'a [String],
+        ) -> BoxFuture<'a, Result<(), NameStoreError>> {
+            Box::pin(async move {
+                let _permit = self.release_gate.acquire().await.unwrap();
+                self.inner.release(actor_id, names).await
+            })
+        }
+    }
+
+    common::run(async {
+        let name_store = Arc::new(GatedStore {
+            inner: InMemoryNameStore::new(),
+            release_gate: Semaphore::new(0),
+            failing: AtomicBool::new(false),
+        });
+        let system =
+            System::new(SystemConfig { name_store: name_store.to_owned(), ..Default::default() });
+
+        let old = system.spawn(idle, (), Default::default()).await.unwrap();
+        system.register("old", old).await.unwrap();
+        let updates = system.watch_name("old").await;
+        futures::pin_mut!(updates);
+        assert_eq!(updates.next().await, Some(Some(old)));
+
+        system.exit(old, Exit::shutdown()).await;
+        // the name is taken off the index: the store is being updated
+        assert_eq!(updates.next().await, Some(None));
+
+        let new = system.spawn(idle, (), Default::default()).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), system.register("new", new))
+            .await
+            .expect("the names are locked while the store is being updated")
+            .unwrap();
+        // no name can be bound to the terminating actor
+        assert!(matches!(system.register("alias", old).await, Err(SysRegisterError::NoActor)));
+
+        name_store.release_gate.add_permits(1);
+        system.wait(old).await;
+        assert_eq!(system.whereis("old").await, None);
+        assert_eq!(system.whereis("new").await, Some(new));
+
+        name_store.failing.store(true, Ordering::SeqCst);
+        assert!(matches!(
+            system.register("another", new).await,
+            Err(SysRegisterError::NameStore(_))
+        ));
+    })
+}
+
+#[test]
+fn actor_info_reports_the_name() {
+    common::run(async {
+        let system = System::new(Default::default());
+        let parent = system.spawn(idle, (), Default::default()).await.unwrap();
+        let child = system
+            .spawn(idle, (), agner_actors::SpawnOpts::new().with_parent(parent).with_label("child"))
+            .await
+            .unwrap();
+
+        let info = system.actor_info(child).await.unwrap();
+        assert_eq!(info.name, None);
+        assert_eq!(info.label.as_deref(), Some("child"));
+        assert_eq!(info.parent, Some(parent));
+
+        let info = system.actor_info(parent).await.unwrap();
+        assert_eq!(info.label, None);
+        assert_eq!(info.parent, None);
+
+        system.register("worker", child).await.unwrap();
+        system.register("alias", child).await.unwrap();
+        assert_eq!(system.actor_info(child).await.unwrap().name.as_deref(), Some("alias"));
+
+        system.unregister("alias").await.unwrap();
+        assert_eq!(system.actor_info(child).await.unwrap().name.as_deref(), Some("worker"));
+
+        system.rebind("worker", parent).await.unwrap();
+        assert_eq!(system.actor_info(child).await.unwrap().name, None);
+        assert_eq!(system.actor_info(parent).await.unwrap().name.as_deref(), Some("worker"));
+    })
+}
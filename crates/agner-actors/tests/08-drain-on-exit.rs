@@ -0,0 +1,115 @@
+use std::sync::{Arc, Mutex};
+
+use agner_actors::{Context, Exit, SpawnOpts, System};
+use tokio::sync::oneshot;
+
+mod common;
+
+type Drained = Arc<Mutex<Vec<usize>>>;
+
+async fn actor_behaviour(
+    context: &mut Context<usize>,
+    (drained, ready, finish): (Drained, oneshot::Sender<usize>, oneshot::Receiver<()>),
+) {
+    context.on_drain(move |message| drained.lock().unwrap().push(message)).await;
+
+    let first = context.next_message().await;
+    let _ = ready.send(first);
+    let _ = finish.await;
+}
+
+async fn run_and_collect(spawn_opts: SpawnOpts, exit_with: Option<Exit>) -> Vec<usize> {
+    let system = System::new(Default::default());
+    let drained = Drained::default();
+    let (ready_tx, ready_rx) = oneshot::channel();
+    let (finish_tx, finish_rx) = oneshot::channel();
+    let actor = system
+        .spawn(actor_behaviour, (drained.to_owned(), ready_tx, finish_rx), spawn_opts)
+        .await
+        .unwrap();
+
+    for i in 1..=4usize {
+        system.send(actor, i).await;
+    }
+    assert!(system.sync(actor).await);
+    assert_eq!(ready_rx.await.unwrap(), 1);
+
+    match exit_with {
+        Some(exit_with) => system.exit(actor, exit_with).await,
+        None => finish_tx.send(()).unwrap(),
+    }
+    system.wait(actor).await;
+
+    let out = drained.lock().unwrap().to_owned();
+    out
+}
+
+#[test]
+fn remaining_messages_are_drained_on_exit() {
+    common::run(async {
+        let drained = run_and_collect(SpawnOpts::new().with_drain_on_exit(true), None).await;
+        assert_eq!(drained, [2, 3, 4]);
+    })
+}
+
+#[test]
+fn no_draining_unless_enabled() {
+    common::run(async {
+        let drained = run_and_collect(SpawnOpts::new(), None).await;
+        assert!(drained.is_empty());
+    })
+}
+
+#[test]
+fn kill_bypasses_draining() {
+    common::run(async {
+        let drained =
+            run_and_collect(SpawnOpts::new().with_drain_on_exit(true), Some(Exit::kill())).await;
+        assert!(drained.is_empty());
+    })
+}
+
+#[test]
+fn draining_precedes_the_deferred_functions() {
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    async fn actor_behaviour(
+        context: &mut Context<usize>,
+        (log, ready, finish): (Log, oneshot::Sender<()>, oneshot::Receiver<()>),
+    ) {
+        let drained = log.to_owned();
+        context
+            .on_drain(move |message| drained.lock().unwrap().push(format!("drained {}", message)))
+            .await;
+        context.defer(move |_exit| log.lock().unwrap().push("closed".to_owned())).await;
+
+        let _first = context.next_message().await;
+        let _ = ready.send(());
+        let _ = finish.await;
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let log = Log::default();
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let (finish_tx, finish_rx) = oneshot::channel();
+        let actor = system
+            .spawn(
+                actor_behaviour,
+                (log.to_owned(), ready_tx, finish_rx),
+                SpawnOpts::new().with_drain_on_exit(true),
+            )
+            .await
+            .unwrap();
+
+        for i in 1..=3usize {
+            system.send(actor, i).await;
+        }
+        assert!(system.sync(actor).await);
+        ready_rx.await.unwrap();
+        finish_tx.send(()).unwrap();
+        system.wait(actor).await;
+
+        assert_eq!(*log.lock().unwrap(), ["drained 2", "drained 3", "closed"]);
+    })
+}
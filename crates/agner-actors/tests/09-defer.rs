@@ -0,0 +1,111 @@
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use agner_actors::{Context, Exit, System};
+use tokio::sync::oneshot;
+
+mod common;
+
+type Released = Arc<Mutex<Vec<(&'static str, Exit)>>>;
+
+async fn actor_behaviour(
+    context: &mut Context<Infallible>,
+    (released, ready): (Released, oneshot::Sender<()>),
+) {
+    for resource in ["first", "second", "third"] {
+        let released = released.to_owned();
+        context
+            .defer(move |exit| released.lock().unwrap().push((resource, exit.to_owned())))
+            .await;
+    }
+    let _ = ready.send(());
+    std::future::pending().await
+}
+
+async fn run_and_collect(exit_with: Exit) -> Vec<(&'static str, Exit)> {
+    let system = System::new(Default::default());
+    let released = Released::default();
+    let (tx, rx) = oneshot::channel();
+    let actor = system
+        .spawn(actor_behaviour, (released.to_owned(), tx), Default::default())
+        .await
+        .unwrap();
+    rx.await.unwrap();
+
+    system.exit(actor, exit_with).await;
+    system.wait(actor).await;
+
+    let out = released.lock().unwrap().to_owned();
+    out
+}
+
+#[test]
+fn deferred_functions_run_in_reverse_order() {
+    common::run(async {
+        let released = run_and_collect(Exit::shutdown()).await;
+        assert_eq!(
+            released.iter().map(|(resource, _)| *resource).collect::<Vec<_>>(),
+            ["third", "second", "first"]
+        );
+        assert!(released.iter().all(|(_, exit)| exit.is_shutdown()));
+    })
+}
+
+#[test]
+fn deferred_functions_run_on_kill() {
+    common::run(async {
+        let released = run_and_collect(Exit::kill()).await;
+        assert_eq!(released.len(), 3);
+        assert!(released.iter().all(|(_, exit)| exit.is_kill()));
+    })
+}
+
+#[test]
+fn cleanup_completes_before_wait_resolves() {
+    use agner_actors::{ActorID, ExitHandler, SpawnOpts};
+
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    #[derive(Debug)]
+    struct Recorder(Log);
+    impl ExitHandler for Recorder {
+        fn on_actor_exit(&self, _actor_id: ActorID, _exit: Exit) {
+            self.0.lock().unwrap().push("exit-handler");
+        }
+    }
+
+    struct Guard(Log);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.lock().unwrap().push("behaviour-dropped");
+        }
+    }
+
+    async fn behaviour(context: &mut Context<()>, log: Log) {
+        let _guard = Guard(log.to_owned());
+        context.defer(move |_| log.lock().unwrap().push("deferred")).await;
+        context.next_message().await;
+        match context.exit(Exit::from_message("done")).await {}
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let log = Log::default();
+        let actor = system
+            .spawn(
+                behaviour,
+                log.to_owned(),
+                SpawnOpts::new().with_exit_handler(Arc::new(Recorder(log.to_owned()))),
+            )
+            .await
+            .unwrap();
+        system.register("the-actor", actor).await.unwrap();
+
+        let wait = system.wait(actor);
+        system.send(actor, ()).await;
+        assert!(wait.await.is_custom());
+
+        assert_eq!(*log.lock().unwrap(), ["deferred", "exit-handler", "behaviour-dropped"]);
+        assert_eq!(system.whereis("the-actor").await, None);
+    })
+}
@@ -0,0 +1,134 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use agner_actors::{
+    ActorID, Context, Event, Exit, ExitHandler, Signal, SpawnOpts, System, SystemConfig,
+};
+use tokio::sync::oneshot;
+
+mod common;
+
+#[test]
+fn panic_becomes_exit_reason() {
+    async fn panicking(context: &mut Context<&'static str>, _arg: ()) {
+        let message = context.next_message().await;
+        panic!("{}", message)
+    }
+
+    async fn observer(
+        context: &mut Context<Infallible>,
+        (ready, report_to): (oneshot::Sender<()>, oneshot::Sender<Exit>),
+    ) {
+        context.trap_exit(true).await;
+        let _ = ready.send(());
+        let Event::Signal(Signal::Exit(_, exit)) = context.next_event().await;
+        let _ = report_to.send(exit);
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let actor = system.spawn(panicking, (), Default::default()).await.unwrap();
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let (tx, rx) = oneshot::channel();
+        let _observer = system
+            .spawn(observer, (ready_tx, tx), SpawnOpts::new().with_link(actor))
+            .await
+            .unwrap();
+        ready_rx.await.unwrap();
+
+        let waiting = system.wait(actor);
+        system.send(actor, "oops").await;
+
+        let exit = waiting.await;
+        assert!(exit.is_panic(), "{:?}", exit);
+        assert!(matches!(
+            &exit,
+            Exit::Standard(agner_actors::exit_reason::WellKnown::Panic(message)) if message == "oops"
+        ));
+
+        assert!(rx.await.unwrap().is_panic());
+        assert!(system.actor_info(actor).await.is_none());
+    });
+}
+
+#[test]
+fn reap_dead_reclaims_stale_entries() {
+    #[derive(Debug)]
+    struct PanickingExitHandler;
+    impl ExitHandler for PanickingExitHandler {
+        fn on_actor_exit(&self, actor_id: ActorID, _exit: Exit) {
+            panic!("failed to handle the exit of {}", actor_id)
+        }
+    }
+
+    async fn actor_behaviour(_context: &mut Context<Infallible>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(SystemConfig { max_actors: 1, ..Default::default() });
+        assert_eq!(system.reap_dead().await, 0);
+
+        let actor = system
+            .spawn(
+                actor_behaviour,
+                (),
+                SpawnOpts::new().with_exit_handler(Arc::new(PanickingExitHandler)),
+            )
+            .await
+            .unwrap();
+        let waiting = tokio::spawn(system.wait(actor));
+
+        system.exit(actor, Exit::shutdown()).await;
+
+        let mut reaped = 0;
+        for _ in 0..100 {
+            reaped = system.reap_dead().await;
+            if reaped > 0 {
+                break
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(reaped, 1);
+        assert_eq!(system.reap_dead().await, 0);
+
+        assert!(waiting.await.unwrap().is_no_actor());
+        assert!(system.spawn(actor_behaviour, (), Default::default()).await.is_ok());
+    });
+}
+
+#[test]
+fn panic_policy_abort_aborts_the_process() {
+    const CHILD_ENV: &str = "AGNER_PANIC_POLICY_ABORT_CHILD";
+
+    async fn panicking(_context: &mut Context<Infallible>, _arg: ()) {
+        panic!("invariant violated")
+    }
+
+    if std::env::var_os(CHILD_ENV).is_some() {
+        common::run(async {
+            let system = System::new(Default::default());
+            let actor = system
+                .spawn(
+                    panicking,
+                    (),
+                    SpawnOpts::new().with_panic_policy(agner_actors::PanicPolicy::Abort),
+                )
+                .await
+                .unwrap();
+            system.wait(actor).await;
+        });
+        return
+    }
+
+    let status = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "panic_policy_abort_aborts_the_process", "--nocapture"])
+        .env(CHILD_ENV, "1")
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .unwrap();
+    assert!(!status.success(), "{:?}", status);
+}
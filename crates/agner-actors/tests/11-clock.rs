@@ -0,0 +1,105 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use agner_actors::clocks::MockClock;
+use agner_actors::{Context, System, SystemConfig};
+use tokio::sync::{mpsc, oneshot};
+
+mod common;
+
+#[test]
+fn context_reads_the_system_clock() {
+    async fn actor_behaviour(
+        context: &mut Context<oneshot::Sender<(Instant, SystemTime)>>,
+        _arg: (),
+    ) {
+        loop {
+            let reply_to = context.next_message().await;
+            let _ = reply_to.send((context.now_instant(), context.now_system()));
+        }
+    }
+
+    common::run(async {
+        let clock = MockClock::new();
+        let system =
+            System::new(SystemConfig { clock: Arc::new(clock.to_owned()), ..Default::default() });
+        let actor = system.spawn(actor_behaviour, (), Default::default()).await.unwrap();
+
+        let now = |system: System| async move {
+            let (tx, rx) = oneshot::channel::<(Instant, SystemTime)>();
+            system.send(actor, tx).await;
+            rx.await.unwrap()
+        };
+
+        let (instant_0, system_0) = now(system.to_owned()).await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(now(system.to_owned()).await, (instant_0, system_0));
+
+        clock.advance(Duration::from_secs(60));
+        let (instant_1, system_1) = now(system.to_owned()).await;
+        assert_eq!(instant_1 - instant_0, Duration::from_secs(60));
+        assert_eq!(system_1.duration_since(system_0).unwrap(), Duration::from_secs(60));
+    })
+}
+
+#[test]
+fn deadline_fires_by_the_system_clock() {
+    async fn actor_behaviour(
+        context: &mut Context<oneshot::Sender<()>>,
+        armed: oneshot::Sender<()>,
+    ) {
+        let at = context.now_instant() + Duration::from_secs(60);
+        context.deadline(at).await;
+        let _ = armed.send(());
+        loop {
+            let reply_to = context.next_message().await;
+            let _ = reply_to.send(());
+        }
+    }
+
+    common::run(async {
+        let clock = MockClock::new();
+        let system =
+            System::new(SystemConfig { clock: Arc::new(clock.to_owned()), ..Default::default() });
+        let (armed_tx, armed_rx) = oneshot::channel();
+        let actor = system.spawn(actor_behaviour, armed_tx, Default::default()).await.unwrap();
+        armed_rx.await.unwrap();
+
+        clock.advance(Duration::from_secs(30));
+        let (tx, rx) = oneshot::channel::<()>();
+        system.send(actor, tx).await;
+        rx.await.unwrap();
+
+        clock.advance(Duration::from_secs(30));
+        assert!(system.wait(actor).await.is_timeout());
+    })
+}
+
+#[test]
+fn send_after_is_timed_by_the_system_clock() {
+    async fn actor_behaviour(
+        context: &mut Context<&'static str>,
+        report_to: mpsc::UnboundedSender<&'static str>,
+    ) {
+        loop {
+            let _ = report_to.send(context.next_message().await);
+        }
+    }
+
+    common::run(async {
+        let clock = MockClock::new();
+        let system =
+            System::new(SystemConfig { clock: Arc::new(clock.to_owned()), ..Default::default() });
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let actor = system.spawn(actor_behaviour, tx, Default::default()).await.unwrap();
+
+        system.send_after(actor, Duration::from_secs(60), "delayed");
+
+        clock.advance(Duration::from_secs(30));
+        system.send(actor, "direct").await;
+        assert_eq!(rx.recv().await, Some("direct"));
+
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(rx.recv().await, Some("delayed"));
+    })
+}
@@ -0,0 +1,92 @@
+use agner_actors::{Actor, ActorID, Context, Exit, System};
+use tokio::sync::oneshot;
+
+mod common;
+
+#[derive(Debug)]
+enum Request {
+    Reinit(usize),
+    ReinitWrongArgs,
+    Get(oneshot::Sender<(usize, usize)>),
+}
+
+async fn actor_behaviour(context: &mut Context<Request>, generation: usize) {
+    let mut handled = 0;
+    loop {
+        handled += 1;
+        match context.next_message().await {
+            Request::Reinit(generation) => match context.reinit(generation).await {},
+            Request::ReinitWrongArgs => match context.reinit("wrong").await {},
+            Request::Get(reply_to) => {
+                let _ = reply_to.send((generation, handled));
+            },
+        }
+    }
+}
+
+async fn idle(_context: &mut Context<std::convert::Infallible>, _arg: ()) {
+    std::future::pending().await
+}
+
+#[test]
+fn reinit_keeps_the_mailbox_and_the_links() {
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let peer = system.spawn(idle, (), Default::default()).await.unwrap();
+        let actor = system
+            .spawn_rerunnable(actor_behaviour, 0, agner_actors::SpawnOpts::new().with_link(peer))
+            .await
+            .unwrap();
+        system.register("the-actor", actor).await.unwrap();
+
+        let (tx, rx) = oneshot::channel();
+        system.send(actor, Request::Reinit(1)).await;
+        system.send(actor, Request::Reinit(2)).await;
+        system.send(actor, Request::Get(tx)).await;
+        assert_eq!(rx.await.unwrap(), (2, 1));
+
+        let links: Vec<ActorID> = system.actor_info(actor).await.unwrap().links.to_vec();
+        assert_eq!(links, [peer]);
+        assert_eq!(system.whereis("the-actor").await, Some(actor));
+    })
+}
+
+#[test]
+fn reinit_with_wrong_args_exits() {
+    common::run(async {
+        let system = System::new(Default::default());
+        let actor = system.spawn_rerunnable(actor_behaviour, 0, Default::default()).await.unwrap();
+
+        system.send(actor, Request::ReinitWrongArgs).await;
+        let exit: Exit = system.wait(actor).await;
+        assert!(exit.is_custom(), "{}", exit);
+    })
+}
+
+/// A behaviour that is not [`Clone`].
+struct Once(oneshot::Sender<()>);
+
+impl<'a> Actor<'a, usize, Request> for Once {
+    type Out = ();
+    type Fut = futures::future::BoxFuture<'a, ()>;
+
+    fn run(self, context: &'a mut Context<Request>, generation: usize) -> Self::Fut {
+        let _ = self.0.send(());
+        Box::pin(actor_behaviour(context, generation))
+    }
+}
+
+#[test]
+fn reinit_of_a_non_rerunnable_actor_exits() {
+    common::run(async {
+        let system = System::new(Default::default());
+        let (started_tx, started_rx) = oneshot::channel();
+        let actor = system.spawn(Once(started_tx), 0, Default::default()).await.unwrap();
+        started_rx.await.unwrap();
+
+        system.send(actor, Request::Reinit(1)).await;
+        let exit: Exit = system.wait(actor).await;
+        assert!(exit.is_custom(), "{}", exit);
+    })
+}
@@ -0,0 +1,46 @@
+use agner_actors::{ActorID, Context, SpawnOpts, System};
+use tokio::sync::oneshot;
+
+mod common;
+
+async fn report_parent(
+    context: &mut Context<std::convert::Infallible>,
+    reply_to: oneshot::Sender<Option<ActorID>>,
+) {
+    let _ = reply_to.send(context.parent());
+    std::future::pending().await
+}
+
+async fn idle(_context: &mut Context<std::convert::Infallible>, _arg: ()) {
+    std::future::pending().await
+}
+
+#[test]
+fn parent_is_none_by_default() {
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let (tx, rx) = oneshot::channel();
+        let actor = system.spawn(report_parent, tx, Default::default()).await.unwrap();
+
+        assert_eq!(rx.await.unwrap(), None);
+        assert_eq!(system.actor_info(actor).await.unwrap().parent, None);
+    })
+}
+
+#[test]
+fn parent_is_reported() {
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let parent = system.spawn(idle, (), Default::default()).await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        let actor = system
+            .spawn(report_parent, tx, SpawnOpts::new().with_parent(parent))
+            .await
+            .unwrap();
+
+        assert_eq!(rx.await.unwrap(), Some(parent));
+        assert_eq!(system.actor_info(actor).await.unwrap().parent, Some(parent));
+    })
+}
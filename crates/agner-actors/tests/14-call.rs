@@ -0,0 +1,157 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use agner_actors::clocks::MockClock;
+use agner_actors::system_error::SysCallError;
+use agner_actors::{ActorID, Context, Event, Exit, Signal, System, SystemConfig};
+use tokio::sync::oneshot;
+
+mod common;
+
+#[derive(Debug)]
+enum Request {
+    Echo(usize, oneshot::Sender<usize>),
+    Ignore(oneshot::Sender<usize>),
+    Exit(oneshot::Sender<usize>),
+    Drop(oneshot::Sender<usize>),
+}
+
+async fn callee(context: &mut Context<Request>, _arg: ()) {
+    let mut ignored = vec![];
+    loop {
+        match context.next_message().await {
+            Request::Echo(value, reply_to) => {
+                let _ = reply_to.send(value);
+            },
+            Request::Ignore(reply_to) => ignored.push(reply_to),
+            Request::Exit(_reply_to) => {
+                context.exit(Exit::from_message("requested")).await;
+            },
+            Request::Drop(reply_to) => drop(reply_to),
+        }
+    }
+}
+
+type Report = oneshot::Sender<(Result<usize, SysCallError>, Option<Event<()>>)>;
+type CallerArgs = (ActorID, fn(oneshot::Sender<usize>) -> Request, bool, Report);
+
+async fn caller(context: &mut Context<()>, (to, make_req, trap_exit, report_to): CallerArgs) {
+    context.trap_exit(trap_exit).await;
+    let result = context.call(to, make_req, Duration::from_millis(200)).await;
+    let event = if trap_exit { Some(context.next_event().await) } else { None };
+    let _ = report_to.send((result, event));
+}
+
+async fn run_call(
+    system: &System,
+    make_req: fn(oneshot::Sender<usize>) -> Request,
+) -> Result<usize, SysCallError> {
+    let to = system.spawn(callee, (), Default::default()).await.unwrap();
+    let (tx, rx) = oneshot::channel();
+    system
+        .spawn(caller, (to, make_req, false, tx), Default::default())
+        .await
+        .unwrap();
+    rx.await.unwrap().0
+}
+
+#[test]
+fn call_returns_the_reply() {
+    common::run(async {
+        let system = System::new(Default::default());
+        assert_eq!(run_call(&system, |tx| Request::Echo(42, tx)).await.unwrap(), 42);
+    })
+}
+
+#[test]
+fn call_fails() {
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let result = run_call(&system, Request::Exit).await;
+        assert!(matches!(result, Err(SysCallError::CalleeDown(ref exit)) if exit.is_custom()));
+
+        let result = run_call(&system, Request::Ignore).await;
+        assert!(matches!(result, Err(SysCallError::Timeout)));
+
+        let result = run_call(&system, Request::Drop).await;
+        assert!(matches!(result, Err(SysCallError::Timeout)));
+
+        let gone = system.spawn(callee, (), Default::default()).await.unwrap();
+        let to: ActorID = format!("{}.{}.{}", gone.system_id(), gone.slot(), 1000).parse().unwrap();
+        let (tx, rx) = oneshot::channel();
+        let args = (to, Request::Ignore as fn(_) -> _, false, tx);
+        system.spawn(caller, args, Default::default()).await.unwrap();
+        assert!(matches!(rx.await.unwrap().0, Err(SysCallError::Send(_))));
+    })
+}
+
+#[test]
+fn caller_can_be_killed_mid_call() {
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let to = system.spawn(callee, (), Default::default()).await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        let args = (to, Request::Ignore as fn(_) -> _, false, tx);
+        let caller = system.spawn(caller, args, Default::default()).await.unwrap();
+
+        system.exit(caller, Exit::shutdown()).await;
+        assert!(system.wait(caller).await.is_shutdown());
+        assert!(rx.await.is_err());
+    })
+}
+
+#[test]
+fn signals_received_mid_call_stay_queued() {
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let to = system.spawn(callee, (), Default::default()).await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        let args = (to, Request::Ignore as fn(_) -> _, true, tx);
+        let caller = system.spawn(caller, args, Default::default()).await.unwrap();
+
+        while !system.actor_info(caller).await.unwrap().trap_exit {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        system.exit(caller, Exit::from_message("signal")).await;
+
+        let (result, event) = rx.await.unwrap();
+        assert!(matches!(result, Err(SysCallError::Timeout)));
+        assert!(matches!(
+            event,
+            Some(Event::Signal(Signal::Exit(from, exit))) if from == caller && exit.is_custom()
+        ));
+    })
+}
+
+#[test]
+fn call_times_out_by_the_clock() {
+    async fn silent(context: &mut Context<Request>, received: oneshot::Sender<()>) {
+        let _request = context.next_message().await;
+        let _ = received.send(());
+        std::future::pending().await
+    }
+    async fn patient_caller(
+        context: &mut Context<()>,
+        (to, report_to): (ActorID, oneshot::Sender<Result<usize, SysCallError>>),
+    ) {
+        let _ = report_to.send(context.call(to, Request::Ignore, Duration::from_secs(60)).await);
+    }
+
+    common::run(async {
+        let clock = MockClock::new();
+        let system =
+            System::new(SystemConfig { clock: Arc::new(clock.to_owned()), ..Default::default() });
+
+        let (received_tx, received_rx) = oneshot::channel();
+        let to = system.spawn(silent, received_tx, Default::default()).await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        system.spawn(patient_caller, (to, tx), Default::default()).await.unwrap();
+
+        received_rx.await.unwrap();
+        clock.advance(Duration::from_secs(60));
+        assert!(matches!(rx.await.unwrap(), Err(SysCallError::Timeout)));
+    })
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use agner_actors::{Context, Event, Exit, Selected, Signal, System};
+use tokio::sync::oneshot;
+
+mod common;
+
+#[test]
+fn messages_and_registered_futures() {
+    async fn actor(context: &mut Context<&'static str>, report_to: oneshot::Sender<Vec<String>>) {
+        let (tx, mut rx) = oneshot::channel::<&'static str>();
+        let mut tx = Some(tx);
+        let mut log = vec![];
+        loop {
+            match context.select().with(&mut rx).await {
+                Selected::Ready(value) => {
+                    log.push(format!("ready:{}", value.unwrap()));
+                    break
+                },
+                Selected::Event(Event::Message(message)) => {
+                    log.push(format!("message:{}", message));
+                    let _ = tx.take().expect("a single message is expected").send("external");
+                },
+                Selected::Event(Event::Signal(signal)) => panic!("unexpected signal: {:?}", signal),
+            }
+        }
+        let _ = report_to.send(log);
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (tx, rx) = oneshot::channel();
+        let actor = system.spawn(actor, tx, Default::default()).await.unwrap();
+        system.send(actor, "hello").await;
+        assert_eq!(rx.await.unwrap(), ["message:hello", "ready:external"]);
+    })
+}
+
+type Report = oneshot::Sender<Selected<&'static str, usize>>;
+
+#[test]
+fn signals_come_first() {
+    async fn actor(
+        context: &mut Context<&'static str>,
+        (ready, go, report_to): (oneshot::Sender<()>, oneshot::Receiver<()>, Report),
+    ) {
+        context.trap_exit(true).await;
+        let _ = ready.send(());
+        let _ = go.await;
+        let selected = context.select().with(std::future::ready(1)).await;
+        let _ = report_to.send(selected);
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let (go_tx, go_rx) = oneshot::channel();
+        let (tx, rx) = oneshot::channel();
+        let actor = system.spawn(actor, (ready_tx, go_rx, tx), Default::default()).await.unwrap();
+        ready_rx.await.unwrap();
+
+        system.send(actor, "message").await;
+        system.exit(actor, Exit::from_message("signal")).await;
+        assert!(system.sync(actor).await);
+        go_tx.send(()).unwrap();
+
+        assert!(matches!(
+            rx.await.unwrap(),
+            Selected::Event(Event::Signal(Signal::Exit(from, exit))) if from == actor && exit.is_custom()
+        ));
+    })
+}
+
+#[test]
+fn without_messages_leaves_the_inbox_intact() {
+    async fn actor(
+        context: &mut Context<&'static str>,
+        report_to: oneshot::Sender<(usize, &'static str)>,
+    ) {
+        while context.inbox_len() == 0 {
+            context
+                .select()
+                .without_messages()
+                .with(tokio::time::sleep(Duration::from_millis(1)))
+                .await;
+        }
+        let Selected::Ready(value) =
+            context.select().without_messages().with(std::future::ready(1)).await
+        else {
+            panic!("the inbox should have been skipped")
+        };
+        let message = context.next_message().await;
+        let _ = report_to.send((value, message));
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let (tx, rx) = oneshot::channel();
+        let actor = system.spawn(actor, tx, Default::default()).await.unwrap();
+        system.send(actor, "queued").await;
+        assert_eq!(rx.await.unwrap(), (1, "queued"));
+    })
+}
+
+#[test]
+fn exit_signal_interrupts_a_pending_select() {
+    async fn actor(context: &mut Context<&'static str>, _arg: ()) {
+        let (_tx, rx) = oneshot::channel::<()>();
+        context.select().with(rx).await;
+        unreachable!("the registered future never completes")
+    }
+
+    common::run(async {
+        let system = System::new(Default::default());
+        let actor = system.spawn(actor, (), Default::default()).await.unwrap();
+        system.exit(actor, Exit::shutdown()).await;
+        assert!(system.wait(actor).await.is_shutdown());
+    })
+}
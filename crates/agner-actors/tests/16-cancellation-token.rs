@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use agner_actors::{Context, Exit, System};
+use agner_utils::future_timeout_ext::FutureTimeoutExt;
+use tokio::sync::oneshot;
+use tokio_util::sync::CancellationToken;
+
+mod common;
+
+async fn actor(context: &mut Context<&'static str>, report_to: oneshot::Sender<CancellationToken>) {
+    let _ = report_to.send(context.cancellation_token());
+    loop {
+        match context.next_message().await {
+            "exit" => match context.exit(Exit::from_message("exit")).await {},
+            "panic" => panic!("panic"),
+            _ => (),
+        }
+    }
+}
+
+async fn start(system: &System) -> (agner_actors::ActorID, CancellationToken) {
+    let (tx, rx) = oneshot::channel();
+    let actor = system.spawn(actor, tx, Default::default()).await.unwrap();
+    (actor, rx.await.unwrap())
+}
+
+#[test]
+fn token_is_cancelled_on_any_exit() {
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let (actor, token) = start(&system).await;
+        assert!(token.cancelled().timeout(Duration::from_millis(50)).await.is_err());
+        system.send(actor, "exit").await;
+        token.cancelled().await;
+        assert!(system.wait(actor).await.is_custom());
+
+        let (actor, token) = start(&system).await;
+        system.send(actor, "panic").await;
+        token.cancelled().await;
+        system.wait(actor).await;
+
+        let (actor, token) = start(&system).await;
+        system.exit(actor, Exit::kill()).await;
+        token.cancelled().await;
+        assert!(system.wait(actor).await.is_kill());
+
+        let (actor, token) = start(&system).await;
+        system.exit(actor, Exit::shutdown()).await;
+        token.cancelled().await;
+        assert!(system.wait(actor).await.is_shutdown());
+    })
+}
+
+#[test]
+fn cancelling_the_token_does_not_affect_the_actor() {
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let (actor, token) = start(&system).await;
+        token.cancel();
+        assert!(system.wait(actor).timeout(Duration::from_millis(50)).await.is_err());
+        assert!(system.actor_info(actor).await.is_some());
+    })
+}
@@ -0,0 +1,30 @@
+use agner_actors::{actor_debug, actor_info, ActorID, ActorLog, Context, System};
+use tokio::sync::oneshot;
+
+mod common;
+
+async fn greeter(
+    context: &mut Context<std::convert::Infallible>,
+    report_to: oneshot::Sender<ActorLog>,
+) {
+    actor_info!(context.log(), "started");
+    actor_debug!(context.log(), "parent: {:?}", context.parent());
+    let _ = report_to.send(context.log());
+    std::future::pending().await
+}
+
+#[test]
+fn log_is_tagged_with_the_actor() {
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let (tx, rx) = oneshot::channel();
+        let actor: ActorID = system.spawn(greeter, tx, Default::default()).await.unwrap();
+        let log = rx.await.unwrap();
+
+        assert_eq!(log.actor_id(), actor);
+        assert_eq!(log.label(), "greeter");
+        assert!(log.behaviour().ends_with("greeter"), "{}", log.behaviour());
+        assert_eq!(log.to_string(), format!("[{}/greeter]", actor));
+    })
+}
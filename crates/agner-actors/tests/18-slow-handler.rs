@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use agner_actors::{Context, SpawnOpts, System};
+
+mod common;
+
+async fn sleeper(context: &mut Context<Duration>, _arg: ()) {
+    loop {
+        let sleep_for = context.next_message().await;
+        tokio::time::sleep(sleep_for).await;
+    }
+}
+
+#[test]
+fn max_handler_time_is_reported() {
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let untimed = system.spawn(sleeper, (), Default::default()).await.unwrap();
+        assert_eq!(system.actor_info(untimed).await.unwrap().max_handler_time, None);
+
+        let spawn_opts = SpawnOpts::new().with_slow_handler_threshold(Duration::from_millis(10));
+        let timed = system.spawn(sleeper, (), spawn_opts).await.unwrap();
+        assert_eq!(system.actor_info(timed).await.unwrap().max_handler_time, Some(Duration::ZERO));
+
+        system.send(timed, Duration::from_millis(1)).await;
+        system.send(timed, Duration::from_millis(30)).await;
+        system.send(timed, Duration::ZERO).await;
+
+        let max_handler_time = loop {
+            let info = system.actor_info(timed).await.unwrap();
+            if info.m_queue_len.0 == 0 && info.max_handler_time >= Some(Duration::from_millis(30)) {
+                break info.max_handler_time.unwrap()
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        };
+        assert!(max_handler_time < Duration::from_secs(1));
+    })
+}
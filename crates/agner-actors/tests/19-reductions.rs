@@ -0,0 +1,53 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use agner_actors::{Context, SpawnOpts, System};
+
+const MESSAGES: usize = 1000;
+
+async fn recorder(context: &mut Context<()>, (tag, log): (u8, Arc<Mutex<Vec<u8>>>)) {
+    loop {
+        context.next_message().await;
+        log.lock().unwrap().push(tag);
+    }
+}
+
+/// Flood two actors on a single-threaded runtime, and return the longest run of the messages
+/// handled by the same actor.
+fn longest_run(make_spawn_opts: fn() -> SpawnOpts) -> usize {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .expect("Failed to create tokio-runtime");
+
+    runtime.block_on(async {
+        let system = System::new(Default::default());
+        let log = Arc::new(Mutex::new(Vec::with_capacity(2 * MESSAGES)));
+
+        let mut actors = vec![];
+        for tag in [1, 2] {
+            let args = (tag, log.to_owned());
+            actors.push(system.spawn(recorder, args, make_spawn_opts()).await.unwrap());
+        }
+        for _ in 0..MESSAGES {
+            for &actor in &actors {
+                system.send(actor, ()).await;
+            }
+        }
+        while log.lock().unwrap().len() < 2 * MESSAGES {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        let log = log.lock().unwrap();
+        log.chunk_by(|a, b| a == b).map(<[u8]>::len).max().unwrap_or_default()
+    })
+}
+
+#[test]
+fn reductions_interleave_busy_actors() {
+    let unbudgeted = longest_run(SpawnOpts::new);
+    let budgeted = longest_run(|| SpawnOpts::new().with_reductions(8));
+
+    assert!(budgeted <= 8, "budgeted: {}", budgeted);
+    assert!(unbudgeted > budgeted, "unbudgeted: {}, budgeted: {}", unbudgeted, budgeted);
+}
@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use agner_actors::{Context, SpawnOpts, System, TaskPriority};
+use tokio::sync::{oneshot, Semaphore};
+
+const COUNT: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Inbound {
+    Message,
+    Task,
+}
+
+type Args = (Arc<Semaphore>, oneshot::Sender<()>, oneshot::Sender<Vec<Inbound>>);
+
+async fn recorder(context: &mut Context<Inbound>, (gate, spawned, report_to): Args) {
+    for _ in 0..COUNT {
+        let gate = gate.to_owned();
+        context
+            .future_to_inbox(async move {
+                gate.acquire().await.expect("closed").forget();
+                Inbound::Task
+            })
+            .await;
+    }
+    let _ = spawned.send(());
+
+    let mut received = vec![];
+    for _ in 0..2 * COUNT {
+        received.push(context.next_message().await);
+    }
+    let _ = report_to.send(received);
+    std::future::pending().await
+}
+
+/// Have both the messages and the task outcomes pending at once, and return the order the actor
+/// receives them in.
+fn received_order(task_priority: TaskPriority) -> Vec<Inbound> {
+    // a single thread: the actor does not run until the test yields.
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .expect("Failed to create tokio-runtime");
+
+    runtime.block_on(async {
+        let system = System::new(Default::default());
+        let gate = Arc::new(Semaphore::new(0));
+        let (spawned_tx, spawned_rx) = oneshot::channel();
+        let (report_tx, report_rx) = oneshot::channel();
+        let spawn_opts = SpawnOpts::new().with_task_priority(task_priority);
+        let actor = system
+            .spawn(recorder, (gate.to_owned(), spawned_tx, report_tx), spawn_opts)
+            .await
+            .unwrap();
+        spawned_rx.await.unwrap();
+
+        gate.add_permits(COUNT);
+        for _ in 0..COUNT {
+            system.send(actor, Inbound::Message).await;
+        }
+
+        report_rx.await.unwrap()
+    })
+}
+
+#[test]
+fn task_outcomes_after_messages() {
+    let received = received_order(TaskPriority::AfterMessages);
+    let expected = [[Inbound::Message; COUNT], [Inbound::Task; COUNT]].concat();
+    assert_eq!(received, expected);
+}
+
+#[test]
+fn task_outcomes_interleaved() {
+    let received = received_order(TaskPriority::Interleaved);
+    let expected = [[Inbound::Message, Inbound::Task]; COUNT].concat();
+    assert_eq!(received, expected);
+}
+
+#[test]
+fn ordered_task_outcomes_in_submission_order() {
+    use std::time::Duration;
+
+    use tokio::sync::mpsc;
+
+    type OrderedArgs =
+        (Vec<oneshot::Receiver<()>>, oneshot::Receiver<()>, mpsc::UnboundedSender<usize>);
+
+    /// The outcomes `0..` of the ordered tasks, and `usize::MAX` of the unordered one.
+    async fn recorder(context: &mut Context<usize>, (ordered, unordered, report_to): OrderedArgs) {
+        for (idx, release) in ordered.into_iter().enumerate() {
+            context
+                .future_to_inbox_ordered(async move {
+                    let _ = release.await;
+                    idx
+                })
+                .await;
+        }
+        context
+            .future_to_inbox(async move {
+                let _ = unordered.await;
+                usize::MAX
+            })
+            .await;
+        loop {
+            let _ = report_to.send(context.next_message().await);
+        }
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .expect("Failed to create tokio-runtime");
+
+    runtime.block_on(async {
+        let system = System::new(Default::default());
+        let (release_ordered, ordered): (Vec<_>, Vec<_>) =
+            (0..3).map(|_| oneshot::channel::<()>()).unzip();
+        let (release_unordered, unordered) = oneshot::channel();
+        let (report_tx, mut report_rx) = mpsc::unbounded_channel();
+        system
+            .spawn(recorder, (ordered, unordered, report_tx), Default::default())
+            .await
+            .unwrap();
+
+        // the later ordered tasks complete first: their outcomes are held
+        let mut release_ordered = release_ordered.into_iter();
+        let release_first = release_ordered.next().unwrap();
+        for release in release_ordered {
+            release.send(()).unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(report_rx.try_recv().is_err());
+
+        // the unordered task is not held by the ordered ones
+        release_unordered.send(()).unwrap();
+        assert_eq!(report_rx.recv().await, Some(usize::MAX));
+
+        release_first.send(()).unwrap();
+        for expected in 0..3 {
+            assert_eq!(report_rx.recv().await, Some(expected));
+        }
+    })
+}
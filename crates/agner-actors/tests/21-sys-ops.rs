@@ -0,0 +1,45 @@
+#![cfg(feature = "unstable-sys-ops")]
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use agner_actors::system_ext::{SysOp, SystemExt};
+use agner_actors::{Context, Exit, System};
+
+mod common;
+
+async fn idle(_context: &mut Context<Infallible>, _: ()) {
+    std::future::pending().await
+}
+
+#[test]
+fn sys_ops_affect_a_single_side() {
+    common::run(async {
+        let system = System::new(Default::default());
+        let worker = system.spawn(idle, (), Default::default()).await.unwrap();
+        let peer = system.spawn(idle, (), Default::default()).await.unwrap();
+
+        // not linked: the signal is ignored
+        assert!(system.send_sys_op(worker, SysOp::SigExit(peer, Exit::from_message("1"))).await);
+        assert!(tokio::time::timeout(Duration::from_millis(50), system.wait(worker))
+            .await
+            .is_err());
+
+        // linked, then unlinked: the signal is ignored
+        assert!(system.send_sys_op(worker, SysOp::Link(peer)).await);
+        assert!(system.send_sys_op(worker, SysOp::Unlink(peer)).await);
+        assert!(system.send_sys_op(worker, SysOp::SigExit(peer, Exit::from_message("2"))).await);
+        assert!(tokio::time::timeout(Duration::from_millis(50), system.wait(worker))
+            .await
+            .is_err());
+
+        // linked: the worker exits, but the peer knows nothing of the link
+        assert!(system.send_sys_op(worker, SysOp::Link(peer)).await);
+        assert!(system.send_sys_op(worker, SysOp::SigExit(peer, Exit::from_message("3"))).await);
+        let exit = system.wait(worker).await;
+        assert!(exit.eq_kind(&Exit::linked(peer, Exit::from_message("3"))), "{:?}", exit);
+        assert!(system.actor_info(peer).await.is_some_and(|info| info.links.is_empty()));
+
+        assert!(!system.send_sys_op(worker, SysOp::Link(peer)).await);
+    });
+}
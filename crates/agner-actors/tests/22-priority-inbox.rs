@@ -0,0 +1,147 @@
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use agner_actors::system_error::SysChannelError;
+use agner_actors::{ActorID, Context, Event, Exit, Interceptor, Prioritized, System, SystemConfig};
+use tokio::sync::{mpsc, oneshot};
+
+mod common;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Control {
+    Pause,
+    Resume,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Seen {
+    Control(Control),
+    Message(usize),
+}
+
+async fn worker(
+    context: &mut Context<usize, Control>,
+    (start, report): (oneshot::Receiver<()>, mpsc::UnboundedSender<Seen>),
+) {
+    // everything is queued by the time the worker starts reading
+    start.await.unwrap();
+
+    let first = context.next_control().await;
+    report.send(Seen::Control(first)).unwrap();
+
+    loop {
+        let seen = match context.next_prioritized().await {
+            Prioritized::Control(control) => Seen::Control(control),
+            Prioritized::Event(Event::Message(message)) => Seen::Message(message),
+            Prioritized::Event(Event::Signal(signal)) => panic!("unexpected signal: {:?}", signal),
+        };
+        report.send(seen).unwrap();
+    }
+}
+
+async fn idle(_context: &mut Context<Infallible>, _: ()) {
+    std::future::pending().await
+}
+
+async fn idle_controlled(_context: &mut Context<Infallible, Control>, _: ()) {
+    std::future::pending().await
+}
+
+#[test]
+fn control_messages_overtake_the_inbox() {
+    common::run(async {
+        let system = System::new(Default::default());
+        let (report_tx, mut report_rx) = mpsc::unbounded_channel();
+        let (start_tx, start_rx) = oneshot::channel();
+        let worker = system.spawn(worker, (start_rx, report_tx), Default::default()).await.unwrap();
+
+        for i in 0..5usize {
+            system.send(worker, i).await;
+        }
+        system.send_control(worker, Control::Pause).await.unwrap();
+        system.send_control(worker, Control::Resume).await.unwrap();
+        start_tx.send(()).unwrap();
+
+        let mut seen = vec![];
+        while seen.len() < 7 {
+            seen.push(report_rx.recv().await.unwrap());
+        }
+        assert_eq!(
+            seen,
+            vec![
+                Seen::Control(Control::Pause),
+                Seen::Control(Control::Resume),
+                Seen::Message(0),
+                Seen::Message(1),
+                Seen::Message(2),
+                Seen::Message(3),
+                Seen::Message(4),
+            ]
+        );
+    })
+}
+
+#[test]
+fn send_control_checks_the_type() {
+    common::run(async {
+        let system = System::new(Default::default());
+        let plain = system.spawn(idle, (), Default::default()).await.unwrap();
+        let controlled = system.spawn(idle_controlled, (), Default::default()).await.unwrap();
+
+        assert!(matches!(
+            system.send_control(plain, Control::Pause).await,
+            Err(SysChannelError::InvalidMessageType)
+        ));
+        assert!(matches!(
+            system.send_control(controlled, "pause").await,
+            Err(SysChannelError::InvalidMessageType)
+        ));
+        assert!(system.send_control(controlled, Control::Pause).await.is_ok());
+
+        system.exit(controlled, Exit::kill()).await;
+        system.wait(controlled).await;
+        assert!(system.send_control(controlled, Control::Resume).await.is_err());
+    })
+}
+
+#[test]
+fn control_messages_are_intercepted_and_attributed() {
+    type Observed = (Option<ActorID>, ActorID, &'static str);
+
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<Observed>>);
+    impl Interceptor for Recorder {
+        fn on_send(&self, from: Option<ActorID>, to: ActorID, message_type: &'static str) {
+            self.0.lock().unwrap().push((from, to, message_type));
+        }
+    }
+
+    async fn receiver(
+        context: &mut Context<Infallible, Control>,
+        report: oneshot::Sender<(Control, Option<ActorID>)>,
+    ) {
+        let control = context.next_control().await;
+        let _ = report.send((control, context.sender()));
+        std::future::pending().await
+    }
+
+    async fn sender(context: &mut Context<Infallible>, to: ActorID) {
+        context.send_control(to, Control::Pause).await.unwrap();
+        std::future::pending().await
+    }
+
+    common::run(async {
+        let recorder = Arc::new(Recorder::default());
+        let system = System::new(SystemConfig::default().with_interceptor(recorder.to_owned()));
+
+        let (report_tx, report_rx) = oneshot::channel();
+        let receiver = system.spawn(receiver, report_tx, Default::default()).await.unwrap();
+        let sender = system.spawn(sender, receiver, Default::default()).await.unwrap();
+
+        assert_eq!(report_rx.await.unwrap(), (Control::Pause, Some(sender)));
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![(Some(sender), receiver, std::any::type_name::<Control>())]
+        );
+    })
+}
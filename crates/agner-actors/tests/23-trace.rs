@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use agner_actors::{Context, Event, Exit, System, TraceFlags};
+use agner_utils::std_error_pp::StdErrorPP;
+use tokio::sync::{mpsc, oneshot};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Metadata, Subscriber};
+
+mod common;
+
+/// Reports the fields of the `[trace] ...` events.
+struct TraceRecorder(mpsc::UnboundedSender<Fields>);
+
+impl Subscriber for TraceRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+    fn event(&self, event: &tracing::Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        if fields.0.get("message").is_some_and(|message| message.starts_with("[trace]")) {
+            let _ = self.0.send(fields);
+        }
+    }
+    fn enter(&self, _span: &Id) {}
+    fn exit(&self, _span: &Id) {}
+}
+
+#[derive(Debug, Default)]
+struct Fields(BTreeMap<&'static str, String>);
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name(), value.to_owned());
+    }
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name(), format!("{:?}", value));
+    }
+}
+
+async fn traced(context: &mut Context<&'static str>, ready: oneshot::Sender<()>) {
+    context.enable_inbox_peek().await;
+    let _ = ready.send(());
+    loop {
+        if let Event::Signal(signal) = context.next_event().await {
+            panic!("unexpected signal: {:?}", signal);
+        }
+    }
+}
+
+#[test]
+fn delivered_messages_and_signals_are_traced() {
+    let (trace_tx, mut trace_rx) = mpsc::unbounded_channel();
+    tracing::subscriber::set_global_default(TraceRecorder(trace_tx)).unwrap();
+
+    common::run(async {
+        let system = System::new(Default::default());
+
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let actor = system.spawn(traced, ready_tx, Default::default()).await.unwrap();
+        ready_rx.await.unwrap();
+
+        system.set_trace(actor, TraceFlags { messages: true, signals: true }).await;
+        assert!(system.sync(actor).await);
+        system.send(actor, "hello").await;
+        let traced = trace_rx.recv().await.unwrap();
+        assert_eq!(traced.0["event"], "message-delivered");
+        assert_eq!(traced.0["message"], "[trace] message delivered: \"hello\"");
+
+        // the messages are no longer traced, the signals still are
+        system.set_trace(actor, TraceFlags { messages: false, signals: true }).await;
+        assert!(system.sync(actor).await);
+        system.send(actor, "quiet").await;
+        assert!(system.sync(actor).await);
+        system.exit(actor, Exit::shutdown()).await;
+        system.wait(actor).await;
+
+        let traced = trace_rx.recv().await.unwrap();
+        assert_eq!(traced.0["event"], "exit-signal-delivered", "{:?}", traced);
+        assert_eq!(traced.0["actor"], actor.to_string());
+        assert_eq!(traced.0["from"], actor.to_string());
+        assert_eq!(traced.0["reason"], Exit::shutdown().pp().to_string());
+        assert!(trace_rx.try_recv().is_err());
+    })
+}
@@ -10,8 +10,6 @@ use agner_sup::common::ParentActor;
 
 use futures::StreamExt;
 
-mod exit_reason_serde;
-
 pub fn routes(router: Router) -> Router {
     router
         .route("/actors", get(actors_list))
@@ -49,17 +47,12 @@ async fn actors_actor_info(
 async fn actors_actor_exit(
     Extension(system): Extension<System>,
     Path(actor_id): Path<ActorID>,
-    Json(exit_reason): Json<exit_reason_serde::ExitSerde>,
+    Json(exit_reason): Json<Exit>,
 ) -> impl IntoResponse {
-    let exit_reason: Exit = exit_reason.into();
     system.exit(actor_id, exit_reason).await;
 
     match system.wait(actor_id).timeout(system.config().actor_termination_timeout).await {
-        Ok(exit_reason) => {
-            let exit_reason = exit_reason_serde::ExitSerde::from(exit_reason);
-            let response = Json(exit_reason);
-            (StatusCode::ACCEPTED, response).into_response()
-        },
+        Ok(exit_reason) => (StatusCode::ACCEPTED, Json(exit_reason)).into_response(),
 
         Err(_) => StatusCode::REQUEST_TIMEOUT.into_response(),
     }
@@ -6,6 +6,9 @@ use axum::{Extension, Router, Server};
 mod actors;
 mod system;
 
+pub mod tree;
+pub use tree::{launch, reconcile, LaunchError, ReconcileError};
+
 pub async fn run(system: System, bind_addr: SocketAddr) -> Result<(), BoxError> {
     let router = Router::new();
 
@@ -0,0 +1,450 @@
+//! Declarative Supervision Tree
+//! =====
+//!
+//! A whole supervision tree can be described as a value ([`SupNode`]), and then instantiated with
+//! [`launch`]. Each [`SupNode`] compiles down to a [mixed supervisor](agner_sup::mixed), each
+//! [`UniformNode`] — to a [uniform supervisor](agner_sup::uniform), each [`WorkerNode`] — to a
+//! [mixed child-spec](agner_sup::mixed::MixedChildSpec).
+//!
+//! A child may be given a name: whenever the child (re)starts, the name is
+//! [rebound](agner_actors::System::rebind) to the new actor before the supervisor proceeds (see
+//! [`GenChildSpec::bind_name`](agner_sup::common::GenChildSpec::bind_name)).
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use agner_actors::system_error::{SysRegisterError, SysSpawnError};
+use agner_actors::{Actor, ActorID, Context, Exit, Never, System};
+use agner_sup::common::{GenChildSpec, InitType};
+use agner_sup::mixed::{
+    self, AllForOne, BoxedMixedChildSpec, ChildType, MixedChildSpec, OneForOne, RestForOne,
+    RestartIntensity, SupSpec,
+};
+use agner_sup::uniform::{self, UniformChildSpec};
+
+mod reconcile;
+pub use reconcile::{reconcile, ReconcileError};
+
+#[cfg(test)]
+mod tests;
+
+/// The type identifying the children within a [`SupNode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChildID(pub &'static str);
+
+/// A failure to [`launch`] a tree.
+#[derive(Debug, thiserror::Error)]
+pub enum LaunchError {
+    #[error("Duplicate child-id: {:?}", _0)]
+    DuplicateId(ChildID),
+
+    #[error("Duplicate name: {:?}", _0)]
+    DuplicateName(String),
+
+    #[error("Failed to spawn the root supervisor")]
+    Spawn(#[source] SysSpawnError),
+
+    #[error("Failed to register the root supervisor")]
+    Register(#[source] SysRegisterError),
+}
+
+/// The restart strategy of a [`SupNode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    OneForOne(RestartIntensity<Duration>),
+    AllForOne(RestartIntensity<Duration>),
+    RestForOne(RestartIntensity<Duration>),
+}
+
+/// A node of the tree: either a supervisor, or a worker.
+#[derive(Debug, Clone)]
+pub enum Node {
+    Sup(SupNode),
+    Uniform(UniformNode),
+    Worker(WorkerNode),
+}
+
+/// A supervisor node.
+#[derive(Debug, Clone)]
+pub struct SupNode {
+    strategy: Strategy,
+    name: Option<String>,
+    children: Vec<ChildNode>,
+}
+
+/// A child of a [`SupNode`].
+#[derive(Debug, Clone)]
+pub struct ChildNode {
+    id: ChildID,
+    child_type: ChildType,
+    name: Option<String>,
+    node: Node,
+}
+
+/// A worker node.
+#[derive(Clone)]
+pub struct WorkerNode {
+    behaviour: &'static str,
+    args: Arc<dyn WorkerArgs>,
+    init_type: InitType,
+    make_child_spec: Arc<dyn Fn(ChildID) -> WorkerChildSpec + Send + Sync>,
+}
+
+/// A uniform supervisor node: its children, all alike, are started on demand (see
+/// [`agner_sup::uniform::start_child`]).
+#[derive(Clone)]
+pub struct UniformNode {
+    behaviour: &'static str,
+    init_type: InitType,
+    make_child_spec: Arc<dyn Fn(ChildID) -> WorkerChildSpec + Send + Sync>,
+}
+
+type WorkerChildSpec =
+    Box<dyn FnOnce(ChildType, InitType, Option<String>) -> BoxedMixedChildSpec<ChildID>>;
+
+/// Instantiate the tree, returning the [`ActorID`] of the root supervisor.
+pub async fn launch(system: &System, tree: SupNode) -> Result<ActorID, LaunchError> {
+    tree.validate(&mut Default::default())?;
+
+    let name = tree.name.to_owned();
+    let root = system
+        .spawn(run_sup_node, SharedSupNode::new(tree), Default::default())
+        .await
+        .map_err(LaunchError::Spawn)?;
+    if let Some(name) = name {
+        system.register(&name, root).await.map_err(LaunchError::Register)?;
+    }
+
+    Ok(root)
+}
+
+impl SupNode {
+    pub fn new(strategy: Strategy) -> Self {
+        Self { strategy, name: None, children: Default::default() }
+    }
+
+    /// The name under which the supervisor is registered (only applies to the root of the tree).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn with_child(mut self, child: ChildNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    pub fn children(&self) -> &[ChildNode] {
+        &self.children
+    }
+
+    fn same_as(&self, other: &Self) -> bool {
+        self.strategy == other.strategy &&
+            self.name == other.name &&
+            self.children.len() == other.children.len() &&
+            self.children.iter().zip(other.children.iter()).all(|(l, r)| l.same_as(r))
+    }
+
+    fn validate(&self, names: &mut HashSet<String>) -> Result<(), LaunchError> {
+        let mut ids = HashSet::new();
+        for name in self.name.iter() {
+            if !names.insert(name.to_owned()) {
+                return Err(LaunchError::DuplicateName(name.to_owned()))
+            }
+        }
+        for child in self.children.iter() {
+            if !ids.insert(child.id) {
+                return Err(LaunchError::DuplicateId(child.id))
+            }
+            if let Some(name) = child.name.as_ref() {
+                if !names.insert(name.to_owned()) {
+                    return Err(LaunchError::DuplicateName(name.to_owned()))
+                }
+            }
+            if let Node::Sup(sup) = &child.node {
+                sup.validate(names)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ChildNode {
+    pub fn new(id: impl Into<ChildID>, node: impl Into<Node>) -> Self {
+        Self { id: id.into(), child_type: ChildType::Permanent, name: None, node: node.into() }
+    }
+
+    pub fn with_child_type(mut self, child_type: ChildType) -> Self {
+        self.child_type = child_type;
+        self
+    }
+
+    /// The name to be bound to the child every time it starts.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn id(&self) -> ChildID {
+        self.id
+    }
+
+    pub fn child_type(&self) -> ChildType {
+        self.child_type
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    /// Whether both nodes would produce identical children.
+    fn same_as(&self, other: &Self) -> bool {
+        self.id == other.id &&
+            self.child_type == other.child_type &&
+            self.name == other.name &&
+            match (&self.node, &other.node) {
+                (Node::Sup(left), Node::Sup(right)) => left.same_as(right),
+                (Node::Uniform(left), Node::Uniform(right)) =>
+                    left.behaviour == right.behaviour && left.init_type == right.init_type,
+                (Node::Worker(left), Node::Worker(right)) =>
+                    left.behaviour == right.behaviour &&
+                        left.args.same_as(right.args.as_ref()) &&
+                        left.init_type == right.init_type,
+                _ => false,
+            }
+    }
+
+    fn child_spec(&self) -> BoxedMixedChildSpec<ChildID> {
+        let name = self.name.to_owned();
+        match &self.node {
+            Node::Sup(sup) => {
+                let child_spec = MixedChildSpec::mixed(self.id)
+                    .behaviour(run_sup_node)
+                    .args_clone(SharedSupNode::new(sup.to_owned()))
+                    .child_type(self.child_type)
+                    .init_type(InitType::with_ack());
+                with_name(child_spec, name).into()
+            },
+            Node::Uniform(uniform) =>
+                (uniform.make_child_spec)(self.id)(self.child_type, uniform.init_type, name),
+            Node::Worker(worker) =>
+                (worker.make_child_spec)(self.id)(self.child_type, worker.init_type, name),
+        }
+    }
+}
+
+impl WorkerNode {
+    /// The worker runs the `behaviour` with the `args`.
+    ///
+    /// Upon [`reconcile`], the args are compared by value: the worker is started anew if they
+    /// differ from those of the running one.
+    pub fn new<B, A, M>(behaviour: B, args: A) -> Self
+    where
+        B: for<'a> Actor<'a, A, M>,
+        B: Clone + Unpin + Send + Sync + 'static,
+        A: Clone + PartialEq + fmt::Debug + Unpin + Send + Sync + 'static,
+        M: Unpin + Send + Sync + 'static,
+    {
+        let worker_args = Arc::new(args.to_owned());
+        let make_child_spec = move |id| -> WorkerChildSpec {
+            let behaviour = behaviour.to_owned();
+            let args = args.to_owned();
+            Box::new(move |child_type, init_type, name| {
+                let child_spec = MixedChildSpec::mixed(id)
+                    .behaviour(behaviour)
+                    .args_clone(args)
+                    .child_type(child_type)
+                    .init_type(init_type);
+                with_name(child_spec, name).into()
+            })
+        };
+        Self {
+            behaviour: std::any::type_name::<B>(),
+            args: worker_args,
+            init_type: InitType::no_ack(),
+            make_child_spec: Arc::new(make_child_spec),
+        }
+    }
+
+    pub fn with_init_type(mut self, init_type: impl Into<InitType>) -> Self {
+        self.init_type = init_type.into();
+        self
+    }
+}
+
+impl UniformNode {
+    /// The children run the `behaviour`, each with the arguments passed to
+    /// [`start_child`](agner_sup::uniform::start_child).
+    pub fn new<B, A, M>(behaviour: B) -> Self
+    where
+        B: for<'a> Actor<'a, A, M>,
+        B: Clone + Unpin + Send + Sync + 'static,
+        A: Unpin + Send + Sync + 'static,
+        M: Unpin + Send + Sync + 'static,
+    {
+        let make_child_spec = move |id| -> WorkerChildSpec {
+            let behaviour = behaviour.to_owned();
+            Box::new(move |child_type, init_type, name| {
+                let sup_spec = move || {
+                    uniform::SupSpec::new(
+                        UniformChildSpec::uniform()
+                            .behaviour(behaviour.to_owned())
+                            .args_call1(|args: A| args)
+                            .init_type(init_type),
+                    )
+                };
+                let child_spec = MixedChildSpec::mixed(id)
+                    .behaviour(uniform::run)
+                    .args_call0(sup_spec)
+                    .child_type(child_type)
+                    .init_type(InitType::with_ack());
+                with_name(child_spec, name).into()
+            })
+        };
+        Self {
+            behaviour: std::any::type_name::<B>(),
+            init_type: InitType::no_ack(),
+            make_child_spec: Arc::new(make_child_spec),
+        }
+    }
+
+    /// The init-type of the children.
+    pub fn with_init_type(mut self, init_type: impl Into<InitType>) -> Self {
+        self.init_type = init_type.into();
+        self
+    }
+}
+
+fn with_name<B, A, M, X>(
+    child_spec: GenChildSpec<B, A, M, X>,
+    name: Option<String>,
+) -> GenChildSpec<B, A, M, X> {
+    match name {
+        Some(name) => child_spec.bind_name(name),
+        None => child_spec,
+    }
+}
+
+impl From<&'static str> for ChildID {
+    fn from(id: &'static str) -> Self {
+        Self(id)
+    }
+}
+
+impl From<SupNode> for Node {
+    fn from(sup: SupNode) -> Self {
+        Self::Sup(sup)
+    }
+}
+
+impl From<UniformNode> for Node {
+    fn from(uniform: UniformNode) -> Self {
+        Self::Uniform(uniform)
+    }
+}
+
+impl From<WorkerNode> for Node {
+    fn from(worker: WorkerNode) -> Self {
+        Self::Worker(worker)
+    }
+}
+
+/// The arguments of a [`WorkerNode`], comparable regardless of their type.
+trait WorkerArgs: fmt::Debug + Send + Sync + 'static {
+    fn as_any(&self) -> &dyn Any;
+    fn same_as(&self, other: &dyn WorkerArgs) -> bool;
+}
+
+impl<A> WorkerArgs for A
+where
+    A: PartialEq + fmt::Debug + Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn same_as(&self, other: &dyn WorkerArgs) -> bool {
+        other.as_any().downcast_ref::<A>() == Some(self)
+    }
+}
+
+impl fmt::Debug for WorkerNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerNode")
+            .field("behaviour", &self.behaviour)
+            .field("args", &self.args)
+            .field("init_type", &self.init_type)
+            .finish()
+    }
+}
+
+impl fmt::Debug for UniformNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UniformNode")
+            .field("behaviour", &self.behaviour)
+            .field("init_type", &self.init_type)
+            .finish()
+    }
+}
+
+/// The spec of a running supervisor node.
+///
+/// It is shared between the supervisor (which reads it upon each start) and [`reconcile`], which
+/// updates it.
+#[derive(Debug, Clone)]
+struct SharedSupNode(Arc<Mutex<SupNode>>);
+
+impl SharedSupNode {
+    fn new(node: SupNode) -> Self {
+        Self(Arc::new(Mutex::new(node)))
+    }
+    fn get(&self) -> SupNode {
+        self.0.lock().expect("poisoned").to_owned()
+    }
+    fn set(&self, node: SupNode) {
+        *self.0.lock().expect("poisoned") = node;
+    }
+}
+
+async fn run_sup_node(
+    context: &mut Context<mixed::Message<ChildID>>,
+    shared: SharedSupNode,
+) -> Result<Never, Exit> {
+    context.system().put_data(context.actor_id(), shared.to_owned()).await;
+
+    let node = shared.get();
+    let children = node.children.iter().map(ChildNode::child_spec).collect::<Vec<_>>();
+
+    fn sup_spec<RS>(
+        restart_strategy: RS,
+        children: Vec<BoxedMixedChildSpec<ChildID>>,
+    ) -> SupSpec<ChildID, RS> {
+        SupSpec {
+            restart_strategy,
+            children,
+            lifecycle_hooks: vec![],
+            auto_shutdown: Default::default(),
+            shutdown_concurrency: 1,
+            start_arg: (),
+        }
+    }
+
+    match node.strategy {
+        Strategy::OneForOne(intensity) =>
+            mixed::run(context, sup_spec(OneForOne::new(intensity), children)).await,
+        Strategy::AllForOne(intensity) =>
+            mixed::run(context, sup_spec(AllForOne::new(intensity), children)).await,
+        Strategy::RestForOne(intensity) =>
+            mixed::run(context, sup_spec(RestForOne::new(intensity), children)).await,
+    }
+}
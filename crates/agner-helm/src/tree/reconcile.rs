@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use agner_actors::system_error::SysRegisterError;
+use agner_actors::{ActorID, NameStoreError, System};
+use agner_sup::mixed::{self, SupervisorError};
+use futures::future::BoxFuture;
+
+use super::{ChildID, ChildNode, LaunchError, Node, SharedSupNode, SupNode};
+
+/// A failure to [`reconcile`] a running tree.
+#[derive(Debug, thiserror::Error)]
+pub enum ReconcileError {
+    #[error("Invalid tree")]
+    Invalid(#[source] LaunchError),
+
+    #[error("{} is not a supervisor launched by helm", _0)]
+    NotATree(ActorID),
+
+    #[error("The restart strategy of the root supervisor cannot be changed")]
+    StrategyChanged,
+
+    #[error("Supervisor request failed")]
+    Supervisor(#[source] SupervisorError),
+
+    #[error("Failed to register the root supervisor")]
+    Register(#[source] SysRegisterError),
+
+    #[error("Failed to unregister the root supervisor")]
+    Unregister(#[source] NameStoreError),
+}
+
+/// Bring the running tree (previously [launched](crate::tree::launch)) in accordance with the
+/// desired `tree`.
+///
+/// The children of each supervisor are matched by their ids:
+/// - the children absent in the desired tree are terminated;
+/// - the children absent in the running tree are started (appended after the existing ones);
+/// - the children whose spec has changed are terminated and started anew with the new spec.
+///
+/// A nested supervisor whose own restart strategy, child-type and name are unchanged is reconciled
+/// in place (recursively), without restarting it. If either of those has changed, the nested
+/// supervisor is restarted along with its whole sub-tree. The restart strategy of the root
+/// supervisor cannot be changed: [`ReconcileError::StrategyChanged`] is returned.
+///
+/// If the reconciliation fails half-way, the tree remains partially updated; the running
+/// supervisors will use the desired specs upon their subsequent restarts.
+pub async fn reconcile(
+    system: &System,
+    root: ActorID,
+    tree: SupNode,
+) -> Result<(), ReconcileError> {
+    tree.validate(&mut Default::default()).map_err(ReconcileError::Invalid)?;
+
+    let current = shared_sup_node(system, root).await?.get();
+    if current.strategy != tree.strategy {
+        return Err(ReconcileError::StrategyChanged)
+    }
+    if current.name != tree.name {
+        if let Some(old_name) = current.name.as_ref() {
+            if system.try_whereis(old_name).await.map_err(ReconcileError::Unregister)? == Some(root)
+            {
+                system.unregister(old_name).await.map_err(ReconcileError::Unregister)?;
+            }
+        }
+        if let Some(new_name) = tree.name.as_ref() {
+            system.register(new_name, root).await.map_err(ReconcileError::Register)?;
+        }
+    }
+
+    reconcile_sup(system, root, tree).await
+}
+
+fn reconcile_sup(
+    system: &System,
+    sup: ActorID,
+    desired: SupNode,
+) -> BoxFuture<'_, Result<(), ReconcileError>> {
+    Box::pin(async move {
+        let shared = shared_sup_node(system, sup).await?;
+        let current = shared.get();
+        let running = mixed::which_children::<ChildID>(system, sup)
+            .await
+            .map_err(ReconcileError::Supervisor)?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        shared.set(desired.to_owned());
+
+        for child in current.children.iter() {
+            if !desired.children.iter().any(|c| c.id == child.id) {
+                tracing::debug!("[{}] removing child {:?}", sup, child.id);
+                terminate_child(system, sup, child.id).await?;
+            }
+        }
+
+        for child in desired.children.iter() {
+            let Some(current_child) = current.children.iter().find(|c| c.id == child.id) else {
+                tracing::debug!("[{}] adding child {:?}", sup, child.id);
+                start_child(system, sup, child).await?;
+                continue
+            };
+            if current_child.same_as(child) {
+                continue
+            }
+
+            match (&current_child.node, &child.node, running.get(&child.id)) {
+                (Node::Sup(current_sup), Node::Sup(desired_sup), Some(nested))
+                    if current_child.child_type == child.child_type &&
+                        current_child.name == child.name &&
+                        current_sup.strategy == desired_sup.strategy =>
+                {
+                    tracing::debug!("[{}] reconciling child {:?} in place", sup, child.id);
+                    reconcile_sup(system, *nested, desired_sup.to_owned()).await?;
+                },
+                _ => {
+                    tracing::debug!("[{}] replacing child {:?}", sup, child.id);
+                    terminate_child(system, sup, child.id).await?;
+                    start_child(system, sup, child).await?;
+                },
+            }
+        }
+
+        Ok(())
+    })
+}
+
+async fn shared_sup_node(system: &System, sup: ActorID) -> Result<SharedSupNode, ReconcileError> {
+    system.get_data::<SharedSupNode>(sup).await.ok_or(ReconcileError::NotATree(sup))
+}
+
+async fn terminate_child(
+    system: &System,
+    sup: ActorID,
+    child_id: ChildID,
+) -> Result<(), ReconcileError> {
+    mixed::terminate_child(system, sup, child_id)
+        .await
+        .map_err(ReconcileError::Supervisor)?;
+    Ok(())
+}
+
+async fn start_child(
+    system: &System,
+    sup: ActorID,
+    child: &ChildNode,
+) -> Result<(), ReconcileError> {
+    mixed::start_child(system, sup, child.child_spec())
+        .await
+        .map_err(ReconcileError::Supervisor)?;
+    Ok(())
+}
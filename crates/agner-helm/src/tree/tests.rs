@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::time::Duration;
+
+use agner_actors::{Context, Exit, System};
+use agner_sup::mixed::{self, ChildType, RestartIntensity};
+use agner_sup::uniform;
+use tokio::sync::mpsc;
+
+use super::*;
+
+async fn worker(_context: &mut Context<Infallible>, _arg: ()) {
+    std::future::pending().await
+}
+
+fn intensity() -> RestartIntensity<Duration> {
+    RestartIntensity::new(5, Duration::from_secs(30))
+}
+
+/// Reports the started workers (the args of a [`WorkerNode`] are compared upon
+/// [`reconcile`]: the same channel is the same args).
+#[derive(Debug, Clone)]
+struct Started(mpsc::UnboundedSender<(&'static str, ActorID)>);
+
+impl PartialEq for Started {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.same_channel(&other.0)
+    }
+}
+
+async fn reporting_worker(
+    context: &mut Context<Infallible>,
+    (tag, started): (&'static str, Started),
+) {
+    let _ = started.0.send((tag, context.actor_id()));
+    std::future::pending().await
+}
+
+async fn connection(_context: &mut Context<Infallible>, _peer: &'static str) {
+    std::future::pending().await
+}
+
+/// The running children of the supervisor.
+///
+/// The supervisor replies once it is done starting the current child, hence once the child's
+/// name is bound.
+async fn children_of(system: &System, sup: ActorID) -> HashMap<&'static str, ActorID> {
+    mixed::which_children::<ChildID>(system, sup)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id, actor_id)| (id.0, actor_id))
+        .collect()
+}
+
+#[tokio::test]
+async fn launch_a_tree_with_names() {
+    let (started_tx, mut started_rx) = mpsc::unbounded_channel();
+    let worker = |tag| WorkerNode::new(reporting_worker, (tag, Started(started_tx.to_owned())));
+
+    let tree = SupNode::new(Strategy::OneForOne(intensity()))
+        .with_name("top-sup")
+        .with_child(ChildNode::new("room", worker("room")).with_name("room"))
+        .with_child(ChildNode::new("conn-sup", UniformNode::new(connection)).with_name("conn-sup"))
+        .with_child(
+            ChildNode::new(
+                "acceptors",
+                SupNode::new(Strategy::AllForOne(intensity())).with_child(
+                    ChildNode::new("acceptor", worker("acceptor"))
+                        .with_child_type(ChildType::Transient)
+                        .with_name("acceptor"),
+                ),
+            )
+            .with_name("acceptors"),
+        );
+
+    let system = System::new(Default::default());
+    let top_sup = launch(&system, tree).await.unwrap();
+    assert_eq!(system.whereis("top-sup").await, Some(top_sup));
+
+    let mut started = HashMap::new();
+    while started.len() < 2 {
+        let (tag, actor_id) = started_rx.recv().await.unwrap();
+        started.insert(tag, actor_id);
+    }
+    let children = children_of(&system, top_sup).await;
+    let acceptors = children_of(&system, children["acceptors"]).await;
+
+    let room = started["room"];
+    let acceptor = started["acceptor"];
+    assert_eq!(children["room"], room);
+    assert_eq!(acceptors["acceptor"], acceptor);
+    assert_eq!(system.whereis("room").await, Some(room));
+    assert_eq!(system.whereis("conn-sup").await, Some(children["conn-sup"]));
+    assert_eq!(system.whereis("acceptors").await, Some(children["acceptors"]));
+    assert_eq!(system.whereis("acceptor").await, Some(acceptor));
+
+    // the uniform supervisor starts its children on demand
+    let conn_sup = children["conn-sup"];
+    let conn = uniform::start_child(&system, conn_sup, "alice").await.unwrap();
+    assert_eq!(system.actor_info(conn).await.unwrap().parent, Some(conn_sup));
+
+    // the name is never left bound to the terminated child
+    system.exit(room, Exit::from_message("oops")).await;
+    system.wait(room).await;
+    assert_ne!(system.whereis("room").await, Some(room));
+    let (tag, restarted) = started_rx.recv().await.unwrap();
+    assert_eq!(tag, "room");
+    assert_eq!(children_of(&system, top_sup).await["room"], restarted);
+    assert_eq!(system.whereis("room").await, Some(restarted));
+
+    system.exit(top_sup, Exit::shutdown()).await;
+    system.wait(top_sup).await;
+    assert!(system.wait(acceptor).await.is_shutdown());
+    assert!(system.wait(conn).await.is_shutdown());
+}
+
+#[tokio::test]
+async fn duplicate_ids_are_rejected() {
+    let tree = SupNode::new(Strategy::OneForOne(intensity()))
+        .with_child(ChildNode::new("worker", WorkerNode::new(worker, ())))
+        .with_child(ChildNode::new("worker", WorkerNode::new(worker, ())));
+
+    let system = System::new(Default::default());
+    assert!(matches!(
+        launch(&system, tree).await,
+        Err(LaunchError::DuplicateId(ChildID("worker")))
+    ));
+}
+
+#[tokio::test]
+async fn reconcile_applies_the_difference() {
+    async fn named_worker(_context: &mut Context<Infallible>, _name: &'static str) {
+        std::future::pending().await
+    }
+
+    let nested = |children: &[&'static str]| {
+        children.iter().fold(SupNode::new(Strategy::OneForOne(intensity())), |sup, id| {
+            sup.with_child(ChildNode::new(*id, WorkerNode::new(named_worker, *id)))
+        })
+    };
+
+    let tree = SupNode::new(Strategy::OneForOne(intensity()))
+        .with_child(ChildNode::new("removed", WorkerNode::new(named_worker, "removed")))
+        .with_child(ChildNode::new("kept", WorkerNode::new(named_worker, "kept")))
+        .with_child(ChildNode::new("changed", WorkerNode::new(named_worker, "before")))
+        .with_child(ChildNode::new("nested", nested(&["one"])));
+
+    let system = System::new(Default::default());
+    let root = launch(&system, tree).await.unwrap();
+
+    let children = |sup, count| {
+        let system = system.to_owned();
+        async move {
+            loop {
+                let children = mixed::which_children::<ChildID>(&system, sup).await.unwrap();
+                if children.len() == count {
+                    break children
+                        .into_iter()
+                        .map(|(id, actor_id)| (id.0, actor_id))
+                        .collect::<HashMap<_, _>>()
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+    };
+
+    let before = children(root, 4).await;
+    let nested_before = children(before["nested"], 1).await;
+
+    let tree = SupNode::new(Strategy::OneForOne(intensity()))
+        .with_child(ChildNode::new("kept", WorkerNode::new(named_worker, "kept")))
+        .with_child(ChildNode::new("changed", WorkerNode::new(named_worker, "after")))
+        .with_child(ChildNode::new("nested", nested(&["one", "two"])))
+        .with_child(ChildNode::new("added", WorkerNode::new(named_worker, "added")));
+    reconcile(&system, root, tree).await.unwrap();
+
+    let after = children(root, 4).await;
+    let nested_after = children(after["nested"], 2).await;
+
+    assert!(!after.contains_key("removed"));
+    assert!(system.wait(before["removed"]).await.is_shutdown());
+    assert_eq!(after["kept"], before["kept"]);
+    assert_ne!(after["changed"], before["changed"]);
+    assert_eq!(after["nested"], before["nested"]);
+    assert_eq!(nested_after["one"], nested_before["one"]);
+    assert!(nested_after.contains_key("two"));
+
+    let tree = SupNode::new(Strategy::AllForOne(intensity()));
+    assert!(matches!(reconcile(&system, root, tree).await, Err(ReconcileError::StrategyChanged)));
+}
@@ -1,47 +1,126 @@
 use agner_utils::result_err_flatten::ResultErrFlattenIn;
+use std::any::{Any, TypeId};
 use std::future::Future;
+use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use tokio::sync::oneshot;
 
 use agner_actors::{ActorID, Exit};
 
+type Acked = Result<(ActorID, Box<dyn Any + Send>), Exit>;
+
 pub fn new() -> (InitAckTx, InitAckRx) {
+    with_payload()
+}
+
+/// Same as [`new`], but the successful ack carries a `T` payload along with the actor-id (e.g. the
+/// address the actor has bound to).
+pub fn with_payload<T>() -> (InitAckTx, InitAckRx<T>)
+where
+    T: Send + 'static,
+{
     let (tx, rx) = oneshot::channel();
-    (InitAckTx(tx), InitAckRx(rx))
+    let tx = InitAckTx {
+        tx,
+        payload_type_id: TypeId::of::<T>(),
+        payload_type_name: std::any::type_name::<T>(),
+    };
+    (tx, InitAckRx(rx, PhantomData))
+}
+
+/// The payload of the ack is not of the type expected by the party starting the actor.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Init-ack payload type mismatch [expected: {}; got: {}]", expected, got)]
+pub struct PayloadTypeMismatch {
+    pub expected: &'static str,
+    pub got: &'static str,
 }
 
 #[derive(Debug)]
-pub struct InitAckTx(oneshot::Sender<Result<ActorID, Exit>>);
+pub struct InitAckTx {
+    tx: oneshot::Sender<Acked>,
+    payload_type_id: TypeId,
+    payload_type_name: &'static str,
+}
 
 #[derive(Debug)]
 #[pin_project::pin_project]
-pub struct InitAckRx(#[pin] oneshot::Receiver<Result<ActorID, Exit>>);
+pub struct InitAckRx<T = ()>(#[pin] oneshot::Receiver<Acked>, PhantomData<fn() -> T>);
 
 impl InitAckTx {
     pub fn ok(self, actor_id: ActorID) {
-        let _ = self.0.send(Ok(actor_id));
+        self.ok_with(actor_id, ())
+    }
+
+    pub fn ack(self, result: Result<ActorID, impl Into<Exit>>) {
+        self.ack_with(result.map(|actor_id| (actor_id, ())))
+    }
+
+    /// Acknowledge the successful initialization with a `payload`.
+    ///
+    /// Should the party starting the actor expect a payload of another type, it receives a
+    /// [`PayloadTypeMismatch`] error instead.
+    pub fn ok_with<T>(self, actor_id: ActorID, payload: T)
+    where
+        T: Send + 'static,
+    {
+        if TypeId::of::<T>() == self.payload_type_id {
+            let _ = self.tx.send(Ok((actor_id, Box::new(payload))));
+        } else {
+            let mismatch = PayloadTypeMismatch {
+                expected: self.payload_type_name,
+                got: std::any::type_name::<T>(),
+            };
+            self.err(Exit::custom(mismatch))
+        }
     }
 
     pub fn err(self, reason: impl Into<Exit>) {
-        let _ = self.0.send(Err(reason.into()));
+        let _ = self.tx.send(Err(reason.into()));
     }
 
-    pub fn ack(self, result: Result<ActorID, impl Into<Exit>>) {
+    pub fn ack_with<T>(self, result: Result<(ActorID, T), impl Into<Exit>>)
+    where
+        T: Send + 'static,
+    {
         match result {
-            Ok(actor_id) => self.ok(actor_id),
+            Ok((actor_id, payload)) => self.ok_with(actor_id, payload),
             Err(err) => self.err(err),
         }
     }
 }
 
+impl<T: 'static> InitAckRx<T> {
+    /// Wait for the ack, and return the actor-id along with the payload.
+    pub async fn recv(self) -> Result<(ActorID, T), Exit> {
+        self.0
+            .await
+            .ok()
+            .ok_or_else(Exit::no_actor)
+            .err_flatten_in()
+            .map(downcast_payload)
+    }
+}
+
 impl Future for InitAckRx {
     type Output = Result<ActorID, Exit>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.project();
         let out = futures::ready!(this.0.poll(cx)).ok();
-        let out = out.ok_or_else(Exit::no_actor).err_flatten_in();
+        let out = out
+            .ok_or_else(Exit::no_actor)
+            .err_flatten_in()
+            .map(downcast_payload::<()>)
+            .map(|(actor_id, ())| actor_id);
         Poll::Ready(out)
     }
 }
+
+fn downcast_payload<T: 'static>(
+    (actor_id, payload): (ActorID, Box<dyn Any + Send>),
+) -> (ActorID, T) {
+    let payload = payload.downcast().expect("The payload does not match the type-id.");
+    (actor_id, *payload)
+}
@@ -3,6 +3,12 @@ use agner_actors::{ActorID, Exit, Never};
 use crate::channel::InitAckTx;
 
 pub trait ContextInitAckExt {
+    /// Report the outcome of the initialization to the party starting the actor.
+    ///
+    /// The ack does not let any message in: the messages sent to the actor once it has acked are
+    /// queued until the behaviour receives from the inbox (e.g. via `Context::next_event`). Hence
+    /// the setup performed between the ack and the first receive (registering in a group, arming
+    /// a timer) is complete by the time the first message is handled.
     fn init_ack<E>(&mut self, result: Result<ActorID, E>)
     where
         E: Into<Exit>;
@@ -15,9 +21,34 @@ pub trait ContextInitAckExt {
     {
         self.init_ack(Err(err))
     }
+
+    /// Same as [`init_ack`](Self::init_ack), but the successful ack carries a `T` payload.
+    ///
+    /// The party starting the actor is expected to wait for a `T` payload (see
+    /// [`new_channel_with_payload`](crate::new_channel_with_payload)): should it expect another
+    /// type, it receives a [`PayloadTypeMismatch`](crate::PayloadTypeMismatch) error instead.
+    fn init_ack_with<T, E>(&mut self, result: Result<(ActorID, T), E>)
+    where
+        T: Send + 'static,
+        E: Into<Exit>;
+
+    fn init_ack_ok_with<T>(&mut self, actor_id_opt: Option<ActorID>, payload: T)
+    where
+        T: Send + 'static;
+
+    /// Acknowledge the successful initialization, then run `f` over the context: `f` is done
+    /// before any message is handled (see [`init_ack`](Self::init_ack)).
+    fn init_ack_then<F, T>(&mut self, actor_id_opt: Option<ActorID>, f: F) -> T
+    where
+        Self: Sized,
+        F: FnOnce(&mut Self) -> T,
+    {
+        self.init_ack_ok(actor_id_opt);
+        f(self)
+    }
 }
 
-impl<M> ContextInitAckExt for agner_actors::Context<M> {
+impl<M, C> ContextInitAckExt for agner_actors::Context<M, C> {
     fn init_ack<E>(&mut self, result: Result<ActorID, E>)
     where
         E: Into<Exit>,
@@ -31,4 +62,22 @@ impl<M> ContextInitAckExt for agner_actors::Context<M> {
         let actor_id = actor_id_opt.unwrap_or_else(|| self.actor_id());
         self.init_ack::<Never>(Ok(actor_id))
     }
+
+    fn init_ack_with<T, E>(&mut self, result: Result<(ActorID, T), E>)
+    where
+        T: Send + 'static,
+        E: Into<Exit>,
+    {
+        if let Some(init_ack_tx) = self.take::<InitAckTx>() {
+            init_ack_tx.ack_with(result)
+        }
+    }
+
+    fn init_ack_ok_with<T>(&mut self, actor_id_opt: Option<ActorID>, payload: T)
+    where
+        T: Send + 'static,
+    {
+        let actor_id = actor_id_opt.unwrap_or_else(|| self.actor_id());
+        self.init_ack_with::<T, Never>(Ok((actor_id, payload)))
+    }
 }
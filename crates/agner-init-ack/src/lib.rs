@@ -1,5 +1,8 @@
 mod channel;
-pub use channel::{new as new_channel, InitAckRx, InitAckTx};
+pub use channel::{
+    new as new_channel, with_payload as new_channel_with_payload, InitAckRx, InitAckTx,
+    PayloadTypeMismatch,
+};
 
 mod context_ext;
 pub use context_ext::ContextInitAckExt;
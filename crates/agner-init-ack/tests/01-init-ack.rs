@@ -0,0 +1,108 @@
+use agner_actors::{Context, Exit, Never, SpawnOpts, System};
+use agner_init_ack::{ContextInitAckExt, PayloadTypeMismatch};
+use tokio::sync::{mpsc, oneshot};
+
+async fn bound_to(context: &mut Context<Never>, port: u16) {
+    context.init_ack_ok_with(None, format!("127.0.0.1:{}", port));
+    std::future::pending().await
+}
+
+async fn plain(context: &mut Context<Never>, (): ()) {
+    context.init_ack_ok(None);
+    std::future::pending().await
+}
+
+async fn failing(context: &mut Context<Never>, (): ()) {
+    context.init_ack_err(Exit::from_message("failed to bind"));
+    std::future::pending().await
+}
+
+#[tokio::test]
+async fn ack_carries_the_payload() {
+    let system = System::new(Default::default());
+
+    let (init_ack_tx, init_ack_rx) = agner_init_ack::new_channel_with_payload::<String>();
+    let spawn_opts = SpawnOpts::new().with_data(init_ack_tx);
+    let actor_id = system.spawn(bound_to, 8080, spawn_opts).await.unwrap();
+    let (acked_id, bound_to) = init_ack_rx.recv().await.unwrap();
+    assert_eq!(acked_id, actor_id);
+    assert_eq!(bound_to, "127.0.0.1:8080");
+
+    // the tx dropped without an ack
+    let (init_ack_tx, init_ack_rx) = agner_init_ack::new_channel_with_payload::<String>();
+    std::mem::drop(init_ack_tx);
+    assert!(init_ack_rx.recv().await.unwrap_err().is_no_actor());
+}
+
+#[tokio::test]
+async fn payload_type_mismatch_is_reported() {
+    let system = System::new(Default::default());
+
+    // the starter expects a payload, the actor acks without one
+    let (init_ack_tx, init_ack_rx) = agner_init_ack::new_channel_with_payload::<String>();
+    system.spawn(plain, (), SpawnOpts::new().with_data(init_ack_tx)).await.unwrap();
+    let reason = init_ack_rx.recv().await.unwrap_err();
+    let mismatch = reason.source_downcast_ref::<PayloadTypeMismatch>().unwrap();
+    assert_eq!(mismatch.expected, std::any::type_name::<String>());
+    assert_eq!(mismatch.got, std::any::type_name::<()>());
+
+    // the starter expects no payload, the actor acks with one
+    let (init_ack_tx, init_ack_rx) = agner_init_ack::new_channel();
+    system
+        .spawn(bound_to, 8080, SpawnOpts::new().with_data(init_ack_tx))
+        .await
+        .unwrap();
+    let reason = init_ack_rx.await.unwrap_err();
+    let mismatch = reason.source_downcast_ref::<PayloadTypeMismatch>().unwrap();
+    assert_eq!(mismatch.expected, std::any::type_name::<()>());
+    assert_eq!(mismatch.got, std::any::type_name::<String>());
+}
+
+#[tokio::test]
+async fn init_error_reaches_a_typed_starter() {
+    let system = System::new(Default::default());
+
+    let (init_ack_tx, init_ack_rx) = agner_init_ack::new_channel_with_payload::<String>();
+    system
+        .spawn(failing, (), SpawnOpts::new().with_data(init_ack_tx))
+        .await
+        .unwrap();
+    let reason = init_ack_rx.recv().await.unwrap_err();
+    assert_eq!(reason.to_string(), Exit::from_message("failed to bind").to_string());
+}
+
+#[tokio::test]
+async fn setup_after_the_ack_precedes_the_messages() {
+    async fn actor(
+        context: &mut Context<&'static str>,
+        (setup_done, log): (oneshot::Receiver<()>, mpsc::UnboundedSender<&'static str>),
+    ) {
+        context.init_ack_then(None, |_context| log.send("setup").unwrap());
+        setup_done.await.unwrap();
+        log.send("async setup").unwrap();
+
+        loop {
+            let message = context.next_message().await;
+            log.send(message).unwrap();
+        }
+    }
+
+    let system = System::new(Default::default());
+
+    let (setup_done_tx, setup_done_rx) = oneshot::channel();
+    let (log_tx, mut log_rx) = mpsc::unbounded_channel();
+    let (init_ack_tx, init_ack_rx) = agner_init_ack::new_channel();
+    let spawn_opts = SpawnOpts::new().with_data(init_ack_tx);
+    system.spawn(actor, (setup_done_rx, log_tx), spawn_opts).await.unwrap();
+    let actor_id = init_ack_rx.await.unwrap();
+
+    // the message sent right upon the ack is handled after the setup
+    system.send(actor_id, "message").await;
+    setup_done_tx.send(()).unwrap();
+
+    let mut log = vec![];
+    while log.len() < 3 {
+        log.push(log_rx.recv().await.unwrap());
+    }
+    assert_eq!(log, ["setup", "async setup", "message"]);
+}
@@ -1,5 +1,8 @@
 mod reg;
-pub use reg::{new, RegGuard, RegRx, RegTx};
+pub use reg::{new, NotReady, RegGuard, RegRx, RegTx};
+
+mod names;
+pub use names::{wait_all_ready, NamesNotReady};
 
 #[cfg(test)]
 mod tests;
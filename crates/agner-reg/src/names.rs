@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use agner_actors::{ActorID, System};
+use agner_utils::future_timeout_ext::FutureTimeoutExt;
+use futures::{stream, StreamExt};
+
+/// Not all of the names have been bound in time (see [`wait_all_ready`]).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Timed out waiting for the names to be bound: {:?}", unbound)]
+pub struct NamesNotReady {
+    /// The names that were still unbound by the timeout.
+    pub unbound: Vec<String>,
+}
+
+/// Wait until each of the `names` is [registered](agner_actors::System::register) in the
+/// `system`, but no longer than `timeout`.
+///
+/// Returns the actors bound to the names, as of the moment the last of them got bound.
+pub async fn wait_all_ready(
+    system: &System,
+    names: &[&str],
+    timeout: Duration,
+) -> Result<HashMap<String, ActorID>, NamesNotReady> {
+    let mut watches = Vec::with_capacity(names.len());
+    for (idx, name) in names.iter().enumerate() {
+        let watch = system.watch_name(name).await;
+        watches.push(watch.map(move |bound| (idx, bound)).boxed());
+    }
+
+    let mut bound = vec![None; names.len()];
+    let all_bound = async {
+        let mut changes = stream::select_all(watches);
+        while !bound.iter().all(Option::is_some) {
+            let Some((idx, actor_id)) = changes.next().await else { return false };
+            bound[idx] = actor_id;
+        }
+        true
+    };
+
+    if matches!(all_bound.timeout(timeout).await, Ok(true)) {
+        Ok(names
+            .iter()
+            .map(|name| name.to_string())
+            .zip(bound.into_iter().flatten())
+            .collect())
+    } else {
+        let unbound = names
+            .iter()
+            .zip(bound)
+            .filter(|(_, actor_id)| actor_id.is_none())
+            .map(|(name, _)| name.to_string())
+            .collect();
+        Err(NamesNotReady { unbound })
+    }
+}
@@ -1,6 +1,8 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use agner_actors::ActorID;
+use agner_utils::future_timeout_ext::FutureTimeoutExt;
 use tokio::sync::watch;
 
 pub fn new() -> (RegTx, RegRx) {
@@ -16,6 +18,15 @@ pub struct RegRx(watch::Receiver<State>);
 #[derive(Debug, Clone)]
 pub struct RegTx(RegTxInner);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum NotReady {
+    #[error("Timed out waiting for the registration")]
+    Timeout,
+
+    #[error("The registration has been closed")]
+    Closed,
+}
+
 #[derive(Debug)]
 pub struct RegGuard {
     tx_inner: RegTxInner,
@@ -26,6 +37,11 @@ impl RegTx {
     pub fn register(&self, actor_id: ActorID) -> RegGuard {
         RegGuard::new(Arc::clone(&self.0), actor_id)
     }
+
+    /// Whether both `RegTx` are the clones of the same one (i.e. register to the same `RegRx`).
+    pub fn same_as(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
 }
 
 impl RegRx {
@@ -45,6 +61,15 @@ impl RegRx {
             }
         }
     }
+
+    /// Wait until an actor is registered, but no longer than `timeout`.
+    pub async fn wait_ready(&self, timeout: Duration) -> Result<ActorID, NotReady> {
+        self.wait()
+            .timeout(timeout)
+            .await
+            .map_err(|_elapsed| NotReady::Timeout)?
+            .ok_or(NotReady::Closed)
+    }
 }
 
 type State = Option<ActorID>;
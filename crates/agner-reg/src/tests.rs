@@ -1,5 +1,9 @@
+use std::time::Duration;
+
 use agner_actors::ActorID;
 
+use crate::NotReady;
+
 #[tokio::test]
 async fn happy_case() {
     let id_1: ActorID = "1.0.0".parse().unwrap();
@@ -24,3 +28,62 @@ async fn happy_case() {
     std::mem::drop(tx);
     assert!(rx.wait().await.is_none());
 }
+
+#[tokio::test]
+async fn wait_ready() {
+    let id: ActorID = "1.0.0".parse().unwrap();
+    let timeout = Duration::from_millis(100);
+
+    let (tx, rx) = super::new();
+    assert_eq!(rx.wait_ready(timeout).await, Err(NotReady::Timeout));
+
+    let registering = {
+        let tx = tx.to_owned();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            tx.register(id)
+        })
+    };
+    assert_eq!(rx.wait_ready(timeout).await, Ok(id));
+    std::mem::drop(registering.await.unwrap());
+
+    std::mem::drop(tx);
+    assert_eq!(rx.wait_ready(timeout).await, Err(NotReady::Closed));
+}
+
+#[tokio::test]
+async fn wait_all_ready() {
+    use agner_actors::{Context, System};
+
+    use crate::NamesNotReady;
+
+    async fn idle(_context: &mut Context<()>, (): ()) {
+        std::future::pending().await
+    }
+
+    let system = System::new(Default::default());
+    let timeout = Duration::from_millis(100);
+    let room = system.spawn(idle, (), Default::default()).await.unwrap();
+    let acceptor = system.spawn(idle, (), Default::default()).await.unwrap();
+
+    assert!(crate::wait_all_ready(&system, &[], timeout).await.unwrap().is_empty());
+
+    system.register("room", room).await.unwrap();
+    assert_eq!(
+        crate::wait_all_ready(&system, &["room", "acceptor"], timeout).await,
+        Err(NamesNotReady { unbound: vec!["acceptor".to_owned()] })
+    );
+
+    let registering = {
+        let system = system.to_owned();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            system.register("acceptor", acceptor).await.unwrap();
+        })
+    };
+    let bound = crate::wait_all_ready(&system, &["room", "acceptor"], timeout).await.unwrap();
+    registering.await.unwrap();
+    assert_eq!(bound.len(), 2);
+    assert_eq!(bound["room"], room);
+    assert_eq!(bound["acceptor"], acceptor);
+}
@@ -16,10 +16,17 @@ mod start_child;
 pub use start_child::{start_child, StartChildError};
 
 mod stop_child;
-pub use stop_child::{stop_child, ShutdownSequence, StopChildError};
+pub use stop_child::{
+    stop_child, ContextStopChildExt, Escalation, EscalationHook, ShutdownSequence,
+    ShutdownSequenceError, StopChildError,
+};
 
 mod init_type;
 pub use init_type::{InitType, WithAck};
 
+mod sup_hook;
+pub(crate) use sup_hook::Lifecycle;
+pub use sup_hook::SupHook;
+
 pub mod gen_child_spec;
-pub use gen_child_spec::{CreateArgs, CreateChild, GenChildSpec};
+pub use gen_child_spec::{CreateArgs, CreateArgsAsync, CreateChild, GenChildSpec};
@@ -1,3 +1,4 @@
+mod args_async;
 mod args_call;
 mod args_clone;
 mod args_unique;
@@ -11,9 +12,10 @@ use std::marker::PhantomData;
 
 #[cfg(feature = "reg")]
 use agner_reg::RegTx;
-pub use traits::{CreateArgs, CreateChild};
+pub use traits::{CreateArgs, CreateArgsAsync, CreateChild};
 
 use crate::common::init_type::InitType;
+use crate::common::start_child::InitAckPayload;
 
 /// A base for both [uniform](crate::uniform::UniformChildSpec) and
 /// [mixed](crate::mixed::MixedChildSpec) child specifications.
@@ -22,6 +24,8 @@ pub struct GenChildSpec<B, A, M, X> {
     create_args: A,
     message: PhantomData<M>,
     init_type: InitType,
+    init_ack_payload: Option<InitAckPayload>,
+    name: Option<String>,
 
     #[cfg(feature = "reg")]
     reg_tx: Option<RegTx>,
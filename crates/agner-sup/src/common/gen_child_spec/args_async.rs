@@ -0,0 +1,96 @@
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+
+use agner_actors::BoxError;
+
+use crate::common::gen_child_spec::traits::CreateArgsAsync;
+use crate::common::StaticBoxedFuture;
+
+pub fn args_async0<F, Out>(f: F) -> ArgsAsyncFn0<F, Out>
+where
+    ArgsAsyncFn0<F, Out>: CreateArgsAsync<Input = (), Output = Out>,
+{
+    ArgsAsyncFn0(f, Default::default())
+}
+
+pub fn args_async1<F, In, Out>(f: F) -> ArgsAsyncFn1<F, In, Out>
+where
+    ArgsAsyncFn1<F, In, Out>: CreateArgsAsync<Input = In, Output = Out>,
+{
+    ArgsAsyncFn1(f, Default::default())
+}
+
+pub struct ArgsAsyncFn0<F, Out>(F, PhantomData<Out>);
+
+pub struct ArgsAsyncFn1<F, In, Out>(F, PhantomData<(In, Out)>);
+
+impl<F, Fut, Out, E> CreateArgsAsync for ArgsAsyncFn0<F, Out>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Out, E>> + Send + 'static,
+    E: Into<BoxError>,
+{
+    type Input = ();
+    type Output = Out;
+
+    fn create_args_async(&mut self, (): Self::Input) -> StaticBoxedFuture<Result<Out, BoxError>> {
+        let fut = (self.0)();
+        Box::pin(async move { fut.await.map_err(Into::into) })
+    }
+}
+
+impl<F, Fut, In, Out, E> CreateArgsAsync for ArgsAsyncFn1<F, In, Out>
+where
+    F: FnMut(In) -> Fut,
+    Fut: Future<Output = Result<Out, E>> + Send + 'static,
+    E: Into<BoxError>,
+{
+    type Input = In;
+    type Output = Out;
+
+    fn create_args_async(
+        &mut self,
+        input: Self::Input,
+    ) -> StaticBoxedFuture<Result<Out, BoxError>> {
+        let fut = (self.0)(input);
+        Box::pin(async move { fut.await.map_err(Into::into) })
+    }
+}
+
+impl<F, Out> fmt::Debug for ArgsAsyncFn0<F, Out> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArgsAsyncFn0")
+            .field("out", &std::any::type_name::<Out>())
+            .field("func", &std::any::type_name::<F>())
+            .finish()
+    }
+}
+
+impl<F, In, Out> fmt::Debug for ArgsAsyncFn1<F, In, Out> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArgsAsyncFn1")
+            .field("in", &std::any::type_name::<In>())
+            .field("out", &std::any::type_name::<Out>())
+            .field("func", &std::any::type_name::<F>())
+            .finish()
+    }
+}
+
+impl<F, Out> Clone for ArgsAsyncFn0<F, Out>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.to_owned(), Default::default())
+    }
+}
+
+impl<F, In, Out> Clone for ArgsAsyncFn1<F, In, Out>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.to_owned(), Default::default())
+    }
+}
@@ -1,18 +1,23 @@
 use std::fmt;
+use std::time::Duration;
 
 use agner_actors::{Actor, ActorID, System};
 
 #[cfg(feature = "reg")]
 use agner_reg::RegTx;
+use agner_utils::std_error_pp::StdErrorPP;
 use futures::TryFutureExt;
 
+use crate::common::gen_child_spec::args_async::{
+    args_async0, args_async1, ArgsAsyncFn0, ArgsAsyncFn1,
+};
 use crate::common::gen_child_spec::args_call::{args_call0, args_call1, ArgsCallFn0, ArgsCallFn1};
 use crate::common::gen_child_spec::args_clone::{args_clone, ArgsClone};
 use crate::common::gen_child_spec::args_unique::{args_unique, ArgsUnique};
-use crate::common::gen_child_spec::traits::{CreateArgs, CreateChild};
+use crate::common::gen_child_spec::traits::{CreateArgs, CreateArgsAsync, CreateChild};
 use crate::common::gen_child_spec::GenChildSpec;
-use crate::common::start_child::start_child;
-use crate::common::InitType;
+use crate::common::start_child::{do_start_child, InitAckPayload};
+use crate::common::{InitType, StartChildError};
 
 impl GenChildSpec<(), (), (), ()> {
     pub fn new() -> Self {
@@ -27,6 +32,8 @@ impl Default for GenChildSpec<(), (), (), ()> {
             create_args: (),
             message: Default::default(),
             init_type: InitType::NoAck,
+            init_ack_payload: None,
+            name: None,
 
             #[cfg(feature = "reg")]
             reg_tx: None,
@@ -43,6 +50,8 @@ impl<X> GenChildSpec<(), (), (), X> {
             create_args: (),
             message: Default::default(),
             init_type: InitType::NoAck,
+            init_ack_payload: None,
+            name: None,
 
             #[cfg(feature = "reg")]
             reg_tx: None,
@@ -68,6 +77,8 @@ impl<A, M, X> GenChildSpec<(), A, M, X> {
             create_args: self.create_args,
             message: Default::default(),
             init_type: self.init_type,
+            init_ack_payload: self.init_ack_payload,
+            name: self.name,
 
             #[cfg(feature = "reg")]
             reg_tx: self.reg_tx,
@@ -89,6 +100,8 @@ impl<B, OldA, OldM, X> GenChildSpec<B, OldA, OldM, X> {
             create_args,
             message: Default::default(),
             init_type: self.init_type,
+            init_ack_payload: self.init_ack_payload,
+            name: self.name,
 
             #[cfg(feature = "reg")]
             reg_tx: self.reg_tx,
@@ -108,6 +121,8 @@ impl<B, OldA, OldM, X> GenChildSpec<B, OldA, OldM, X> {
             create_args,
             message: Default::default(),
             init_type: self.init_type,
+            init_ack_payload: self.init_ack_payload,
+            name: self.name,
 
             #[cfg(feature = "reg")]
             reg_tx: self.reg_tx,
@@ -127,6 +142,8 @@ impl<B, OldA, OldM, X> GenChildSpec<B, OldA, OldM, X> {
             create_args,
             message: Default::default(),
             init_type: self.init_type,
+            init_ack_payload: self.init_ack_payload,
+            name: self.name,
 
             #[cfg(feature = "reg")]
             reg_tx: self.reg_tx,
@@ -149,6 +166,61 @@ impl<B, OldA, OldM, X> GenChildSpec<B, OldA, OldM, X> {
             create_args,
             message: Default::default(),
             init_type: self.init_type,
+            init_ack_payload: self.init_ack_payload,
+            name: self.name,
+
+            #[cfg(feature = "reg")]
+            reg_tx: self.reg_tx,
+
+            ext: self.ext,
+        }
+    }
+}
+
+impl<B, OldA, OldM, X> GenChildSpec<B, OldA, OldM, X> {
+    /// Produce the child's arguments with an async function.
+    ///
+    /// If the returned future fails, so does the start of the child.
+    pub fn args_async0<F, Out, M>(self, make_args: F) -> GenChildSpec<B, ArgsAsyncFn0<F, Out>, M, X>
+    where
+        B: for<'a> Actor<'a, Out, M>,
+        ArgsAsyncFn0<F, Out>: CreateArgsAsync<Input = (), Output = Out>,
+    {
+        let create_args = args_async0(make_args);
+        GenChildSpec {
+            behaviour: self.behaviour,
+            create_args,
+            message: Default::default(),
+            init_type: self.init_type,
+            init_ack_payload: self.init_ack_payload,
+            name: self.name,
+
+            #[cfg(feature = "reg")]
+            reg_tx: self.reg_tx,
+
+            ext: self.ext,
+        }
+    }
+
+    /// Produce the child's arguments with an async function of the start argument.
+    ///
+    /// If the returned future fails, so does the start of the child.
+    pub fn args_async1<F, In, Out, M>(
+        self,
+        make_args: F,
+    ) -> GenChildSpec<B, ArgsAsyncFn1<F, In, Out>, M, X>
+    where
+        B: for<'a> Actor<'a, Out, M>,
+        ArgsAsyncFn1<F, In, Out>: CreateArgsAsync<Input = In, Output = Out>,
+    {
+        let create_args = args_async1(make_args);
+        GenChildSpec {
+            behaviour: self.behaviour,
+            create_args,
+            message: Default::default(),
+            init_type: self.init_type,
+            init_ack_payload: self.init_ack_payload,
+            name: self.name,
 
             #[cfg(feature = "reg")]
             reg_tx: self.reg_tx,
@@ -167,6 +239,52 @@ impl<B, A, M, X> GenChildSpec<B, A, M, X> {
         let init_type = init_type.into();
         Self { init_type, ..self }
     }
+
+    /// Wait for the child's init-ack no longer than `start_timeout`. Upon expiry the child is
+    /// terminated, and the start fails with [`StartChildError::Timeout`].
+    ///
+    /// Implies the [`InitType::WithAck`] init-type.
+    pub fn start_timeout(self, start_timeout: Duration) -> Self {
+        let with_ack = match self.init_type {
+            InitType::WithAck(with_ack) => with_ack,
+            InitType::NoAck => Default::default(),
+        };
+        self.init_type(with_ack.with_init_timeout(start_timeout))
+    }
+
+    /// Let the child acknowledge its start with a `T` payload (see
+    /// [`init_ack_ok_with`](agner_init_ack::ContextInitAckExt::init_ack_ok_with)), and pass that
+    /// payload to `on_payload` each time the child starts: e.g. to learn the address an acceptor
+    /// has bound to.
+    ///
+    /// Implies the [`InitType::WithAck`] init-type. Should the child ack with a payload of another
+    /// type, its start fails with [`StartChildError::InitAckFailure`].
+    pub fn on_init_ack<T, F>(self, on_payload: F) -> Self
+    where
+        T: Send + 'static,
+        F: Fn(ActorID, T) + Send + Sync + 'static,
+    {
+        let init_type = match self.init_type {
+            InitType::WithAck(with_ack) => InitType::WithAck(with_ack),
+            InitType::NoAck => InitType::with_ack(),
+        };
+        let init_ack_payload = Some(InitAckPayload::new(on_payload));
+        Self { init_type, init_ack_payload, ..self }
+    }
+
+    /// [Bind the name](agner_actors::System::rebind) to the child each time it starts.
+    ///
+    /// The name is bound before the start completes, hence before the supervisor handles the
+    /// exit of the child: the name is never left bound to a terminated child. A failure to bind
+    /// the name does not fail the start (e.g. the child that has already exited is about to be
+    /// restarted anyway); it is logged.
+    pub fn bind_name(self, name: impl Into<String>) -> Self {
+        Self { name: Some(name.into()), ..self }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
 }
 
 #[cfg(feature = "reg")]
@@ -176,13 +294,17 @@ impl<B, A, M, X> GenChildSpec<B, A, M, X> {
         let reg_tx = Some(reg_tx);
         Self { reg_tx, ..self }
     }
+
+    pub fn reg_tx(&self) -> Option<&RegTx> {
+        self.reg_tx.as_ref()
+    }
 }
 
 impl<B, A, M, X> CreateChild for GenChildSpec<B, A, M, X>
 where
     B: for<'a> Actor<'a, A::Output, M>,
-    A: CreateArgs,
-    B: Clone,
+    A: CreateArgsAsync,
+    B: Clone + Send + 'static,
     M: Unpin + Send + 'static,
     A::Output: Send + 'static,
 {
@@ -197,15 +319,35 @@ where
         Result<agner_actors::ActorID, crate::common::StartChildError>,
     > {
         let system = system.to_owned();
-        let args = self.create_args.create_args(args);
+        let args_fut = self.create_args.create_args_async(args);
         let behaviour = self.behaviour.to_owned();
         let init_type = self.init_type;
+        let init_ack_payload = self.init_ack_payload.to_owned();
+        let name = self.name.to_owned();
 
         #[cfg(feature = "reg")]
         let registered_service = self.reg_tx.to_owned();
 
-        let start_child_fut = start_child(system.to_owned(), sup_id, behaviour, args, init_type)
+        let start_child_fut = args_fut
+            .map_err(|reason| StartChildError::CreateArgs(reason.into()))
+            .and_then({
+                let system = system.to_owned();
+                move |args| {
+                    do_start_child(system, sup_id, behaviour, args, init_type, init_ack_payload)
+                }
+            })
             .and_then(move |child_id| async move {
+                if let Some(name) = name {
+                    if let Err(reason) = system.rebind(&name, child_id).await {
+                        tracing::warn!(
+                            "failed to bind the name {:?} to {}: {}",
+                            name,
+                            child_id,
+                            reason.pp()
+                        );
+                    }
+                }
+
                 #[cfg(feature = "reg")]
                 if let Some(service) = registered_service {
                     let reg_guard = service.register(child_id);
@@ -228,6 +370,7 @@ where
             .field("behaviour", &std::any::type_name::<B>())
             .field("create_args", &self.create_args)
             .field("init_type", &self.init_type)
+            .field("name", &self.name)
             .finish()
     }
 }
@@ -244,6 +387,8 @@ where
             create_args: self.create_args.clone(),
             message: Default::default(),
             init_type: self.init_type,
+            init_ack_payload: self.init_ack_payload.clone(),
+            name: self.name.clone(),
 
             #[cfg(feature = "reg")]
             reg_tx: self.reg_tx.clone(),
@@ -4,6 +4,7 @@ use agner_init_ack::ContextInitAckExt;
 use crate::common::gen_child_spec::traits::CreateChild;
 use crate::common::gen_child_spec::GenChildSpec;
 use crate::common::init_type::WithAck;
+use crate::common::StartChildError;
 
 #[tokio::test]
 async fn t01() {
@@ -133,3 +134,169 @@ async fn t04() {
     let child_id = gen_child_spec.create_child(&system, sup_id, 11).await.unwrap();
     assert!(system.wait(child_id).await.is_normal());
 }
+
+#[tokio::test]
+async fn t05() {
+    async fn sup(context: &mut Context<Never>, (): ()) {
+        context.init_ack_ok(Default::default());
+        std::future::pending().await
+    }
+
+    async fn actor(context: &mut Context<Never>, token: String) {
+        context.init_ack_ok(Default::default());
+        eprintln!("[{}] token: {}", context.actor_id(), token);
+    }
+
+    let system: System = System::new(Default::default());
+    let sup_id: ActorID = system.spawn(sup, (), Default::default()).await.unwrap();
+
+    let mut gen_child_spec = GenChildSpec::new()
+        .behaviour(actor)
+        .args_async1(|fail: bool| async move {
+            tokio::task::yield_now().await;
+            if fail {
+                Err("token unavailable")
+            } else {
+                Ok("a-token".to_owned())
+            }
+        })
+        .init_type(WithAck::default());
+
+    let child_id = gen_child_spec.create_child(&system, sup_id, false).await.unwrap();
+    assert!(system.wait(child_id).await.is_normal());
+    assert!(matches!(
+        gen_child_spec.create_child(&system, sup_id, true).await,
+        Err(StartChildError::CreateArgs(_))
+    ));
+}
+
+#[tokio::test]
+async fn t06() {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+
+    async fn sup(context: &mut Context<Never>, (): ()) {
+        context.init_ack_ok(Default::default());
+        std::future::pending().await
+    }
+
+    async fn never_acks(_context: &mut Context<Never>, (): ()) {
+        std::future::pending().await
+    }
+
+    let system: System = System::new(Default::default());
+    let sup_id: ActorID = system.spawn(sup, (), Default::default()).await.unwrap();
+
+    let mut gen_child_spec = GenChildSpec::new()
+        .behaviour(never_acks)
+        .args_clone(())
+        .start_timeout(Duration::from_millis(50));
+
+    let started_at = std::time::Instant::now();
+    let result = gen_child_spec.create_child(&system, sup_id, ()).await;
+    assert!(matches!(result, Err(StartChildError::Timeout(_))), "{:?}", result);
+    assert!(started_at.elapsed() < Duration::from_secs(1));
+
+    assert_eq!(system.all_actors().collect::<Vec<_>>().await, [sup_id]);
+}
+
+#[tokio::test]
+async fn t07() {
+    async fn sup(context: &mut Context<Never>, (): ()) {
+        context.init_ack_ok(Default::default());
+        std::future::pending().await
+    }
+
+    async fn actor(context: &mut Context<Never>, (): ()) {
+        context.init_ack_ok(Default::default());
+        std::future::pending().await
+    }
+
+    let system: System = System::new(Default::default());
+    let sup_id: ActorID = system.spawn(sup, (), Default::default()).await.unwrap();
+
+    let mut with_ack = GenChildSpec::new()
+        .behaviour(actor)
+        .args_clone(())
+        .init_type(WithAck::default());
+    let child_id = with_ack.create_child(&system, sup_id, ()).await.unwrap();
+    assert_eq!(system.actor_info(child_id).await.unwrap().parent, Some(sup_id));
+
+    let mut without_ack = GenChildSpec::new()
+        .behaviour(actor)
+        .args_clone(())
+        .init_type(crate::common::InitType::NoAck);
+    let child_id = without_ack.create_child(&system, sup_id, ()).await.unwrap();
+    assert_eq!(system.actor_info(child_id).await.unwrap().parent, Some(sup_id));
+}
+
+#[tokio::test]
+async fn t08() {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::mpsc;
+
+    use crate::mixed::{self, MixedChildSpec, OneForOne, RestartIntensity, SupSpec};
+
+    async fn acceptor(context: &mut Context<Never>, (): ()) -> Result<Never, Exit> {
+        let listener = TcpListener::bind("127.0.0.1:0").await.map_err(Exit::custom)?;
+        context.init_ack_ok_with(None, listener.local_addr().map_err(Exit::custom)?);
+        loop {
+            let _ = listener.accept().await.map_err(Exit::custom)?;
+        }
+    }
+
+    let (bound_tx, mut bound_rx) = mpsc::unbounded_channel();
+    let acceptor_spec = MixedChildSpec::mixed("acceptor")
+        .behaviour(acceptor)
+        .args_clone(())
+        .on_init_ack(move |child_id, bound_to: SocketAddr| {
+            let _ = bound_tx.send((child_id, bound_to));
+        });
+    let sup_spec = SupSpec::new(OneForOne::new(RestartIntensity::new(5, Duration::from_secs(30))))
+        .with_child(acceptor_spec);
+
+    let system: System = System::new(Default::default());
+    system.spawn(mixed::run, sup_spec, Default::default()).await.unwrap();
+
+    let (acceptor_id, bound_to) = bound_rx.recv().await.unwrap();
+    TcpStream::connect(bound_to).await.unwrap();
+
+    // the restarted acceptor reports the address it has bound anew
+    system.exit(acceptor_id, Exit::from_message("oops")).await;
+    let (restarted_id, bound_to) = bound_rx.recv().await.unwrap();
+    assert_ne!(restarted_id, acceptor_id);
+    TcpStream::connect(bound_to).await.unwrap();
+}
+
+#[tokio::test]
+async fn t09() {
+    use agner_init_ack::PayloadTypeMismatch;
+
+    async fn sup(context: &mut Context<Never>, (): ()) {
+        context.init_ack_ok(Default::default());
+        std::future::pending().await
+    }
+
+    async fn actor(context: &mut Context<Never>, port: u16) {
+        context.init_ack_ok_with(None, port);
+        std::future::pending().await
+    }
+
+    let system: System = System::new(Default::default());
+    let sup_id: ActorID = system.spawn(sup, (), Default::default()).await.unwrap();
+
+    let mut gen_child_spec = GenChildSpec::new()
+        .behaviour(actor)
+        .args_clone(8080)
+        .on_init_ack(|_child_id, _bound_to: String| ());
+    let reason = gen_child_spec.create_child(&system, sup_id, ()).await.unwrap_err();
+    assert!(matches!(
+        reason,
+        StartChildError::InitAckFailure(reason)
+            if reason.source_downcast_ref::<PayloadTypeMismatch>().is_some()
+    ));
+}
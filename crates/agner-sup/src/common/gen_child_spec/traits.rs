@@ -1,4 +1,4 @@
-use agner_actors::{ActorID, System};
+use agner_actors::{ActorID, BoxError, System};
 
 use crate::common::{StartChildError, StaticBoxedFuture};
 
@@ -19,3 +19,33 @@ pub trait CreateArgs {
 
     fn create_args(&mut self, input: Self::Input) -> Self::Output;
 }
+
+/// Produces the child's arguments asynchronously, possibly failing.
+///
+/// Implemented for every [`CreateArgs`].
+pub trait CreateArgsAsync {
+    type Input;
+    type Output;
+
+    fn create_args_async(
+        &mut self,
+        input: Self::Input,
+    ) -> StaticBoxedFuture<Result<Self::Output, BoxError>>;
+}
+
+impl<T> CreateArgsAsync for T
+where
+    T: CreateArgs,
+    T::Output: Send + 'static,
+{
+    type Input = T::Input;
+    type Output = T::Output;
+
+    fn create_args_async(
+        &mut self,
+        input: Self::Input,
+    ) -> StaticBoxedFuture<Result<Self::Output, BoxError>> {
+        let output = self.create_args(input);
+        Box::pin(async move { Ok(output) })
+    }
+}
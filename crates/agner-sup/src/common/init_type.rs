@@ -3,13 +3,13 @@ use std::time::Duration;
 const DEFAULT_INIT_TIMEOUT: Duration = Duration::from_secs(5);
 const DEFAULT_STOP_TIMEOUT: Duration = Duration::from_secs(5);
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InitType {
     NoAck,
     WithAck(WithAck),
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WithAck {
     pub init_timeout: Duration,
     pub stop_timeout: Duration,
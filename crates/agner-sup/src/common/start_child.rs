@@ -3,12 +3,13 @@ use std::sync::Arc;
 use tokio::sync::oneshot;
 
 use agner_actors::system_error::SysSpawnError;
-use agner_actors::{Actor, ActorID, Exit, SpawnOpts, System};
+use agner_actors::{Actor, ActorID, ArcError, Exit, SpawnOpts, System};
+use agner_init_ack::InitAckTx;
 use agner_utils::future_timeout_ext::FutureTimeoutExt;
 use agner_utils::result_err_flatten::ResultErrFlattenIn;
 use agner_utils::std_error_pp::StdErrorPP;
 
-use crate::common::{stop_child, InitType, ShutdownSequence, WithAck};
+use crate::common::{stop_child, InitType, ShutdownSequence, StaticBoxedFuture, WithAck};
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum StartChildError {
@@ -23,20 +24,67 @@ pub enum StartChildError {
 
     #[error("oneshot-rx failure")]
     OneshotRx(#[source] oneshot::error::RecvError),
+
+    #[error("Failed to create the child's arguments")]
+    CreateArgs(#[source] ArcError),
+}
+
+type AckedFuture = StaticBoxedFuture<Result<ActorID, Exit>>;
+
+/// Creates the init-ack channel expecting a payload, and passes the received payload on (see
+/// [`GenChildSpec::on_init_ack`](crate::common::GenChildSpec::on_init_ack)).
+#[derive(Clone)]
+pub(crate) struct InitAckPayload(Arc<dyn Fn() -> (InitAckTx, AckedFuture) + Send + Sync>);
+
+impl InitAckPayload {
+    pub(crate) fn new<T, F>(on_payload: F) -> Self
+    where
+        T: Send + 'static,
+        F: Fn(ActorID, T) + Send + Sync + 'static,
+    {
+        let on_payload = Arc::new(on_payload);
+        Self(Arc::new(move || {
+            let (init_ack_tx, init_ack_rx) = agner_init_ack::new_channel_with_payload::<T>();
+            let on_payload = on_payload.to_owned();
+            let acked = async move {
+                let (child_id, payload) = init_ack_rx.recv().await?;
+                on_payload(child_id, payload);
+                Ok(child_id)
+            };
+            (init_ack_tx, Box::pin(acked) as AckedFuture)
+        }))
+    }
 }
 
 /// Start a child in accordance with the supervision design principles.
+pub async fn start_child<B, A, M>(
+    system: System,
+    sup_id: ActorID,
+    behaviour: B,
+    args: A,
+    init_type: InitType,
+) -> Result<ActorID, StartChildError>
+where
+    B: for<'a> Actor<'a, A, M>,
+    B: Send + 'static,
+    A: Send + 'static,
+    M: Send + Unpin + 'static,
+{
+    do_start_child(system, sup_id, behaviour, args, init_type, None).await
+}
+
 #[tracing::instrument(skip_all, fields(
     sup = display(sup_id),
     behaviour = std::any::type_name::<B>(),
     init_type = debug(init_type),
 ))]
-pub async fn start_child<B, A, M>(
+pub(crate) async fn do_start_child<B, A, M>(
     system: System,
     sup_id: ActorID,
     behaviour: B,
     args: A,
     init_type: InitType,
+    init_ack_payload: Option<InitAckPayload>,
 ) -> Result<ActorID, StartChildError>
 where
     B: for<'a> Actor<'a, A, M>,
@@ -49,7 +97,8 @@ where
     let child_id = match init_type {
         InitType::NoAck => do_start_child_no_ack(&system, sup_id, behaviour, args).await?,
         InitType::WithAck(with_ack) =>
-            do_start_child_init_ack(&system, sup_id, behaviour, args, with_ack).await?,
+            do_start_child_init_ack(&system, sup_id, behaviour, args, with_ack, init_ack_payload)
+                .await?,
     };
 
     system.put_data(child_id, crate::common::ParentActor(sup_id)).await;
@@ -69,7 +118,7 @@ where
     A: Send + 'static,
     M: Send + Unpin + 'static,
 {
-    let spawn_opts = SpawnOpts::new().with_link(sup_id);
+    let spawn_opts = SpawnOpts::new().with_parent(sup_id).with_link(sup_id);
     let child_id = system.spawn(behaviour, args, spawn_opts).await?;
     tracing::trace!("[start_child_no_ack] started [child_id: {}]", child_id);
 
@@ -81,6 +130,7 @@ async fn do_start_child_init_ack<B, A, M>(
     behaviour: B,
     args: A,
     with_ack: WithAck,
+    init_ack_payload: Option<InitAckPayload>,
 ) -> Result<ActorID, StartChildError>
 where
     B: for<'a> Actor<'a, A, M>,
@@ -88,8 +138,14 @@ where
     A: Send + 'static,
     M: Send + Unpin + 'static,
 {
-    let (init_ack_tx, init_ack_rx) = agner_init_ack::new_channel();
-    let spawn_opts = SpawnOpts::new().with_data(init_ack_tx);
+    let (init_ack_tx, init_ack_rx) = match init_ack_payload {
+        Some(InitAckPayload(new_channel)) => new_channel(),
+        None => {
+            let (init_ack_tx, init_ack_rx) = agner_init_ack::new_channel();
+            (init_ack_tx, Box::pin(init_ack_rx) as AckedFuture)
+        },
+    };
+    let spawn_opts = SpawnOpts::new().with_parent(sup_id).with_data(init_ack_tx);
     let intermediary_id = system.spawn(behaviour, args, spawn_opts).await?;
 
     let init_ack_result = init_ack_rx
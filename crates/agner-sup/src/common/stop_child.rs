@@ -1,7 +1,13 @@
-use std::time::Duration;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use agner_actors::{ActorID, Exit, System};
+use agner_actors::{ActorID, Context, Exit, Priority, System};
 use agner_utils::future_timeout_ext::FutureTimeoutExt;
+use agner_utils::std_error_pp::StdErrorPP;
+use tokio::sync::oneshot;
+
+use crate::common::BoxedFuture;
 
 const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 const DEFAULT_KILL_TIMEOUT: Duration = Duration::from_secs(5);
@@ -10,8 +16,55 @@ const DEFAULT_KILL_TIMEOUT: Duration = Duration::from_secs(5);
 #[error("Exit failed")]
 pub struct StopChildError;
 
+/// A [`ShutdownSequence`] that may fail to stop a child (see [`ShutdownSequence::validate`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ShutdownSequenceError {
+    #[error("The shutdown sequence is empty")]
+    Empty,
+
+    #[error("The stage #{} has a zero timeout", _0)]
+    ZeroTimeout(usize),
+
+    #[error("The stage #{} follows the kill-stage, thus is never reached", _0)]
+    AfterKill(usize),
+
+    #[error("The shutdown sequence does not end with a kill-stage")]
+    NoKill,
+}
+
 #[derive(Debug, Clone)]
-pub struct ShutdownSequence(Vec<(Exit, Duration)>);
+pub struct ShutdownSequence {
+    stages: Vec<(Exit, Duration)>,
+    escalation_hooks: Vec<Arc<dyn EscalationHook>>,
+}
+
+/// Notified when a child does not terminate within the timeout of a stage of the
+/// [`ShutdownSequence`].
+pub trait EscalationHook: fmt::Debug + Send + Sync + 'static {
+    fn on_kill_escalation(&self, escalation: &Escalation);
+}
+
+/// A stage of the [`ShutdownSequence`] that timed out.
+#[derive(Debug, Clone)]
+pub struct Escalation {
+    /// The child being stopped.
+    pub actor_id: ActorID,
+
+    /// The index of the stage that timed out.
+    pub stage: usize,
+
+    /// The exit reason the child has not reacted to.
+    pub exit: Exit,
+
+    /// The timeout of the stage.
+    pub timeout: Duration,
+
+    /// The time elapsed since the beginning of the sequence.
+    pub elapsed: Duration,
+
+    /// The exit reason of the next stage, or `None` if this was the last one.
+    pub next: Option<Exit>,
+}
 
 /// Stop the child in accordance with the supervision design principles.
 pub async fn stop_child(
@@ -19,15 +72,94 @@ pub async fn stop_child(
     actor_id: ActorID,
     shutdown_sequence: ShutdownSequence,
 ) -> Result<Exit, StopChildError> {
-    for (exit, timeout) in shutdown_sequence.0.into_iter() {
-        system.exit(actor_id, exit).await;
+    let ShutdownSequence { stages, escalation_hooks } = shutdown_sequence;
+    let started_at = Instant::now();
+
+    let mut stages = stages.into_iter().enumerate().peekable();
+    while let Some((stage, (exit, timeout))) = stages.next() {
+        tracing::debug!(
+            "[stop_child] {} entering stage {} [exit: {}, timeout: {:?}]",
+            actor_id,
+            stage,
+            exit.pp(),
+            timeout
+        );
+        system.exit(actor_id, exit.to_owned()).await;
         if let Ok(actual_exit) = system.wait(actor_id).timeout(timeout).await {
+            tracing::debug!(
+                "[stop_child] {} is down [stage: {}, elapsed: {:?}, exit: {}]",
+                actor_id,
+                stage,
+                started_at.elapsed(),
+                actual_exit.pp()
+            );
             return Ok(actual_exit)
         }
+
+        let escalation = Escalation {
+            actor_id,
+            stage,
+            exit,
+            timeout,
+            elapsed: started_at.elapsed(),
+            next: stages.peek().map(|(_, (next, _))| next.to_owned()),
+        };
+        tracing::warn!(
+            "[stop_child] {} has not terminated within {:?} [stage: {}, exit: {}, next: {:?}]",
+            actor_id,
+            timeout,
+            stage,
+            escalation.exit.pp(),
+            escalation.next.as_ref().map(|next| next.pp().to_string())
+        );
+        for hook in escalation_hooks.iter() {
+            hook.on_kill_escalation(&escalation);
+        }
     }
     Err(StopChildError)
 }
 
+/// Stop the actors from within an actor.
+pub trait ContextStopChildExt {
+    /// Unlink from the `child`, and [stop](stop_child) it in accordance with the
+    /// `shutdown_sequence`.
+    ///
+    /// Once started, the sequence runs to its end even if the returned future is dropped (e.g. as
+    /// the calling actor itself is being shut down): the `child` is not left half-stopped.
+    fn stop_child(
+        &mut self,
+        child: ActorID,
+        shutdown_sequence: ShutdownSequence,
+    ) -> BoxedFuture<'_, Result<Exit, StopChildError>>;
+}
+
+impl<M, C> ContextStopChildExt for Context<M, C>
+where
+    M: Send,
+    C: Send,
+{
+    fn stop_child(
+        &mut self,
+        child: ActorID,
+        shutdown_sequence: ShutdownSequence,
+    ) -> BoxedFuture<'_, Result<Exit, StopChildError>> {
+        Box::pin(async move {
+            self.unlink(child).await;
+
+            let system = self.system();
+            let (stopped_tx, stopped_rx) = oneshot::channel();
+            let stopping = stop_child(system.to_owned(), child, shutdown_sequence);
+            system.config().spawner.spawn(
+                Priority::default(),
+                Box::pin(async move {
+                    let _ = stopped_tx.send(stopping.await);
+                }),
+            );
+            stopped_rx.await.map_err(|_| StopChildError)?
+        })
+    }
+}
+
 impl Default for ShutdownSequence {
     fn default() -> Self {
         [(Exit::shutdown(), DEFAULT_SHUTDOWN_TIMEOUT), (Exit::kill(), DEFAULT_KILL_TIMEOUT)].into()
@@ -36,10 +168,32 @@ impl Default for ShutdownSequence {
 
 impl ShutdownSequence {
     pub fn empty() -> Self {
-        Self(vec![])
+        Self { stages: vec![], escalation_hooks: vec![] }
     }
     pub fn add(mut self, exit: Exit, timeout: Duration) -> Self {
-        self.0.push((exit, timeout));
+        self.stages.push((exit, timeout));
+        self
+    }
+    /// Check that the sequence is certain to stop a child: it should end with an
+    /// [`Exit::kill()`], and each of its stages should have a non-zero timeout.
+    pub fn validate(&self) -> Result<(), ShutdownSequenceError> {
+        if self.stages.is_empty() {
+            return Err(ShutdownSequenceError::Empty)
+        }
+        if let Some(stage) = self.stages.iter().position(|(_, timeout)| timeout.is_zero()) {
+            return Err(ShutdownSequenceError::ZeroTimeout(stage))
+        }
+        match self.stages.iter().position(|(exit, _)| exit.is_kill()) {
+            None => Err(ShutdownSequenceError::NoKill),
+            Some(kill) if kill + 1 < self.stages.len() =>
+                Err(ShutdownSequenceError::AfterKill(kill + 1)),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Invoke the `hook` each time a stage of the sequence times out.
+    pub fn with_escalation_hook(mut self, hook: Arc<dyn EscalationHook>) -> Self {
+        self.escalation_hooks.push(hook);
         self
     }
 }
@@ -49,6 +203,98 @@ where
     I: IntoIterator<Item = (Exit, Duration)>,
 {
     fn from(seq: I) -> Self {
-        Self(seq.into_iter().collect())
+        Self { stages: seq.into_iter().collect(), escalation_hooks: vec![] }
+    }
+}
+
+#[tokio::test]
+async fn escalation_hook_is_invoked() {
+    use std::convert::Infallible;
+    use std::sync::Mutex;
+
+    use agner_actors::Context;
+    use tokio::sync::oneshot;
+
+    #[derive(Debug, Default)]
+    struct Hook(Mutex<Vec<Escalation>>);
+    impl EscalationHook for Hook {
+        fn on_kill_escalation(&self, escalation: &Escalation) {
+            self.0.lock().unwrap().push(escalation.to_owned());
+        }
+    }
+
+    async fn stubborn(context: &mut Context<Infallible>, ready: oneshot::Sender<()>) {
+        context.trap_exit(true).await;
+        let _ = ready.send(());
+        std::future::pending().await
     }
+
+    let system = System::new(Default::default());
+    let (ready_tx, ready_rx) = oneshot::channel();
+    let actor_id = system.spawn(stubborn, ready_tx, Default::default()).await.unwrap();
+    ready_rx.await.unwrap();
+
+    let hook = Arc::new(Hook::default());
+    let shutdown_sequence = ShutdownSequence::empty()
+        .add(Exit::shutdown(), Duration::from_millis(50))
+        .add(Exit::kill(), Duration::from_millis(50))
+        .with_escalation_hook(hook.to_owned());
+
+    let exit = stop_child(system, actor_id, shutdown_sequence).await.unwrap();
+    assert!(exit.is_kill());
+
+    let escalations = hook.0.lock().unwrap();
+    assert_eq!(escalations.len(), 1);
+    assert_eq!(escalations[0].actor_id, actor_id);
+    assert_eq!(escalations[0].stage, 0);
+    assert!(escalations[0].exit.is_shutdown());
+    assert!(escalations[0].elapsed >= Duration::from_millis(50));
+    assert!(escalations[0].next.as_ref().unwrap().is_kill());
+}
+
+#[tokio::test]
+async fn context_stops_a_linked_child() {
+    use std::convert::Infallible;
+
+    use tokio::sync::oneshot;
+
+    async fn stubborn(context: &mut Context<Infallible>, ready: oneshot::Sender<()>) {
+        context.trap_exit(true).await;
+        let _ = ready.send(());
+        std::future::pending().await
+    }
+
+    type ParentArgs = (ActorID, ShutdownSequence, oneshot::Sender<Result<Exit, StopChildError>>);
+    async fn parent(context: &mut Context<Infallible>, (child, seq, report_to): ParentArgs) {
+        context.link(child).await;
+        let _ = report_to.send(context.stop_child(child, seq).await);
+        std::future::pending().await
+    }
+
+    let system = System::new(Default::default());
+    let ms = |ms| Duration::from_millis(ms);
+
+    // the parent survives its child
+    let (ready_tx, ready_rx) = oneshot::channel();
+    let child = system.spawn(stubborn, ready_tx, Default::default()).await.unwrap();
+    ready_rx.await.unwrap();
+    let (report_tx, report_rx) = oneshot::channel();
+    let seq = [(Exit::shutdown(), ms(50)), (Exit::kill(), ms(50))].into();
+    let parent_id =
+        system.spawn(parent, (child, seq, report_tx), Default::default()).await.unwrap();
+    assert!(report_rx.await.unwrap().unwrap().is_kill());
+    assert!(system.actor_info(parent_id).await.is_some());
+
+    // the child is stopped even though the parent has been shut down midway
+    let (ready_tx, ready_rx) = oneshot::channel();
+    let child = system.spawn(stubborn, ready_tx, Default::default()).await.unwrap();
+    ready_rx.await.unwrap();
+    let (report_tx, _report_rx) = oneshot::channel();
+    let seq = [(Exit::shutdown(), ms(100)), (Exit::kill(), ms(50))].into();
+    let parent_id =
+        system.spawn(parent, (child, seq, report_tx), Default::default()).await.unwrap();
+    tokio::time::sleep(ms(20)).await;
+    system.exit(parent_id, Exit::kill()).await;
+    assert!(system.wait(parent_id).await.is_kill());
+    assert!(system.wait(child).await.is_kill());
 }
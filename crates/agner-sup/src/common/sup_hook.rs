@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use agner_actors::{ActorID, Exit, Priority, System};
+use tokio::sync::mpsc;
+
+use crate::mixed::ChildID;
+
+/// Lifecycle hooks invoked by a supervisor as it starts and stops its children.
+///
+/// A [Mixed Supervisor](crate::mixed) identifies its children by their IDs, while a
+/// [Uniform Supervisor](crate::uniform) has no IDs for its children (hence its hooks are
+/// `SupHook<()>`).
+///
+/// The hooks are invoked off the supervisor's loop, by a task of their own (one per supervisor),
+/// in the order of the events: a slow hook delays the subsequent invocations, but not the
+/// supervisor's restarts.
+pub trait SupHook<ID>: fmt::Debug + Send + Sync + 'static {
+    /// The supervisor is about to start the child.
+    fn on_child_starting(&self, _sup: ActorID, _child_id: ID) {}
+
+    /// The child has been started.
+    fn on_child_up(&self, _sup: ActorID, _child_id: ID, _actor_id: ActorID) {}
+
+    /// The child has terminated.
+    fn on_child_down(&self, _sup: ActorID, _child_id: ID, _actor_id: ActorID, _exit: &Exit) {}
+
+    /// The supervisor is shutting down.
+    fn on_shutdown_initiated(&self, _sup: ActorID, _exit: &Exit) {}
+}
+
+#[derive(Debug)]
+enum HookEvent<ID> {
+    ChildStarting(ID),
+    ChildUp(ID, ActorID),
+    ChildDown(ID, ActorID, Exit),
+    ShutdownInitiated(Exit),
+}
+
+/// The children started (and not yet terminated) by the supervisor, along with the channel to the
+/// task invoking the hooks.
+#[derive(Debug)]
+pub(crate) struct Lifecycle<ID> {
+    hooks_tx: Option<mpsc::UnboundedSender<HookEvent<ID>>>,
+    running: HashMap<ActorID, ID>,
+}
+
+impl<ID: ChildID> Lifecycle<ID> {
+    pub fn new(system: &System, sup: ActorID, hooks: Vec<Arc<dyn SupHook<ID>>>) -> Self {
+        let hooks_tx = (!hooks.is_empty()).then(|| {
+            let (hooks_tx, hooks_rx) = mpsc::unbounded_channel();
+            system
+                .config()
+                .spawner
+                .spawn(Priority::default(), Box::pin(invoke_hooks(sup, hooks, hooks_rx)));
+            hooks_tx
+        });
+        Self { hooks_tx, running: Default::default() }
+    }
+
+    pub fn child_starting(&self, child_id: ID) {
+        self.notify(HookEvent::ChildStarting(child_id));
+    }
+
+    pub fn child_up(&mut self, child_id: ID, actor_id: ActorID) {
+        self.running.insert(actor_id, child_id);
+        self.notify(HookEvent::ChildUp(child_id, actor_id));
+    }
+
+    pub fn actor_down(&mut self, actor_id: ActorID, exit: &Exit) {
+        if let Some(child_id) = self.running.remove(&actor_id) {
+            self.notify(HookEvent::ChildDown(child_id, actor_id, exit.to_owned()));
+        }
+    }
+
+    pub fn shutdown_initiated(&self, exit: &Exit) {
+        self.notify(HookEvent::ShutdownInitiated(exit.to_owned()));
+    }
+
+    fn notify(&self, event: HookEvent<ID>) {
+        if let Some(hooks_tx) = self.hooks_tx.as_ref() {
+            let _ = hooks_tx.send(event);
+        }
+    }
+}
+
+/// Invoke the hooks upon each event, until the supervisor is gone (and the events are drained).
+async fn invoke_hooks<ID: ChildID>(
+    sup: ActorID,
+    hooks: Vec<Arc<dyn SupHook<ID>>>,
+    mut hooks_rx: mpsc::UnboundedReceiver<HookEvent<ID>>,
+) {
+    while let Some(event) = hooks_rx.recv().await {
+        for hook in hooks.iter() {
+            match &event {
+                HookEvent::ChildStarting(child_id) => hook.on_child_starting(sup, *child_id),
+                HookEvent::ChildUp(child_id, actor_id) =>
+                    hook.on_child_up(sup, *child_id, *actor_id),
+                HookEvent::ChildDown(child_id, actor_id, exit) =>
+                    hook.on_child_down(sup, *child_id, *actor_id, exit),
+                HookEvent::ShutdownInitiated(exit) => hook.on_shutdown_initiated(sup, exit),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::time::Duration;
+
+    use agner_actors::{Context, System};
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::common::InitType;
+
+    /// Reports each invocation.
+    #[derive(Debug)]
+    struct Recorder(mpsc::UnboundedSender<String>);
+
+    impl<ID: fmt::Debug> SupHook<ID> for Recorder {
+        fn on_child_starting(&self, _sup: ActorID, child_id: ID) {
+            let _ = self.0.send(format!("starting {:?}", child_id));
+        }
+        fn on_child_up(&self, _sup: ActorID, child_id: ID, _actor_id: ActorID) {
+            let _ = self.0.send(format!("up {:?}", child_id));
+        }
+        fn on_child_down(&self, _sup: ActorID, child_id: ID, _actor_id: ActorID, _exit: &Exit) {
+            let _ = self.0.send(format!("down {:?}", child_id));
+        }
+        fn on_shutdown_initiated(&self, _sup: ActorID, _exit: &Exit) {
+            let _ = self.0.send("shutdown".to_owned());
+        }
+    }
+
+    async fn actor(_context: &mut Context<Infallible>, (): ()) {
+        std::future::pending().await
+    }
+
+    async fn recorded(
+        records_rx: &mut mpsc::UnboundedReceiver<String>,
+        count: usize,
+    ) -> Vec<String> {
+        let mut records = vec![];
+        while records.len() < count {
+            records.push(records_rx.recv().await.unwrap());
+        }
+        records
+    }
+
+    #[tokio::test]
+    async fn mixed_sup_lifecycle_hooks_are_invoked() {
+        use crate::mixed::{MixedChildSpec, OneForOne, RestartIntensity, SupSpec};
+
+        let child = MixedChildSpec::mixed("first")
+            .behaviour(actor)
+            .args_clone(())
+            .init_type(InitType::no_ack());
+
+        let (records_tx, mut records_rx) = mpsc::unbounded_channel();
+        let restart_strategy = OneForOne::new(RestartIntensity::new(0, Duration::from_secs(30)));
+        let sup_spec = SupSpec::new(restart_strategy)
+            .with_child(child)
+            .with_lifecycle_hook(Arc::new(Recorder(records_tx)));
+
+        let system = System::new(Default::default());
+        let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+
+        assert_eq!(recorded(&mut records_rx, 2).await, ["starting \"first\"", "up \"first\""]);
+
+        let children = crate::mixed::which_children::<&'static str>(&system, sup).await.unwrap();
+        system.exit(children[0].1, Exit::from_message("oops")).await;
+        system.wait(sup).await;
+
+        assert_eq!(recorded(&mut records_rx, 2).await, ["down \"first\"", "shutdown"]);
+        assert!(records_rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn uniform_sup_lifecycle_hooks_are_invoked() {
+        use crate::uniform::{SupSpec, UniformChildSpec};
+
+        let child_spec = UniformChildSpec::uniform()
+            .behaviour(actor)
+            .args_call0(|| ())
+            .init_type(InitType::no_ack());
+
+        let (records_tx, mut records_rx) = mpsc::unbounded_channel();
+        let sup_spec = SupSpec::new(child_spec).with_lifecycle_hook(Arc::new(Recorder(records_tx)));
+
+        let system = System::new(Default::default());
+        let sup = system.spawn(crate::uniform::run, sup_spec, Default::default()).await.unwrap();
+
+        let child = crate::uniform::start_child(&system, sup, ()).await.unwrap();
+        assert_eq!(recorded(&mut records_rx, 2).await, ["starting ()", "up ()"]);
+
+        system.exit(child, Exit::from_message("oops")).await;
+        assert_eq!(recorded(&mut records_rx, 1).await, ["down ()"]);
+
+        system.exit(sup, Exit::shutdown()).await;
+        system.wait(sup).await;
+        assert_eq!(recorded(&mut records_rx, 1).await, ["shutdown"]);
+        assert!(records_rx.recv().await.is_none());
+    }
+}
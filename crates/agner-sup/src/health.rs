@@ -0,0 +1,291 @@
+//! Health of a Supervision Tree
+//! =====
+//!
+//! [`health`] walks a supervision tree, and summarizes the state of each of its subtrees: e.g. to
+//! serve it from a `/healthz` endpoint.
+//!
+//! The children are reported for the [mixed](crate::mixed) and the [uniform](crate::uniform)
+//! supervisors: any other actor in the tree is treated as a worker — it is up as long as it runs.
+
+use std::fmt;
+
+use agner_actors::{ActorID, System};
+use tokio::sync::oneshot;
+
+use crate::common::{BoxedFuture, StaticBoxedFuture};
+use crate::mixed::ChildState;
+
+/// The health of an actor, along with its subtree.
+///
+/// The variants are ordered by their severity: the health of a subtree is the most severe of the
+/// health of its nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Health {
+    /// All the children that are expected to run are up.
+    Up,
+    /// Some of the children are being restarted.
+    Restarting,
+    /// Some of the children are not restarted (their circuit-breakers are open).
+    Degraded,
+    /// The actor is not running.
+    Down,
+}
+
+/// The health of the root of a supervision tree (see [`health`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HealthReport {
+    pub actor_id: ActorID,
+    pub health: Health,
+    pub children: Vec<ChildHealth>,
+}
+
+/// The health of a child within a [`HealthReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChildHealth {
+    /// The child-id, as rendered by its [`Debug`](fmt::Debug) implementation (the children of a
+    /// uniform supervisor are identified by their [`ActorID`]).
+    pub id: String,
+    /// The actor of the child, unless it is down.
+    pub actor_id: Option<ActorID>,
+    pub health: Health,
+    pub children: Vec<ChildHealth>,
+}
+
+/// Walk the supervision tree rooted at `root`, and report the health of each of its subtrees.
+pub async fn health(system: &System, root: ActorID) -> HealthReport {
+    let (health, children) = node_health(system, root).await;
+    HealthReport { actor_id: root, health, children }
+}
+
+impl HealthReport {
+    pub fn is_up(&self) -> bool {
+        self.health == Health::Up
+    }
+
+    /// The child of the root, identified by its child-id.
+    pub fn child(&self, id: impl fmt::Debug) -> Option<&ChildHealth> {
+        find_child(&self.children, id)
+    }
+}
+
+impl ChildHealth {
+    pub fn is_up(&self) -> bool {
+        self.health == Health::Up
+    }
+
+    /// The child of this child (should it be a supervisor), identified by its child-id.
+    pub fn child(&self, id: impl fmt::Debug) -> Option<&ChildHealth> {
+        find_child(&self.children, id)
+    }
+}
+
+/// A request for the states of the children of a supervisor, sent by [`health`].
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct ProbeRequest(oneshot::Sender<Vec<(String, ChildState)>>);
+
+impl ProbeRequest {
+    pub(crate) fn reply<ID: fmt::Debug>(
+        self,
+        child_states: impl IntoIterator<Item = (ID, ChildState)>,
+    ) {
+        let child_states =
+            child_states.into_iter().map(|(id, state)| (render_id(id), state)).collect();
+        let _ = self.0.send(child_states);
+    }
+}
+
+/// Published by a supervisor as the data of its actor: tells [`health`] how to query the states
+/// of its children.
+#[derive(Clone, Copy)]
+pub(crate) struct SupProbe {
+    send_request: fn(System, ActorID, ProbeRequest) -> StaticBoxedFuture<()>,
+}
+
+impl SupProbe {
+    pub(crate) fn new<M>() -> Self
+    where
+        M: From<ProbeRequest> + Send + 'static,
+    {
+        Self {
+            send_request: |system, sup_id, request| {
+                Box::pin(async move { system.send(sup_id, M::from(request)).await })
+            },
+        }
+    }
+
+    /// The states of the children, unless the supervisor terminates before replying.
+    async fn child_states(
+        &self,
+        system: &System,
+        sup_id: ActorID,
+    ) -> Option<Vec<(String, ChildState)>> {
+        let (tx, rx) = oneshot::channel();
+        (self.send_request)(system.to_owned(), sup_id, ProbeRequest(tx)).await;
+        rx.await.ok()
+    }
+}
+
+fn node_health(system: &System, actor_id: ActorID) -> BoxedFuture<'_, (Health, Vec<ChildHealth>)> {
+    Box::pin(async move {
+        let Some(probe) = system.get_data::<SupProbe>(actor_id).await else {
+            let health =
+                if system.actor_info(actor_id).await.is_some() { Health::Up } else { Health::Down };
+            return (health, vec![])
+        };
+        let Some(child_states) = probe.child_states(system, actor_id).await else {
+            return (Health::Down, vec![])
+        };
+
+        let mut children = vec![];
+        for (id, state) in child_states {
+            let child = match state {
+                ChildState::Running(child_actor_id) => {
+                    let (health, grandchildren) = node_health(system, child_actor_id).await;
+                    // the child has just exited: it is about to be restarted
+                    let health = if health == Health::Down { Health::Restarting } else { health };
+                    ChildHealth {
+                        id,
+                        actor_id: Some(child_actor_id),
+                        health,
+                        children: grandchildren,
+                    }
+                },
+                ChildState::Restarting =>
+                    ChildHealth { id, actor_id: None, health: Health::Restarting, children: vec![] },
+                ChildState::Tripped =>
+                    ChildHealth { id, actor_id: None, health: Health::Degraded, children: vec![] },
+                ChildState::Idle =>
+                    ChildHealth { id, actor_id: None, health: Health::Up, children: vec![] },
+            };
+            children.push(child);
+        }
+        let health = children.iter().map(|child| child.health).max().unwrap_or(Health::Up);
+
+        (health, children)
+    })
+}
+
+fn find_child(children: &[ChildHealth], id: impl fmt::Debug) -> Option<&ChildHealth> {
+    let id = render_id(id);
+    children.iter().find(|child| child.id == id)
+}
+
+fn render_id(id: impl fmt::Debug) -> String {
+    format!("{:?}", id)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use agner_actors::{Context, Exit, System};
+
+    use super::{health, Health, HealthReport};
+    use crate::common::InitType;
+    use crate::mixed::{
+        self, CircuitBreaker, MixedChildSpec, OneForOne, RestartIntensity, SupSpec,
+    };
+    use crate::uniform::{self, UniformChildSpec};
+
+    async fn idle(_context: &mut Context<()>, (): ()) {
+        std::future::pending().await
+    }
+
+    async fn health_when(
+        system: &System,
+        root: agner_actors::ActorID,
+        predicate: impl Fn(&HealthReport) -> bool,
+    ) -> HealthReport {
+        loop {
+            let report = health(system, root).await;
+            if predicate(&report) {
+                break report
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn health_of_a_tree() {
+        let worker = |id| {
+            MixedChildSpec::mixed(id)
+                .behaviour(idle)
+                .args_clone(())
+                .init_type(InitType::no_ack())
+        };
+        let inner_sup_spec = move || {
+            SupSpec::new(CircuitBreaker::new(0, Duration::from_secs(30), Duration::from_secs(60)))
+                .with_child(worker("flaky"))
+        };
+        let sup_spec =
+            SupSpec::new(OneForOne::new(RestartIntensity::new(5, Duration::from_secs(30))))
+                .with_child(worker("acceptor"))
+                .with_child(
+                    MixedChildSpec::mixed("conn_sup")
+                        .behaviour(mixed::run)
+                        .args_call0(inner_sup_spec)
+                        .init_type(InitType::with_ack()),
+                );
+
+        let system = System::new(Default::default());
+        let root = system.spawn(mixed::run, sup_spec, Default::default()).await.unwrap();
+
+        let report = health_when(&system, root, |report| {
+            report.child("conn_sup").and_then(|conn_sup| conn_sup.child("flaky")).is_some()
+        })
+        .await;
+        assert!(report.is_up(), "{:#?}", report);
+        assert!(report.child("acceptor").unwrap().is_up());
+        assert!(report.child("conn_sup").unwrap().is_up());
+        assert!(report.children.iter().any(|child| child.id == r#""acceptor""#));
+
+        // the circuit-breaker of the flaky worker opens: the subtree is degraded
+        let flaky = report.child("conn_sup").unwrap().child("flaky").unwrap().actor_id.unwrap();
+        system.exit(flaky, Exit::from_message("oops")).await;
+        let report = health_when(&system, root, |report| report.health == Health::Degraded).await;
+        assert!(report.child("acceptor").unwrap().is_up());
+        let conn_sup = report.child("conn_sup").unwrap();
+        assert_eq!(conn_sup.health, Health::Degraded);
+        assert_eq!(conn_sup.child("flaky").unwrap().health, Health::Degraded);
+        assert!(conn_sup.child("flaky").unwrap().actor_id.is_none());
+
+        // the tree is gone
+        system.exit(root, Exit::shutdown()).await;
+        system.wait(root).await;
+        let report = health(&system, root).await;
+        assert_eq!(report.health, Health::Down);
+        assert!(report.children.is_empty());
+    }
+
+    #[tokio::test]
+    async fn health_of_a_uniform_supervisor() {
+        let child_spec = UniformChildSpec::uniform()
+            .behaviour(idle)
+            .args_call1(|()| ())
+            .init_type(InitType::no_ack());
+
+        let system = System::new(Default::default());
+        let sup = system
+            .spawn(uniform::run, uniform::SupSpec::new(child_spec), Default::default())
+            .await
+            .unwrap();
+        let w1 = uniform::start_child(&system, sup, ()).await.unwrap();
+        let w2 = uniform::start_child(&system, sup, ()).await.unwrap();
+
+        let report = health(&system, sup).await;
+        assert!(report.is_up(), "{:#?}", report);
+        assert_eq!(report.children.len(), 2);
+        assert_eq!(report.child(w1).unwrap().actor_id, Some(w1));
+        assert_eq!(report.child(w2).unwrap().actor_id, Some(w2));
+
+        uniform::stop_child::<()>(&system, sup, w1).await.unwrap();
+        let report = health_when(&system, sup, |report| report.children.len() == 1).await;
+        assert!(report.child(w1).is_none());
+        assert!(report.child(w2).unwrap().is_up());
+    }
+}
@@ -1,3 +1,4 @@
 pub mod common;
+pub mod health;
 pub mod mixed;
 pub mod uniform;
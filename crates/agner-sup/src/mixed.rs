@@ -8,24 +8,32 @@ mod restart_strategy;
 mod sup_spec;
 mod supervisor;
 
+use std::time::Duration;
+
+pub use crate::common::SupHook;
 use agner_actors::{ActorID, Exit, System};
 use agner_utils::result_err_flatten::ResultErrFlattenIn;
 pub use child_id::ChildID;
-pub use child_spec::{BoxedMixedChildSpec, ChildType, FlatMixedChildSpec, MixedChildSpec};
+pub use child_spec::{
+    BoxedMixedChildSpec, ChildType, FlatMixedChildSpec, MixedChildSpec, RestartWhen,
+};
 pub use restart_intensity::RestartIntensity;
-pub use restart_strategy::{AllForOne, OneForOne, RestForOne, RestartStrategy};
-pub use sup_spec::SupSpec;
+pub use restart_strategy::{
+    AllForOne, BreakerState, ChildRestartStats, ChildState, CircuitBreaker, EscalationReason,
+    MaxStartIntensityReached, OneForOne, RestForOne, RestartStrategy,
+};
+pub use sup_spec::{AutoShutdown, SupSpec, SupSpecError};
 
 pub mod plumbing {
-    pub use super::restart_intensity::{DurationToInstant, ElapsedSince, RestartStats};
+    pub use super::restart_intensity::{
+        DurationToInstant, ElapsedSince, MaxRestartIntensityReached, RestartStats,
+    };
     pub use super::restart_strategy::{Action, Decider};
 }
 
-pub use supervisor::run;
+pub use supervisor::{run, BoxedRestartStrategy, Message, SupervisorError};
 use tokio::sync::oneshot;
 
-use self::supervisor::SupervisorError;
-
 pub async fn start_child<ID, CS>(
     system: &System,
     sup: ActorID,
@@ -34,9 +42,24 @@ pub async fn start_child<ID, CS>(
 where
     ID: ChildID,
     CS: Into<BoxedMixedChildSpec<ID>>,
+{
+    start_child_with_arg(system, sup, child_spec).await
+}
+
+/// Same as [`start_child`], but for a supervisor passing an `Arg` to its children (see
+/// [`SupSpec::with_start_arg`]).
+pub async fn start_child_with_arg<ID, CS, Arg>(
+    system: &System,
+    sup: ActorID,
+    child_spec: CS,
+) -> Result<ActorID, SupervisorError>
+where
+    ID: ChildID,
+    CS: Into<BoxedMixedChildSpec<ID, Arg>>,
+    Arg: Send + 'static,
 {
     let (tx, rx) = oneshot::channel();
-    let message = supervisor::Message::StartChild(child_spec.into(), tx);
+    let message = supervisor::Message::<ID, Arg>::StartChild(child_spec.into(), tx);
     system.send(sup, message).await;
     rx.await.err_flatten_in()
 }
@@ -48,9 +71,54 @@ pub async fn terminate_child<ID>(
 ) -> Result<Exit, SupervisorError>
 where
     ID: ChildID,
+{
+    terminate_child_with_arg::<ID, ()>(system, sup, child_id).await
+}
+
+/// Same as [`terminate_child`], but for a supervisor passing an `Arg` to its children.
+pub async fn terminate_child_with_arg<ID, Arg>(
+    system: &System,
+    sup: ActorID,
+    child_id: ID,
+) -> Result<Exit, SupervisorError>
+where
+    ID: ChildID,
+    Arg: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    let message = supervisor::Message::<ID, Arg>::TerminateChild(child_id, tx);
+    system.send(sup, message).await;
+    rx.await.err_flatten_in()
+}
+
+/// Make sure the child is started (see [`MixedChildSpec::lazy`]), and return its actor-id.
+///
+/// If the child is yet to be started, the reply is sent once the child has been started. A child
+/// that has stopped and is not going to be restarted (e.g. a temporary child that has exited) is
+/// not started: the request fails with [`SupervisorError::ChildStopped`].
+pub async fn ensure_started<ID>(
+    system: &System,
+    sup: ActorID,
+    child_id: ID,
+) -> Result<ActorID, SupervisorError>
+where
+    ID: ChildID,
+{
+    ensure_started_with_arg::<ID, ()>(system, sup, child_id).await
+}
+
+/// Same as [`ensure_started`], but for a supervisor passing an `Arg` to its children.
+pub async fn ensure_started_with_arg<ID, Arg>(
+    system: &System,
+    sup: ActorID,
+    child_id: ID,
+) -> Result<ActorID, SupervisorError>
+where
+    ID: ChildID,
+    Arg: Send + 'static,
 {
     let (tx, rx) = oneshot::channel();
-    let message = supervisor::Message::TerminateChild(child_id, tx);
+    let message = supervisor::Message::<ID, Arg>::EnsureStarted(child_id, tx);
     system.send(sup, message).await;
     rx.await.err_flatten_in()
 }
@@ -61,9 +129,94 @@ pub async fn which_children<ID>(
 ) -> Result<Vec<(ID, ActorID)>, SupervisorError>
 where
     ID: ChildID,
+{
+    which_children_with_arg::<ID, ()>(system, sup).await
+}
+
+/// Same as [`which_children`], but for a supervisor passing an `Arg` to its children.
+pub async fn which_children_with_arg<ID, Arg>(
+    system: &System,
+    sup: ActorID,
+) -> Result<Vec<(ID, ActorID)>, SupervisorError>
+where
+    ID: ChildID,
+    Arg: Send + 'static,
 {
     let (tx, rx) = oneshot::channel();
-    let message = supervisor::Message::WhichChildren(tx);
+    let message = supervisor::Message::<ID, Arg>::WhichChildren(tx);
     system.send(sup, message).await;
     rx.await.map_err(Into::into)
 }
+
+/// The restart statistics of each child of the supervisor.
+pub async fn restart_stats<ID>(
+    system: &System,
+    sup: ActorID,
+) -> Result<Vec<ChildRestartStats<ID, Duration>>, SupervisorError>
+where
+    ID: ChildID,
+{
+    restart_stats_with_arg::<ID, ()>(system, sup).await
+}
+
+/// Same as [`restart_stats`], but for a supervisor passing an `Arg` to its children.
+pub async fn restart_stats_with_arg<ID, Arg>(
+    system: &System,
+    sup: ActorID,
+) -> Result<Vec<ChildRestartStats<ID, Duration>>, SupervisorError>
+where
+    ID: ChildID,
+    Arg: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    let message = supervisor::Message::<ID, Arg>::RestartStats(tx);
+    system.send(sup, message).await;
+    rx.await.map_err(Into::into)
+}
+
+/// Switch the running supervisor to another restart strategy.
+///
+/// The switch takes place once the supervisor is done with the actions decided upon by the current
+/// strategy (e.g. a restart of several children is completed first). The new strategy takes over
+/// the children as they are, along with their restart statistics: e.g. the tightened restart
+/// intensity applies to the restarts that have already happened within its window.
+///
+/// The new strategy should use the same [`Decider`](plumbing::Decider) as the current one (all of
+/// the strategies provided by this crate do), and the decider should support the
+/// [take-over](plumbing::Decider::take_over), otherwise [`SupervisorError::DeciderMismatch`] is
+/// returned and the supervisor keeps on with the current strategy.
+///
+/// Since the supervisor's children IDs cannot be inferred from the strategy, they are specified
+/// explicitly (e.g. `set_restart_strategy::<&'static str, _>(...)`), as with
+/// [`which_children`].
+pub async fn set_restart_strategy<ID, RS>(
+    system: &System,
+    sup: ActorID,
+    restart_strategy: RS,
+) -> Result<(), SupervisorError>
+where
+    ID: ChildID,
+    RS: RestartStrategy<ID> + Sync,
+    RS::Decider: Send + 'static,
+{
+    set_restart_strategy_with_arg::<ID, RS, ()>(system, sup, restart_strategy).await
+}
+
+/// Same as [`set_restart_strategy`], but for a supervisor passing an `Arg` to its children.
+pub async fn set_restart_strategy_with_arg<ID, RS, Arg>(
+    system: &System,
+    sup: ActorID,
+    restart_strategy: RS,
+) -> Result<(), SupervisorError>
+where
+    ID: ChildID,
+    RS: RestartStrategy<ID> + Sync,
+    RS::Decider: Send + 'static,
+    Arg: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    let restart_strategy = BoxedRestartStrategy::new(restart_strategy);
+    let message = supervisor::Message::<ID, Arg>::SetRestartStrategy(restart_strategy, tx);
+    system.send(sup, message).await;
+    rx.await.err_flatten_in()
+}
@@ -1,3 +1,8 @@
+use std::fmt;
+use std::sync::Arc;
+
+use agner_actors::Exit;
+
 use crate::common::{GenChildSpec, ShutdownSequence};
 
 mod flat_mixed_child_spec;
@@ -5,16 +10,25 @@ pub use flat_mixed_child_spec::FlatMixedChildSpec;
 
 pub type MixedChildSpec<ID, B, A, M> = GenChildSpec<B, A, M, Ext<ID>>;
 
-pub type BoxedMixedChildSpec<ID> = Box<dyn FlatMixedChildSpec<ID>>;
+pub type BoxedMixedChildSpec<ID, Arg = ()> = Box<dyn FlatMixedChildSpec<ID, Arg>>;
 
 #[derive(Debug, Clone)]
 pub struct Ext<ID> {
     id: ID,
     child_type: ChildType,
+    significant: bool,
+    lazy: bool,
+    restart_when: Option<RestartWhen>,
     shutdown: ShutdownSequence,
+    concurrent_shutdown: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A predicate on the abnormal exit reasons of a child: the child is restarted only upon those
+/// matching it (see [`MixedChildSpec::restart_when`]).
+#[derive(Clone)]
+pub struct RestartWhen(Arc<dyn Fn(&Exit) -> bool + Send + Sync>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChildType {
     Permanent,
     Transient,
@@ -23,7 +37,15 @@ pub enum ChildType {
 
 impl<ID> MixedChildSpec<ID, (), (), ()> {
     pub fn mixed(id: ID) -> Self {
-        let ext = Ext { id, child_type: ChildType::Permanent, shutdown: Default::default() };
+        let ext = Ext {
+            id,
+            child_type: ChildType::Permanent,
+            significant: false,
+            lazy: false,
+            restart_when: None,
+            shutdown: Default::default(),
+            concurrent_shutdown: false,
+        };
 
         Self::from_ext(ext)
     }
@@ -33,8 +55,68 @@ impl<ID, B, A, M> MixedChildSpec<ID, B, A, M> {
         self.ext_mut().child_type = child_type;
         self
     }
+    /// A significant child shuts the supervisor down upon its termination, provided the child is
+    /// not going to be restarted (i.e. a [transient](ChildType::Transient) child exiting normally,
+    /// or a [temporary](ChildType::Temporary) child exiting for whatever reason).
+    ///
+    /// A [permanent](ChildType::Permanent) child is restarted however it exits, hence it shuts the
+    /// supervisor down only if its restart is denied (see
+    /// [`restart_when`](MixedChildSpec::restart_when)). Note that the supervisor is notified of
+    /// the normal exits of the significant children only: unlike the other permanent children, a
+    /// significant one is restarted upon its normal exit too.
+    ///
+    /// Whether the supervisor waits for its other significant children to terminate too, is
+    /// specified by [`SupSpec::with_auto_shutdown`](crate::mixed::SupSpec::with_auto_shutdown).
+    pub fn significant(mut self, significant: bool) -> Self {
+        self.ext_mut().significant = significant;
+        self
+    }
+    /// A lazy child is not started along with the supervisor, but upon the first
+    /// [`ensure_started`](crate::mixed::ensure_started) request. Once started, it is supervised
+    /// (and restarted) as any other child; until then, it is not restarted along with its
+    /// siblings.
+    ///
+    /// Has no effect on the children added via [`start_child`](crate::mixed::start_child).
+    pub fn lazy(mut self, lazy: bool) -> Self {
+        self.ext_mut().lazy = lazy;
+        self
+    }
+    /// Restart the child upon an abnormal exit only if the exit reason matches the `predicate`.
+    ///
+    /// An abnormal exit not matching it is not restarted, whatever the [child type](ChildType) is:
+    /// the child stays stopped (and, if [significant](MixedChildSpec::significant), may shut the
+    /// supervisor down), as a [transient](ChildType::Transient) child does upon its normal exit.
+    ///
+    /// The normal exits are handled according to the child type regardless of the `predicate`
+    /// (e.g. a [permanent](ChildType::Permanent) child is still restarted upon its normal exit).
+    pub fn restart_when(mut self, predicate: Arc<dyn Fn(&Exit) -> bool + Send + Sync>) -> Self {
+        self.ext_mut().restart_when = Some(RestartWhen(predicate));
+        self
+    }
     pub fn shutdown(mut self, shutdown: ShutdownSequence) -> Self {
         self.ext_mut().shutdown = shutdown;
         self
     }
+    /// Allow the supervisor to stop this child along with its adjacent concurrently-stoppable
+    /// siblings, rather than strictly one after another (see
+    /// [`SupSpec::with_shutdown_concurrency`](crate::mixed::SupSpec::with_shutdown_concurrency)).
+    pub fn concurrent_shutdown(mut self, concurrent_shutdown: bool) -> Self {
+        self.ext_mut().concurrent_shutdown = concurrent_shutdown;
+        self
+    }
+}
+
+impl RestartWhen {
+    pub fn new(predicate: Arc<dyn Fn(&Exit) -> bool + Send + Sync>) -> Self {
+        Self(predicate)
+    }
+    pub fn matches(&self, exit: &Exit) -> bool {
+        (self.0)(exit)
+    }
+}
+
+impl fmt::Debug for RestartWhen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RestartWhen").finish_non_exhaustive()
+    }
 }
@@ -1,24 +1,34 @@
 use std::fmt;
 
+#[cfg(feature = "reg")]
+use agner_reg::RegTx;
+
 use crate::common::gen_child_spec::CreateChild;
 use crate::common::ShutdownSequence;
 use crate::mixed::child_spec::MixedChildSpec;
 use crate::mixed::ChildID;
 
-use super::ChildType;
+use super::{ChildType, RestartWhen};
 
-pub trait FlatMixedChildSpec<ID>:
-    CreateChild<Args = ()> + fmt::Debug + Unpin + Send + Sync + 'static
+/// A type-erased [`MixedChildSpec`], started with an argument of type `Arg`.
+pub trait FlatMixedChildSpec<ID, Arg = ()>:
+    CreateChild<Args = Arg> + fmt::Debug + Unpin + Send + Sync + 'static
 {
     fn id(&self) -> ID;
     fn child_type(&self) -> ChildType;
+    fn significant(&self) -> bool;
+    fn lazy(&self) -> bool;
+    fn restart_when(&self) -> Option<RestartWhen>;
     fn shutdown(&self) -> &ShutdownSequence;
+    fn concurrent_shutdown(&self) -> bool;
+    #[cfg(feature = "reg")]
+    fn reg_tx(&self) -> Option<&RegTx>;
 }
 
-impl<ID, B, A, M> FlatMixedChildSpec<ID> for MixedChildSpec<ID, B, A, M>
+impl<ID, B, A, M, Arg> FlatMixedChildSpec<ID, Arg> for MixedChildSpec<ID, B, A, M>
 where
     ID: ChildID,
-    Self: CreateChild<Args = ()>,
+    Self: CreateChild<Args = Arg>,
     A: fmt::Debug,
     B: Unpin + Send + Sync + 'static,
     A: Unpin + Send + Sync + 'static,
@@ -30,14 +40,30 @@ where
     fn child_type(&self) -> ChildType {
         self.ext().child_type
     }
+    fn significant(&self) -> bool {
+        self.ext().significant
+    }
+    fn lazy(&self) -> bool {
+        self.ext().lazy
+    }
+    fn restart_when(&self) -> Option<RestartWhen> {
+        self.ext().restart_when.to_owned()
+    }
     fn shutdown(&self) -> &ShutdownSequence {
         &self.ext().shutdown
     }
+    fn concurrent_shutdown(&self) -> bool {
+        self.ext().concurrent_shutdown
+    }
+    #[cfg(feature = "reg")]
+    fn reg_tx(&self) -> Option<&RegTx> {
+        MixedChildSpec::reg_tx(self)
+    }
 }
 
-impl<ID, B, A, M> From<MixedChildSpec<ID, B, A, M>> for Box<dyn FlatMixedChildSpec<ID>>
+impl<ID, B, A, M, Arg> From<MixedChildSpec<ID, B, A, M>> for Box<dyn FlatMixedChildSpec<ID, Arg>>
 where
-    MixedChildSpec<ID, B, A, M>: FlatMixedChildSpec<ID>,
+    MixedChildSpec<ID, B, A, M>: FlatMixedChildSpec<ID, Arg>,
 {
     fn from(cs: MixedChildSpec<ID, B, A, M>) -> Self {
         Box::new(cs)
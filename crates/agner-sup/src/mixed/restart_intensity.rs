@@ -1,12 +1,18 @@
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Copy)]
+/// The restart intensity: no more than `max_restarts` restarts within a sliding window of the
+/// `within` duration.
+///
+/// The window is inclusive: a restart that has occurred exactly `within` before `now` is still
+/// within the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RestartIntensity<D> {
     pub max_restarts: usize,
     pub within: D,
 }
 
+/// The record of the restarts, accounted by a [`RestartIntensity`].
 #[derive(Debug, Clone)]
 pub struct RestartStats<I>(VecDeque<I>);
 
@@ -26,6 +32,10 @@ impl<D> RestartIntensity<D> {
         RestartStats::new()
     }
 
+    /// Record a restart at `now`, forgetting those that are out of the window.
+    ///
+    /// Fails if, with this one, there are more than `max_restarts` restarts within the window. The
+    /// instants should be reported in the ascending order.
     pub fn report_exit<I>(
         &self,
         stats: &mut RestartStats<I>,
@@ -44,16 +54,29 @@ impl<D> RestartIntensity<D> {
 }
 
 impl<I> RestartStats<I> {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self(Default::default())
     }
 }
 
+impl<I> Default for RestartStats<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<I> RestartStats<I> {
     fn len(&self) -> usize {
         self.0.len()
     }
-    fn truncate(&mut self, now: &I, within: &<I as ElapsedSince>::Elapsed) -> &mut Self
+    /// The number of the recorded events that have occurred within the `within` before `now`.
+    pub fn count_within(&self, now: &I, within: &<I as ElapsedSince>::Elapsed) -> usize
+    where
+        I: ElapsedSince,
+    {
+        self.0.iter().filter(|past| now.elapsed_since(past) <= *within).count()
+    }
+    pub(crate) fn truncate(&mut self, now: &I, within: &<I as ElapsedSince>::Elapsed) -> &mut Self
     where
         I: ElapsedSince,
     {
@@ -65,11 +88,12 @@ impl<I> RestartStats<I> {
         self
     }
 
-    fn push(&mut self, now: I) -> &mut Self
+    pub(crate) fn push(&mut self, now: I) -> &mut Self
     where
         I: Ord,
     {
-        if self.0.back().into_iter().all(|past| *past < now) {
+        // the instants read from a coarse (or a mock) clock may coincide
+        if self.0.back().into_iter().all(|past| *past <= now) {
             self.0.push_back(now);
             self
         } else {
@@ -125,6 +149,23 @@ fn basic_test_usizes() {
     assert!(intensity.report_exit(&mut stats, 4).is_err());
 }
 
+#[test]
+fn window_is_inclusive() {
+    let intensity = RestartIntensity::new(2, 10);
+    let mut stats = intensity.new_stats();
+
+    assert!(intensity.report_exit(&mut stats, 0).is_ok());
+    assert!(intensity.report_exit(&mut stats, 5).is_ok());
+    assert_eq!(stats.count_within(&10, &10), 2);
+    assert_eq!(stats.count_within(&11, &10), 1);
+    assert!(intensity.report_exit(&mut stats, 10).is_err());
+
+    let mut stats = intensity.new_stats();
+    assert!(intensity.report_exit(&mut stats, 0).is_ok());
+    assert!(intensity.report_exit(&mut stats, 5).is_ok());
+    assert!(intensity.report_exit(&mut stats, 11).is_ok());
+}
+
 #[test]
 fn basic_test_instant_and_duration() {
     let intensity = RestartIntensity::new(3, Duration::from_secs(10));
@@ -1,15 +1,18 @@
-use agner_actors::{ActorID, Exit};
+use agner_actors::{ActorID, ArcError, Exit};
 use std::error::Error as StdError;
 use std::fmt;
+use std::sync::Arc;
 
 mod common_decider;
 mod strategies;
-pub use strategies::{AllForOne, OneForOne, RestForOne};
+pub use common_decider::MaxStartIntensityReached;
+pub use strategies::{AllForOne, CircuitBreaker, OneForOne, RestForOne};
 
 #[cfg(test)]
 mod tests;
 
-use crate::mixed::child_spec::ChildType;
+use crate::mixed::child_spec::{ChildType, RestartWhen};
+use crate::mixed::sup_spec::AutoShutdown;
 
 pub trait RestartStrategy<ID>: Clone + fmt::Debug + Send + 'static {
     type Decider;
@@ -21,12 +24,151 @@ pub trait Decider<ID, D, I>: fmt::Debug + Send + 'static {
     type Error: StdError + Send + Sync + 'static;
 
     fn add_child(&mut self, id: ID, child_type: ChildType) -> Result<(), Self::Error>;
+    /// Add a child that is not started until [`ensure_started`](Self::ensure_started) is invoked
+    /// for it. Once started, the child is supervised as any other child.
+    ///
+    /// The deciders not supporting the lazy children add it as any other child.
+    fn add_lazy_child(&mut self, id: ID, child_type: ChildType) -> Result<(), Self::Error> {
+        self.add_child(id, child_type)
+    }
+    /// Mark the child [significant](crate::mixed::MixedChildSpec::significant).
+    ///
+    /// The deciders not supporting the significant children ignore it.
+    fn set_significant(&mut self, id: ID, significant: bool) -> Result<(), Self::Error> {
+        let _ = (id, significant);
+        Ok(())
+    }
+    /// Specify when the termination of the significant children shuts the supervisor down.
+    ///
+    /// The deciders not supporting the significant children ignore it.
+    fn auto_shutdown(&mut self, auto_shutdown: AutoShutdown) {
+        let _ = auto_shutdown;
+    }
+    /// Restart the child upon an abnormal exit only if the exit reason matches `restart_when`.
+    fn restart_when(&mut self, id: ID, restart_when: RestartWhen) -> Result<(), Self::Error>;
     fn rm_child(&mut self, id: ID) -> Result<(), Self::Error>;
 
+    /// Make sure the child is started: a lazy child that has not been started yet is going to be
+    /// started.
+    ///
+    /// Returns the state of the child: [`Running`](ChildState::Running), or
+    /// [`Restarting`](ChildState::Restarting) if it is yet to be started. A child that has stopped
+    /// and is not going to be restarted (e.g. a temporary child that has exited) is not started:
+    /// it remains [`Idle`](ChildState::Idle).
+    ///
+    /// By default, the state is looked up in the [`child_states`](Self::child_states).
+    fn ensure_started(&mut self, id: ID) -> Result<ChildState, Self::Error>
+    where
+        ID: PartialEq,
+    {
+        let state = self.child_states().into_iter().find(|(child_id, _)| *child_id == id);
+        Ok(state.map_or(ChildState::Idle, |(_, state)| state))
+    }
+
     fn next_action(&mut self) -> Result<Option<Action<ID>>, Self::Error>;
 
     fn exit_signal(&mut self, actor_id: ActorID, exit: Exit, at: I) -> Result<(), Self::Error>;
     fn child_started(&mut self, id: ID, actor_id: ActorID) -> Result<(), Self::Error>;
+
+    /// The child could not be started: treated as the child's failure (counts towards the restart
+    /// intensity, or towards the start intensity if the strategy has a separate one).
+    fn start_failed(&mut self, id: ID, exit: Exit, at: I) -> Result<(), Self::Error>;
+
+    /// Take over the children of the `previous` decider of the same supervisor (see
+    /// [`set_restart_strategy`](crate::mixed::set_restart_strategy)): their states, and their
+    /// restart statistics.
+    ///
+    /// A decider that cannot take over hands the `previous` one back, and the supervisor keeps
+    /// on with it (that is what the default implementation does).
+    fn take_over(&mut self, previous: Self) -> Result<Option<Self>, Self::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Some(previous))
+    }
+
+    /// The restart statistics of each child, as of `at`.
+    fn restart_stats(&self, at: I) -> Vec<ChildRestartStats<ID, D>> {
+        let _ = at;
+        vec![]
+    }
+
+    /// The state of the child's circuit-breaker, if the decider has one (see [`CircuitBreaker`]).
+    fn breaker_state(&self, id: ID) -> Option<BreakerState> {
+        let _ = id;
+        None
+    }
+
+    /// The state of each child (see [`health`](crate::health::health)).
+    fn child_states(&self) -> Vec<(ID, ChildState)> {
+        vec![]
+    }
+
+    /// The instant at which [`wake_up`](Self::wake_up) should be invoked, if any.
+    fn wake_up_at(&self) -> Option<I> {
+        None
+    }
+
+    /// Let the decider act upon the passage of time (e.g. end a circuit-breaker's cooldown).
+    fn wake_up(&mut self, at: I) -> Result<(), Self::Error> {
+        let _ = at;
+        Ok(())
+    }
+}
+
+/// The state of a child's circuit-breaker (see [`CircuitBreaker`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// The child is restarted upon failures.
+    Closed,
+    /// The child has failed too often: it is not restarted until the cooldown is over.
+    Open,
+    /// The cooldown is over: the child is being started once again.
+    HalfOpen,
+}
+
+/// Makes the exit reason a supervisor escalates to its parent with, once the restart intensity is
+/// exceeded (see [`OneForOne::with_escalation`]).
+///
+/// The reason is made of the cause of the escalation: the error telling which child has exceeded
+/// the intensity, with the child's last exit reason as its source.
+#[derive(Clone)]
+pub struct EscalationReason(Arc<dyn Fn(ArcError) -> Exit + Send + Sync>);
+
+/// The state of a child, as seen by its supervisor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChildState {
+    Running(ActorID),
+    /// The child is expected to run, but is yet to be (re)started.
+    Restarting,
+    /// The child's circuit-breaker is [open](BreakerState::Open).
+    Tripped,
+    /// The child is not expected to run (e.g. a lazy child that has not been requested yet, or a
+    /// temporary child that has exited).
+    Idle,
+}
+
+/// The restart statistics of a child (see [`restart_stats`](crate::mixed::restart_stats)).
+///
+/// Note that the restart intensity is shared by all the children of a supervisor: it is tripped
+/// once the restarts of all the children within the `window` exceed `max_restarts`. The only
+/// exception is the [`CircuitBreaker`], which accounts the failures of each child separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChildRestartStats<ID, D> {
+    pub id: ID,
+    /// The number of the child's restarts within the last `window`.
+    pub restarts_in_window: usize,
+    /// The window within which the restart intensity is measured.
+    pub window: D,
+    /// The number of restarts within the `window` allowed by the restart intensity.
+    pub max_restarts: usize,
+    /// The number of the child's failures that have exceeded the restart intensity.
+    ///
+    /// Only a supervisor with the [`CircuitBreaker`] outlives such a failure, thus reports it
+    /// (opening the child's breaker); the other strategies shut the supervisor down upon it.
+    pub trips: usize,
+    /// The state of the child's circuit-breaker, if the supervisor has one.
+    pub breaker: Option<BreakerState>,
 }
 
 #[derive(Debug)]
@@ -35,3 +177,25 @@ pub enum Action<ID> {
     Stop(ID),
     Shutdown(Exit),
 }
+
+impl EscalationReason {
+    pub fn new(make_reason: Arc<dyn Fn(ArcError) -> Exit + Send + Sync>) -> Self {
+        Self(make_reason)
+    }
+    pub fn make_reason(&self, cause: ArcError) -> Exit {
+        (self.0)(cause)
+    }
+}
+
+impl Default for EscalationReason {
+    /// Exit with the cause itself, as a [custom](Exit::custom) reason.
+    fn default() -> Self {
+        Self::new(Arc::new(Exit::custom))
+    }
+}
+
+impl fmt::Debug for EscalationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EscalationReason").finish_non_exhaustive()
+    }
+}
@@ -2,17 +2,21 @@ use std::error::Error as StdError;
 use std::fmt;
 
 use std::collections::{HashSet, VecDeque};
+use std::ops::Add;
 use std::sync::Arc;
 
-use agner_actors::{ActorID, Exit};
+use agner_actors::{ActorID, ArcError, Exit};
 use agner_utils::std_error_pp::StdErrorPP;
 
 use crate::mixed::child_id::ChildID;
-use crate::mixed::child_spec::ChildType;
+use crate::mixed::child_spec::{ChildType, RestartWhen};
 use crate::mixed::restart_intensity::{
     DurationToInstant, ElapsedSince, RestartIntensity, RestartStats,
 };
-use crate::mixed::restart_strategy::{Action, Decider};
+use crate::mixed::restart_strategy::{
+    Action, BreakerState, ChildRestartStats, ChildState, Decider, EscalationReason,
+};
+use crate::mixed::sup_spec::AutoShutdown;
 
 #[derive(Debug, thiserror::Error)]
 pub enum DeciderError {
@@ -35,6 +39,22 @@ pub struct MaxRestartIntensityReached<ID: ChildID, E: StdError> {
     last_error: E,
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error("Max start intensity reached [child-id: {:?}]", child_id)]
+pub struct MaxStartIntensityReached<ID: ChildID, E: StdError> {
+    child_id: ID,
+
+    #[source]
+    last_error: E,
+}
+
+impl<ID: ChildID, E: StdError> MaxStartIntensityReached<ID, E> {
+    /// The child that has failed to start once too many.
+    pub fn child_id(&self) -> ID {
+        self.child_id
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RestartType {
     One,
@@ -46,8 +66,9 @@ pub enum RestartType {
 pub struct CommonDecider<ID, D, I> {
     sup: ActorID,
     sup_state: SupState<ID>,
+    auto_shutdown: AutoShutdown,
 
-    ch_infos: Vec<ChInfo<ID>>,
+    ch_infos: Vec<ChInfo<ID, I>>,
     ch_states: Vec<ChState>,
 
     expected_exits: HashSet<ActorID>,
@@ -56,6 +77,19 @@ pub struct CommonDecider<ID, D, I> {
     restart_type: RestartType,
     restart_intensity: RestartIntensity<D>,
     restart_stats: RestartStats<I>,
+
+    /// If set, the failures to start a child are accounted separately from the failures of the
+    /// running children.
+    start_intensity: Option<RestartIntensity<D>>,
+    start_stats: RestartStats<I>,
+
+    /// If set, the restart intensity is accounted per child, and a child exceeding it is not
+    /// restarted for the `cooldown` (see [`CircuitBreaker`](super::CircuitBreaker)).
+    cooldown: Option<D>,
+
+    /// If set, upon exceeding the restart intensity the supervisor escalates with the reason made
+    /// by it, rather than shuts down.
+    escalation: Option<EscalationReason>,
 }
 
 impl<ID, D, I> CommonDecider<ID, D, I>
@@ -72,6 +106,7 @@ where
         Self {
             sup,
             sup_state: SupState::Running,
+            auto_shutdown: Default::default(),
             ch_infos: Default::default(),
             ch_states: Default::default(),
 
@@ -81,37 +116,84 @@ where
             restart_type,
             restart_intensity,
             restart_stats,
+
+            start_intensity: None,
+            start_stats: RestartStats::new(),
+
+            cooldown: None,
+
+            escalation: None,
         }
     }
+
+    /// Instead of shutting the supervisor down, open the failing child's circuit-breaker for the
+    /// `cooldown`.
+    pub fn with_circuit_breaker(self, cooldown: D) -> Self {
+        Self { cooldown: Some(cooldown), ..self }
+    }
+
+    /// Account the failures to start a child against the `start_intensity`, rather than against
+    /// the restart intensity shared with the failures of the running children.
+    pub fn with_start_intensity(self, start_intensity: RestartIntensity<D>) -> Self {
+        Self { start_intensity: Some(start_intensity), ..self }
+    }
+
+    /// Upon exceeding the restart (or the start) intensity, stop the children and
+    /// [shut down](Action::Shutdown) with the reason made by `escalation`.
+    pub fn with_escalation(self, escalation: EscalationReason) -> Self {
+        Self { escalation: Some(escalation), ..self }
+    }
 }
 
 impl<ID, D, I> Decider<ID, D, I> for CommonDecider<ID, D, I>
 where
     ID: ChildID,
-    I: ElapsedSince<Elapsed = D> + fmt::Debug + Send + 'static,
-    D: DurationToInstant<Instant = I> + fmt::Debug + Send + 'static,
+    I: ElapsedSince<Elapsed = D> + Add<D, Output = I> + fmt::Debug + Send + 'static,
+    D: DurationToInstant<Instant = I> + Ord + fmt::Debug + Send + 'static,
 {
     type Error = DeciderError;
 
     fn add_child(&mut self, id: ID, ch_type: ChildType) -> Result<(), Self::Error> {
-        self.ensure_state_integrity();
-        if self.idx(id).is_ok() {
-            return Err(DeciderError::DuplicateId)
-        }
+        self.push_child(id, ch_type, ChState::ToStart)?;
+        self.sup_state = SupState::Starting;
 
-        tracing::trace!("[sup:{:?}] adding child {:?}/{:?}", self.restart_type, id, ch_type);
+        Ok(())
+    }
 
-        let info = ChInfo { id, ch_type };
-        let state = ChState::ToStart;
+    fn add_lazy_child(&mut self, id: ID, ch_type: ChildType) -> Result<(), Self::Error> {
+        self.push_child(id, ch_type, ChState::Dormant)
+    }
 
-        self.ch_states.push(state);
-        self.ch_infos.push(info);
+    fn set_significant(&mut self, id: ID, significant: bool) -> Result<(), Self::Error> {
+        let idx = self.idx(id)?;
+        self.ch_infos[idx].significant = significant;
+        Ok(())
+    }
 
-        self.sup_state = SupState::Starting;
+    fn auto_shutdown(&mut self, auto_shutdown: AutoShutdown) {
+        self.auto_shutdown = auto_shutdown;
+    }
 
+    fn restart_when(&mut self, id: ID, restart_when: RestartWhen) -> Result<(), Self::Error> {
+        let idx = self.idx(id)?;
+        self.ch_infos[idx].restart_when = Some(restart_when);
         Ok(())
     }
 
+    fn ensure_started(&mut self, id: ID) -> Result<ChildState, Self::Error> {
+        self.ensure_state_integrity();
+
+        let idx = self.idx(id)?;
+        if matches!(self.ch_states[idx], ChState::Dormant) {
+            tracing::trace!("[sup:{:?}] child to be started {:?}", self.restart_type, id);
+            self.ch_states[idx] = ChState::ToStart;
+            if matches!(self.sup_state, SupState::Running) {
+                self.sup_state = SupState::Starting;
+            }
+        }
+        Ok(self.child_state(idx))
+    }
+
     fn rm_child(&mut self, id: ID) -> Result<(), Self::Error> {
         self.ensure_state_integrity();
 
@@ -180,6 +262,133 @@ where
         Ok(action_opt)
     }
 
+    fn start_failed(&mut self, id: ID, exit: Exit, at: I) -> Result<(), Self::Error> {
+        self.ensure_state_integrity();
+
+        let idx = self.idx(id)?;
+        if !matches!(self.ch_states[idx], ChState::ToStart) {
+            return Err(DeciderError::UnexpectedChildState)
+        }
+
+        self.child_exited(idx, exit, at, true);
+        Ok(())
+    }
+
+    fn restart_stats(&self, at: I) -> Vec<ChildRestartStats<ID, D>> {
+        let RestartIntensity { max_restarts, within } = &self.restart_intensity;
+        self.ch_infos
+            .iter()
+            .map(|info| ChildRestartStats {
+                id: info.id,
+                restarts_in_window: info.restarts.count_within(&at, within),
+                window: within.to_owned(),
+                max_restarts: *max_restarts,
+                trips: info.trips,
+                breaker: self.cooldown.as_ref().map(|_| info.breaker.state()),
+            })
+            .collect()
+    }
+
+    fn breaker_state(&self, id: ID) -> Option<BreakerState> {
+        self.cooldown.as_ref()?;
+        self.idx(id).ok().map(|idx| self.ch_infos[idx].breaker.state())
+    }
+
+    fn child_states(&self) -> Vec<(ID, ChildState)> {
+        (0..self.ch_infos.len())
+            .map(|idx| (self.ch_infos[idx].id, self.child_state(idx)))
+            .collect()
+    }
+
+    fn wake_up_at(&self) -> Option<I> {
+        let cooldown = self.cooldown.as_ref()?;
+        self.ch_infos
+            .iter()
+            .filter_map(|info| match &info.breaker {
+                ChBreaker::Open(since) => Some(since.to_owned() + cooldown.to_owned()),
+                _ => None,
+            })
+            .min()
+    }
+
+    fn wake_up(&mut self, at: I) -> Result<(), Self::Error> {
+        self.ensure_state_integrity();
+
+        let Some(cooldown) = self.cooldown.as_ref() else { return Ok(()) };
+        if matches!(self.sup_state, SupState::ShuttingDown(_)) {
+            return Ok(())
+        }
+
+        for idx in 0..self.ch_infos.len() {
+            let info = &mut self.ch_infos[idx];
+            if matches!(&info.breaker, ChBreaker::Open(since) if at.elapsed_since(since) >= *cooldown)
+            {
+                tracing::trace!(
+                    "[sup:{:?}] cooldown is over, starting child {:?} [at: {:?}]",
+                    self.restart_type,
+                    info.id,
+                    at
+                );
+                info.breaker = ChBreaker::HalfOpen;
+                self.ch_states[idx] = ChState::ToStart;
+                if matches!(self.sup_state, SupState::Running) {
+                    self.sup_state = SupState::Starting;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn take_over(&mut self, previous: Self) -> Result<Option<Self>, Self::Error> {
+        previous.ensure_state_integrity();
+
+        tracing::trace!(
+            "[sup:{:?}] taking over the children [previous: {:?}]",
+            self.restart_type,
+            previous.restart_type
+        );
+
+        let Self {
+            sup_state,
+            auto_shutdown,
+            mut ch_infos,
+            mut ch_states,
+            expected_exits,
+            orphans,
+            restart_stats,
+            start_stats,
+            ..
+        } = previous;
+
+        // without a cooldown, an open circuit-breaker would never close: the tripped children are
+        // started right away
+        let mut sup_state = sup_state;
+        if self.cooldown.is_none() {
+            for (info, ch_state) in ch_infos.iter_mut().zip(ch_states.iter_mut()) {
+                if matches!(info.breaker, ChBreaker::Open(_)) &&
+                    matches!(ch_state, ChState::Stopped)
+                {
+                    *ch_state = ChState::ToStart;
+                    if matches!(sup_state, SupState::Running) {
+                        sup_state = SupState::Starting;
+                    }
+                }
+                info.breaker = ChBreaker::Closed;
+            }
+        }
+
+        self.sup_state = sup_state;
+        self.auto_shutdown = auto_shutdown;
+        self.ch_infos = ch_infos;
+        self.ch_states = ch_states;
+        self.expected_exits = expected_exits;
+        self.orphans = orphans;
+        self.restart_stats = restart_stats;
+        self.start_stats = start_stats;
+
+        Ok(None)
+    }
+
     fn child_started(&mut self, id: ID, actor_id: ActorID) -> Result<(), Self::Error> {
         self.ensure_state_integrity();
 
@@ -191,6 +400,14 @@ where
         tracing::trace!("[sup:{:?}] child started {:?} -> {}", self.restart_type, id, actor_id);
         self.ch_states[idx] = ChState::Running(actor_id);
 
+        let info = &mut self.ch_infos[idx];
+        if matches!(info.breaker, ChBreaker::HalfOpen) {
+            tracing::trace!("[sup:{:?}] circuit-breaker closed {:?}", self.restart_type, id);
+            info.breaker = ChBreaker::Closed;
+            info.restarts = RestartStats::new();
+            info.start_failures = RestartStats::new();
+        }
+
         Ok(())
     }
     fn exit_signal(
@@ -210,95 +427,208 @@ where
             self.sup_state = SupState::ShuttingDown(exit);
             Ok(())
         } else if let Some(idx) = self.resolve_actor_id(actor_id) {
-            let ch_type = self.ch_infos[idx].ch_type;
-
-            match (ch_type, exit.is_shutdown() || exit.is_normal()) {
-                (ChildType::Transient, false) | (ChildType::Permanent, _) => (),
-                (ChildType::Transient, true) | (ChildType::Temporary, _) => {
-                    self.ch_states[idx] = ChState::Stopped;
-                    return Ok(())
-                },
-            }
-
-            let result = self.restart_intensity.report_exit(&mut self.restart_stats, at.to_owned());
-
+            self.child_exited(idx, exit, at, false);
+            Ok(())
+        } else if self.expected_exits.remove(&actor_id) {
             tracing::trace!(
-                "[sup:{:?}] child {:?} exited [at: {:?}; will-restart: {}; exit: {}]",
+                "[sup:{:?}] received an expected exit [actor: {}, exit: {}]",
                 self.restart_type,
-                self.ch_infos[idx].id,
-                at,
-                result.is_ok(),
+                actor_id,
                 exit.pp()
             );
+            Ok(())
+        } else {
+            tracing::trace!(
+                "[sup:{:?}] unknown linked actor exited. Shutting down [actor: {}, exit: {}]",
+                self.restart_type,
+                actor_id,
+                exit.pp()
+            );
+            self.sup_state = SupState::ShuttingDown(Exit::linked(actor_id, exit));
+            Ok(())
+        }
+    }
+}
 
-            if result.is_ok() {
-                self.ch_states[idx] = ChState::ToStart;
+impl<ID, D, I> CommonDecider<ID, D, I>
+where
+    ID: ChildID,
+    I: ElapsedSince<Elapsed = D> + fmt::Debug + Send + 'static,
+    D: DurationToInstant<Instant = I> + fmt::Debug + Send + 'static,
+{
+    fn push_child(
+        &mut self,
+        id: ID,
+        ch_type: ChildType,
+        state: ChState,
+    ) -> Result<(), DeciderError> {
+        self.ensure_state_integrity();
+        if self.idx(id).is_ok() {
+            return Err(DeciderError::DuplicateId)
+        }
 
-                let ids_to_restart: VecDeque<_> = match self.restart_type {
-                    RestartType::One => [].into_iter().collect(),
-                    RestartType::All => self
-                        .idxs()
-                        .rev()
-                        .filter(|i| *i != idx)
-                        .map(|i| self.ch_infos[i].id)
-                        .collect(),
-                    RestartType::Rest => self
-                        .idxs()
-                        .rev()
-                        .take_while(|i| *i > idx)
-                        .map(|i| self.ch_infos[i].id)
-                        .collect(),
-                };
+        tracing::trace!(
+            "[sup:{:?}] adding child {:?}/{:?} [{:?}]",
+            self.restart_type,
+            id,
+            ch_type,
+            state
+        );
+
+        let info = ChInfo {
+            id,
+            ch_type,
+            significant: false,
+            restart_when: None,
+            restarts: RestartStats::new(),
+            start_failures: RestartStats::new(),
+            trips: 0,
+            breaker: ChBreaker::Closed,
+        };
 
-                tracing::trace!(
-                    "[sup:{:?}] stopping children before restart: {:?}",
-                    self.restart_type,
-                    ids_to_restart
-                );
+        self.ch_states.push(state);
+        self.ch_infos.push(info);
 
-                match &mut self.sup_state {
-                    SupState::ShuttingDown(_) => (),
+        Ok(())
+    }
 
-                    SupState::Running | SupState::Starting => {
-                        self.sup_state = SupState::Restarting(ids_to_restart);
-                    },
+    fn child_state(&self, idx: usize) -> ChildState {
+        match self.ch_states[idx] {
+            ChState::Running(actor_id) => ChildState::Running(actor_id),
+            _ if matches!(self.ch_infos[idx].breaker, ChBreaker::Open(_)) => ChildState::Tripped,
+            ChState::ToStart => ChildState::Restarting,
+            ChState::Stopped | ChState::Dormant => ChildState::Idle,
+        }
+    }
 
-                    SupState::Restarting(ids) => {
-                        ids.extend(ids_to_restart);
-                    },
+    /// The child (either running or being started) has failed: restart it (along with the
+    /// siblings, depending on the restart type), unless the restart intensity is exceeded.
+    ///
+    /// A significant child that is not to be restarted may shut the supervisor down (see
+    /// [`AutoShutdown`]).
+    ///
+    /// A failure to start the child is accounted against the start intensity, if there is one.
+    fn child_exited(&mut self, idx: usize, exit: Exit, at: I, start_failure: bool) {
+        let ChInfo { id, ch_type, significant, ref restart_when, .. } = self.ch_infos[idx];
+
+        let is_normal = exit.is_shutdown() || exit.is_normal();
+        let restart_denied =
+            !is_normal && restart_when.as_ref().is_some_and(|pred| !pred.matches(&exit));
+
+        match (ch_type, is_normal) {
+            (ChildType::Transient, false) | (ChildType::Permanent, _) if !restart_denied => (),
+            _ => {
+                self.ch_states[idx] = ChState::Stopped;
+
+                if significant &&
+                    !matches!(self.sup_state, SupState::ShuttingDown(_)) &&
+                    self.auto_shutdown_due()
+                {
+                    tracing::trace!(
+                        "[sup:{:?}] significant child {:?} terminated. Shutting down [exit: {}]",
+                        self.restart_type,
+                        id,
+                        exit.pp()
+                    );
+                    self.sup_state = SupState::ShuttingDown(Exit::normal());
                 }
+                return
+            },
+        }
 
-                Ok(())
-            } else {
-                self.ch_states[idx] = ChState::Stopped;
+        let start_intensity = self.start_intensity.as_ref().filter(|_| start_failure);
+        let (intensity, shared_stats) = match start_intensity {
+            Some(start_intensity) => (start_intensity, &mut self.start_stats),
+            None => (&self.restart_intensity, &mut self.restart_stats),
+        };
 
-                let max_restart_intensity_reached = MaxRestartIntensityReached {
-                    child_id: self.ch_infos[idx].id,
-                    last_error: exit,
-                };
-                self.sup_state = SupState::ShuttingDown(Exit::shutdown_with_source(Arc::new(
-                    max_restart_intensity_reached,
-                )));
+        // with a circuit-breaker, the restart intensity is accounted per child
+        let will_restart = if self.cooldown.is_some() {
+            let info = &mut self.ch_infos[idx];
+            let stats = if start_intensity.is_some() {
+                &mut info.start_failures
+            } else {
+                &mut info.restarts
+            };
+            !matches!(info.breaker, ChBreaker::HalfOpen) &&
+                intensity.report_exit(stats, at.to_owned()).is_ok()
+        } else {
+            intensity.report_exit(shared_stats, at.to_owned()).is_ok()
+        };
 
-                Ok(())
+        tracing::trace!(
+            "[sup:{:?}] child {:?} exited [at: {:?}; start-failure: {}; will-restart: {}; exit: {}]",
+            self.restart_type,
+            self.ch_infos[idx].id,
+            at,
+            start_failure,
+            will_restart,
+            exit.pp()
+        );
+
+        if will_restart {
+            self.ch_states[idx] = ChState::ToStart;
+            if self.cooldown.is_none() && start_intensity.is_none() {
+                self.ch_infos[idx]
+                    .restarts
+                    .truncate(&at, &self.restart_intensity.within)
+                    .push(at);
             }
-        } else if self.expected_exits.remove(&actor_id) {
+
+            let ids_to_restart: VecDeque<_> = match self.restart_type {
+                RestartType::One => [].into_iter().collect(),
+                RestartType::All =>
+                    self.idxs().rev().filter(|i| *i != idx).map(|i| self.ch_infos[i].id).collect(),
+                RestartType::Rest => self
+                    .idxs()
+                    .rev()
+                    .take_while(|i| *i > idx)
+                    .map(|i| self.ch_infos[i].id)
+                    .collect(),
+            };
+
             tracing::trace!(
-                "[sup:{:?}] received an expected exit [actor: {}, exit: {}]",
+                "[sup:{:?}] stopping children before restart: {:?}",
                 self.restart_type,
-                actor_id,
-                exit.pp()
+                ids_to_restart
             );
-            Ok(())
+
+            match &mut self.sup_state {
+                SupState::ShuttingDown(_) => (),
+
+                SupState::Running | SupState::Starting => {
+                    self.sup_state = SupState::Restarting(ids_to_restart);
+                },
+
+                SupState::Restarting(ids) => {
+                    ids.extend(ids_to_restart);
+                },
+            }
+        } else if self.cooldown.is_some() {
+            tracing::trace!("[sup:{:?}] circuit-breaker opened {:?}", self.restart_type, id);
+            self.ch_states[idx] = ChState::Stopped;
+            self.ch_infos[idx].trips += 1;
+            self.ch_infos[idx].breaker = ChBreaker::Open(at);
         } else {
+            self.ch_states[idx] = ChState::Stopped;
+            self.ch_infos[idx].trips += 1;
+
+            let child_id = self.ch_infos[idx].id;
+            let source: ArcError = if start_intensity.is_some() {
+                Arc::new(MaxStartIntensityReached { child_id, last_error: exit })
+            } else {
+                Arc::new(MaxRestartIntensityReached { child_id, last_error: exit })
+            };
+            let reason = match &self.escalation {
+                Some(escalation) => escalation.make_reason(source),
+                None => Exit::shutdown_with_source(source),
+            };
             tracing::trace!(
-                "[sup:{:?}] unknown linked actor exited. Shutting down [actor: {}, exit: {}]",
+                "[sup:{:?}] restart intensity exceeded. Shutting down [exit: {}]",
                 self.restart_type,
-                actor_id,
-                exit.pp()
+                reason.pp()
             );
-            self.sup_state = SupState::ShuttingDown(Exit::linked(actor_id, exit));
-            Ok(())
+            self.sup_state = SupState::ShuttingDown(reason);
         }
     }
 }
@@ -312,13 +642,39 @@ enum SupState<ID> {
 }
 
 #[derive(Debug)]
-struct ChInfo<ID> {
+struct ChInfo<ID, I> {
     id: ID,
     ch_type: ChildType,
+    significant: bool,
+    restart_when: Option<RestartWhen>,
+    restarts: RestartStats<I>,
+    start_failures: RestartStats<I>,
+    trips: usize,
+    breaker: ChBreaker<I>,
+}
+
+#[derive(Debug)]
+enum ChBreaker<I> {
+    Closed,
+    /// Opened at the specified instant.
+    Open(I),
+    HalfOpen,
+}
+
+impl<I> ChBreaker<I> {
+    fn state(&self) -> BreakerState {
+        match self {
+            Self::Closed => BreakerState::Closed,
+            Self::Open(_) => BreakerState::Open,
+            Self::HalfOpen => BreakerState::HalfOpen,
+        }
+    }
 }
 
 #[derive(Debug)]
 enum ChState {
+    /// A lazy child that has never been started.
+    Dormant,
     Stopped,
     Running(ActorID),
     ToStart,
@@ -350,6 +706,19 @@ where
         0..self.ch_infos.len()
     }
 
+    fn auto_shutdown_due(&self) -> bool {
+        match self.auto_shutdown {
+            AutoShutdown::Never => false,
+            AutoShutdown::Any => true,
+            AutoShutdown::AllSignificant => self
+                .ch_infos
+                .iter()
+                .zip(&self.ch_states)
+                .filter(|(info, _)| info.significant)
+                .all(|(_, state)| matches!(state, ChState::Stopped)),
+        }
+    }
+
     fn resolve_actor_id(&self, actor_id: ActorID) -> Option<usize> {
         self.ch_states.iter().enumerate().find_map(|(idx, state)| {
             Some(idx).filter(|_| matches!(state, ChState::Running(id) if *id == actor_id))
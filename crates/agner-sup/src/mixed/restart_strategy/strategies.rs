@@ -4,38 +4,106 @@ use crate::mixed::child_id::ChildID;
 use crate::mixed::restart_intensity::RestartIntensity;
 
 use super::common_decider::{CommonDecider, RestartType};
-use super::RestartStrategy;
+use super::{EscalationReason, RestartStrategy};
 
 #[derive(Debug, Clone, Default)]
 pub struct OneForOne {
     restart_intensity: RestartIntensity<Duration>,
+    start_intensity: Option<RestartIntensity<Duration>>,
+    escalation: Option<EscalationReason>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct AllForOne {
     restart_intensity: RestartIntensity<Duration>,
+    start_intensity: Option<RestartIntensity<Duration>>,
+    escalation: Option<EscalationReason>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct RestForOne {
     restart_intensity: RestartIntensity<Duration>,
+    start_intensity: Option<RestartIntensity<Duration>>,
+    escalation: Option<EscalationReason>,
+}
+
+/// Restart each child on its own (as [`OneForOne`]), but instead of shutting the supervisor down
+/// once a child has failed more than `max_failures` times within the `window`, stop restarting
+/// that child for the `cooldown`.
+///
+/// During the cooldown the child's circuit-breaker is [open](super::BreakerState::Open): the
+/// [`ensure_started`](crate::mixed::ensure_started) requests for it fail fast with
+/// [`SupervisorError::CircuitOpen`](crate::mixed::SupervisorError::CircuitOpen). Once the cooldown
+/// is over, the breaker is [half-open](super::BreakerState::HalfOpen): a single attempt to start
+/// the child is made. Should it succeed, the breaker closes; otherwise it opens once again.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    restart_intensity: RestartIntensity<Duration>,
+    start_intensity: Option<RestartIntensity<Duration>>,
+    cooldown: Duration,
 }
 
 impl OneForOne {
     pub fn new(restart_intensity: RestartIntensity<Duration>) -> Self {
-        Self { restart_intensity }
+        Self { restart_intensity, start_intensity: None, escalation: None }
     }
 }
 
 impl AllForOne {
     pub fn new(restart_intensity: RestartIntensity<Duration>) -> Self {
-        Self { restart_intensity }
+        Self { restart_intensity, start_intensity: None, escalation: None }
     }
 }
 
 impl RestForOne {
     pub fn new(restart_intensity: RestartIntensity<Duration>) -> Self {
-        Self { restart_intensity }
+        Self { restart_intensity, start_intensity: None, escalation: None }
+    }
+}
+
+/// The builder methods shared by the strategies supervising a group of children.
+macro_rules! impl_group_strategy {
+    ($($strategy:ident),*) => {$(
+        impl $strategy {
+            /// Once the restart intensity is exceeded, stop the children and exit with the reason
+            /// made by `escalation`, rather than with
+            /// [`Exit::shutdown()`](agner_actors::Exit::shutdown).
+            ///
+            /// The parent sees the actual cause of the failure: e.g. with the
+            /// [default](EscalationReason::default) escalation, the supervisor exits with a
+            /// [custom](agner_actors::Exit::custom) reason telling which child has failed, and
+            /// why.
+            pub fn with_escalation(self, escalation: EscalationReason) -> Self {
+                Self { escalation: Some(escalation), ..self }
+            }
+        }
+    )*};
+}
+
+impl_group_strategy!(OneForOne, AllForOne, RestForOne);
+
+/// The builder methods shared by all the strategies.
+macro_rules! impl_strategy {
+    ($($strategy:ident),*) => {$(
+        impl $strategy {
+            /// Account the failures to start a child against the `start_intensity`, separately
+            /// from the failures of the running children.
+            pub fn with_start_intensity(self, start_intensity: RestartIntensity<Duration>) -> Self {
+                Self { start_intensity: Some(start_intensity), ..self }
+            }
+        }
+    )*};
+}
+
+impl_strategy!(OneForOne, AllForOne, RestForOne, CircuitBreaker);
+
+impl CircuitBreaker {
+    pub fn new(max_failures: usize, window: Duration, cooldown: Duration) -> Self {
+        Self {
+            restart_intensity: RestartIntensity::new(max_failures, window),
+            start_intensity: None,
+            cooldown,
+        }
     }
 }
 
@@ -46,7 +114,9 @@ where
     type Decider = CommonDecider<ID, Duration, Instant>;
 
     fn new_decider(&self, sup_id: agner_actors::ActorID) -> Self::Decider {
-        CommonDecider::new(sup_id, RestartType::One, self.restart_intensity.to_owned())
+        let decider =
+            CommonDecider::new(sup_id, RestartType::One, self.restart_intensity.to_owned());
+        with_escalation(with_start_intensity(decider, self.start_intensity), &self.escalation)
     }
 }
 
@@ -57,7 +127,9 @@ where
     type Decider = CommonDecider<ID, Duration, Instant>;
 
     fn new_decider(&self, sup_id: agner_actors::ActorID) -> Self::Decider {
-        CommonDecider::new(sup_id, RestartType::All, self.restart_intensity.to_owned())
+        let decider =
+            CommonDecider::new(sup_id, RestartType::All, self.restart_intensity.to_owned());
+        with_escalation(with_start_intensity(decider, self.start_intensity), &self.escalation)
     }
 }
 
@@ -68,6 +140,42 @@ where
     type Decider = CommonDecider<ID, Duration, Instant>;
 
     fn new_decider(&self, sup_id: agner_actors::ActorID) -> Self::Decider {
-        CommonDecider::new(sup_id, RestartType::Rest, self.restart_intensity.to_owned())
+        let decider =
+            CommonDecider::new(sup_id, RestartType::Rest, self.restart_intensity.to_owned());
+        with_escalation(with_start_intensity(decider, self.start_intensity), &self.escalation)
+    }
+}
+
+impl<ID> RestartStrategy<ID> for CircuitBreaker
+where
+    ID: ChildID,
+{
+    type Decider = CommonDecider<ID, Duration, Instant>;
+
+    fn new_decider(&self, sup_id: agner_actors::ActorID) -> Self::Decider {
+        let decider =
+            CommonDecider::new(sup_id, RestartType::One, self.restart_intensity.to_owned())
+                .with_circuit_breaker(self.cooldown);
+        with_start_intensity(decider, self.start_intensity)
+    }
+}
+
+fn with_start_intensity<ID>(
+    decider: CommonDecider<ID, Duration, Instant>,
+    start_intensity: Option<RestartIntensity<Duration>>,
+) -> CommonDecider<ID, Duration, Instant> {
+    match start_intensity {
+        Some(start_intensity) => decider.with_start_intensity(start_intensity),
+        None => decider,
+    }
+}
+
+fn with_escalation<ID>(
+    decider: CommonDecider<ID, Duration, Instant>,
+    escalation: &Option<EscalationReason>,
+) -> CommonDecider<ID, Duration, Instant> {
+    match escalation {
+        Some(escalation) => decider.with_escalation(escalation.to_owned()),
+        None => decider,
     }
 }
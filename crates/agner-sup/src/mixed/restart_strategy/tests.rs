@@ -4,9 +4,20 @@ use agner_actors::{ActorID, Exit};
 
 use crate::mixed::restart_intensity::*;
 use crate::mixed::restart_strategy::common_decider::*;
-use crate::mixed::restart_strategy::{Action, ChildType, Decider};
+use crate::mixed::restart_strategy::{
+    Action, BreakerState, ChildRestartStats, ChildState, ChildType, Decider,
+};
 
+mod auto_shutdown;
 mod basic;
+mod circuit_breaker;
+mod escalation;
+mod lazy;
+mod restart_when;
+mod significant;
+mod start_intensity;
+mod stats;
+mod take_over;
 
 fn next_id() -> ActorID {
     static NEXT: AtomicUsize = AtomicUsize::new(0);
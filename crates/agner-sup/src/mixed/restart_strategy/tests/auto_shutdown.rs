@@ -0,0 +1,101 @@
+use super::*;
+
+use crate::mixed::sup_spec::AutoShutdown;
+
+/// Start two significant transient children and an insignificant one.
+fn start_children(auto_shutdown: AutoShutdown) -> (TestDecider, [ActorID; 3]) {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(3, 60));
+    decider.auto_shutdown(auto_shutdown);
+
+    assert!(decider.add_child("first", ChildType::Transient).is_ok());
+    assert!(decider.set_significant("first", true).is_ok());
+    assert!(decider.add_child("second", ChildType::Transient).is_ok());
+    assert!(decider.set_significant("second", true).is_ok());
+    assert!(decider.add_child("other", ChildType::Permanent).is_ok());
+
+    let mut actors = vec![];
+    for id in ["first", "second", "other"] {
+        let action = decider.next_action().unwrap().unwrap();
+        assert!(matches!(&action, Action::Start(started) if *started == id), "{:?}", action);
+        let actor = next_id();
+        assert!(decider.child_started(id, actor).is_ok());
+        actors.push(actor);
+    }
+    assert!(decider.next_action().unwrap().is_none());
+
+    (decider, actors.try_into().unwrap())
+}
+
+fn assert_shuts_down(decider: &mut TestDecider, to_stop: &[ID]) {
+    for id in to_stop {
+        let action = decider.next_action().unwrap().unwrap();
+        assert!(matches!(&action, Action::Stop(stopped) if stopped == id), "{:?}", action);
+    }
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Shutdown(reason) if reason.is_normal()), "{:?}", action);
+}
+
+#[test]
+fn auto_shutdown_never() {
+    let (mut decider, [first, second, _other]) = start_children(AutoShutdown::Never);
+
+    assert!(decider.exit_signal(first, Exit::normal(), next_tick()).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+    assert!(decider.exit_signal(second, Exit::normal(), next_tick()).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+}
+
+#[test]
+fn auto_shutdown_any() {
+    let (mut decider, [_first, second, _other]) = start_children(AutoShutdown::Any);
+
+    assert!(decider.exit_signal(second, Exit::normal(), next_tick()).is_ok());
+    assert_shuts_down(&mut decider, &["other", "first"]);
+}
+
+#[test]
+fn auto_shutdown_all_significant() {
+    let (mut decider, [first, second, _other]) = start_children(AutoShutdown::AllSignificant);
+
+    assert!(decider.exit_signal(first, Exit::normal(), next_tick()).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+
+    // a failed child is restarted, hence has not terminated yet
+    assert!(decider.exit_signal(second, Exit::from_message("failure"), next_tick()).is_ok());
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("second")), "{:?}", action);
+    let second = next_id();
+    assert!(decider.child_started("second", second).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+
+    assert!(decider.exit_signal(second, Exit::normal(), next_tick()).is_ok());
+    assert_shuts_down(&mut decider, &["other"]);
+}
+
+#[test]
+fn auto_shutdown_all_significant_waits_for_dormant_children() {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(3, 60));
+    decider.auto_shutdown(AutoShutdown::AllSignificant);
+
+    assert!(decider.add_child("eager", ChildType::Temporary).is_ok());
+    assert!(decider.set_significant("eager", true).is_ok());
+    assert!(decider.add_lazy_child("lazy", ChildType::Temporary).is_ok());
+    assert!(decider.set_significant("lazy", true).is_ok());
+
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("eager")), "{:?}", action);
+    let eager = next_id();
+    assert!(decider.child_started("eager", eager).is_ok());
+
+    assert!(decider.exit_signal(eager, Exit::normal(), next_tick()).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+
+    assert_eq!(decider.ensure_started("lazy").unwrap(), ChildState::Restarting);
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("lazy")), "{:?}", action);
+    let lazy = next_id();
+    assert!(decider.child_started("lazy", lazy).is_ok());
+
+    assert!(decider.exit_signal(lazy, Exit::from_message("failure"), next_tick()).is_ok());
+    assert_shuts_down(&mut decider, &[]);
+}
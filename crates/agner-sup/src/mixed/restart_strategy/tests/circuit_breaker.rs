@@ -0,0 +1,101 @@
+use super::*;
+
+fn start(decider: &mut TestDecider, id: ID) -> ActorID {
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start(started) if *started == id), "{:?}", action);
+    let actor = next_id();
+    assert!(decider.child_started(id, actor).is_ok());
+    actor
+}
+
+/// At most two failures within 10 ticks; the cooldown is 5 ticks.
+fn new_decider() -> (TestDecider, ActorID, ActorID) {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(2, 10))
+        .with_circuit_breaker(5);
+
+    assert!(decider.add_child("flaky", ChildType::Permanent).is_ok());
+    assert!(decider.add_child("other", ChildType::Permanent).is_ok());
+    let flaky = start(&mut decider, "flaky");
+    let other = start(&mut decider, "other");
+    assert!(decider.next_action().unwrap().is_none());
+
+    (decider, flaky, other)
+}
+
+fn trip(decider: &mut TestDecider, mut flaky: ActorID, at: usize) {
+    for at in [at, at + 1] {
+        assert!(decider.exit_signal(flaky, Exit::from_message("failure"), at).is_ok());
+        flaky = start(decider, "flaky");
+    }
+    assert!(decider.exit_signal(flaky, Exit::from_message("failure"), at + 2).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+}
+
+#[test]
+fn breaker_opens_instead_of_shutting_down() {
+    let (mut decider, flaky, other) = new_decider();
+    assert_eq!(decider.breaker_state("flaky"), Some(BreakerState::Closed));
+    assert_eq!(decider.wake_up_at(), None);
+
+    trip(&mut decider, flaky, 1);
+    assert_eq!(decider.breaker_state("flaky"), Some(BreakerState::Open));
+    assert_eq!(decider.breaker_state("other"), Some(BreakerState::Closed));
+    assert_eq!(decider.wake_up_at(), Some(8));
+    assert_eq!(decider.ensure_started("flaky").unwrap(), ChildState::Tripped);
+    assert_eq!(decider.restart_stats(3)[0].trips, 1);
+    assert_eq!(decider.restart_stats(3)[0].breaker, Some(BreakerState::Open));
+
+    // the other children are restarted as usual
+    assert!(decider.exit_signal(other, Exit::from_message("failure"), 4).is_ok());
+    start(&mut decider, "other");
+    assert!(decider.next_action().unwrap().is_none());
+
+    // too early
+    assert!(decider.wake_up(7).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+    assert_eq!(decider.breaker_state("flaky"), Some(BreakerState::Open));
+}
+
+#[test]
+fn breaker_closes_once_the_child_starts() {
+    let (mut decider, flaky, _other) = new_decider();
+    trip(&mut decider, flaky, 1);
+
+    assert!(decider.wake_up(8).is_ok());
+    assert_eq!(decider.breaker_state("flaky"), Some(BreakerState::HalfOpen));
+    assert_eq!(decider.wake_up_at(), None);
+    let flaky = start(&mut decider, "flaky");
+    assert_eq!(decider.breaker_state("flaky"), Some(BreakerState::Closed));
+    assert_eq!(decider.restart_stats(8)[0].restarts_in_window, 0);
+    assert!(decider.next_action().unwrap().is_none());
+
+    // the failures before the cooldown do not count
+    trip(&mut decider, flaky, 9);
+    assert_eq!(decider.breaker_state("flaky"), Some(BreakerState::Open));
+    assert_eq!(decider.restart_stats(11)[0].trips, 2);
+}
+
+#[test]
+fn breaker_reopens_if_the_child_fails_to_start() {
+    let (mut decider, flaky, _other) = new_decider();
+    trip(&mut decider, flaky, 1);
+
+    assert!(decider.wake_up(8).is_ok());
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("flaky")), "{:?}", action);
+    assert!(decider.start_failed("flaky", Exit::from_message("failure"), 8).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+
+    assert_eq!(decider.breaker_state("flaky"), Some(BreakerState::Open));
+    assert_eq!(decider.wake_up_at(), Some(13));
+}
+
+#[test]
+fn no_breaker_by_default() {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(2, 10));
+    assert!(decider.add_child("child", ChildType::Permanent).is_ok());
+    start(&mut decider, "child");
+
+    assert_eq!(decider.breaker_state("child"), None);
+    assert_eq!(decider.restart_stats(0)[0].breaker, None);
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use agner_utils::std_error_pp::StdErrorPP;
+
+use crate::mixed::restart_strategy::EscalationReason;
+
+use super::*;
+
+fn crash_past_the_intensity(decider: &mut TestDecider) -> Action<ID> {
+    assert!(decider.add_child("first", ChildType::Permanent).is_ok());
+    assert!(decider.add_child("second", ChildType::Permanent).is_ok());
+
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("first")), "{:?}", action);
+    let first = next_id();
+    assert!(decider.child_started("first", first).is_ok());
+
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("second")), "{:?}", action);
+    let second = next_id();
+    assert!(decider.child_started("second", second).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+
+    assert!(decider
+        .exit_signal(second, Exit::from_message("disk full"), next_tick())
+        .is_ok());
+
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Stop("first")), "{:?}", action);
+    assert!(decider.exit_signal(first, Exit::shutdown(), next_tick()).is_ok());
+
+    decider.next_action().unwrap().unwrap()
+}
+
+#[test]
+fn shuts_down_by_default() {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(0, 10));
+
+    let action = crash_past_the_intensity(&mut decider);
+    assert!(matches!(&action, Action::Shutdown(reason) if reason.is_shutdown()), "{:?}", action);
+}
+
+#[test]
+fn escalates_the_cause() {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(0, 10))
+        .with_escalation(Default::default());
+
+    let Action::Shutdown(reason) = crash_past_the_intensity(&mut decider) else {
+        panic!("expected a shutdown")
+    };
+    assert!(reason.is_custom(), "{}", reason.pp());
+
+    let rendered = reason.pp().to_string();
+    assert!(
+        rendered.contains("Max restart intensity reached [child-id: \"second\"]"),
+        "{}",
+        rendered
+    );
+    assert!(rendered.contains("disk full"), "{}", rendered);
+    assert!(decider.expected_exits().is_empty());
+}
+
+#[test]
+fn escalates_with_the_configured_reason() {
+    let escalation =
+        EscalationReason::new(Arc::new(|cause| Exit::shutdown_with_source(cause).with_note("sla")));
+    let mut decider = TestDecider::new(next_id(), RestartType::All, RestartIntensity::new(0, 10))
+        .with_escalation(escalation);
+
+    let Action::Shutdown(reason) = crash_past_the_intensity(&mut decider) else {
+        panic!("expected a shutdown")
+    };
+    assert!(reason.is_shutdown(), "{}", reason.pp());
+    assert_eq!(reason.notes(), ["sla"]);
+    assert!(reason.pp().to_string().contains("disk full"), "{}", reason.pp());
+}
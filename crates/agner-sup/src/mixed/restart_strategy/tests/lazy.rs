@@ -0,0 +1,69 @@
+use super::*;
+
+#[test]
+fn lazy_child_is_started_on_demand() {
+    let sup = next_id();
+    let mut decider = TestDecider::new(sup, RestartType::All, RestartIntensity::new(3, 60));
+
+    assert!(decider.add_child("eager", ChildType::Permanent).is_ok());
+    assert!(decider.add_lazy_child("lazy", ChildType::Permanent).is_ok());
+
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("eager")), "{:?}", action);
+    let eager = next_id();
+    assert!(decider.child_started("eager", eager).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+
+    // the dormant lazy child is not involved in the restart of its siblings
+    assert!(decider.exit_signal(eager, Exit::from_message("failure"), next_tick()).is_ok());
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("eager")), "{:?}", action);
+    let eager = next_id();
+    assert!(decider.child_started("eager", eager).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+
+    assert_eq!(decider.ensure_started("lazy").unwrap(), ChildState::Restarting);
+    assert_eq!(decider.ensure_started("lazy").unwrap(), ChildState::Restarting);
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("lazy")), "{:?}", action);
+    let lazy = next_id();
+    assert!(decider.child_started("lazy", lazy).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+    assert_eq!(decider.ensure_started("lazy").unwrap(), ChildState::Running(lazy));
+
+    // once started, the lazy child is restarted along with its siblings
+    assert!(decider.exit_signal(eager, Exit::from_message("failure"), next_tick()).is_ok());
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Stop("lazy")), "{:?}", action);
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("eager")), "{:?}", action);
+    assert!(decider.child_started("eager", next_id()).is_ok());
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("lazy")), "{:?}", action);
+}
+
+#[test]
+fn stopped_child_is_not_started() {
+    let sup = next_id();
+    let mut decider = TestDecider::new(sup, RestartType::One, RestartIntensity::new(3, 60));
+
+    assert!(decider.add_child("temporary", ChildType::Temporary).is_ok());
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("temporary")), "{:?}", action);
+    let temporary = next_id();
+    assert!(decider.child_started("temporary", temporary).is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+
+    assert!(decider
+        .exit_signal(temporary, Exit::from_message("failure"), next_tick())
+        .is_ok());
+    assert!(decider.next_action().unwrap().is_none());
+    assert_eq!(decider.ensure_started("temporary").unwrap(), ChildState::Idle);
+    assert!(decider.next_action().unwrap().is_none());
+}
+
+#[test]
+fn ensure_started_with_unknown_id() {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(3, 60));
+    assert!(matches!(decider.ensure_started("unknown"), Err(DeciderError::UnknownId)));
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use crate::mixed::RestartWhen;
+
+use super::*;
+
+fn start(decider: &mut TestDecider, id: ID) -> ActorID {
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start(started) if *started == id), "{:?}", action);
+    let actor = next_id();
+    assert!(decider.child_started(id, actor).is_ok());
+    actor
+}
+
+fn restarted(child_type: ChildType, exit: Exit) -> bool {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(3, 60));
+    assert!(decider.add_child("child", child_type).is_ok());
+    let restart_when = RestartWhen::new(Arc::new(|exit: &Exit| {
+        exit.to_string().contains("connection reset") ||
+            std::error::Error::source(exit)
+                .is_some_and(|source| source.to_string().contains("connection reset"))
+    }));
+    assert!(decider.restart_when("child", restart_when).is_ok());
+
+    let child = start(&mut decider, "child");
+    assert!(decider.exit_signal(child, exit, next_tick()).is_ok());
+    match decider.next_action().unwrap() {
+        Some(Action::Start("child")) => true,
+        None => false,
+        unexpected => panic!("unexpected action: {:?}", unexpected),
+    }
+}
+
+#[test]
+fn restart_only_on_matching_exits() {
+    for child_type in [ChildType::Permanent, ChildType::Transient] {
+        assert!(restarted(child_type, Exit::from_message("connection reset")));
+        assert!(!restarted(child_type, Exit::from_message("auth failure")));
+    }
+    assert!(restarted(ChildType::Permanent, Exit::normal()));
+    assert!(!restarted(ChildType::Transient, Exit::normal()));
+    assert!(!restarted(ChildType::Temporary, Exit::from_message("connection reset")));
+}
+
+#[test]
+fn restart_when_with_unknown_id() {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(3, 60));
+    let restart_when = RestartWhen::new(Arc::new(|_: &Exit| true));
+    assert!(matches!(decider.restart_when("unknown", restart_when), Err(DeciderError::UnknownId)));
+}
+
+#[test]
+fn denied_restart_of_a_significant_child_shuts_down() {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(3, 60));
+    assert!(decider.add_child("child", ChildType::Permanent).is_ok());
+    assert!(decider.set_significant("child", true).is_ok());
+    let restart_when = RestartWhen::new(Arc::new(|_: &Exit| false));
+    assert!(decider.restart_when("child", restart_when).is_ok());
+
+    let child = start(&mut decider, "child");
+    assert!(decider
+        .exit_signal(child, Exit::from_message("auth failure"), next_tick())
+        .is_ok());
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Shutdown(reason) if reason.is_normal()), "{:?}", action);
+}
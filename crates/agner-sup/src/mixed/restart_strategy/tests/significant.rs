@@ -0,0 +1,109 @@
+use super::*;
+
+#[derive(Debug)]
+enum Outcome {
+    Ignored,
+    Restarted,
+    ShutDown,
+}
+
+fn child_exits(
+    restart_type: RestartType,
+    child_type: ChildType,
+    significant: bool,
+    exit: Exit,
+) -> Outcome {
+    let sup = next_id();
+
+    let mut decider = TestDecider::new(sup, restart_type, RestartIntensity::new(3, 60));
+
+    assert!(decider.add_child("other", ChildType::Permanent).is_ok());
+    assert!(decider.add_child("child", child_type).is_ok());
+    assert!(decider.set_significant("child", significant).is_ok());
+
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("other")), "{:?}", action);
+    let other = next_id();
+    assert!(decider.child_started("other", other).is_ok());
+
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("child")), "{:?}", action);
+    let child = next_id();
+    assert!(decider.child_started("child", child).is_ok());
+
+    assert!(decider.next_action().unwrap().is_none());
+
+    assert!(decider.exit_signal(child, exit, next_tick()).is_ok());
+
+    match decider.next_action().unwrap() {
+        None => Outcome::Ignored,
+        Some(Action::Start("child")) => Outcome::Restarted,
+        Some(Action::Stop("other")) if restart_type == RestartType::All => {
+            assert!(decider.exit_signal(other, Exit::shutdown(), next_tick()).is_ok());
+            match decider.next_action().unwrap() {
+                Some(Action::Start("other")) => Outcome::Restarted,
+                Some(Action::Shutdown(reason)) if reason.is_normal() => Outcome::ShutDown,
+                unexpected => panic!("unexpected: {:?}", unexpected),
+            }
+        },
+        Some(Action::Stop("other")) => {
+            assert!(decider.exit_signal(other, Exit::shutdown(), next_tick()).is_ok());
+            let action = decider.next_action().unwrap().unwrap();
+            assert!(
+                matches!(&action, Action::Shutdown(reason) if reason.is_normal()),
+                "{:?}",
+                action
+            );
+            Outcome::ShutDown
+        },
+        unexpected => panic!("unexpected: {:?}", unexpected),
+    }
+}
+
+fn failure() -> Exit {
+    Exit::from_message("failure")
+}
+
+#[test]
+fn significant_permanent_child() {
+    for restart_type in [RestartType::One, RestartType::All] {
+        for significant in [false, true] {
+            for exit in [Exit::normal(), failure()] {
+                let outcome = child_exits(restart_type, ChildType::Permanent, significant, exit);
+                assert!(matches!(outcome, Outcome::Restarted), "{:?}", outcome);
+            }
+        }
+    }
+}
+
+#[test]
+fn significant_transient_child() {
+    for restart_type in [RestartType::One, RestartType::All] {
+        let outcome = child_exits(restart_type, ChildType::Transient, false, Exit::normal());
+        assert!(matches!(outcome, Outcome::Ignored), "{:?}", outcome);
+
+        let outcome = child_exits(restart_type, ChildType::Transient, true, Exit::normal());
+        assert!(matches!(outcome, Outcome::ShutDown), "{:?}", outcome);
+
+        let outcome = child_exits(restart_type, ChildType::Transient, true, Exit::shutdown());
+        assert!(matches!(outcome, Outcome::ShutDown), "{:?}", outcome);
+
+        for significant in [false, true] {
+            let outcome = child_exits(restart_type, ChildType::Transient, significant, failure());
+            assert!(matches!(outcome, Outcome::Restarted), "{:?}", outcome);
+        }
+    }
+}
+
+#[test]
+fn significant_temporary_child() {
+    for restart_type in [RestartType::One, RestartType::All] {
+        for exit in [Exit::normal(), failure()] {
+            let outcome = child_exits(restart_type, ChildType::Temporary, false, exit.to_owned());
+            assert!(matches!(outcome, Outcome::Ignored), "{:?}", outcome);
+
+            let outcome = child_exits(restart_type, ChildType::Temporary, true, exit);
+            assert!(matches!(outcome, Outcome::ShutDown), "{:?}", outcome);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+fn start(decider: &mut TestDecider, id: ID) -> ActorID {
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start(started) if *started == id), "{:?}", action);
+    let actor = next_id();
+    assert!(decider.child_started(id, actor).is_ok());
+    actor
+}
+
+fn fail_to_start(decider: &mut TestDecider, id: ID, at: usize) {
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start(started) if *started == id), "{:?}", action);
+    assert!(decider.start_failed(id, Exit::from_message("init failure"), at).is_ok());
+}
+
+fn is_shutting_down(decider: &mut TestDecider) -> bool {
+    matches!(decider.next_action().unwrap(), Some(Action::Shutdown(_)))
+}
+
+/// At most one runtime failure and three start failures within 10 ticks.
+fn new_decider() -> (TestDecider, ActorID) {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(1, 10))
+        .with_start_intensity(RestartIntensity::new(3, 10));
+
+    assert!(decider.add_child("child", ChildType::Permanent).is_ok());
+    let child = start(&mut decider, "child");
+    assert!(decider.next_action().unwrap().is_none());
+
+    (decider, child)
+}
+
+#[test]
+fn start_failures_do_not_spend_the_restart_intensity() {
+    let (mut decider, child) = new_decider();
+
+    assert!(decider.exit_signal(child, Exit::from_message("failure"), 1).is_ok());
+    for at in 2..5 {
+        fail_to_start(&mut decider, "child", at);
+    }
+    let child = start(&mut decider, "child");
+    assert!(decider.next_action().unwrap().is_none());
+    assert_eq!(decider.restart_stats(5)[0].restarts_in_window, 1);
+
+    // the second runtime failure exceeds the restart intensity
+    assert!(decider.exit_signal(child, Exit::from_message("failure"), 6).is_ok());
+    assert!(is_shutting_down(&mut decider));
+}
+
+#[test]
+fn start_failures_exceed_the_start_intensity() {
+    let (mut decider, child) = new_decider();
+
+    assert!(decider.exit_signal(child, Exit::from_message("failure"), 1).is_ok());
+    for at in 2..5 {
+        fail_to_start(&mut decider, "child", at);
+    }
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start("child")), "{:?}", action);
+    assert!(decider.start_failed("child", Exit::from_message("init failure"), 5).is_ok());
+
+    let Some(Action::Shutdown(exit)) = decider.next_action().unwrap() else {
+        panic!("expected a shutdown")
+    };
+    let reached = exit.source_downcast_ref::<MaxStartIntensityReached<ID, Exit>>().unwrap();
+    assert_eq!(reached.child_id(), "child");
+}
+
+#[test]
+fn start_failures_share_the_restart_intensity_by_default() {
+    let mut decider = TestDecider::new(next_id(), RestartType::One, RestartIntensity::new(1, 10));
+
+    assert!(decider.add_child("child", ChildType::Permanent).is_ok());
+    let child = start(&mut decider, "child");
+
+    assert!(decider.exit_signal(child, Exit::from_message("failure"), 1).is_ok());
+    fail_to_start(&mut decider, "child", 2);
+    assert!(is_shutting_down(&mut decider));
+}
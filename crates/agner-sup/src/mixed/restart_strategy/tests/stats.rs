@@ -0,0 +1,54 @@
+use super::*;
+
+fn start(decider: &mut TestDecider, id: ID) -> ActorID {
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start(started) if *started == id), "{:?}", action);
+    let actor = next_id();
+    assert!(decider.child_started(id, actor).is_ok());
+    actor
+}
+
+#[test]
+fn restart_stats_are_tracked_per_child() {
+    let sup = next_id();
+    let mut decider = TestDecider::new(sup, RestartType::One, RestartIntensity::new(2, 10));
+
+    assert!(decider.add_child("one", ChildType::Permanent).is_ok());
+    assert!(decider.add_child("two", ChildType::Permanent).is_ok());
+    let mut one = start(&mut decider, "one");
+    let two = start(&mut decider, "two");
+    assert!(decider.next_action().unwrap().is_none());
+
+    for at in [1, 2] {
+        assert!(decider.exit_signal(one, Exit::from_message("failure"), at).is_ok());
+        one = start(&mut decider, "one");
+    }
+
+    let stats = decider.restart_stats(5);
+    assert_eq!(
+        stats,
+        [
+            ChildRestartStats {
+                id: "one",
+                restarts_in_window: 2,
+                window: 10,
+                max_restarts: 2,
+                trips: 0,
+                breaker: None,
+            },
+            ChildRestartStats {
+                id: "two",
+                restarts_in_window: 0,
+                window: 10,
+                max_restarts: 2,
+                trips: 0,
+                breaker: None,
+            },
+        ]
+    );
+    assert_eq!(decider.restart_stats(13)[0].restarts_in_window, 0);
+
+    assert!(decider.exit_signal(two, Exit::from_message("failure"), 3).is_ok());
+    assert_eq!(decider.restart_stats(3)[1].trips, 1);
+    assert_eq!(decider.restart_stats(3)[1].restarts_in_window, 0);
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+fn start(decider: &mut TestDecider, id: ID) -> ActorID {
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Start(started) if *started == id), "{:?}", action);
+    let actor = next_id();
+    assert!(decider.child_started(id, actor).is_ok());
+    actor
+}
+
+fn one_for_one(sup: ActorID) -> (TestDecider, ActorID, ActorID) {
+    let mut decider = TestDecider::new(sup, RestartType::One, RestartIntensity::new(5, 60));
+    assert!(decider.add_child("first", ChildType::Permanent).is_ok());
+    assert!(decider.add_child("second", ChildType::Permanent).is_ok());
+    let first = start(&mut decider, "first");
+    let second = start(&mut decider, "second");
+    assert!(decider.next_action().unwrap().is_none());
+
+    (decider, first, second)
+}
+
+fn switch(previous: TestDecider, decider: TestDecider) -> TestDecider {
+    let mut decider = decider;
+    assert!(matches!(decider.take_over(previous), Ok(None)));
+    decider
+}
+
+#[test]
+fn running_children_are_taken_over() {
+    let sup = next_id();
+    let (previous, first, second) = one_for_one(sup);
+    let mut decider =
+        switch(previous, TestDecider::new(sup, RestartType::All, RestartIntensity::new(5, 60)));
+    assert!(decider.next_action().unwrap().is_none());
+    assert_eq!(decider.ensure_started("first").unwrap(), ChildState::Running(first));
+
+    // now all of the children are restarted
+    assert!(decider.exit_signal(second, Exit::from_message("failure"), 1).is_ok());
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Stop("first")), "{:?}", action);
+    assert!(decider.expected_exits().contains(&first));
+    start(&mut decider, "first");
+    start(&mut decider, "second");
+    assert!(decider.next_action().unwrap().is_none());
+}
+
+#[test]
+fn restart_stats_are_taken_over() {
+    let sup = next_id();
+    let (mut previous, first, _second) = one_for_one(sup);
+    assert!(previous.exit_signal(first, Exit::from_message("failure"), 1).is_ok());
+    let first = start(&mut previous, "first");
+    assert!(previous.next_action().unwrap().is_none());
+
+    let mut decider =
+        switch(previous, TestDecider::new(sup, RestartType::One, RestartIntensity::new(1, 60)));
+    assert_eq!(decider.restart_stats(2)[0].restarts_in_window, 1);
+    assert_eq!(decider.restart_stats(2)[0].max_restarts, 1);
+
+    // the restart that happened before the switch counts towards the tightened intensity
+    assert!(decider.exit_signal(first, Exit::from_message("failure"), 2).is_ok());
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Stop("second")), "{:?}", action);
+    let action = decider.next_action().unwrap().unwrap();
+    assert!(matches!(&action, Action::Shutdown(_)), "{:?}", action);
+}
+
+#[test]
+fn open_breaker_does_not_outlive_the_cooldown() {
+    let sup = next_id();
+    let mut previous = TestDecider::new(sup, RestartType::One, RestartIntensity::new(0, 60))
+        .with_circuit_breaker(10);
+    assert!(previous.add_child("child", ChildType::Permanent).is_ok());
+    let child = start(&mut previous, "child");
+    assert!(previous.exit_signal(child, Exit::from_message("failure"), 1).is_ok());
+    assert_eq!(previous.breaker_state("child"), Some(BreakerState::Open));
+
+    let mut decider =
+        switch(previous, TestDecider::new(sup, RestartType::One, RestartIntensity::new(5, 60)));
+    assert_eq!(decider.breaker_state("child"), None);
+    assert_eq!(decider.child_states(), [("child", ChildState::Restarting)]);
+    start(&mut decider, "child");
+    assert!(decider.next_action().unwrap().is_none());
+}
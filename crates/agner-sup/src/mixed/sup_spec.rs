@@ -1,25 +1,148 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::common::{ShutdownSequenceError, SupHook};
+use crate::mixed::child_id::ChildID;
 use crate::mixed::child_spec::BoxedMixedChildSpec;
 
+/// When the supervisor shuts itself down, as its
+/// [significant](crate::mixed::MixedChildSpec::significant) children terminate.
+///
+/// A significant child is deemed terminated when it exits and is not going to be restarted. Upon
+/// the auto-shutdown, the supervisor stops the remaining children, and exits with
+/// [`Exit::normal()`](agner_actors::Exit::normal).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AutoShutdown {
+    /// The significant children have no effect on the supervisor's lifetime.
+    Never,
+    /// The supervisor shuts down as soon as any of its significant children terminates.
+    #[default]
+    Any,
+    /// The supervisor shuts down once all of its significant children have terminated.
+    AllSignificant,
+}
+
+/// A mistake in a [`SupSpec`] (see [`SupSpec::validate`]).
+#[derive(Debug, thiserror::Error)]
+pub enum SupSpecError<ID: ChildID> {
+    #[error("Duplicate child ID: {:?}", _0)]
+    DuplicateId(ID),
+
+    #[error("The children {:?} and {:?} register under the same name", _0, _1)]
+    DuplicateRegistration(ID, ID),
+
+    #[error("Invalid shutdown sequence of the child {:?}", _0)]
+    InvalidShutdown(ID, #[source] ShutdownSequenceError),
+}
+
 #[derive(Debug)]
-pub struct SupSpec<ID, RS> {
+pub struct SupSpec<ID, RS, Arg = ()> {
     pub restart_strategy: RS,
-    pub children: Vec<BoxedMixedChildSpec<ID>>,
+    pub children: Vec<BoxedMixedChildSpec<ID, Arg>>,
+    pub lifecycle_hooks: Vec<Arc<dyn SupHook<ID>>>,
+    pub auto_shutdown: AutoShutdown,
+
+    /// How many [concurrently-stoppable](crate::mixed::MixedChildSpec::concurrent_shutdown)
+    /// children are stopped at once (see [`SupSpec::with_shutdown_concurrency`]).
+    pub shutdown_concurrency: usize,
+
+    /// The argument passed to each child upon its start.
+    pub start_arg: Arg,
 }
 
 impl<ID, RS> SupSpec<ID, RS> {
     pub fn new(restart_strategy: RS) -> Self {
-        Self { restart_strategy, children: Default::default() }
+        Self::with_start_arg(restart_strategy, ())
+    }
+}
+
+impl<ID, RS, Arg> SupSpec<ID, RS, Arg> {
+    /// Create a spec of a supervisor that passes a clone of `start_arg` to each of its children
+    /// upon their start.
+    pub fn with_start_arg(restart_strategy: RS, start_arg: Arg) -> Self {
+        Self {
+            restart_strategy,
+            children: Default::default(),
+            lifecycle_hooks: Default::default(),
+            auto_shutdown: Default::default(),
+            shutdown_concurrency: 1,
+            start_arg,
+        }
+    }
+
+    /// Specify when the termination of the [significant](crate::mixed::MixedChildSpec::significant)
+    /// children shuts the supervisor down (by default — upon the termination of
+    /// [any](AutoShutdown::Any) of them).
+    pub fn with_auto_shutdown(mut self, auto_shutdown: AutoShutdown) -> Self {
+        self.auto_shutdown = auto_shutdown;
+        self
+    }
+
+    /// Stop up to `n` children at once.
+    ///
+    /// The children are stopped in the reverse order; only the adjacent children marked as
+    /// [concurrently-stoppable](crate::mixed::MixedChildSpec::concurrent_shutdown) are stopped
+    /// together, the others are still stopped strictly one after another. By default `n` is `1`.
+    pub fn with_shutdown_concurrency(mut self, n: usize) -> Self {
+        self.shutdown_concurrency = n;
+        self
+    }
+
+    /// Invoke the `hook` as the supervisor starts and stops its children (see [`SupHook`]).
+    pub fn with_lifecycle_hook(mut self, hook: Arc<dyn SupHook<ID>>) -> Self {
+        self.lifecycle_hooks.push(hook);
+        self
     }
 
     pub fn with_child<CS>(mut self, child_spec: CS) -> Self
     where
-        CS: Into<BoxedMixedChildSpec<ID>>,
+        CS: Into<BoxedMixedChildSpec<ID, Arg>>,
     {
         self.children.push(child_spec.into());
         self
     }
 }
 
+impl<ID, RS, Arg> SupSpec<ID, RS, Arg>
+where
+    ID: ChildID,
+    Arg: 'static,
+{
+    /// Check the children for the mistakes that would otherwise surface only at runtime: the
+    /// duplicate ids, the children registering under the same name, and the shutdown sequences
+    /// that may fail to stop a child (see [`ShutdownSequence::validate`]).
+    ///
+    /// The [supervisor](crate::mixed::run) validates its spec upon start.
+    ///
+    /// [`ShutdownSequence::validate`]: crate::common::ShutdownSequence::validate
+    pub fn validate(&self) -> Result<(), SupSpecError<ID>> {
+        let mut ids = HashSet::new();
+        for child_spec in self.children.iter() {
+            let id = child_spec.id();
+            if !ids.insert(id) {
+                return Err(SupSpecError::DuplicateId(id))
+            }
+            child_spec
+                .shutdown()
+                .validate()
+                .map_err(|reason| SupSpecError::InvalidShutdown(id, reason))?;
+        }
+
+        #[cfg(feature = "reg")]
+        for (idx, child_spec) in self.children.iter().enumerate() {
+            let Some(reg_tx) = child_spec.reg_tx() else { continue };
+            if let Some(first) = self.children[..idx]
+                .iter()
+                .find(|other| other.reg_tx().is_some_and(|other| other.same_as(reg_tx)))
+            {
+                return Err(SupSpecError::DuplicateRegistration(first.id(), child_spec.id()))
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[tokio::test]
 async fn ergonomics() {
     use std::convert::Infallible;
@@ -55,3 +178,610 @@ async fn ergonomics() {
 
     crate::mixed::start_child(&system, sup, child_three).await.unwrap();
 }
+
+#[tokio::test]
+async fn start_arg_is_passed_to_children() {
+    use std::time::Duration;
+
+    use agner_actors::{Context, System};
+    use tokio::sync::mpsc;
+
+    use crate::common::InitType;
+    use crate::mixed::{MixedChildSpec, OneForOne, RestartIntensity};
+
+    type Environment = mpsc::UnboundedSender<(&'static str, &'static str)>;
+
+    async fn actor(_context: &mut Context<()>, (name, env): (&'static str, Environment)) {
+        let _ = env.send(("started", name));
+        std::future::pending().await
+    }
+
+    let child = |name: &'static str| {
+        MixedChildSpec::mixed(name)
+            .behaviour(actor)
+            .args_call1(move |env: Environment| (name, env))
+            .init_type(InitType::no_ack())
+    };
+
+    let (env, mut started) = mpsc::unbounded_channel();
+    let restart_strategy = OneForOne::new(RestartIntensity::new(5, Duration::from_secs(30)));
+    let sup_spec = SupSpec::with_start_arg(restart_strategy, env).with_child(child("first"));
+
+    let system = System::new(Default::default());
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+    assert_eq!(started.recv().await, Some(("started", "first")));
+
+    crate::mixed::start_child_with_arg(&system, sup, child("second")).await.unwrap();
+    assert_eq!(started.recv().await, Some(("started", "second")));
+}
+
+#[tokio::test]
+async fn failing_args_count_towards_restart_intensity() {
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use agner_actors::{Context, System};
+
+    use crate::common::InitType;
+    use crate::mixed::{MixedChildSpec, OneForOne, RestartIntensity};
+
+    async fn actor(_context: &mut Context<Infallible>, (): ()) {
+        std::future::pending().await
+    }
+
+    let child = |attempts: Arc<AtomicUsize>, failures: usize| {
+        MixedChildSpec::mixed("child")
+            .behaviour(actor)
+            .args_async0(move || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt < failures {
+                        Err("not yet")
+                    } else {
+                        Ok(())
+                    }
+                }
+            })
+            .init_type(InitType::no_ack())
+    };
+    let restart_strategy = OneForOne::new(RestartIntensity::new(3, Duration::from_secs(30)));
+    let system = System::new(Default::default());
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let sup_spec = SupSpec::new(restart_strategy.to_owned()).with_child(child(attempts.clone(), 2));
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+    loop {
+        let children = crate::mixed::which_children::<&str>(&system, sup).await.unwrap();
+        if !children.is_empty() {
+            break
+        }
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let sup_spec = SupSpec::new(restart_strategy).with_child(child(attempts.clone(), usize::MAX));
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+    assert!(system.wait(sup).await.is_shutdown());
+    assert_eq!(attempts.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn significant_child_shuts_the_supervisor_down() {
+    use std::time::Duration;
+
+    use agner_actors::{Context, System};
+
+    use crate::common::InitType;
+    use crate::mixed::{ChildType, MixedChildSpec, OneForOne, RestartIntensity};
+
+    async fn idle(_context: &mut Context<()>, (): ()) {
+        std::future::pending().await
+    }
+    async fn done_on_message(context: &mut Context<()>, (): ()) {
+        context.next_message().await
+    }
+
+    let restart_strategy = OneForOne::new(RestartIntensity::new(5, Duration::from_secs(30)));
+    let sup_spec = SupSpec::new(restart_strategy)
+        .with_child(
+            MixedChildSpec::mixed("idle")
+                .behaviour(idle)
+                .args_clone(())
+                .init_type(InitType::no_ack()),
+        )
+        .with_child(
+            MixedChildSpec::mixed("significant")
+                .behaviour(done_on_message)
+                .args_clone(())
+                .child_type(ChildType::Transient)
+                .significant(true)
+                .init_type(InitType::no_ack()),
+        );
+
+    let system = System::new(Default::default());
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+
+    let children = loop {
+        let children = crate::mixed::which_children::<&'static str>(&system, sup).await.unwrap();
+        if children.len() == 2 {
+            break children
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let (_, significant) = children.into_iter().find(|(id, _)| *id == "significant").unwrap();
+    system.send(significant, ()).await;
+
+    assert!(system.wait(sup).await.is_normal());
+}
+
+#[tokio::test]
+async fn batch_supervisor_shuts_down_once_all_jobs_are_done() {
+    use std::time::Duration;
+
+    use agner_actors::{Context, System};
+    use agner_utils::future_timeout_ext::FutureTimeoutExt;
+
+    use crate::common::InitType;
+    use crate::mixed::{ChildType, MixedChildSpec, OneForOne, RestartIntensity};
+
+    async fn job(context: &mut Context<()>, (): ()) {
+        context.next_message().await
+    }
+
+    let job = |id| {
+        MixedChildSpec::mixed(id)
+            .behaviour(job)
+            .args_clone(())
+            .child_type(ChildType::Transient)
+            .significant(true)
+            .init_type(InitType::no_ack())
+    };
+    let restart_strategy = OneForOne::new(RestartIntensity::new(5, Duration::from_secs(30)));
+    let sup_spec = SupSpec::new(restart_strategy)
+        .with_auto_shutdown(AutoShutdown::AllSignificant)
+        .with_child(job("first"))
+        .with_child(job("second"));
+
+    let system = System::new(Default::default());
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+
+    let children = loop {
+        let children = crate::mixed::which_children::<&'static str>(&system, sup).await.unwrap();
+        if children.len() == 2 {
+            break children
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+
+    system.send(children[0].1, ()).await;
+    assert!(system.wait(sup).timeout(Duration::from_millis(100)).await.is_err());
+
+    system.send(children[1].1, ()).await;
+    assert!(system.wait(sup).await.is_normal());
+}
+
+#[tokio::test]
+async fn restart_stats_are_reported() {
+    use std::time::Duration;
+
+    use agner_actors::{Context, Exit, System};
+
+    use crate::common::InitType;
+    use crate::mixed::{MixedChildSpec, OneForOne, RestartIntensity};
+
+    async fn actor(_context: &mut Context<()>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    let child = |name: &'static str| {
+        MixedChildSpec::mixed(name)
+            .behaviour(actor)
+            .args_clone(())
+            .init_type(InitType::no_ack())
+    };
+    let restart_strategy = OneForOne::new(RestartIntensity::new(5, Duration::from_secs(30)));
+    let sup_spec = SupSpec::new(restart_strategy)
+        .with_child(child("first"))
+        .with_child(child("second"));
+
+    let system = System::new(Default::default());
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+
+    let children = loop {
+        let children = crate::mixed::which_children::<&'static str>(&system, sup).await.unwrap();
+        if children.len() == 2 {
+            break children
+        }
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    };
+    system.exit(children[0].1, Exit::from_message("failure")).await;
+    system.wait(children[0].1).await;
+
+    let stats = loop {
+        let stats = crate::mixed::restart_stats::<&'static str>(&system, sup).await.unwrap();
+        if stats[0].restarts_in_window > 0 {
+            break stats
+        }
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    };
+    assert_eq!(
+        stats.iter().map(|s| (s.id, s.restarts_in_window, s.trips)).collect::<Vec<_>>(),
+        [("first", 1, 0), ("second", 0, 0)]
+    );
+    assert_eq!(stats[0].window, Duration::from_secs(30));
+    assert_eq!(stats[0].max_restarts, 5);
+}
+
+#[tokio::test]
+async fn lazy_child_is_started_on_demand() {
+    use std::time::Duration;
+
+    use agner_actors::{Context, Exit, System};
+
+    use crate::common::InitType;
+    use crate::mixed::{AllForOne, MixedChildSpec, RestartIntensity, SupervisorError};
+
+    async fn actor(_context: &mut Context<()>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    let child = |name: &'static str| {
+        MixedChildSpec::mixed(name)
+            .behaviour(actor)
+            .args_clone(())
+            .init_type(InitType::no_ack())
+    };
+    let restart_strategy = AllForOne::new(RestartIntensity::new(5, Duration::from_secs(30)));
+    let sup_spec = SupSpec::new(restart_strategy)
+        .with_child(child("eager"))
+        .with_child(child("lazy").lazy(true));
+
+    let system = System::new(Default::default());
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+
+    let eager = loop {
+        let children = crate::mixed::which_children::<&'static str>(&system, sup).await.unwrap();
+        if let [(_, eager)] = children[..] {
+            break eager
+        }
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    };
+
+    // a never started lazy child is not restarted along with its siblings
+    system.exit(eager, Exit::from_message("failure")).await;
+    system.wait(eager).await;
+    let children = loop {
+        let children = crate::mixed::which_children::<&'static str>(&system, sup).await.unwrap();
+        if children.iter().all(|(_, actor_id)| *actor_id != eager) {
+            break children
+        }
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    };
+    assert_eq!(children.iter().map(|(id, _)| *id).collect::<Vec<_>>(), ["eager"]);
+
+    let lazy = crate::mixed::ensure_started(&system, sup, "lazy").await.unwrap();
+    assert_eq!(crate::mixed::ensure_started(&system, sup, "lazy").await.unwrap(), lazy);
+    assert!(matches!(
+        crate::mixed::ensure_started(&system, sup, "unknown").await,
+        Err(SupervisorError::UnknownId)
+    ));
+
+    // once started, the lazy child is restarted as any other
+    system.exit(lazy, Exit::from_message("failure")).await;
+    system.wait(lazy).await;
+    let restarted = loop {
+        let restarted = crate::mixed::ensure_started(&system, sup, "lazy").await.unwrap();
+        if restarted != lazy {
+            break restarted
+        }
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    };
+    assert!(system.actor_info(restarted).await.is_some());
+}
+
+#[tokio::test]
+async fn circuit_breaker_holds_a_failing_child_for_the_cooldown() {
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use agner_actors::clocks::MockClock;
+    use agner_actors::{Context, System, SystemConfig};
+
+    use crate::common::InitType;
+    use crate::mixed::{BreakerState, CircuitBreaker, MixedChildSpec, SupervisorError};
+
+    async fn actor(_context: &mut Context<Infallible>, (): ()) {
+        std::future::pending().await
+    }
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let child = MixedChildSpec::mixed("child")
+        .behaviour(actor)
+        .args_async0({
+            let attempts = attempts.clone();
+            move || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt < 2 {
+                        Err("not yet")
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+        })
+        .init_type(InitType::no_ack());
+    let restart_strategy = CircuitBreaker::new(1, Duration::from_secs(30), Duration::from_secs(60));
+    let sup_spec = SupSpec::new(restart_strategy).with_child(child);
+
+    // the cooldown is over as soon as the clock says so
+    let clock = MockClock::new();
+    let system =
+        System::new(SystemConfig { clock: Arc::new(clock.to_owned()), ..Default::default() });
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+
+    loop {
+        let stats = crate::mixed::restart_stats::<&str>(&system, sup).await.unwrap();
+        if stats[0].breaker == Some(BreakerState::Open) {
+            assert_eq!(stats[0].trips, 1);
+            break
+        }
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    assert!(matches!(
+        crate::mixed::ensure_started(&system, sup, "child").await,
+        Err(SupervisorError::CircuitOpen)
+    ));
+
+    clock.advance(Duration::from_secs(60));
+    let child = loop {
+        match crate::mixed::ensure_started(&system, sup, "child").await {
+            Ok(child) => break child,
+            Err(SupervisorError::CircuitOpen) =>
+                tokio::time::sleep(Duration::from_millis(10)).await,
+            Err(reason) => panic!("unexpected error: {}", reason),
+        }
+    };
+    assert!(system.actor_info(child).await.is_some());
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    let stats = crate::mixed::restart_stats::<&str>(&system, sup).await.unwrap();
+    assert_eq!(stats[0].breaker, Some(BreakerState::Closed));
+}
+
+#[tokio::test]
+async fn restart_strategy_is_switched_at_runtime() {
+    use std::time::Duration;
+
+    use agner_actors::{Context, Exit, System};
+
+    use crate::common::InitType;
+    use crate::mixed::{AllForOne, MixedChildSpec, OneForOne, RestartIntensity};
+
+    async fn actor(_context: &mut Context<()>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    let child = |name: &'static str| {
+        MixedChildSpec::mixed(name)
+            .behaviour(actor)
+            .args_clone(())
+            .init_type(InitType::no_ack())
+    };
+    let restart_intensity = RestartIntensity::new(5, Duration::from_secs(30));
+    let sup_spec = SupSpec::new(OneForOne::new(restart_intensity.to_owned()))
+        .with_child(child("first"))
+        .with_child(child("second"));
+
+    let system = System::new(Default::default());
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+
+    crate::mixed::set_restart_strategy::<&str, _>(&system, sup, AllForOne::new(restart_intensity))
+        .await
+        .unwrap();
+
+    let children = crate::mixed::which_children::<&str>(&system, sup).await.unwrap();
+    assert_eq!(children.len(), 2);
+    system.exit(children[0].1, Exit::from_message("failure")).await;
+
+    // both of the children are restarted
+    loop {
+        let restarted = crate::mixed::which_children::<&str>(&system, sup).await.unwrap();
+        if restarted.iter().zip(&children).all(|(after, before)| after.1 != before.1) {
+            break
+        }
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+    assert!(system.actor_info(children[1].1).await.is_none());
+}
+
+#[tokio::test]
+async fn invalid_specs_are_rejected() {
+    use std::time::Duration;
+
+    use agner_actors::{Context, Exit, System};
+
+    use crate::common::{InitType, ShutdownSequence, ShutdownSequenceError};
+    use crate::mixed::{MixedChildSpec, OneForOne, RestartIntensity, SupervisorError};
+
+    async fn actor(_context: &mut Context<()>, _arg: ()) {
+        std::future::pending().await
+    }
+
+    let child = |name: &'static str| {
+        MixedChildSpec::mixed(name)
+            .behaviour(actor)
+            .args_clone(())
+            .init_type(InitType::no_ack())
+    };
+    let new_sup_spec = || {
+        let restart_intensity = RestartIntensity::new(5, Duration::from_secs(30));
+        SupSpec::new(OneForOne::new(restart_intensity)).with_child(child("first"))
+    };
+    let sec = Duration::from_secs(1);
+
+    assert!(new_sup_spec().validate().is_ok());
+    assert!(matches!(
+        new_sup_spec().with_child(child("first")).validate(),
+        Err(SupSpecError::DuplicateId("first"))
+    ));
+    for (shutdown, expected) in [
+        (ShutdownSequence::empty(), ShutdownSequenceError::Empty),
+        (ShutdownSequence::empty().add(Exit::shutdown(), sec), ShutdownSequenceError::NoKill),
+        (
+            ShutdownSequence::empty().add(Exit::kill(), sec).add(Exit::shutdown(), sec),
+            ShutdownSequenceError::AfterKill(1),
+        ),
+        (
+            ShutdownSequence::empty()
+                .add(Exit::shutdown(), Duration::ZERO)
+                .add(Exit::kill(), sec),
+            ShutdownSequenceError::ZeroTimeout(0),
+        ),
+    ] {
+        let sup_spec = new_sup_spec().with_child(child("second").shutdown(shutdown));
+        assert!(
+            matches!(
+                sup_spec.validate(),
+                Err(SupSpecError::InvalidShutdown("second", reason)) if reason == expected
+            ),
+            "{:?}",
+            expected
+        );
+    }
+
+    #[cfg(feature = "reg")]
+    {
+        let (reg_tx, _reg_rx) = agner_reg::new();
+        let sup_spec = new_sup_spec()
+            .with_child(child("second").register(reg_tx.to_owned()))
+            .with_child(child("third").register(reg_tx));
+        assert!(matches!(
+            sup_spec.validate(),
+            Err(SupSpecError::DuplicateRegistration("second", "third"))
+        ));
+    }
+
+    // the supervisor does not start with an invalid spec...
+    let system = System::new(Default::default());
+    let sup_spec = new_sup_spec().with_child(child("first"));
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+    assert!(system.wait(sup).await.is_custom());
+
+    // ... nor does it accept a child with an invalid shutdown sequence
+    let sup = system
+        .spawn(crate::mixed::run, new_sup_spec(), Default::default())
+        .await
+        .unwrap();
+    let second = child("second").shutdown(ShutdownSequence::empty());
+    assert!(matches!(
+        crate::mixed::start_child(&system, sup, second).await,
+        Err(SupervisorError::InvalidShutdown(ShutdownSequenceError::Empty))
+    ));
+}
+
+#[tokio::test]
+async fn concurrently_stoppable_children_are_stopped_together() {
+    use std::time::Duration;
+
+    use agner_actors::{Context, Event, Exit, System};
+    use tokio::sync::mpsc;
+
+    use crate::common::InitType;
+    use crate::mixed::{MixedChildSpec, OneForOne, RestartIntensity};
+
+    type Environment = mpsc::UnboundedSender<(&'static str, &'static str)>;
+
+    async fn slow_to_stop(context: &mut Context<()>, (name, env): (&'static str, Environment)) {
+        context.trap_exit(true).await;
+        let _ = env.send(("up", name));
+        loop {
+            if let Event::Signal(_) = context.next_event().await {
+                break
+            }
+        }
+        let _ = env.send(("stopping", name));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let _ = env.send(("down", name));
+    }
+
+    let child = |name: &'static str, concurrent_shutdown: bool| {
+        MixedChildSpec::mixed(name)
+            .behaviour(slow_to_stop)
+            .args_call1(move |env: Environment| (name, env))
+            .init_type(InitType::no_ack())
+            .concurrent_shutdown(concurrent_shutdown)
+    };
+
+    let (env, mut events) = mpsc::unbounded_channel();
+    let restart_strategy = OneForOne::new(RestartIntensity::new(5, Duration::from_secs(30)));
+    let sup_spec = SupSpec::with_start_arg(restart_strategy, env)
+        .with_shutdown_concurrency(3)
+        .with_child(child("a", false))
+        .with_child(child("b", true))
+        .with_child(child("c", true))
+        .with_child(child("d", true))
+        .with_child(child("e", false));
+
+    let system = System::new(Default::default());
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+    for _ in 0..5 {
+        assert_eq!(events.recv().await.unwrap().0, "up");
+    }
+
+    system.exit(sup, Exit::shutdown()).await;
+    assert!(system.wait(sup).await.is_shutdown());
+
+    let mut log = vec![];
+    while let Ok(event) = events.try_recv() {
+        log.push(event);
+    }
+    assert_eq!(log.len(), 10, "{:?}", log);
+
+    // the non-concurrent children are stopped one by one, in the reverse order...
+    assert_eq!(log[..2], [("stopping", "e"), ("down", "e")]);
+    assert_eq!(log[8..], [("stopping", "a"), ("down", "a")]);
+
+    // ... while the concurrent ones — all at once
+    let mut stopping = log[2..5].iter().map(|(stage, name)| (*stage, *name)).collect::<Vec<_>>();
+    stopping.sort();
+    assert_eq!(stopping, [("stopping", "b"), ("stopping", "c"), ("stopping", "d")]);
+    assert!(log[5..8].iter().all(|(stage, _)| *stage == "down"));
+}
+
+#[tokio::test]
+async fn escalation_surfaces_the_cause() {
+    use std::convert::Infallible;
+    use std::time::Duration;
+
+    use agner_actors::{Context, Exit, System};
+    use agner_utils::std_error_pp::StdErrorPP;
+
+    use crate::common::InitType;
+    use crate::mixed::{MixedChildSpec, OneForOne, RestartIntensity};
+
+    async fn faulty(_context: &mut Context<Infallible>, (): ()) -> Result<Infallible, Exit> {
+        Err(Exit::from_message("disk full"))
+    }
+
+    let restart_strategy = OneForOne::new(RestartIntensity::new(1, Duration::from_secs(30)))
+        .with_escalation(Default::default());
+    let sup_spec = SupSpec::new(restart_strategy).with_child(
+        MixedChildSpec::mixed("faulty")
+            .behaviour(faulty)
+            .args_clone(())
+            .init_type(InitType::no_ack()),
+    );
+
+    let system = System::new(Default::default());
+    let sup = system.spawn(crate::mixed::run, sup_spec, Default::default()).await.unwrap();
+    let exit = system.wait(sup).await;
+
+    assert!(exit.is_custom(), "{}", exit.pp());
+    assert!(exit.pp().to_string().contains("disk full"), "{}", exit.pp());
+}
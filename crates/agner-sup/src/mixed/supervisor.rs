@@ -1,56 +1,173 @@
-use std::collections::hash_map::Entry as HashMapEntry;
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use agner_actors::{ActorID, Context, Event, Exit, Never, Signal};
+use agner_actors::{ActorID, Context, Event, Exit, Never, Selected, Signal};
 use agner_init_ack::ContextInitAckExt;
 use agner_utils::future_timeout_ext::FutureTimeoutExt;
 use agner_utils::std_error_pp::StdErrorPP;
 
+use futures::future;
 use tokio::sync::oneshot;
 
-use crate::common::StartChildError;
+use crate::common::{Lifecycle, ShutdownSequenceError, StartChildError};
+use crate::health::{ProbeRequest, SupProbe};
 use crate::mixed::child_id::ChildID;
-use crate::mixed::restart_strategy::{Action, Decider, RestartStrategy};
+use crate::mixed::restart_strategy::{
+    Action, ChildRestartStats, ChildState, Decider, RestartStrategy,
+};
 use crate::mixed::sup_spec::SupSpec;
-use crate::mixed::FlatMixedChildSpec;
+use crate::mixed::BoxedMixedChildSpec;
 
 #[derive(Debug)]
-pub enum Message<ID> {
+pub enum Message<ID, Arg = ()> {
     TerminateChild(ID, oneshot::Sender<Result<Exit, SupervisorError>>),
-    StartChild(Box<dyn FlatMixedChildSpec<ID>>, oneshot::Sender<Result<ActorID, SupervisorError>>),
+    StartChild(BoxedMixedChildSpec<ID, Arg>, oneshot::Sender<Result<ActorID, SupervisorError>>),
+    EnsureStarted(ID, oneshot::Sender<Result<ActorID, SupervisorError>>),
     WhichChildren(oneshot::Sender<Vec<(ID, ActorID)>>),
+    RestartStats(oneshot::Sender<Vec<ChildRestartStats<ID, Duration>>>),
+    SetRestartStrategy(BoxedRestartStrategy, oneshot::Sender<Result<(), SupervisorError>>),
+
+    #[doc(hidden)]
+    Internal(Internal),
+}
+
+/// The events the supervisor sends to itself: opaque outside of this module.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Internal(InternalEvent);
+
+#[derive(Debug)]
+enum InternalEvent {
+    SignificantChildDown(ActorID, Exit),
+    Probe(ProbeRequest),
+}
+
+impl<ID, Arg> From<InternalEvent> for Message<ID, Arg> {
+    fn from(event: InternalEvent) -> Self {
+        Self::Internal(Internal(event))
+    }
+}
+
+impl<ID, Arg> From<ProbeRequest> for Message<ID, Arg> {
+    fn from(request: ProbeRequest) -> Self {
+        InternalEvent::Probe(request).into()
+    }
+}
+
+/// A [`RestartStrategy`] sent to a running supervisor (see
+/// [`set_restart_strategy`](crate::mixed::set_restart_strategy)).
+pub struct BoxedRestartStrategy {
+    description: String,
+    new_decider: Box<dyn FnOnce(ActorID) -> Box<dyn Any + Send> + Send + Sync>,
 }
 
+impl BoxedRestartStrategy {
+    pub fn new<ID, RS>(restart_strategy: RS) -> Self
+    where
+        RS: RestartStrategy<ID> + Sync,
+        RS::Decider: Send + 'static,
+    {
+        Self {
+            description: format!("{:?}", restart_strategy),
+            new_decider: Box::new(move |sup_id| Box::new(restart_strategy.new_decider(sup_id))),
+        }
+    }
+}
+
+impl fmt::Debug for BoxedRestartStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BoxedRestartStrategy").field(&self.description).finish()
+    }
+}
+
+/// The child-specs known to the supervisor, along with the argument passed to each child upon
+/// its start.
+struct ChildSpecs<ID, Arg> {
+    ids: Vec<ID>,
+    specs: HashMap<ID, BoxedMixedChildSpec<ID, Arg>>,
+    start_arg: Arg,
+    /// The children added via `start_child` that have not been started yet: such a child is not
+    /// retained if it fails to start.
+    unconfirmed: HashSet<ID>,
+}
+
+impl<ID: ChildID, Arg: 'static> ChildSpecs<ID, Arg> {
+    fn concurrent_shutdown(&self, id: ID) -> bool {
+        self.specs.get(&id).is_some_and(|child_spec| child_spec.concurrent_shutdown())
+    }
+}
+
+type SubscribersUp<ID> = HashMap<ID, Vec<oneshot::Sender<Result<ActorID, SupervisorError>>>>;
+
+/// The restart strategies to switch to, once the pending actions of the current one are done.
+type PendingStrategies = Vec<(BoxedRestartStrategy, oneshot::Sender<Result<(), SupervisorError>>)>;
+
 /// The behaviour function of the [Mixed Supervisor](crate::mixed).
-pub async fn run<ID, RS>(
-    context: &mut Context<Message<ID>>,
-    sup_spec: SupSpec<ID, RS>,
+pub async fn run<ID, RS, Arg>(
+    context: &mut Context<Message<ID, Arg>>,
+    sup_spec: SupSpec<ID, RS, Arg>,
 ) -> Result<Never, Exit>
 where
     ID: ChildID,
     RS: RestartStrategy<ID>,
     RS::Decider: Decider<ID, Duration, Instant>,
+    Arg: Clone + Unpin + Send + Sync + 'static,
 {
     context.trap_exit(true).await;
+    if let Err(reason) = sup_spec.validate() {
+        tracing::error!("[{}] invalid sup-spec: {}", context.actor_id(), reason.pp());
+        let exit = Exit::custom(reason);
+        context.init_ack_err(exit.to_owned());
+        return Err(exit)
+    }
     context.init_ack_ok(Default::default());
 
     tracing::trace!("initializing decider [restart-strategy: {:?}]", sup_spec.restart_strategy);
-    let SupSpec { restart_strategy, children } = sup_spec;
+    let SupSpec {
+        restart_strategy,
+        children,
+        lifecycle_hooks,
+        auto_shutdown,
+        shutdown_concurrency,
+        start_arg,
+    } = sup_spec;
     let mut decider = restart_strategy.new_decider(context.actor_id());
-    let mut child_ids: Vec<ID> = vec![];
+    decider.auto_shutdown(auto_shutdown);
     let mut child_actors: HashMap<ID, ActorID> = Default::default();
-    let mut child_specs: HashMap<ID, Box<dyn FlatMixedChildSpec<ID>>> = Default::default();
-    let mut subscribers_up: HashMap<ID, oneshot::Sender<Result<ActorID, SupervisorError>>> =
-        Default::default();
+    let mut lifecycle = Lifecycle::new(&context.system(), context.actor_id(), lifecycle_hooks);
+    let mut child_specs = ChildSpecs {
+        ids: vec![],
+        specs: HashMap::new(),
+        start_arg,
+        unconfirmed: Default::default(),
+    };
+    let mut subscribers_up: SubscribersUp<ID> = Default::default();
+    let mut pending_strategies: PendingStrategies = Default::default();
+    // an action taken from the decider while collecting the children to stop concurrently
+    let mut held_action: Option<Action<ID>> = None;
+    context
+        .system()
+        .put_data(context.actor_id(), SupProbe::new::<Message<ID, Arg>>())
+        .await;
 
     for child_spec in children {
-        decider
-            .add_child(child_spec.id(), child_spec.child_type())
-            .map_err(Exit::custom)?;
-        child_ids.push(child_spec.id());
-        assert!(child_specs.insert(child_spec.id(), child_spec).is_none());
+        if child_spec.lazy() {
+            decider.add_lazy_child(child_spec.id(), child_spec.child_type())
+        } else {
+            decider.add_child(child_spec.id(), child_spec.child_type())
+        }
+        .map_err(Exit::custom)?;
+        if child_spec.significant() {
+            decider.set_significant(child_spec.id(), true).map_err(Exit::custom)?;
+        }
+        if let Some(restart_when) = child_spec.restart_when() {
+            decider.restart_when(child_spec.id(), restart_when).map_err(Exit::custom)?;
+        }
+        child_specs.ids.push(child_spec.id());
+        assert!(child_specs.specs.insert(child_spec.id(), child_spec).is_none());
     }
 
     let mut decider_has_actions = true;
@@ -66,6 +183,13 @@ where
 
             let next_event_opt = if decider_has_actions || !first_context_poll {
                 context.next_event().timeout(Duration::ZERO).await.ok()
+            } else if let Some(wake_up_at) = decider.wake_up_at() {
+                // some of the children are to be started once their cooldown is over
+                let cooldown_over = context.system().config().clock.sleep_until(wake_up_at);
+                match context.select().with(cooldown_over).await {
+                    Selected::Event(next_event) => Some(next_event),
+                    Selected::Ready(()) => None,
+                }
             } else {
                 Some(context.next_event().await)
             };
@@ -78,22 +202,59 @@ where
                         handle_message(
                             context,
                             &mut decider,
-                            &mut child_ids,
                             &mut child_actors,
                             &mut child_specs,
                             &mut subscribers_up,
+                            &mut pending_strategies,
                             message,
                         )
                         .await?,
-                    Event::Signal(signal) => handle_signal(context, &mut decider, signal).await?,
+                    Event::Signal(signal) =>
+                        handle_signal(context, &mut decider, &mut lifecycle, signal).await?,
                 }
             } else {
                 break
             }
         }
 
-        decider_has_actions = match decider.next_action().map_err(Exit::custom)? {
-            None => false,
+        // checked on every iteration, so that a steady flow of events does not hold the wake-up off
+        let now = context.now_instant();
+        if decider.wake_up_at().is_some_and(|wake_up_at| wake_up_at <= now) {
+            decider.wake_up(now).map_err(Exit::custom)?;
+        }
+
+        let next_action = match held_action.take() {
+            Some(action) => Some(action),
+            None => decider.next_action().map_err(Exit::custom)?,
+        };
+        decider_has_actions = match next_action {
+            Some(Action::Stop(child_id))
+                if shutdown_concurrency > 1 && child_specs.concurrent_shutdown(child_id) =>
+            {
+                let mut child_ids = vec![child_id];
+                while child_ids.len() < shutdown_concurrency {
+                    match decider.next_action().map_err(Exit::custom)? {
+                        Some(Action::Stop(child_id))
+                            if child_specs.concurrent_shutdown(child_id) =>
+                            child_ids.push(child_id),
+                        other => {
+                            held_action = other;
+                            break
+                        },
+                    }
+                }
+                stop_children(context, &child_specs, &mut child_actors, child_ids).await?;
+                true
+            },
+            None if pending_strategies.is_empty() => false,
+            None => {
+                for (restart_strategy, reply_to) in pending_strategies.drain(..) {
+                    let result =
+                        set_restart_strategy(context.actor_id(), &mut decider, restart_strategy)?;
+                    let _ = reply_to.send(result);
+                }
+                true
+            },
             Some(action) => {
                 process_action(
                     context,
@@ -101,6 +262,7 @@ where
                     &mut child_specs,
                     &mut child_actors,
                     &mut subscribers_up,
+                    &mut lifecycle,
                     action,
                 )
                 .await?;
@@ -110,9 +272,10 @@ where
     }
 }
 
-async fn handle_signal<ID, D>(
-    _context: &mut Context<Message<ID>>,
+async fn handle_signal<ID, D, Arg>(
+    context: &mut Context<Message<ID, Arg>>,
     decider: &mut D,
+    lifecycle: &mut Lifecycle<ID>,
     signal: Signal,
 ) -> Result<(), Exit>
 where
@@ -121,39 +284,85 @@ where
 {
     match signal {
         Signal::Exit(actor_id, exit_reason) => {
+            lifecycle.actor_down(actor_id, &exit_reason);
             decider
-                .exit_signal(actor_id, exit_reason, Instant::now())
+                .exit_signal(actor_id, exit_reason, context.now_instant())
                 .map_err(Exit::custom)?;
             Ok(())
         },
     }
 }
 
-async fn handle_message<ID, D>(
-    context: &mut Context<Message<ID>>,
+async fn handle_message<ID, D, Arg>(
+    context: &mut Context<Message<ID, Arg>>,
     decider: &mut D,
-    child_ids: &mut Vec<ID>,
     child_actors: &mut HashMap<ID, ActorID>,
-    child_specs: &mut HashMap<ID, Box<dyn FlatMixedChildSpec<ID>>>,
-    subscribers_up: &mut HashMap<ID, oneshot::Sender<Result<ActorID, SupervisorError>>>,
-    message: Message<ID>,
+    child_specs: &mut ChildSpecs<ID, Arg>,
+    subscribers_up: &mut SubscribersUp<ID>,
+    pending_strategies: &mut PendingStrategies,
+    message: Message<ID, Arg>,
 ) -> Result<(), Exit>
 where
     ID: ChildID,
     D: Decider<ID, Duration, Instant>,
+    Arg: 'static,
 {
     match message {
         Message::WhichChildren(reply_to) => {
-            let out = child_ids
+            let out = child_specs
+                .ids
                 .iter()
                 .filter_map(|id| child_actors.get(id).map(|actor| (*id, *actor)))
                 .collect::<Vec<_>>();
             let _ = reply_to.send(out);
             Ok(())
         },
+        Message::EnsureStarted(id, reply_to) => {
+            if !child_specs.ids.contains(&id) {
+                let _ = reply_to.send(Err(SupervisorError::UnknownId));
+                return Ok(())
+            }
+            match decider.ensure_started(id).map_err(Exit::custom)? {
+                ChildState::Running(actor_id) => {
+                    let _ = reply_to.send(Ok(actor_id));
+                },
+                ChildState::Restarting => subscribers_up.entry(id).or_default().push(reply_to),
+                ChildState::Tripped => {
+                    let _ = reply_to.send(Err(SupervisorError::CircuitOpen));
+                },
+                ChildState::Idle => {
+                    let _ = reply_to.send(Err(SupervisorError::ChildStopped));
+                },
+            }
+            Ok(())
+        },
+        Message::RestartStats(reply_to) => {
+            let _ = reply_to.send(decider.restart_stats(context.now_instant()));
+            Ok(())
+        },
+        Message::Internal(Internal(InternalEvent::Probe(request))) => {
+            request.reply(decider.child_states());
+            Ok(())
+        },
+        Message::SetRestartStrategy(restart_strategy, reply_to) => {
+            // the switch happens once the current decider has no more actions to perform
+            pending_strategies.push((restart_strategy, reply_to));
+            Ok(())
+        },
+        Message::Internal(Internal(InternalEvent::SignificantChildDown(actor_id, exit))) => {
+            // the abnormal exits arrive as exit-signals, the normal ones — only this way
+            if exit.is_normal() {
+                let now = context.now_instant();
+                decider.exit_signal(actor_id, exit, now).map_err(Exit::custom)?;
+            }
+            Ok(())
+        },
         Message::TerminateChild(id, reply_to) => {
-            if child_specs.contains_key(&id) {
+            if child_specs.ids.contains(&id) {
                 decider.rm_child(id).map_err(Exit::custom)?;
+                child_specs.ids.retain(|child_id| *child_id != id);
+                child_specs.unconfirmed.remove(&id);
+                subscribers_up.remove(&id);
                 if let Some(actor_id) = child_actors.get(&id).copied() {
                     let system = context.system();
                     context
@@ -173,13 +382,23 @@ where
         Message::StartChild(child_spec, reply_to) => {
             let child_id = child_spec.id();
 
-            if let HashMapEntry::Vacant(vacant) = child_specs.entry(child_id) {
-                decider.add_child(child_id, child_spec.child_type()).map_err(Exit::custom)?;
-                child_ids.push(child_id);
-                vacant.insert(child_spec);
-                subscribers_up.insert(child_id, reply_to);
-            } else {
+            // the spec of a terminated child is kept until a new one with the same id is added
+            if child_specs.ids.contains(&child_id) {
                 let _ = reply_to.send(Err(SupervisorError::DuplicateId));
+            } else if let Err(reason) = child_spec.shutdown().validate() {
+                let _ = reply_to.send(Err(SupervisorError::InvalidShutdown(reason)));
+            } else {
+                decider.add_child(child_id, child_spec.child_type()).map_err(Exit::custom)?;
+                if child_spec.significant() {
+                    decider.set_significant(child_id, true).map_err(Exit::custom)?;
+                }
+                if let Some(restart_when) = child_spec.restart_when() {
+                    decider.restart_when(child_id, restart_when).map_err(Exit::custom)?;
+                }
+                child_specs.ids.push(child_id);
+                child_specs.specs.insert(child_id, child_spec);
+                child_specs.unconfirmed.insert(child_id);
+                subscribers_up.entry(child_id).or_default().push(reply_to);
             }
 
             Ok(())
@@ -187,17 +406,44 @@ where
     }
 }
 
-async fn process_action<ID, D>(
-    context: &mut Context<Message<ID>>,
+/// Replace the decider with the one made by the `restart_strategy`, handing the children over to
+/// it.
+fn set_restart_strategy<ID, D>(
+    sup_id: ActorID,
+    decider: &mut D,
+    restart_strategy: BoxedRestartStrategy,
+) -> Result<Result<(), SupervisorError>, Exit>
+where
+    ID: ChildID,
+    D: Decider<ID, Duration, Instant>,
+{
+    tracing::trace!("[{}] switching to {:?}", sup_id, restart_strategy);
+
+    let Ok(new_decider) = (restart_strategy.new_decider)(sup_id).downcast::<D>() else {
+        return Ok(Err(SupervisorError::DeciderMismatch))
+    };
+    let previous = std::mem::replace(decider, *new_decider);
+    if let Some(previous) = decider.take_over(previous).map_err(Exit::custom)? {
+        *decider = previous;
+        return Ok(Err(SupervisorError::DeciderMismatch))
+    }
+
+    Ok(Ok(()))
+}
+
+async fn process_action<ID, D, Arg>(
+    context: &mut Context<Message<ID, Arg>>,
     decider: &mut D,
-    child_specs: &mut HashMap<ID, Box<dyn FlatMixedChildSpec<ID>>>,
+    child_specs: &mut ChildSpecs<ID, Arg>,
     child_actors: &mut HashMap<ID, ActorID>,
-    subscribers_up: &mut HashMap<ID, oneshot::Sender<Result<ActorID, SupervisorError>>>,
+    subscribers_up: &mut SubscribersUp<ID>,
+    lifecycle: &mut Lifecycle<ID>,
     action: Action<ID>,
 ) -> Result<(), Exit>
 where
     ID: ChildID,
     D: Decider<ID, Duration, Instant>,
+    Arg: Clone + 'static,
 {
     match action {
         Action::Shutdown(reason) => {
@@ -206,47 +452,110 @@ where
                 context.actor_id(),
                 reason.pp()
             );
+            lifecycle.shutdown_initiated(&reason);
             context.exit(reason).await;
             unreachable!()
         },
         Action::Start(child_id) => {
             tracing::trace!("starting child[{:?}]", child_id);
 
-            if let Some(child_spec) = child_specs.get_mut(&child_id) {
-                let actor_id = child_spec
-                    .create_child(&context.system(), context.actor_id(), ())
-                    .await
-                    .map_err(SupervisorError::StartChildFailure)
-                    .map_err(Exit::custom)?;
-                child_actors.insert(child_id, actor_id);
-                decider.child_started(child_id, actor_id).map_err(Exit::custom)?;
-
-                if let Some(reply_to) = subscribers_up.remove(&child_id) {
-                    let _ = reply_to.send(Ok(actor_id));
-                }
-            } else {
+            let Some(child_spec) = child_specs.specs.get_mut(&child_id) else {
                 return Err(Exit::custom(SupervisorError::UnknownId))
-            }
-        },
-        Action::Stop(child_id) => {
-            tracing::trace!("[{}] stopping child[{:?}]", context.actor_id(), child_id);
-
-            if let Some((actor_id, child_spec)) =
-                child_actors.remove(&child_id).zip(child_specs.get(&child_id))
-            {
-                tracing::trace!("[{}] stopping child[{:?}]", context.actor_id(), actor_id);
-                crate::common::stop_child(
-                    context.system(),
-                    actor_id,
-                    child_spec.shutdown().to_owned(),
+            };
+            lifecycle.child_starting(child_id);
+            let significant = child_spec.significant();
+            let start_result = child_spec
+                .create_child(
+                    &context.system(),
+                    context.actor_id(),
+                    child_specs.start_arg.to_owned(),
                 )
-                .await
-                .map_err(Exit::custom)?;
-            } else {
-                return Err(Exit::custom(SupervisorError::UnknownId))
+                .await;
+
+            let unconfirmed = child_specs.unconfirmed.remove(&child_id);
+            match (start_result, unconfirmed) {
+                (Ok(actor_id), _) => {
+                    child_actors.insert(child_id, actor_id);
+                    lifecycle.child_up(child_id, actor_id);
+                    decider.child_started(child_id, actor_id).map_err(Exit::custom)?;
+
+                    if significant {
+                        let child_down = context.monitor_fut(actor_id);
+                        context
+                            .future_to_inbox(async move {
+                                InternalEvent::SignificantChildDown(actor_id, child_down.await)
+                            })
+                            .await;
+                    }
+
+                    for reply_to in subscribers_up.remove(&child_id).unwrap_or_default() {
+                        let _ = reply_to.send(Ok(actor_id));
+                    }
+                },
+                (Err(reason), true) => {
+                    tracing::warn!(
+                        "[{}] failed to start child[{:?}]: {}",
+                        context.actor_id(),
+                        child_id,
+                        reason.pp()
+                    );
+                    // the child requested via `start_child` is not retained
+                    decider.rm_child(child_id).map_err(Exit::custom)?;
+                    child_specs.ids.retain(|id| *id != child_id);
+                    child_specs.specs.remove(&child_id);
+                    for reply_to in subscribers_up.remove(&child_id).unwrap_or_default() {
+                        let _ = reply_to
+                            .send(Err(SupervisorError::StartChildFailure(reason.to_owned())));
+                    }
+                },
+                (Err(reason), false) => {
+                    tracing::warn!(
+                        "[{}] failed to start child[{:?}]: {}",
+                        context.actor_id(),
+                        child_id,
+                        reason.pp()
+                    );
+                    let exit = Exit::custom(SupervisorError::StartChildFailure(reason));
+                    let now = context.now_instant();
+                    decider.start_failed(child_id, exit, now).map_err(Exit::custom)?;
+                },
             }
         },
+        Action::Stop(child_id) =>
+            stop_children(context, child_specs, child_actors, vec![child_id]).await?,
+    }
+    Ok(())
+}
+
+/// Stop the children concurrently, each — in accordance with its shutdown sequence.
+async fn stop_children<ID, Arg>(
+    context: &mut Context<Message<ID, Arg>>,
+    child_specs: &ChildSpecs<ID, Arg>,
+    child_actors: &mut HashMap<ID, ActorID>,
+    child_ids: Vec<ID>,
+) -> Result<(), Exit>
+where
+    ID: ChildID,
+    Arg: 'static,
+{
+    tracing::trace!("[{}] stopping children {:?}", context.actor_id(), child_ids);
+
+    let mut stopping = vec![];
+    for child_id in child_ids {
+        let Some((actor_id, child_spec)) =
+            child_actors.remove(&child_id).zip(child_specs.specs.get(&child_id))
+        else {
+            return Err(Exit::custom(SupervisorError::UnknownId))
+        };
+        tracing::trace!("[{}] stopping child[{:?}] [{}]", context.actor_id(), child_id, actor_id);
+        stopping.push(crate::common::stop_child(
+            context.system(),
+            actor_id,
+            child_spec.shutdown().to_owned(),
+        ));
     }
+    future::try_join_all(stopping).await.map_err(Exit::custom)?;
+
     Ok(())
 }
 
@@ -258,6 +567,18 @@ pub enum SupervisorError {
     #[error("Duplicate ID")]
     DuplicateId,
 
+    #[error("Invalid shutdown sequence")]
+    InvalidShutdown(#[source] ShutdownSequenceError),
+
+    #[error("Circuit-breaker is open")]
+    CircuitOpen,
+
+    #[error("The child has stopped and is not going to be restarted")]
+    ChildStopped,
+
+    #[error("The restart strategy's decider cannot take over from the current one")]
+    DeciderMismatch,
+
     #[error("Failed to start child")]
     StartChildFailure(#[source] StartChildError),
 
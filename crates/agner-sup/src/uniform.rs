@@ -1,8 +1,11 @@
 //! Uniform Supervisor
 //! =======
 
-use std::collections::HashSet;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use agner_actors::{ActorID, Context, Event, Exit, Never, Signal, System};
 use agner_init_ack::ContextInitAckExt;
@@ -11,11 +14,22 @@ use agner_utils::std_error_pp::StdErrorPP;
 
 use tokio::sync::oneshot;
 
-use crate::common::{CreateChild, StartChildError};
+use crate::common::{
+    CreateChild, Lifecycle, ShutdownSequence, StartChildError, StopChildError, SupHook,
+};
+use crate::health::{ProbeRequest, SupProbe};
+use crate::mixed::ChildState;
 
 mod child_spec;
 pub use child_spec::UniformChildSpec;
 
+mod sup_handle;
+pub use sup_handle::{ContextUniformSupExt, SupHandle};
+
+mod rate_limit;
+pub use rate_limit::RateLimitPolicy;
+use rate_limit::{StartRateLimit, TokenBucket};
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum SupervisorError {
     #[error("Failed to start a child")]
@@ -26,8 +40,40 @@ pub enum SupervisorError {
 
     #[error("Timeout")]
     Timeout(#[source] Arc<tokio::time::error::Elapsed>),
+
+    #[error("Failed to stop a child")]
+    StopChildError(#[source] Arc<StopChildError>),
+
+    #[error("No such child: {}", _0)]
+    UnknownChild(ActorID),
+
+    #[error("The child {} was not started with a restartable recipe", _0)]
+    NotRestartable(ActorID),
+
+    #[error("The supervisor is shutting down")]
+    ShuttingDown,
+
+    #[error("The start rate limit is exceeded")]
+    RateLimited,
+
+    #[error("The supervisor already has the maximum number of children: {}", _0)]
+    AtCapacity(usize),
+
+    #[error("No such supervisor: {}", _0)]
+    NoSupervisor(ActorID),
+
+    #[error(
+        "{} is not a uniform supervisor with the expected args-type (message-type: {})",
+        _0,
+        _1
+    )]
+    NotAUniformSupervisor(ActorID, &'static str),
 }
 
+/// A recipe producing the arguments for a [restartable](crate::uniform::start_child_restartable)
+/// child.
+pub type ArgsFactory<A> = Box<dyn FnMut() -> A + Send + Sync + 'static>;
+
 pub async fn start_child<A>(
     system: &System,
     sup: ActorID,
@@ -41,6 +87,48 @@ where
     rx.await.err_flatten_in()
 }
 
+/// Start a child, keeping the arguments in the supervisor, so that the child can later be
+/// [restarted](crate::uniform::restart_child).
+///
+/// The supervisors with [restartable
+/// children](crate::uniform::SupSpec::with_restartable_children) keep the arguments of every
+/// child, hence there [`start_child`] suffices.
+pub async fn start_child_restartable<A>(
+    system: &System,
+    sup: ActorID,
+    args: A,
+) -> Result<ActorID, SupervisorError>
+where
+    A: Clone + Send + Sync + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    system
+        .send(sup, Message::StartRestartable(Box::new(move || args.clone()), tx))
+        .await;
+    rx.await.err_flatten_in()
+}
+
+/// Gracefully stop the child and start a fresh one using the same arguments.
+///
+/// Returns the id of the new child. Only the children whose arguments are kept by the supervisor
+/// can be restarted: those started via
+/// [`start_child_restartable`], or any child of a
+/// supervisor with [restartable children](crate::uniform::SupSpec::with_restartable_children).
+/// If the child has already terminated by the time the request is processed by the supervisor —
+/// the request fails with [`SupervisorError::UnknownChild`].
+pub async fn restart_child<A>(
+    system: &System,
+    sup: ActorID,
+    child: ActorID,
+) -> Result<ActorID, SupervisorError>
+where
+    A: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    system.send(sup, Message::<A>::Restart(child, tx)).await;
+    rx.await.err_flatten_in()
+}
+
 pub async fn stop_child<A>(
     system: &System,
     sup: ActorID,
@@ -57,14 +145,130 @@ where
 pub enum Message<InArgs> {
     Start(InArgs, oneshot::Sender<Result<ActorID, SupervisorError>>),
     Stop(ActorID, oneshot::Sender<Result<Exit, SupervisorError>>),
+    StartRestartable(ArgsFactory<InArgs>, oneshot::Sender<Result<ActorID, SupervisorError>>),
+    Restart(ActorID, oneshot::Sender<Result<ActorID, SupervisorError>>),
+
+    #[doc(hidden)]
+    Internal(Internal),
+}
+
+/// The events the supervisor sends to itself: opaque outside of this module.
+#[doc(hidden)]
+pub struct Internal(InternalEvent);
+
+enum InternalEvent {
+    RestartStopped(
+        ActorID,
+        Result<Exit, Arc<StopChildError>>,
+        oneshot::Sender<Result<ActorID, SupervisorError>>,
+    ),
+    RateLimitTick,
+    Probe(ProbeRequest),
+}
+
+impl<InArgs> From<InternalEvent> for Message<InArgs> {
+    fn from(event: InternalEvent) -> Self {
+        Self::Internal(Internal(event))
+    }
+}
+
+impl<InArgs> From<ProbeRequest> for Message<InArgs> {
+    fn from(request: ProbeRequest) -> Self {
+        InternalEvent::Probe(request).into()
+    }
 }
 
 #[derive(Debug, Clone)]
-pub struct SupSpec<CS>(CS);
+pub struct SupSpec<CS> {
+    child_spec: CS,
+    start_rate_limit: Option<StartRateLimit>,
+    rate_limit_policy: RateLimitPolicy,
+    max_children: Option<usize>,
+    shutdown: Option<ShutdownSequence>,
+    keep_args: Option<KeepArgs>,
+    lifecycle_hooks: Vec<Arc<dyn SupHook<()>>>,
+}
+
+/// A type-erased `fn(&Args) -> ArgsFactory<Args>` (see
+/// [`SupSpec::with_restartable_children`]).
+#[derive(Clone)]
+struct KeepArgs(Arc<dyn Any + Send + Sync>);
 
 impl<CS> SupSpec<CS> {
     pub fn new(child_spec: CS) -> Self {
-        Self(child_spec)
+        Self {
+            child_spec,
+            start_rate_limit: None,
+            rate_limit_policy: Default::default(),
+            max_children: None,
+            shutdown: None,
+            keep_args: None,
+            lifecycle_hooks: Default::default(),
+        }
+    }
+
+    /// Keep the arguments of every child, so that any child can be
+    /// [restarted](crate::uniform::restart_child), not only those started via
+    /// [`start_child_restartable`].
+    pub fn with_restartable_children(mut self) -> Self
+    where
+        CS: CreateChild,
+        CS::Args: Clone + Send + Sync + 'static,
+    {
+        fn keep_args<A: Clone + Send + Sync + 'static>(args: &A) -> ArgsFactory<A> {
+            let args = args.to_owned();
+            Box::new(move || args.clone())
+        }
+        let keep_args: fn(&CS::Args) -> ArgsFactory<CS::Args> = keep_args::<CS::Args>;
+        self.keep_args = Some(KeepArgs(Arc::new(keep_args)));
+        self
+    }
+
+    /// Stop the children using the `shutdown` sequence (e.g. escalating from
+    /// [`Exit::shutdown()`] to [`Exit::kill()`] within a time budget) when the supervisor itself
+    /// shuts down.
+    ///
+    /// By default, the children are sent [`Exit::shutdown()`], and the supervisor waits for them
+    /// to terminate for as long as it takes.
+    pub fn with_shutdown(mut self, shutdown: impl Into<ShutdownSequence>) -> Self {
+        self.shutdown = Some(shutdown.into());
+        self
+    }
+
+    /// Start no more than `max_per` children per `per` period.
+    ///
+    /// The start-requests exceeding the limit are held until the rate allows to process them,
+    /// unless specified otherwise via
+    /// [`with_rate_limit_policy`](crate::uniform::SupSpec::with_rate_limit_policy).
+    pub fn with_start_rate_limit(mut self, max_per: usize, per: Duration) -> Self {
+        self.start_rate_limit = Some(StartRateLimit { max_per, per });
+        self
+    }
+
+    /// Hold no more than `max_children` children at a time.
+    ///
+    /// Once the limit is reached, the start-requests are rejected with
+    /// [`SupervisorError::AtCapacity`] until some of the children terminate. The restarts are not
+    /// subject to the limit.
+    pub fn with_max_children(mut self, max_children: usize) -> Self {
+        self.max_children = Some(max_children);
+        self
+    }
+
+    /// Invoke the `hook` as the supervisor starts and stops its children (the children of a
+    /// uniform supervisor have no IDs, hence they are reported as `()`).
+    pub fn with_lifecycle_hook(mut self, hook: Arc<dyn SupHook<()>>) -> Self {
+        self.lifecycle_hooks.push(hook);
+        self
+    }
+
+    /// Specify what to do with the start-requests exceeding the
+    /// [start rate limit](crate::uniform::SupSpec::with_start_rate_limit).
+    ///
+    /// Takes effect regardless of whether it is specified before or after the limit itself.
+    pub fn with_rate_limit_policy(mut self, policy: RateLimitPolicy) -> Self {
+        self.rate_limit_policy = policy;
+        self
     }
 }
 
@@ -82,29 +286,196 @@ where
 {
     context.trap_exit(true).await;
     context.init_ack_ok(Default::default());
-
-    let SupSpec(mut child_spec) = sup_spec;
+    context
+        .system()
+        .put_data(context.actor_id(), SupProbe::new::<Message<SupArg>>())
+        .await;
+
+    let SupSpec {
+        mut child_spec,
+        start_rate_limit,
+        rate_limit_policy,
+        max_children,
+        shutdown,
+        keep_args,
+        lifecycle_hooks,
+    } = sup_spec;
+    let keep_args = keep_args.map(|KeepArgs(keep_args)| {
+        *keep_args
+            .downcast_ref::<fn(&SupArg) -> ArgsFactory<SupArg>>()
+            .expect("KeepArgs of another args-type")
+    });
+
+    let mut lifecycle = Lifecycle::new(&context.system(), context.actor_id(), lifecycle_hooks);
 
     let mut shutting_down = None;
-    let mut children: HashSet<ActorID> = Default::default();
+    let mut children: HashMap<ActorID, Option<ArgsFactory<SupArg>>> = Default::default();
+    let mut restarting: HashMap<ActorID, ArgsFactory<SupArg>> = Default::default();
+
+    let mut token_bucket = start_rate_limit.as_ref().map(TokenBucket::new);
+    let mut throttled: VecDeque<Message<SupArg>> = Default::default();
+    let mut unthrottled: VecDeque<Message<SupArg>> = Default::default();
+    let mut tick_scheduled = false;
+
     loop {
-        match context.next_event().await {
+        let event = if let Some(message) = unthrottled.pop_front() {
+            Event::Message(message)
+        } else {
+            context.next_event().await
+        };
+
+        // rejected at capacity, the request does not spend a start-rate token
+        let event = match (event, max_children) {
+            (
+                Event::Message(message @ (Message::Start(..) | Message::StartRestartable(..))),
+                Some(max_children),
+            ) if children.len() >= max_children => {
+                tracing::trace!("at capacity ({} children), rejecting", max_children);
+                message.reject(SupervisorError::AtCapacity(max_children));
+                continue
+            },
+            (event, _) => event,
+        };
+
+        let event = match (event, token_bucket.as_mut()) {
+            (
+                Event::Message(message @ (Message::Start(..) | Message::StartRestartable(..))),
+                Some(token_bucket),
+            ) => {
+                let acquired = if throttled.is_empty() {
+                    token_bucket.try_acquire(Instant::now())
+                } else {
+                    Err(Duration::ZERO)
+                };
+                match (acquired, rate_limit_policy) {
+                    (Ok(()), _) => Event::Message(message),
+                    (Err(_), RateLimitPolicy::Reject) => {
+                        tracing::trace!("start rate limit exceeded, rejecting");
+                        message.reject(SupervisorError::RateLimited);
+                        continue
+                    },
+                    (Err(wait_for), RateLimitPolicy::Wait) => {
+                        tracing::trace!("start rate limit exceeded, throttling");
+                        throttled.push_back(message);
+                        if !tick_scheduled {
+                            tick_scheduled = true;
+                            context
+                                .future_to_inbox(async move {
+                                    tokio::time::sleep(wait_for).await;
+                                    InternalEvent::RateLimitTick
+                                })
+                                .await;
+                        }
+                        continue
+                    },
+                }
+            },
+            (event, _) => event,
+        };
+
+        match event {
+            Event::Message(Message::Internal(Internal(InternalEvent::RateLimitTick))) => {
+                tick_scheduled = false;
+                unthrottled.extend(throttled.drain(..));
+            },
+            Event::Message(Message::Internal(Internal(InternalEvent::Probe(request)))) => request
+                .reply(children.keys().map(|&actor_id| (actor_id, ChildState::Running(actor_id)))),
             Event::Message(Message::Start(args, reply_to)) => {
                 tracing::trace!("starting child");
 
+                let args_factory = keep_args.map(|keep_args| keep_args(&args));
+                lifecycle.child_starting(());
                 let result =
                     child_spec.create_child(&context.system(), context.actor_id(), args).await;
 
                 if let Some(actor_id) = result.as_ref().ok().copied() {
-                    children.insert(actor_id);
+                    lifecycle.child_up((), actor_id);
+                    children.insert(actor_id, args_factory);
                 }
 
                 tracing::trace!("start result {:?}", result);
 
                 let _ = reply_to.send(result.map_err(Into::into));
             },
+            Event::Message(Message::StartRestartable(mut args_factory, reply_to)) => {
+                tracing::trace!("starting restartable child");
+
+                lifecycle.child_starting(());
+                let result = child_spec
+                    .create_child(&context.system(), context.actor_id(), args_factory())
+                    .await;
+
+                if let Some(actor_id) = result.as_ref().ok().copied() {
+                    lifecycle.child_up((), actor_id);
+                    children.insert(actor_id, Some(args_factory));
+                }
+
+                tracing::trace!("start result {:?}", result);
+
+                let _ = reply_to.send(result.map_err(Into::into));
+            },
+            Event::Message(Message::Restart(actor_id, reply_to)) => {
+                let args_factory = match children.get_mut(&actor_id) {
+                    None => {
+                        let _ = reply_to.send(Err(SupervisorError::UnknownChild(actor_id)));
+                        continue
+                    },
+                    Some(args_factory_opt) => args_factory_opt.take(),
+                };
+                let Some(args_factory) = args_factory else {
+                    let _ = reply_to.send(Err(SupervisorError::NotRestartable(actor_id)));
+                    continue
+                };
+                tracing::trace!("restarting child {}", actor_id);
+
+                restarting.insert(actor_id, args_factory);
+
+                let system = context.system();
+                let shutdown_sequence = child_spec.shutdown_sequence().to_owned();
+                context
+                    .future_to_inbox(async move {
+                        let result = crate::common::stop_child(system, actor_id, shutdown_sequence)
+                            .await
+                            .map_err(Arc::new);
+                        InternalEvent::RestartStopped(actor_id, result, reply_to)
+                    })
+                    .await;
+            },
+            Event::Message(Message::Internal(Internal(InternalEvent::RestartStopped(
+                actor_id,
+                stopped,
+                reply_to,
+            )))) => {
+                let Some(mut args_factory) = restarting.remove(&actor_id) else { continue };
+
+                if let Err(reason) = stopped {
+                    if let Some(args_factory_opt) = children.get_mut(&actor_id) {
+                        *args_factory_opt = Some(args_factory);
+                    }
+                    let _ = reply_to.send(Err(SupervisorError::StopChildError(reason)));
+                    continue
+                }
+                if shutting_down.is_some() {
+                    let _ = reply_to.send(Err(SupervisorError::ShuttingDown));
+                    continue
+                }
+
+                lifecycle.child_starting(());
+                let result = child_spec
+                    .create_child(&context.system(), context.actor_id(), args_factory())
+                    .await;
+
+                if let Some(new_actor_id) = result.as_ref().ok().copied() {
+                    lifecycle.child_up((), new_actor_id);
+                    children.insert(new_actor_id, Some(args_factory));
+                }
+
+                tracing::trace!("restart result [old: {}; new: {:?}]", actor_id, result);
+
+                let _ = reply_to.send(result.map_err(Into::into));
+            },
             Event::Message(Message::Stop(actor_id, reply_to)) =>
-                if children.contains(&actor_id) {
+                if children.contains_key(&actor_id) {
                     tracing::trace!("stopping child {}", actor_id);
 
                     let system = context.system();
@@ -139,22 +510,31 @@ where
                 if actor_id == context.actor_id() {
                     tracing::trace!("received a shutdown signal to myself. Shutting down");
 
+                    lifecycle.shutdown_initiated(&exit_reason);
                     shutting_down = Some(exit_reason.to_owned());
 
                     let system = context.system();
-                    let mut has_some_children = false;
-                    for actor_id in children.iter().copied() {
-                        has_some_children = true;
-
-                        system.exit(actor_id, Exit::shutdown()).await;
+                    for actor_id in children.keys().copied() {
+                        if let Some(shutdown) = shutdown.as_ref() {
+                            // the children's exits are delivered as exit-signals
+                            let stopping = crate::common::stop_child(
+                                system.to_owned(),
+                                actor_id,
+                                shutdown.to_owned(),
+                            );
+                            context.spawn_job(stopping).await;
+                        } else {
+                            system.exit(actor_id, Exit::shutdown()).await;
+                        }
                     }
 
-                    if !has_some_children {
+                    if children.is_empty() {
                         context.exit(exit_reason).await;
                         unreachable!()
                     }
-                } else if children.remove(&actor_id) {
+                } else if children.remove(&actor_id).is_some() {
                     tracing::trace!("child {} terminated [exit: {}]", actor_id, exit_reason.pp());
+                    lifecycle.actor_down(actor_id, &exit_reason);
                     if children.is_empty() {
                         if let Some(exit_reason) = shutting_down {
                             tracing::trace!(
@@ -171,13 +551,54 @@ where
                         actor_id,
                         exit_reason.pp()
                     );
-                    context.exit(Exit::linked(actor_id, exit_reason)).await;
+                    let exit_reason = Exit::linked(actor_id, exit_reason);
+                    lifecycle.shutdown_initiated(&exit_reason);
+                    context.exit(exit_reason).await;
                     unreachable!()
                 },
         }
     }
 }
 
+impl<InArgs> Message<InArgs> {
+    fn reject(self, reason: SupervisorError) {
+        match self {
+            Self::Start(_, reply_to) |
+            Self::StartRestartable(_, reply_to) |
+            Self::Restart(_, reply_to) |
+            Self::Internal(Internal(InternalEvent::RestartStopped(_, _, reply_to))) => {
+                let _ = reply_to.send(Err(reason));
+            },
+            Self::Stop(_, reply_to) => {
+                let _ = reply_to.send(Err(reason));
+            },
+            Self::Internal(Internal(InternalEvent::RateLimitTick | InternalEvent::Probe(_))) => (),
+        }
+    }
+}
+
+impl fmt::Debug for KeepArgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("KeepArgs").finish()
+    }
+}
+
+impl From<oneshot::error::RecvError> for SupervisorError {
+    fn from(e: oneshot::error::RecvError) -> Self {
+        Self::OneshotRx(e)
+    }
+}
+impl From<StartChildError> for SupervisorError {
+    fn from(e: StartChildError) -> Self {
+        Self::StartChildError(e)
+    }
+}
+impl From<tokio::time::error::Elapsed> for SupervisorError {
+    fn from(e: tokio::time::error::Elapsed) -> Self {
+        Self::Timeout(Arc::new(e))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +608,7 @@ mod tests {
     use std::time::Duration;
 
     use agner_actors::System;
+    use agner_utils::future_timeout_ext::FutureTimeoutExt;
 
     use crate::common::InitType;
 
@@ -231,20 +653,229 @@ mod tests {
 
         assert!(system.all_actors().collect::<Vec<_>>().await.is_empty());
     }
-}
 
-impl From<oneshot::error::RecvError> for SupervisorError {
-    fn from(e: oneshot::error::RecvError) -> Self {
-        Self::OneshotRx(e)
+    #[tokio::test]
+    async fn restart_child_by_id() {
+        async fn worker(
+            context: &mut Context<oneshot::Sender<&'static str>>,
+            name: &'static str,
+        ) -> Result<Never, Exit> {
+            loop {
+                let _ = context.next_message().await.send(name);
+            }
+        }
+        let child_spec = UniformChildSpec::uniform()
+            .behaviour(worker)
+            .args_call1(|name| name)
+            .init_type(InitType::no_ack());
+
+        let system = System::new(Default::default());
+        let sup = system
+            .spawn(crate::uniform::run, SupSpec::new(child_spec.to_owned()), Default::default())
+            .await
+            .unwrap();
+
+        let w1 = start_child_restartable(&system, sup, "one").await.unwrap();
+        let w2 = restart_child::<&str>(&system, sup, w1).await.unwrap();
+        assert_ne!(w1, w2);
+        assert!(system.wait(w1).await.is_shutdown());
+
+        let (tx, rx) = oneshot::channel::<&str>();
+        system.send(w2, tx).await;
+        assert_eq!(rx.await.unwrap(), "one");
+
+        assert!(matches!(
+            restart_child::<&str>(&system, sup, w1).await,
+            Err(SupervisorError::UnknownChild(id)) if id == w1
+        ));
+
+        let w3 = start_child(&system, sup, "three").await.unwrap();
+        assert!(matches!(
+            restart_child::<&str>(&system, sup, w3).await,
+            Err(SupervisorError::NotRestartable(id)) if id == w3
+        ));
+
+        system.exit(sup, Exit::shutdown()).await;
+        assert!(system.wait(sup).await.is_shutdown());
+
+        // every child of a supervisor with restartable children can be restarted
+        let sup_spec = SupSpec::new(child_spec).with_restartable_children();
+        let sup = system.spawn(crate::uniform::run, sup_spec, Default::default()).await.unwrap();
+
+        let w1 = start_child(&system, sup, "four").await.unwrap();
+        let w2 = restart_child::<&str>(&system, sup, w1).await.unwrap();
+        assert!(system.wait(w1).await.is_shutdown());
+        let w3 = restart_child::<&str>(&system, sup, w2).await.unwrap();
+        assert!(system.wait(w2).await.is_shutdown());
+
+        let (tx, rx) = oneshot::channel::<&str>();
+        system.send(w3, tx).await;
+        assert_eq!(rx.await.unwrap(), "four");
+
+        system.exit(sup, Exit::shutdown()).await;
+        assert!(system.wait(sup).await.is_shutdown());
     }
-}
-impl From<StartChildError> for SupervisorError {
-    fn from(e: StartChildError) -> Self {
-        Self::StartChildError(e)
+
+    #[tokio::test]
+    async fn start_rate_limit() {
+        async fn worker(_context: &mut Context<Infallible>, _name: &'static str) {
+            std::future::pending().await
+        }
+        let child_spec = UniformChildSpec::uniform()
+            .behaviour(worker)
+            .args_call1(|name| name)
+            .init_type(InitType::no_ack());
+
+        let system = System::new(Default::default());
+
+        // the policy applies regardless of the order of the setters
+        for sup_spec in [
+            SupSpec::new(child_spec.to_owned())
+                .with_start_rate_limit(2, Duration::from_millis(200))
+                .with_rate_limit_policy(RateLimitPolicy::Reject),
+            SupSpec::new(child_spec.to_owned())
+                .with_rate_limit_policy(RateLimitPolicy::Reject)
+                .with_start_rate_limit(2, Duration::from_millis(200)),
+        ] {
+            let sup =
+                system.spawn(crate::uniform::run, sup_spec, Default::default()).await.unwrap();
+            assert!(start_child(&system, sup, "one").await.is_ok());
+            assert!(start_child(&system, sup, "two").await.is_ok());
+            assert!(matches!(
+                start_child(&system, sup, "three").await,
+                Err(SupervisorError::RateLimited)
+            ));
+        }
+
+        let sup_spec =
+            SupSpec::new(child_spec).with_start_rate_limit(2, Duration::from_millis(200));
+        let sup = system.spawn(crate::uniform::run, sup_spec, Default::default()).await.unwrap();
+        let t0 = std::time::Instant::now();
+        let started = futures::future::join_all(
+            ["one", "two", "three", "four"].map(|name| start_child(&system, sup, name)),
+        )
+        .await;
+        assert!(started.iter().all(Result::is_ok));
+        assert!(t0.elapsed() >= Duration::from_millis(200));
     }
-}
-impl From<tokio::time::error::Elapsed> for SupervisorError {
-    fn from(e: tokio::time::error::Elapsed) -> Self {
-        Self::Timeout(Arc::new(e))
+
+    #[tokio::test]
+    async fn max_children() {
+        async fn worker(_context: &mut Context<Infallible>, _name: &'static str) {
+            std::future::pending().await
+        }
+        let child_spec = UniformChildSpec::uniform()
+            .behaviour(worker)
+            .args_call1(|name| name)
+            .init_type(InitType::no_ack());
+
+        /// Reports the children going up and down.
+        #[derive(Debug)]
+        struct Recorder(tokio::sync::mpsc::UnboundedSender<&'static str>);
+        impl SupHook<()> for Recorder {
+            fn on_child_up(&self, _sup: ActorID, (): (), _actor_id: ActorID) {
+                let _ = self.0.send("up");
+            }
+            fn on_child_down(&self, _sup: ActorID, (): (), _actor_id: ActorID, _exit: &Exit) {
+                let _ = self.0.send("down");
+            }
+        }
+
+        let (recorder, mut recorded) = tokio::sync::mpsc::unbounded_channel();
+        let system = System::new(Default::default());
+        let sup_spec = SupSpec::new(child_spec)
+            .with_max_children(2)
+            .with_start_rate_limit(3, Duration::from_secs(60))
+            .with_rate_limit_policy(RateLimitPolicy::Reject)
+            .with_lifecycle_hook(Arc::new(Recorder(recorder)));
+        let sup = system.spawn(crate::uniform::run, sup_spec, Default::default()).await.unwrap();
+
+        let w1 = start_child(&system, sup, "one").await.unwrap();
+        let w2 = start_child_restartable(&system, sup, "two").await.unwrap();
+        assert_eq!(recorded.recv().await, Some("up"));
+        assert_eq!(recorded.recv().await, Some("up"));
+        assert!(matches!(
+            start_child(&system, sup, "three").await,
+            Err(SupervisorError::AtCapacity(2))
+        ));
+        assert!(restart_child::<&str>(&system, sup, w2).await.is_ok());
+        // the old child's exit and the new child's start may be handled in either order
+        let mut restarted = [recorded.recv().await.unwrap(), recorded.recv().await.unwrap()];
+        restarted.sort();
+        assert_eq!(restarted, ["down", "up"]);
+
+        // the rejections at capacity have not spent the start-rate tokens
+        assert!(stop_child::<&str>(&system, sup, w1).await.unwrap().is_shutdown());
+        assert_eq!(recorded.recv().await, Some("down"));
+        let w3 = start_child(&system, sup, "three").await.unwrap();
+        assert_ne!(w3, w1);
+
+        system.exit(sup, Exit::shutdown()).await;
+        assert!(system.wait(sup).await.is_shutdown());
+    }
+
+    #[tokio::test]
+    async fn sup_handle_checks_the_args_type() {
+        async fn worker(_context: &mut Context<Infallible>, _name: &'static str) {
+            std::future::pending().await
+        }
+        let child_spec = UniformChildSpec::uniform()
+            .behaviour(worker)
+            .args_call1(|name| name)
+            .init_type(InitType::no_ack());
+
+        let system = System::new(Default::default());
+        let sup = system
+            .spawn(crate::uniform::run, SupSpec::new(child_spec), Default::default())
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            SupHandle::<usize>::new(system.to_owned(), sup).await,
+            Err(SupervisorError::NotAUniformSupervisor(..))
+        ));
+
+        let handle = SupHandle::<&str>::new(system.to_owned(), sup).await.unwrap();
+        let child = handle.start_child("one").await.unwrap();
+        assert!(handle.stop_child(child).await.unwrap().is_shutdown());
+
+        system.exit(sup, Exit::shutdown()).await;
+        system.wait(sup).await;
+        assert!(matches!(
+            SupHandle::<&str>::new(system.to_owned(), sup).await,
+            Err(SupervisorError::NoSupervisor(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn shutdown_escalates_to_kill() {
+        async fn stubborn(
+            context: &mut Context<Infallible>,
+            ready: oneshot::Sender<()>,
+        ) -> Result<Never, Exit> {
+            context.trap_exit(true).await;
+            let _ = ready.send(());
+            std::future::pending().await
+        }
+        let child_spec = UniformChildSpec::uniform()
+            .behaviour(stubborn)
+            .args_call1(|ready| ready)
+            .init_type(InitType::no_ack());
+        let sup_spec = SupSpec::new(child_spec).with_shutdown(vec![
+            (Exit::shutdown(), Duration::from_millis(50)),
+            (Exit::kill(), Duration::from_secs(1)),
+        ]);
+
+        let system = System::new(Default::default());
+        let sup = system.spawn(crate::uniform::run, sup_spec, Default::default()).await.unwrap();
+
+        let (ready_tx, ready_rx) = oneshot::channel::<()>();
+        let child = start_child(&system, sup, ready_tx).await.unwrap();
+        ready_rx.await.unwrap();
+
+        system.exit(sup, Exit::shutdown()).await;
+        let sup_exited = system.wait(sup).timeout(Duration::from_secs(1)).await.unwrap();
+        assert!(sup_exited.is_shutdown());
+        assert!(system.wait(child).await.is_kill());
     }
 }
@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+/// What the [uniform supervisor](crate::uniform) does with a start-request exceeding the
+/// [start rate limit](crate::uniform::SupSpec::with_start_rate_limit).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitPolicy {
+    /// Hold the request until the rate allows to process it.
+    #[default]
+    Wait,
+
+    /// Fail the request with [`SupervisorError::RateLimited`](crate::uniform::SupervisorError).
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StartRateLimit {
+    pub max_per: usize,
+    pub per: Duration,
+}
+
+/// A token bucket holding up to `max_per` tokens, refilled at the rate of `max_per` tokens per
+/// `per`.
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_period: Duration,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(limit: &StartRateLimit) -> Self {
+        let capacity = limit.max_per.max(1) as f64;
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_period: limit.per.div_f64(capacity),
+            updated_at: Instant::now(),
+        }
+    }
+
+    /// Take a token, or tell how long to wait until one becomes available.
+    pub fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() / self.refill_period.as_secs_f64())
+            .min(self.capacity);
+        self.updated_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(self.refill_period.mul_f64(1.0 - self.tokens))
+        }
+    }
+}
+
+#[test]
+fn token_bucket() {
+    let limit = StartRateLimit { max_per: 2, per: Duration::from_secs(1) };
+    let mut bucket = TokenBucket::new(&limit);
+    let t0 = bucket.updated_at;
+
+    assert!(bucket.try_acquire(t0).is_ok());
+    assert!(bucket.try_acquire(t0).is_ok());
+    assert_eq!(bucket.try_acquire(t0), Err(Duration::from_millis(500)));
+    assert!(bucket.try_acquire(t0 + Duration::from_millis(500)).is_ok());
+    assert!(bucket.try_acquire(t0 + Duration::from_millis(500)).is_err());
+    assert!(bucket.try_acquire(t0 + Duration::from_secs(10)).is_ok());
+    assert!(bucket.try_acquire(t0 + Duration::from_secs(10)).is_ok());
+    assert!(bucket.try_acquire(t0 + Duration::from_secs(10)).is_err());
+}
@@ -0,0 +1,101 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use agner_actors::system_error::SysChannelError;
+use agner_actors::{ActorID, Context, Exit, System};
+
+use crate::common::StaticBoxedFuture;
+use crate::uniform::{Message, SupervisorError};
+
+/// A handle to a running [uniform supervisor](crate::uniform), starting its children with the
+/// arguments of type `A`.
+pub struct SupHandle<A> {
+    system: System,
+    sup: ActorID,
+    _args: PhantomData<fn(A)>,
+}
+
+/// Obtain [`SupHandle`]s from within an actor.
+pub trait ContextUniformSupExt {
+    /// Get a handle to the uniform supervisor `sup`, that starts its children with the arguments
+    /// of type `A`.
+    fn uniform_sup<A>(
+        &self,
+        sup: ActorID,
+    ) -> StaticBoxedFuture<Result<SupHandle<A>, SupervisorError>>
+    where
+        A: Send + 'static;
+}
+
+impl<A> SupHandle<A>
+where
+    A: Send + 'static,
+{
+    /// Make sure that `sup` is a running uniform supervisor accepting the arguments of type `A`,
+    /// and create a handle to it.
+    pub async fn new(system: System, sup: ActorID) -> Result<Self, SupervisorError> {
+        match system.check_message_type::<Message<A>>(sup).await {
+            Ok(()) => Ok(Self { system, sup, _args: Default::default() }),
+            Err(SysChannelError::InvalidMessageType) => match system.message_type_name(sup).await {
+                Some(message_type) =>
+                    Err(SupervisorError::NotAUniformSupervisor(sup, message_type)),
+                None => Err(SupervisorError::NoSupervisor(sup)),
+            },
+            Err(_) => Err(SupervisorError::NoSupervisor(sup)),
+        }
+    }
+
+    pub fn sup_id(&self) -> ActorID {
+        self.sup
+    }
+
+    /// See [`uniform::start_child`](crate::uniform::start_child).
+    pub async fn start_child(&self, args: A) -> Result<ActorID, SupervisorError> {
+        crate::uniform::start_child(&self.system, self.sup, args).await
+    }
+
+    /// See [`uniform::start_child_restartable`](crate::uniform::start_child_restartable).
+    pub async fn start_child_restartable(&self, args: A) -> Result<ActorID, SupervisorError>
+    where
+        A: Clone + Sync,
+    {
+        crate::uniform::start_child_restartable(&self.system, self.sup, args).await
+    }
+
+    /// See [`uniform::restart_child`](crate::uniform::restart_child).
+    pub async fn restart_child(&self, child: ActorID) -> Result<ActorID, SupervisorError> {
+        crate::uniform::restart_child::<A>(&self.system, self.sup, child).await
+    }
+
+    /// See [`uniform::stop_child`](crate::uniform::stop_child).
+    pub async fn stop_child(&self, child: ActorID) -> Result<Exit, SupervisorError> {
+        crate::uniform::stop_child::<A>(&self.system, self.sup, child).await
+    }
+}
+
+impl<M, C> ContextUniformSupExt for Context<M, C> {
+    fn uniform_sup<A>(
+        &self,
+        sup: ActorID,
+    ) -> StaticBoxedFuture<Result<SupHandle<A>, SupervisorError>>
+    where
+        A: Send + 'static,
+    {
+        Box::pin(SupHandle::new(self.system(), sup))
+    }
+}
+
+impl<A> Clone for SupHandle<A> {
+    fn clone(&self) -> Self {
+        Self { system: self.system.to_owned(), sup: self.sup, _args: Default::default() }
+    }
+}
+
+impl<A> fmt::Debug for SupHandle<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SupHandle")
+            .field("sup", &self.sup)
+            .field("args", &std::any::type_name::<A>())
+            .finish()
+    }
+}
@@ -3,9 +3,11 @@ pub mod behaviour;
 pub mod exited;
 pub mod query;
 pub mod registry;
+pub mod sup_harness;
 
 pub use api::TestActor;
 pub use registry::TestActorRegistry;
+pub use sup_harness::SupTestHarness;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,270 @@
+//! A harness driving a [mixed supervisor](agner_sup::mixed) of the test-actors.
+//!
+//! The children of the supervisor are the [test-actors](crate::TestActor): each of them may be
+//! killed with an arbitrary exit reason, or otherwise scripted via [`SupTestHarness::child`].
+//! The harness keeps track of the actor-id each child has been last seen with, so that it can
+//! tell whether the child has been restarted since.
+//!
+//! ```
+//! # #[tokio::main] async fn main() {
+//! use std::time::Duration;
+//!
+//! use agner_actors::{Exit, System};
+//! use agner_sup::mixed::{ChildType, OneForOne, RestartIntensity};
+//! use agner_test_actor::SupTestHarness;
+//!
+//! let restart_strategy = OneForOne::new(RestartIntensity::new(1, Duration::from_secs(60)));
+//! let mut harness = SupTestHarness::builder(restart_strategy)
+//!     .with_child("permanent", ChildType::Permanent)
+//!     .with_child("transient", ChildType::Transient)
+//!     .start(System::new(Default::default()))
+//!     .await
+//!     .unwrap();
+//!
+//! harness.kill_child("transient", Exit::normal()).await;
+//! harness.assert_not_restarted("transient").await;
+//!
+//! harness.kill_child("permanent", Exit::from_message("crash")).await;
+//! harness.assert_restarted("permanent", Duration::from_secs(1)).await;
+//!
+//! harness.kill_child("permanent", Exit::from_message("crash")).await;
+//! harness.assert_sup_exited(|exit| !exit.is_normal()).await;
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+use agner_actors::system_error::SysSpawnError;
+use agner_actors::{ActorID, Exit, SpawnOpts, System};
+use agner_sup::common::InitType;
+use agner_sup::mixed::plumbing::Decider;
+use agner_sup::mixed::{
+    AutoShutdown, ChildID, ChildType, MixedChildSpec, RestartStrategy, SupSpec,
+};
+use agner_utils::future_timeout_ext::FutureTimeoutExt;
+use agner_utils::std_error_pp::StdErrorPP;
+
+use crate::{TestActor, TestActorRegistry};
+
+#[cfg(test)]
+mod tests;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_SETTLE_TIME: Duration = Duration::from_millis(100);
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The specification of the child as it is received by
+/// [`SupTestHarnessBuilder::with_configured_child`]: its behaviour and arguments are to be filled
+/// in by the harness.
+pub type TestChildSpec<ID> = MixedChildSpec<ID, (), (), ()>;
+
+#[derive(Debug)]
+pub struct SupTestHarnessBuilder<ID, RS> {
+    registry: TestActorRegistry,
+    sup_spec: SupSpec<ID, RS>,
+    timeout: Duration,
+    settle_time: Duration,
+}
+
+/// A running supervisor, whose children are the test-actors (see the [module](self) docs).
+#[derive(Debug)]
+pub struct SupTestHarness<ID> {
+    system: System,
+    registry: TestActorRegistry,
+    sup: ActorID,
+    last_seen: HashMap<ID, ActorID>,
+    timeout: Duration,
+    settle_time: Duration,
+}
+
+impl<ID, RS> SupTestHarnessBuilder<ID, RS>
+where
+    ID: ChildID,
+    RS: RestartStrategy<ID> + Send + Sync + 'static,
+    RS::Decider: Decider<ID, Duration, Instant>,
+{
+    /// Add a test-actor child of the given type.
+    pub fn with_child(self, id: ID, child_type: ChildType) -> Self {
+        self.with_configured_child(id, |child_spec| child_spec.child_type(child_type))
+    }
+
+    /// Add a test-actor child, having its specification adjusted by `configure` (e.g. made
+    /// [significant](MixedChildSpec::significant) or [lazy](MixedChildSpec::lazy)).
+    pub fn with_configured_child(
+        mut self,
+        id: ID,
+        configure: impl FnOnce(TestChildSpec<ID>) -> TestChildSpec<ID>,
+    ) -> Self {
+        let registry = self.registry.to_owned();
+        let child_spec = configure(MixedChildSpec::mixed(id))
+            .behaviour(crate::behaviour::run::<Infallible>)
+            .args_call0(move || TestActor::<Infallible>::prepare_args(registry.to_owned()).0)
+            .init_type(InitType::no_ack());
+        self.sup_spec = self.sup_spec.with_child(child_spec);
+        self
+    }
+
+    pub fn with_auto_shutdown(mut self, auto_shutdown: AutoShutdown) -> Self {
+        self.sup_spec = self.sup_spec.with_auto_shutdown(auto_shutdown);
+        self
+    }
+
+    /// How long to wait for the things that are expected to happen (e.g. the children to start, or
+    /// the supervisor to exit). Defaults to 5 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How long to wait for the things that are expected not to happen (see
+    /// [`SupTestHarness::assert_not_restarted`]). Defaults to 100 milliseconds.
+    pub fn with_settle_time(mut self, settle_time: Duration) -> Self {
+        self.settle_time = settle_time;
+        self
+    }
+
+    /// Spawn the supervisor in the `system`, and wait for its children (except for the lazy ones)
+    /// to start.
+    pub async fn start(self, system: System) -> Result<SupTestHarness<ID>, SysSpawnError> {
+        let Self { registry, sup_spec, timeout, settle_time } = self;
+        let expected = sup_spec
+            .children
+            .iter()
+            .filter(|child_spec| !child_spec.lazy())
+            .map(|child_spec| child_spec.id())
+            .collect::<Vec<_>>();
+
+        let sup = system.spawn(agner_sup::mixed::run, sup_spec, SpawnOpts::new()).await?;
+
+        let mut harness = SupTestHarness {
+            system,
+            registry,
+            sup,
+            last_seen: Default::default(),
+            timeout,
+            settle_time,
+        };
+        for id in expected {
+            let actor_id = harness.child(id).await.actor_id();
+            harness.last_seen.insert(id, actor_id);
+        }
+
+        Ok(harness)
+    }
+}
+
+impl<ID> SupTestHarness<ID>
+where
+    ID: ChildID,
+{
+    pub fn builder<RS>(restart_strategy: RS) -> SupTestHarnessBuilder<ID, RS> {
+        SupTestHarnessBuilder {
+            registry: TestActorRegistry::new(),
+            sup_spec: SupSpec::new(restart_strategy),
+            timeout: DEFAULT_TIMEOUT,
+            settle_time: DEFAULT_SETTLE_TIME,
+        }
+    }
+
+    pub fn system(&self) -> &System {
+        &self.system
+    }
+
+    pub fn registry(&self) -> &TestActorRegistry {
+        &self.registry
+    }
+
+    /// The actor-id of the supervisor.
+    pub fn sup(&self) -> ActorID {
+        self.sup
+    }
+
+    /// The current actor of the child.
+    ///
+    /// Panics if the child is not running (and has not started within the timeout).
+    pub async fn child(&self, id: ID) -> TestActor<Infallible> {
+        let found = async {
+            loop {
+                if let Some(actor_id) = self.current_actor(id).await {
+                    if let Some(test_actor) = self.registry.lookup(actor_id).await {
+                        break test_actor
+                    }
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        };
+        found
+            .timeout(self.timeout)
+            .await
+            .unwrap_or_else(|_| panic!("child {:?} is not running", id))
+    }
+
+    /// Make the current actor of the child exit with the given reason, and wait for it to
+    /// terminate.
+    pub async fn kill_child(&mut self, id: ID, reason: Exit) {
+        let child = self.child(id).await;
+        self.last_seen.insert(id, child.actor_id());
+
+        child.exit(reason).await;
+        child
+            .wait()
+            .timeout(self.timeout)
+            .await
+            .unwrap_or_else(|_| panic!("child {:?} has not exited", id));
+    }
+
+    /// Assert that the child is restarted (i.e. runs as an actor other than the one it has been
+    /// last seen as) within the given time, and return the actor-id of the restarted child.
+    pub async fn assert_restarted(&mut self, id: ID, within: Duration) -> ActorID {
+        let last_seen = self.last_seen.get(&id).copied();
+        let restarted = async {
+            loop {
+                match self.current_actor(id).await {
+                    Some(actor_id) if Some(actor_id) != last_seen => break actor_id,
+                    _ => tokio::time::sleep(POLL_INTERVAL).await,
+                }
+            }
+        };
+        let actor_id = restarted.timeout(within).await.unwrap_or_else(|_| {
+            panic!("child {:?} has not been restarted within {:?}", id, within)
+        });
+        self.last_seen.insert(id, actor_id);
+        actor_id
+    }
+
+    /// Assert that the child is not restarted during the settle time: it is either not running,
+    /// or runs as the same actor it has been last seen as.
+    pub async fn assert_not_restarted(&self, id: ID) {
+        tokio::time::sleep(self.settle_time).await;
+
+        let last_seen = self.last_seen.get(&id).copied();
+        if let Some(actor_id) = self.current_actor(id).await {
+            assert_eq!(Some(actor_id), last_seen, "child {:?} has been restarted", id);
+        }
+    }
+
+    /// Assert that the supervisor exits with a reason satisfying `is_expected`, and return that
+    /// reason.
+    pub async fn assert_sup_exited(&self, is_expected: impl FnOnce(&Exit) -> bool) -> Exit {
+        let exit = self
+            .system
+            .wait(self.sup)
+            .timeout(self.timeout)
+            .await
+            .unwrap_or_else(|_| panic!("supervisor {} has not exited", self.sup));
+        assert!(is_expected(&exit), "unexpected supervisor's exit reason: {}", exit.pp());
+        exit
+    }
+
+    /// The actor the child currently runs as, unless the child is not running (or the supervisor
+    /// is gone).
+    async fn current_actor(&self, id: ID) -> Option<ActorID> {
+        let children = agner_sup::mixed::which_children::<ID>(&self.system, self.sup).await.ok()?;
+        children
+            .into_iter()
+            .find(|(child_id, _)| *child_id == id)
+            .map(|(_, actor_id)| actor_id)
+    }
+}
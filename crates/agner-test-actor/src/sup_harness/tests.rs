@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use agner_actors::{Exit, System};
+use agner_sup::mixed::{AllForOne, ChildType, OneForOne, RestartIntensity};
+
+use crate::SupTestHarness;
+
+const WITHIN: Duration = Duration::from_secs(1);
+
+#[tokio::test]
+async fn crash_beyond_intensity_shuts_the_tree_down() {
+    let restart_strategy = OneForOne::new(RestartIntensity::new(2, Duration::from_secs(60)));
+    let mut harness = SupTestHarness::builder(restart_strategy)
+        .with_child("first", ChildType::Permanent)
+        .with_child("second", ChildType::Permanent)
+        .start(System::new(Default::default()))
+        .await
+        .unwrap();
+    let sibling = harness.child("second").await;
+
+    for _ in 0..2 {
+        harness.kill_child("first", Exit::from_message("crash")).await;
+        harness.assert_restarted("first", WITHIN).await;
+        harness.assert_not_restarted("second").await;
+    }
+
+    harness.kill_child("first", Exit::from_message("crash")).await;
+    harness.assert_sup_exited(|exit| !exit.is_normal()).await;
+    assert!(sibling.wait().await.is_shutdown());
+}
+
+#[tokio::test]
+async fn child_types_decide_upon_the_restart() {
+    let restart_strategy = OneForOne::new(RestartIntensity::new(5, Duration::from_secs(60)));
+    let mut harness = SupTestHarness::builder(restart_strategy)
+        .with_child("transient", ChildType::Transient)
+        .with_child("temporary", ChildType::Temporary)
+        .start(System::new(Default::default()))
+        .await
+        .unwrap();
+
+    harness.kill_child("transient", Exit::from_message("crash")).await;
+    harness.assert_restarted("transient", WITHIN).await;
+
+    harness.kill_child("transient", Exit::normal()).await;
+    harness.assert_not_restarted("transient").await;
+
+    harness.kill_child("temporary", Exit::from_message("crash")).await;
+    harness.assert_not_restarted("temporary").await;
+}
+
+#[tokio::test]
+async fn all_for_one_restarts_the_siblings() {
+    let restart_strategy = AllForOne::new(RestartIntensity::new(5, Duration::from_secs(60)));
+    let mut harness = SupTestHarness::builder(restart_strategy)
+        .with_child("first", ChildType::Permanent)
+        .with_child("second", ChildType::Permanent)
+        .start(System::new(Default::default()))
+        .await
+        .unwrap();
+
+    harness.kill_child("first", Exit::from_message("crash")).await;
+    harness.assert_restarted("first", WITHIN).await;
+    harness.assert_restarted("second", WITHIN).await;
+}
+
+#[tokio::test]
+async fn significant_child_shuts_the_supervisor_down() {
+    let restart_strategy = OneForOne::new(RestartIntensity::new(5, Duration::from_secs(60)));
+    let mut harness = SupTestHarness::builder(restart_strategy)
+        .with_child("idle", ChildType::Permanent)
+        .with_configured_child("job", |child_spec| {
+            child_spec.child_type(ChildType::Transient).significant(true)
+        })
+        .start(System::new(Default::default()))
+        .await
+        .unwrap();
+
+    harness.kill_child("job", Exit::normal()).await;
+    harness.assert_sup_exited(Exit::is_normal).await;
+}
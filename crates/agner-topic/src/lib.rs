@@ -0,0 +1,16 @@
+//! Topic
+//! =====
+//!
+//! An actor fanning the published messages out to its subscribers.
+//!
+//! The subscribers are monitored by the topic: a terminated subscriber leaves the topic on its own.
+//! Each published message is delivered to every subscriber on behalf of the topic, so a subscriber
+//! spawned with a [sender quota](agner_actors::SpawnOpts::with_sender_quota) caps the number of the
+//! topic's messages awaiting in its inbox: what happens to a subscriber lagging behind that much is
+//! specified by [`SlowSubscriber`].
+
+mod topic;
+pub use topic::{run, Request, SlowSubscriber, Topic};
+
+#[cfg(test)]
+mod tests;
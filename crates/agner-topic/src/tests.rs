@@ -0,0 +1,102 @@
+use agner_actors::{ActorID, Context, Exit, SpawnOpts, System};
+use tokio::sync::mpsc;
+
+use crate::{SlowSubscriber, Topic};
+
+async fn forward(context: &mut Context<usize>, tx: mpsc::UnboundedSender<usize>) {
+    loop {
+        let message = context.next_message().await;
+        let _ = tx.send(message);
+    }
+}
+
+async fn stall(_context: &mut Context<usize>, _arg: ()) {
+    std::future::pending().await
+}
+
+/// Wait until the `topic` runs exactly `count` monitors.
+///
+/// A cancelled monitor delivers its message to the topic before it is retired, so the requests
+/// sent afterwards are handled after it.
+async fn wait_for_monitors(system: &System, topic: ActorID, count: usize) {
+    loop {
+        let info = system.actor_info(topic).await.unwrap();
+        if info.tasks_count == count && info.monitors.len() == count {
+            break
+        }
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn messages_are_fanned_out() {
+    let system = System::new(Default::default());
+    let topic = Topic::<usize>::start(&system, Default::default()).await.unwrap();
+
+    let mut inboxes = vec![];
+    for _ in 0..3 {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let subscriber = system.spawn(forward, tx, Default::default()).await.unwrap();
+        topic.subscribe(subscriber).await;
+        topic.subscribe(subscriber).await;
+        inboxes.push(rx);
+    }
+    assert_eq!(topic.subscriber_count().await, Some(3));
+
+    topic.publish(1).await;
+    topic.publish(2).await;
+    for rx in inboxes.iter_mut() {
+        assert_eq!(rx.recv().await, Some(1));
+        assert_eq!(rx.recv().await, Some(2));
+    }
+}
+
+#[tokio::test]
+async fn subscribers_leave() {
+    let system = System::new(Default::default());
+    let topic = Topic::<usize>::start(&system, Default::default()).await.unwrap();
+
+    let (tx, _rx) = mpsc::unbounded_channel();
+    let leaving = system.spawn(forward, tx.to_owned(), Default::default()).await.unwrap();
+    let dying = system.spawn(forward, tx, Default::default()).await.unwrap();
+    topic.subscribe(leaving).await;
+    topic.subscribe(dying).await;
+    assert_eq!(topic.subscriber_count().await, Some(2));
+
+    topic.unsubscribe(leaving).await;
+    assert_eq!(topic.subscriber_count().await, Some(1));
+
+    system.exit(dying, Exit::shutdown()).await;
+    system.wait(dying).await;
+    wait_for_monitors(&system, topic.actor_id(), 0).await;
+    assert_eq!(topic.subscriber_count().await, Some(0));
+
+    // the unsubscription cancels the monitor, and it does not cancel the resubscription
+    topic.subscribe(leaving).await;
+    topic.unsubscribe(leaving).await;
+    topic.subscribe(leaving).await;
+    wait_for_monitors(&system, topic.actor_id(), 1).await;
+    assert_eq!(topic.subscriber_count().await, Some(1));
+
+    topic.unsubscribe(leaving).await;
+    wait_for_monitors(&system, topic.actor_id(), 0).await;
+    assert_eq!(topic.subscriber_count().await, Some(0));
+}
+
+#[tokio::test]
+async fn slow_subscribers() {
+    let system = System::new(Default::default());
+
+    for (slow_subscriber, expected_count) in
+        [(SlowSubscriber::Drop, 1), (SlowSubscriber::Disconnect, 0)]
+    {
+        let topic = Topic::<usize>::start(&system, slow_subscriber).await.unwrap();
+        let subscriber =
+            system.spawn(stall, (), SpawnOpts::new().with_sender_quota(1)).await.unwrap();
+        topic.subscribe(subscriber).await;
+
+        topic.publish(1).await;
+        topic.publish(2).await;
+        assert_eq!(topic.subscriber_count().await, Some(expected_count));
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::Poll;
+
+use agner_actors::system_error::{SysChannelError, SysSpawnError};
+use agner_actors::{actor_debug, ActorID, Context, SpawnOpts, System};
+use tokio::sync::oneshot;
+
+/// What the topic does to a subscriber whose inbox does not accept any more of the topic's
+/// messages (i.e. the topic has exceeded its [sender
+/// quota](agner_actors::SpawnOpts::with_sender_quota) on the subscriber's inbox).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SlowSubscriber {
+    /// The subscriber misses the message, but stays subscribed.
+    #[default]
+    Drop,
+    /// The subscriber is unsubscribed.
+    Disconnect,
+}
+
+/// A handle to a running topic.
+pub struct Topic<M> {
+    system: System,
+    actor_id: ActorID,
+    _message: PhantomData<fn(M)>,
+}
+
+/// The message accepted by the [topic's behaviour](run).
+pub struct Request<M>(Inner<M>);
+
+enum Inner<M> {
+    Publish(M),
+    Subscribe(ActorID),
+    Unsubscribe(ActorID),
+    SubscriberCount(oneshot::Sender<usize>),
+    SubscriberDown(ActorID, u64),
+}
+
+impl<M> Topic<M>
+where
+    M: Clone + Send + Sync + Unpin + 'static,
+{
+    /// Spawn a topic in the `system`.
+    pub async fn start(
+        system: &System,
+        slow_subscriber: SlowSubscriber,
+    ) -> Result<Self, SysSpawnError> {
+        let actor_id = system.spawn(run::<M>, slow_subscriber, SpawnOpts::new()).await?;
+        Ok(Self::new(system.to_owned(), actor_id))
+    }
+
+    /// A handle to the topic running as the actor `actor_id` (e.g. spawned by a supervisor).
+    pub fn new(system: System, actor_id: ActorID) -> Self {
+        Self { system, actor_id, _message: PhantomData }
+    }
+
+    pub fn actor_id(&self) -> ActorID {
+        self.actor_id
+    }
+
+    /// Send a clone of `message` to each of the subscribers.
+    pub async fn publish(&self, message: M) {
+        self.system.send(self.actor_id, Request(Inner::Publish(message))).await
+    }
+
+    /// Subscribe the `actor_id` to the topic: the actor should accept the messages of type `M`.
+    ///
+    /// The actor is unsubscribed upon its termination.
+    pub async fn subscribe(&self, actor_id: ActorID) {
+        self.system.send(self.actor_id, Request::<M>(Inner::Subscribe(actor_id))).await
+    }
+
+    pub async fn unsubscribe(&self, actor_id: ActorID) {
+        self.system
+            .send(self.actor_id, Request::<M>(Inner::Unsubscribe(actor_id)))
+            .await
+    }
+
+    /// The number of the subscribers, or `None` if the topic is not running.
+    pub async fn subscriber_count(&self) -> Option<usize> {
+        let (tx, rx) = oneshot::channel();
+        self.system.send(self.actor_id, Request::<M>(Inner::SubscriberCount(tx))).await;
+        rx.await.ok()
+    }
+}
+
+/// The behaviour of the topic.
+pub async fn run<M>(context: &mut Context<Request<M>>, slow_subscriber: SlowSubscriber)
+where
+    M: Clone + Send + Sync + Unpin + 'static,
+{
+    // each subscription is tagged, so that a stale monitor does not cancel a later subscription.
+    // Dropping the subscription's `oneshot::Sender` cancels its monitor.
+    let mut subscribers: HashMap<ActorID, (u64, oneshot::Sender<()>)> = Default::default();
+    let mut next_tag = 0u64;
+
+    loop {
+        let Request(request) = context.next_message().await;
+        match request {
+            Inner::Publish(message) => {
+                let mut disconnected = vec![];
+                for &subscriber in subscribers.keys() {
+                    match context.send(subscriber, message.to_owned()).await {
+                        Ok(()) => (),
+                        Err(SysChannelError::QuotaExceeded)
+                            if slow_subscriber == SlowSubscriber::Drop =>
+                            actor_debug!(context.log(), "{} misses a message", subscriber),
+                        Err(reason) => {
+                            actor_debug!(context.log(), "disconnecting {}: {}", subscriber, reason);
+                            disconnected.push(subscriber);
+                        },
+                    }
+                }
+                for subscriber in disconnected {
+                    subscribers.remove(&subscriber);
+                }
+            },
+            Inner::Subscribe(subscriber) =>
+                if let Entry::Vacant(vacant) = subscribers.entry(subscriber) {
+                    let tag = next_tag;
+                    next_tag += 1;
+                    let (cancel_tx, mut cancel_rx) = oneshot::channel();
+                    vacant.insert((tag, cancel_tx));
+
+                    let mut down = Box::pin(context.monitor_fut(subscriber));
+                    context
+                        .future_to_inbox(async move {
+                            std::future::poll_fn(|cx| {
+                                if down.as_mut().poll(cx).is_ready() ||
+                                    Pin::new(&mut cancel_rx).poll(cx).is_ready()
+                                {
+                                    Poll::Ready(())
+                                } else {
+                                    Poll::Pending
+                                }
+                            })
+                            .await;
+                            Request(Inner::SubscriberDown(subscriber, tag))
+                        })
+                        .await;
+                },
+            Inner::Unsubscribe(subscriber) => {
+                subscribers.remove(&subscriber);
+            },
+            Inner::SubscriberCount(reply_to) => {
+                let _ = reply_to.send(subscribers.len());
+            },
+            Inner::SubscriberDown(subscriber, tag) =>
+                if subscribers.get(&subscriber).map(|(t, _)| *t) == Some(tag) {
+                    subscribers.remove(&subscriber);
+                },
+        }
+    }
+}
+
+impl<M> Clone for Topic<M> {
+    fn clone(&self) -> Self {
+        Self { system: self.system.to_owned(), actor_id: self.actor_id, _message: PhantomData }
+    }
+}
+
+impl<M> fmt::Debug for Topic<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Topic")
+            .field("actor_id", &self.actor_id)
+            .field("message", &std::any::type_name::<M>())
+            .finish()
+    }
+}
+
+impl<M> fmt::Debug for Request<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Inner::Publish(_) => write!(f, "Publish<{}>", std::any::type_name::<M>()),
+            Inner::Subscribe(actor_id) => write!(f, "Subscribe({})", actor_id),
+            Inner::Unsubscribe(actor_id) => write!(f, "Unsubscribe({})", actor_id),
+            Inner::SubscriberCount(_) => write!(f, "SubscriberCount"),
+            Inner::SubscriberDown(actor_id, _) => write!(f, "SubscriberDown({})", actor_id),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod async_yield;
 pub mod future_timeout_ext;
+pub mod rate_limiter;
 pub mod result_err_flatten;
 pub mod std_error_pp;
 
@@ -0,0 +1,124 @@
+use std::time::{Duration, Instant};
+
+/// A token bucket: holds up to `burst` tokens, and gets a token back every `refill_every`.
+///
+/// The limiter does not read the time on its own: the caller passes the current instant in, so
+/// that an actor may use the clock of its system (`Context::now_instant`), and thus be tested with
+/// a mock clock.
+///
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use agner_utils::rate_limiter::RateLimiter;
+///
+/// let mut limiter = RateLimiter::new(2, Duration::from_secs(1));
+/// let t0 = Instant::now();
+///
+/// assert!(limiter.check(t0));
+/// assert!(limiter.check(t0));
+/// assert!(!limiter.check(t0));
+/// assert!(limiter.check(t0 + Duration::from_secs(1)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    burst: u32,
+    refill_every: Duration,
+
+    /// The instant at which the bucket is full again (`None` — it has never been drained).
+    full_at: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// A limiter allowing `burst` events at once, and then one event per `refill_every`.
+    ///
+    /// A zero `burst` is treated as one.
+    pub fn new(burst: u32, refill_every: Duration) -> Self {
+        Self { burst: burst.max(1), refill_every, full_at: None }
+    }
+
+    /// A limiter allowing `burst` events at once, and then `per_second` events per second.
+    ///
+    /// Panics if `per_second` is zero.
+    pub fn per_second(burst: u32, per_second: u32) -> Self {
+        assert!(per_second > 0, "per_second should be positive");
+        Self::new(burst, Duration::from_secs(1) / per_second)
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    pub fn refill_every(&self) -> Duration {
+        self.refill_every
+    }
+
+    /// Take a token if there is one as of `now`.
+    ///
+    /// Returns `false` if the event should be rejected. The instants are expected to be reported
+    /// in the ascending order.
+    pub fn check(&mut self, now: Instant) -> bool {
+        let full_at = self.full_at.filter(|full_at| *full_at > now).unwrap_or(now);
+        let capacity = self.refill_every * self.burst;
+
+        if full_at + self.refill_every - now > capacity {
+            false
+        } else {
+            self.full_at = Some(full_at + self.refill_every);
+            true
+        }
+    }
+
+    /// The number of tokens available as of `now`.
+    pub fn available(&self, now: Instant) -> u32 {
+        let Some(full_at) = self.full_at.filter(|full_at| *full_at > now) else {
+            return self.burst
+        };
+        if self.refill_every.is_zero() {
+            return self.burst
+        }
+        let missing = (full_at - now).as_nanos().div_ceil(self.refill_every.as_nanos());
+        self.burst.saturating_sub(missing.try_into().unwrap_or(u32::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::RateLimiter;
+
+    const SEC: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn burst_then_refill() {
+        let mut limiter = RateLimiter::new(3, SEC);
+        let t0 = Instant::now();
+
+        assert_eq!(limiter.available(t0), 3);
+        assert!((0..3).all(|_| limiter.check(t0)));
+        assert!(!limiter.check(t0));
+        assert_eq!(limiter.available(t0), 0);
+
+        // not a whole token yet
+        assert!(!limiter.check(t0 + SEC / 2));
+
+        assert_eq!(limiter.available(t0 + SEC), 1);
+        assert!(limiter.check(t0 + SEC));
+        assert!(!limiter.check(t0 + SEC));
+
+        // refilled up to the burst, not beyond
+        let t1 = t0 + 10 * SEC;
+        assert_eq!(limiter.available(t1), 3);
+        assert!((0..3).all(|_| limiter.check(t1)));
+        assert!(!limiter.check(t1));
+    }
+
+    #[test]
+    fn steady_rate() {
+        let mut limiter = RateLimiter::per_second(1, 4);
+        let t0 = Instant::now();
+
+        let allowed = (0..40).filter(|i| limiter.check(t0 + SEC / 8 * *i)).count();
+        assert_eq!(allowed, 20);
+    }
+}
@@ -1,38 +1,69 @@
-use std::collections::VecDeque;
+//! A bounded single-producer single-consumer queue.
+//!
+//! The queue is a ring of `max_len` slots, with the head and the tail being atomic counters: the
+//! producer only ever advances the tail, the consumer only ever advances the head. Hence neither
+//! [`Sender::send`] nor [`Receiver::recv`] takes a lock, and the length of the queue is a pair of
+//! atomic loads.
+//!
+//! The [`Sender`] may also look into the queue ([`Sender::peek`]), or empty it
+//! ([`Sender::take_all`]): for the time of these operations it claims the consumer's side of the
+//! queue, so that the items are not taken from under it.
+
+use std::cell::UnsafeCell;
 use std::future::Future;
+use std::mem::MaybeUninit;
 use std::pin::Pin;
-use std::task::{Context, Poll, Waker};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::task::AtomicWaker;
 
-use futures::lock::BiLock;
+use crate::async_yield::async_yield;
 
 #[cfg(test)]
 mod tests;
 
 pub fn channel<T>(max_len: usize) -> (Sender<T>, Receiver<T>) {
-    let inner =
-        Inner { queue: Default::default(), max_len, sender_waker: None, receiver_waker: None };
+    let slots = (0..max_len.max(1)).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect();
+    let ring = Arc::new(Ring {
+        slots,
+        max_len,
+        head: Default::default(),
+        tail: Default::default(),
+        consumer_claimed: Default::default(),
+        sender_waker: Default::default(),
+        receiver_waker: Default::default(),
+    });
 
-    let (sender, receiver) = BiLock::new(inner);
-    (Sender(sender), Receiver(receiver))
+    (Sender(ring.to_owned()), Receiver(ring))
 }
 
 #[derive(Debug)]
-pub struct Receiver<T>(BiLock<Inner<T>>);
+pub struct Receiver<T>(Arc<Ring<T>>);
 
 #[derive(Debug)]
-pub struct Sender<T>(BiLock<Inner<T>>);
+pub struct Sender<T>(Arc<Ring<T>>);
 
 impl<T> Receiver<T>
 where
     T: Unpin,
 {
     pub fn recv(&mut self, should_block: bool) -> impl Future<Output = Option<T>> + '_ {
-        Receive { lock: &self.0, should_block }
+        Receive { ring: &self.0, should_block }
+    }
+
+    pub async fn drain(&mut self, max: usize) -> Vec<T> {
+        let consumer = self.0.claim_consumer().await;
+        let drained = std::iter::from_fn(|| consumer.pop()).take(max).collect::<Vec<_>>();
+        if !drained.is_empty() {
+            self.0.sender_waker.wake();
+        }
+        drained
     }
 
-    pub async fn len(&self) -> (usize, usize) {
-        let locked = self.0.lock().await;
-        (locked.queue.len(), locked.max_len)
+    pub fn len(&self) -> (usize, usize) {
+        (self.0.len(), self.0.max_len)
     }
 }
 
@@ -45,36 +76,146 @@ where
         item: T,
         should_block: bool,
     ) -> impl Future<Output = Result<(), T>> + '_ {
-        Send { lock: &self.0, should_block, item: Some(item) }
+        Send { ring: &self.0, should_block, item: Some(item) }
+    }
+
+    pub fn len(&self) -> (usize, usize) {
+        (self.0.len(), self.0.max_len)
+    }
+
+    pub async fn peek<R>(&self, max: usize, f: impl FnMut(&T) -> R) -> Vec<R> {
+        let consumer = self.0.claim_consumer().await;
+        consumer.iter().take(max).map(f).collect()
     }
 
-    pub async fn len(&self) -> (usize, usize) {
-        let locked = self.0.lock().await;
-        (locked.queue.len(), locked.max_len)
+    pub async fn take_all(&mut self) -> Vec<T> {
+        let consumer = self.0.claim_consumer().await;
+        std::iter::from_fn(|| consumer.pop()).collect()
     }
 }
 
-#[derive(Debug)]
-struct Inner<T> {
-    queue: VecDeque<T>,
+struct Ring<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
     max_len: usize,
-    sender_waker: Option<Waker>,
-    receiver_waker: Option<Waker>,
+
+    /// The number of items ever taken from the queue; only advanced by the consumer.
+    head: AtomicUsize,
+    /// The number of items ever put into the queue; only advanced by the producer.
+    tail: AtomicUsize,
+    consumer_claimed: AtomicBool,
+
+    sender_waker: AtomicWaker,
+    receiver_waker: AtomicWaker,
 }
 
+// SAFETY: a slot is only accessed either by the producer (before the tail is advanced past it), or
+// by the holder of the consumer's claim (after that) — never by both at the same time.
+unsafe impl<T: std::marker::Send> std::marker::Send for Ring<T> {}
+unsafe impl<T: std::marker::Send> Sync for Ring<T> {}
+
+/// The consumer's side of the [`Ring`], claimed for the lifetime of this guard.
+struct Consumer<'a, T>(&'a Ring<T>);
+
 #[pin_project::pin_project]
 struct Receive<'a, T> {
-    lock: &'a BiLock<Inner<T>>,
+    ring: &'a Ring<T>,
     should_block: bool,
 }
 
 #[pin_project::pin_project]
 struct Send<'a, T> {
-    lock: &'a BiLock<Inner<T>>,
+    ring: &'a Ring<T>,
     should_block: bool,
     item: Option<T>,
 }
 
+impl<T> Ring<T> {
+    fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        tail.wrapping_sub(head).min(self.max_len)
+    }
+
+    fn slot(&self, idx: usize) -> *mut MaybeUninit<T> {
+        self.slots[idx % self.slots.len()].get()
+    }
+
+    fn try_claim_consumer(&self) -> Option<Consumer<'_, T>> {
+        self.consumer_claimed
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| Consumer(self))
+    }
+
+    async fn claim_consumer(&self) -> Consumer<'_, T> {
+        loop {
+            if let Some(consumer) = self.try_claim_consumer() {
+                break consumer
+            }
+            async_yield().await
+        }
+    }
+
+    /// Must only be invoked by the producer.
+    fn push(&self, item: T) -> Result<(), T> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) >= self.max_len {
+            return Err(item)
+        }
+        // SAFETY: the slot is not within `head..tail`, so the consumer does not touch it.
+        unsafe { (*self.slot(tail)).write(item) };
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+}
+
+impl<'a, T> Consumer<'a, T> {
+    fn pop(&self) -> Option<T> {
+        let head = self.0.head.load(Ordering::Relaxed);
+        let tail = self.0.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None
+        }
+        // SAFETY: the slot is within `head..tail`, i.e. it has been written by the producer, and
+        // is not going to be touched by it until the head is advanced.
+        let item = unsafe { (*self.0.slot(head)).assume_init_read() };
+        self.0.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(item)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let head = self.0.head.load(Ordering::Relaxed);
+        let tail = self.0.tail.load(Ordering::Acquire);
+        (0..tail.wrapping_sub(head)).map(move |offset| {
+            // SAFETY: see `Consumer::pop`; the head is not advanced while `self` is borrowed.
+            unsafe { (*self.0.slot(head.wrapping_add(offset))).assume_init_ref() }
+        })
+    }
+}
+
+impl<'a, T> Drop for Consumer<'a, T> {
+    fn drop(&mut self) {
+        self.0.consumer_claimed.store(false, Ordering::Release);
+    }
+}
+
+impl<T> Drop for Ring<T> {
+    fn drop(&mut self) {
+        let consumer = Consumer(self);
+        while consumer.pop().is_some() {}
+    }
+}
+
+impl<T> std::fmt::Debug for Ring<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ring")
+            .field("len", &self.len())
+            .field("max_len", &self.max_len)
+            .finish()
+    }
+}
+
 impl<'a, T> Future for Receive<'a, T>
 where
     T: Unpin,
@@ -84,22 +225,28 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.project();
 
-        let mut locked = futures::ready!(this.lock.poll_lock(cx));
-        let _ = locked.receiver_waker.take();
+        let Some(consumer) = this.ring.try_claim_consumer() else {
+            // the producer is peeking into the queue: that does not take long.
+            cx.waker().wake_by_ref();
+            return Poll::Pending
+        };
+
+        let popped = consumer.pop().or_else(|| {
+            if *this.should_block {
+                this.ring.receiver_waker.register(cx.waker());
+                consumer.pop()
+            } else {
+                None
+            }
+        });
 
-        match (locked.queue.pop_front(), this.should_block) {
+        match (popped, this.should_block) {
             (Some(item), _) => {
-                if let Some(waker) = locked.sender_waker.take() {
-                    waker.wake();
-                }
+                this.ring.sender_waker.wake();
                 Poll::Ready(Some(item))
             },
             (None, false) => Poll::Ready(None),
-            (None, true) => {
-                let should_be_none = locked.receiver_waker.replace(cx.waker().to_owned());
-                assert!(should_be_none.is_none());
-                Poll::Pending
-            },
+            (None, true) => Poll::Pending,
         }
     }
 }
@@ -113,25 +260,24 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.project();
 
-        let mut locked = futures::ready!(this.lock.poll_lock(cx));
-        let _ = locked.sender_waker.take();
+        let item = this.item.take().expect("Item empty");
+        let pushed = this.ring.push(item).or_else(|item| {
+            if *this.should_block {
+                this.ring.sender_waker.register(cx.waker());
+                this.ring.push(item)
+            } else {
+                Err(item)
+            }
+        });
 
-        match (locked.queue.len() < locked.max_len, this.should_block) {
-            (true, _) => {
-                let item = this.item.take().expect("Item empty");
-                locked.queue.push_back(item);
-                if let Some(waker) = locked.receiver_waker.take() {
-                    waker.wake();
-                }
+        match (pushed, this.should_block) {
+            (Ok(()), _) => {
+                this.ring.receiver_waker.wake();
                 Poll::Ready(Ok(()))
             },
-            (false, false) => {
-                let item = this.item.take().expect("Item empty");
-                Poll::Ready(Err(item))
-            },
-            (false, true) => {
-                let should_be_none = locked.sender_waker.replace(cx.waker().to_owned());
-                assert!(should_be_none.is_none());
+            (Err(item), false) => Poll::Ready(Err(item)),
+            (Err(item), true) => {
+                *this.item = Some(item);
                 Poll::Pending
             },
         }
@@ -37,3 +37,67 @@ async fn blocking_basic() {
 
     assert_eq!(future::join(producer, consumer).await, ((), ()));
 }
+
+#[tokio::test]
+async fn drain_basic() {
+    let (mut tx, mut rx) = channel::<usize>(5);
+    for i in 1..=5 {
+        assert!(tx.send(i, false).await.is_ok());
+    }
+    assert_eq!(rx.drain(3).await, [1, 2, 3]);
+    assert!(tx.send(6, false).await.is_ok());
+    assert_eq!(rx.drain(10).await, [4, 5, 6]);
+    assert!(rx.drain(10).await.is_empty());
+}
+
+#[tokio::test]
+async fn len_peek_take_all() {
+    let (mut tx, mut rx) = channel::<usize>(4);
+    assert_eq!(tx.len(), (0, 4));
+    for i in 1..=4 {
+        assert!(tx.send(i, false).await.is_ok());
+    }
+    assert_eq!(tx.len(), (4, 4));
+    assert_eq!(rx.len(), (4, 4));
+    assert_eq!(tx.peek(2, |i| i * 10).await, [10, 20]);
+
+    assert_eq!(rx.recv(false).await, Some(1));
+    assert!(tx.send(5, false).await.is_ok());
+    assert_eq!(rx.len(), (4, 4));
+    assert_eq!(tx.peek(10, |i| *i).await, [2, 3, 4, 5]);
+
+    assert_eq!(tx.take_all().await, [2, 3, 4, 5]);
+    assert_eq!(tx.len(), (0, 4));
+    assert_eq!(rx.recv(false).await, None);
+}
+
+#[tokio::test]
+async fn undelivered_items_are_dropped() {
+    let item = std::sync::Arc::new(());
+    let (mut tx, rx) = channel(3);
+    for _ in 0..3 {
+        assert!(tx.send(item.to_owned(), false).await.is_ok());
+    }
+    assert_eq!(std::sync::Arc::strong_count(&item), 4);
+    drop((tx, rx));
+    assert_eq!(std::sync::Arc::strong_count(&item), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn blocking_across_threads() {
+    const COUNT: usize = 100_000;
+
+    let (mut tx, mut rx) = channel::<usize>(16);
+    let producer = tokio::spawn(async move {
+        for i in 0..COUNT {
+            assert!(tx.send(i, true).await.is_ok());
+        }
+    });
+    let consumer = tokio::spawn(async move {
+        for i in 0..COUNT {
+            assert_eq!(rx.recv(true).await, Some(i));
+        }
+    });
+    producer.await.unwrap();
+    consumer.await.unwrap();
+}
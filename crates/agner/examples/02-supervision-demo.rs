@@ -7,6 +7,7 @@ use agner::reg;
 use agner::sup::common::WithAck;
 use agner::sup::mixed::{self, AllForOne, RestartIntensity};
 use agner::sup::uniform;
+use agner::utils::std_error_pp::StdErrorPP;
 use agner_sup::mixed::MixedChildSpec;
 use agner_sup::uniform::UniformChildSpec;
 use tokio::net::UnixStream;
@@ -32,7 +33,9 @@ fn main() {
         let system = common::system(max_actors);
 
         let restart_intensity = RestartIntensity::new(0, Duration::ZERO);
-        let restart_strategy = AllForOne::new(restart_intensity);
+        // should a child fail, the top-sup exits with the child's failure as the reason
+        let restart_strategy =
+            AllForOne::new(restart_intensity).with_escalation(Default::default());
 
         let (fanout_reg_tx, fanout_reg_rx) = reg::new();
         let (uds_conn_sup_reg_tx, uds_conn_sup_reg_rx) = reg::new();
@@ -71,7 +74,10 @@ fn main() {
         let mut sig_term = tokio::signal::unix::signal(SignalKind::terminate()).unwrap();
 
         if let Some(exit_reason) = tokio::select! {
-            _top_sup_exited = top_sup_waiting => None,
+            top_sup_exited = top_sup_waiting => {
+                tracing::error!("top-sup has exited: {}", top_sup_exited.pp());
+                None
+            },
             _sig_int = sig_int.recv() => Some(Exit::shutdown()),
             _sig_term = sig_term.recv() => Some(Exit::shutdown()),
         } {
@@ -87,35 +93,41 @@ mod actors {
         use std::convert::Infallible;
         use std::sync::Arc;
 
-        use agner::actors::{ActorID, Context, Never};
+        use agner::actors::{actor_info, actor_warn, ActorID, Context, Never};
         use agner::init_ack::ContextInitAckExt;
         use tokio::sync::oneshot;
 
         use crate::actors::connection;
 
         pub enum Message {
-            Register(ActorID),
+            /// Sent by a connection on its own behalf (see [`Context::sender`]).
+            Register,
             Unregister(ActorID),
             Publish(String, oneshot::Sender<Infallible>),
         }
 
         pub async fn run(context: &mut Context<Message>, _args: ()) -> Never {
             context.init_ack_ok(Default::default());
-            tracing::info!("Fanout started");
+            actor_info!(context.log(), "Fanout started");
 
             let mut connections = HashSet::new();
             loop {
                 match context.next_message().await {
-                    Message::Register(connection) =>
+                    Message::Register => {
+                        let Some(connection) = context.sender() else {
+                            actor_warn!(context.log(), "Register without a sender: ignored");
+                            continue
+                        };
                         if connections.insert(connection) {
-                            let wait = context.system().wait(connection);
+                            let down = context.monitor_fut(connection);
                             context
                                 .future_to_inbox(async move {
-                                    let _exit_reason = wait.await;
+                                    let _exit_reason = down.await;
                                     Message::Unregister(connection)
                                 })
                                 .await;
-                        },
+                        }
+                    },
                     Message::Unregister(connection) => {
                         connections.remove(&connection);
                     },
@@ -139,7 +151,7 @@ mod actors {
         use std::sync::Arc;
 
         use crate::actors::fanout;
-        use agner::actors::{ActorID, Context, Exit, Shutdown, SystemWeakRef};
+        use agner::actors::{actor_info, Context, Event, Exit, Selected, Shutdown};
         use agner::init_ack::ContextInitAckExt;
         use agner::reg;
         use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt};
@@ -148,8 +160,6 @@ mod actors {
         #[derive(Debug)]
         pub enum Message {
             Publish(Arc<str>),
-            ReaderLine(String),
-            ReaderDone(Option<Exit>),
         }
 
         pub async fn run<IO>(
@@ -161,74 +171,46 @@ mod actors {
         {
             let (io_read_half, mut io_write_half) = tokio::io::split(io);
             context
-                .system()
                 .send(
                     fanout.resolve().ok_or_else(|| Exit::from_message("fanout gone"))?,
-                    fanout::Message::Register(context.actor_id()),
+                    fanout::Message::Register,
                 )
-                .await;
+                .await
+                .map_err(Exit::custom)?;
             context.init_ack_ok(Default::default());
 
-            tracing::info!("Connection started");
-
-            let reader_routine_running = {
-                let connection = context.actor_id();
-                let system_weak_ref = context.system().rc_downgrade();
-                async move {
-                    let err_opt =
-                        reader_routine(system_weak_ref, connection, io_read_half).await.err();
-                    Message::ReaderDone(err_opt)
-                }
-            };
-            context.future_to_inbox(reader_routine_running).await;
+            actor_info!(context.log(), "Connection started");
 
+            let mut io_read_lines = tokio::io::BufReader::new(io_read_half).lines();
             loop {
-                match context.next_message().await {
-                    Message::ReaderDone(None) => break Ok(Shutdown::new()),
-                    Message::ReaderDone(Some(reader_failure)) => break Err(reader_failure),
-                    Message::ReaderLine(line) => {
+                match context.select().with(io_read_lines.next_line()).await {
+                    Selected::Ready(Ok(None)) => break Ok(Shutdown::new()),
+                    Selected::Ready(Err(reason)) => break Err(Exit::custom(reason)),
+                    Selected::Ready(Ok(Some(line))) => {
                         let fanout =
                             fanout.resolve().ok_or_else(|| Exit::from_message("fanout gone"))?;
                         let (tx, done) = oneshot::channel();
                         context.system().send(fanout, fanout::Message::Publish(line, tx)).await;
                         let _ = done.await;
                     },
-                    Message::Publish(line) => {
+                    Selected::Event(Event::Message(Message::Publish(line))) => {
                         io_write_half.write_all(line.as_bytes()).await.map_err(Exit::custom)?;
                         io_write_half.flush().await.map_err(Exit::custom)?;
                     },
+                    Selected::Event(Event::Signal(_)) => unreachable!("does not trap exits"),
                 }
             }
         }
-
-        async fn reader_routine<IO>(
-            system: SystemWeakRef,
-            connection: ActorID,
-            io: IO,
-        ) -> Result<(), Exit>
-        where
-            IO: AsyncRead + Unpin,
-        {
-            let io_buf_read = tokio::io::BufReader::new(io);
-            let mut io_read_lines = io_buf_read.lines();
-            while let Some(line) = io_read_lines.next_line().await.map_err(Exit::custom)? {
-                let system =
-                    system.rc_upgrade().ok_or_else(|| Exit::from_message("system gone"))?;
-                system.send(connection, Message::ReaderLine(line)).await;
-            }
-
-            Ok(())
-        }
     }
 
     pub mod uds_acceptor {
         use std::sync::Arc;
 
-        use agner::actors::{Context, Exit, Never};
+        use agner::actors::{actor_info, Context, Exit, Never};
         use agner::init_ack::ContextInitAckExt;
         use agner::reg;
-        use agner::sup::uniform;
-        use tokio::net::UnixListener;
+        use agner::sup::uniform::ContextUniformSupExt;
+        use tokio::net::{UnixListener, UnixStream};
 
         pub mod api {}
 
@@ -241,14 +223,18 @@ mod actors {
         ) -> Result<Never, Exit> {
             context.init_ack_ok(Default::default());
 
-            tracing::info!("UDS-acceptor started");
+            actor_info!(context.log(), "UDS-acceptor started");
 
             loop {
                 let (uds_stream, _) = uds_listener.accept().await.map_err(Exit::custom)?;
                 let conn_sup = conn_sup
                     .resolve()
                     .ok_or_else(|| Exit::from_message("Failed to resolve conn_sup"))?;
-                uniform::start_child(&context.system(), conn_sup, uds_stream)
+                context
+                    .uniform_sup::<UnixStream>(conn_sup)
+                    .await
+                    .map_err(Exit::custom)?
+                    .start_child(uds_stream)
                     .await
                     .map_err(Exit::custom)?;
             }
@@ -28,8 +28,7 @@
 //! - the behaviour function.
 //!
 //! In order to implement an actor one should define an async function that
-//! - returns a value for which the trait [`Into<Exit>`](crate::actors::Exit) is
-//! defined
+//! - returns a value for which the trait [`Into<Exit>`](crate::actors::Exit) is defined
 //! - and accepts two arguments:
 //!     - a mutable reference to [`Context<Message>`](crate::actors::Context);
 //!     - `Argument`.
@@ -161,6 +160,11 @@
 //! TBD:
 //! - [mixed supervisor](crate::sup::mixed)
 //!
+//! # Pub/Sub
+//!
+//! TBD:
+//! - [topic]
+//!
 //! # Introspection
 //!
 //! TBD:
@@ -170,6 +174,7 @@
 //!
 //! TBD:
 //! - [test-actor](crate::test_actor)
+//! - [supervisor test-harness](crate::test_actor::sup_harness)
 
 pub use agner_utils as utils;
 
@@ -190,6 +195,9 @@ pub use agner_helm as helm;
 #[cfg(feature = "test-actor")]
 pub use agner_test_actor as test_actor;
 
+#[cfg(feature = "topic")]
+pub use agner_topic as topic;
+
 #[cfg(feature = "log")]
 pub use agner_tracing::log;
 